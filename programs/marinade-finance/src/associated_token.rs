@@ -0,0 +1,106 @@
+//! Minimal client of the associated token account program,
+//! used to create the user destination token accounts on the fly
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    instruction::{AccountMeta, Instruction},
+    program::invoke,
+};
+use anchor_spl::token::{spl_token, Token, TokenAccount};
+
+use crate::{checks::check_token_mint, error::MarinadeError};
+
+pub mod program {
+    use anchor_lang::declare_id;
+    declare_id!("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL");
+}
+
+// AssociatedTokenAccountInstruction::CreateIdempotent
+const CREATE_IDEMPOTENT: u8 = 1;
+
+pub fn get_associated_token_address(owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[owner.as_ref(), spl_token::ID.as_ref(), mint.as_ref()],
+        &program::ID,
+    )
+    .0
+}
+
+/// optional accounts of the instructions able to create their destination token account
+pub struct CreateAssociatedTokenAccounts<'info> {
+    pub owner: AccountInfo<'info>,
+    pub rent_payer: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+    pub associated_token_program: AccountInfo<'info>,
+}
+
+impl<'info> CreateAssociatedTokenAccounts<'info> {
+    /// None unless all the optional accounts are provided
+    pub fn from_optional_accounts(
+        owner: &Option<UncheckedAccount<'info>>,
+        rent_payer: &Option<Signer<'info>>,
+        associated_token_program: &Option<UncheckedAccount<'info>>,
+        system_program: &Program<'info, System>,
+        token_program: &Program<'info, Token>,
+    ) -> Option<Self> {
+        match (owner, rent_payer, associated_token_program) {
+            (Some(owner), Some(rent_payer), Some(associated_token_program)) => Some(Self {
+                owner: owner.to_account_info(),
+                rent_payer: rent_payer.to_account_info(),
+                system_program: system_program.to_account_info(),
+                token_program: token_program.to_account_info(),
+                associated_token_program: associated_token_program.to_account_info(),
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Returns the `token_account` of `mint`.
+/// If it does not exist yet, it is created as the associated token account of `create.owner`,
+/// so the instruction does not fail for a user receiving the token for the first time
+pub fn init_token_account_if_needed<'info>(
+    token_account: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    create: Option<CreateAssociatedTokenAccounts<'info>>,
+    field_name: &str,
+) -> Result<Account<'info, TokenAccount>> {
+    if token_account.data_is_empty() {
+        let create = create.ok_or_else(|| {
+            error!(MarinadeError::MissingAssociatedTokenAccounts).with_account_name(field_name)
+        })?;
+        require_keys_eq!(
+            *token_account.key,
+            get_associated_token_address(create.owner.key, mint.key),
+            MarinadeError::InvalidAssociatedTokenAccount
+        );
+        invoke(
+            &Instruction {
+                program_id: program::ID,
+                accounts: vec![
+                    AccountMeta::new(*create.rent_payer.key, true),
+                    AccountMeta::new(*token_account.key, false),
+                    AccountMeta::new_readonly(*create.owner.key, false),
+                    AccountMeta::new_readonly(*mint.key, false),
+                    AccountMeta::new_readonly(*create.system_program.key, false),
+                    AccountMeta::new_readonly(*create.token_program.key, false),
+                ],
+                data: vec![CREATE_IDEMPOTENT],
+            },
+            &[
+                create.rent_payer,
+                token_account.clone(),
+                create.owner,
+                mint.clone(),
+                create.system_program,
+                create.token_program,
+                create.associated_token_program,
+            ],
+        )?;
+    }
+    let token = Account::<TokenAccount>::try_from(token_account)
+        .map_err(|e| e.with_account_name(field_name))?;
+    check_token_mint(&token, mint.key, field_name)?;
+    Ok(token)
+}
@@ -4,28 +4,57 @@ use crate::error::MarinadeError;
 use anchor_lang::prelude::{error, Result};
 use std::convert::TryFrom;
 
+/// Direction of the integer division remainder.
+/// Amounts paid to the user round Down and amounts the user pays round Up,
+/// so the rounding always stays in the pool
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Rounding {
+    Down,
+    Up,
+}
+
 /// calculate amount*numerator/denominator
 /// as value  = shares * share_price where share_price=total_value/total_shares
 /// or shares = amount_value / share_price where share_price=total_value/total_shares
 ///     => shares = amount_value * 1/share_price where 1/share_price=total_shares/total_value
-pub fn proportional(amount: u64, numerator: u64, denominator: u64) -> Result<u64> {
+pub fn proportional(
+    amount: u64,
+    numerator: u64,
+    denominator: u64,
+    rounding: Rounding,
+) -> Result<u64> {
     if denominator == 0 {
         return Ok(amount);
     }
-    u64::try_from((amount as u128) * (numerator as u128) / (denominator as u128))
-        .map_err(|_| error!(MarinadeError::CalculationFailure))
+    let product = (amount as u128) * (numerator as u128);
+    let denominator = denominator as u128;
+    let result = match rounding {
+        Rounding::Down => product / denominator,
+        Rounding::Up => (product + denominator - 1) / denominator,
+    };
+    u64::try_from(result).map_err(|_| error!(MarinadeError::CalculationFailure))
 }
 
 #[inline] //alias for proportional
-pub fn value_from_shares(shares: u64, total_value: u64, total_shares: u64) -> Result<u64> {
-    proportional(shares, total_value, total_shares)
+pub fn value_from_shares(
+    shares: u64,
+    total_value: u64,
+    total_shares: u64,
+    rounding: Rounding,
+) -> Result<u64> {
+    proportional(shares, total_value, total_shares, rounding)
 }
 
-pub fn shares_from_value(value: u64, total_value: u64, total_shares: u64) -> Result<u64> {
+pub fn shares_from_value(
+    value: u64,
+    total_value: u64,
+    total_shares: u64,
+    rounding: Rounding,
+) -> Result<u64> {
     if total_shares == 0 {
         //no shares minted yet / First mint
         Ok(value)
     } else {
-        proportional(value, total_shares, total_value)
+        proportional(value, total_shares, total_value, rounding)
     }
 }
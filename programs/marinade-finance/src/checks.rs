@@ -1,7 +1,11 @@
 use crate::MarinadeError;
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::stake::state::StakeState;
-use anchor_spl::token::{Mint, TokenAccount};
+use anchor_lang::solana_program::{
+    ed25519_program, program_pack::Pack, system_program,
+    sysvar::instructions::get_instruction_relative,
+};
+use anchor_spl::token::{spl_token, Mint, TokenAccount};
 
 pub fn check_owner_program<'info, A: ToAccountInfo<'info>>(
     account: &A,
@@ -35,7 +39,10 @@ pub fn check_mint_authority(mint: &Mint, mint_authority: &Pubkey, field_name: &s
             mint.mint_authority.unwrap_or_default(),
             mint_authority
         );
-        Err(Error::from(ProgramError::InvalidAccountData).with_source(source!()))
+        Err(Error::from(ProgramError::InvalidAccountData)
+            .with_account_name(field_name)
+            .with_pubkeys((mint.mint_authority.unwrap_or_default(), *mint_authority))
+            .with_source(source!()))
     }
 }
 
@@ -44,7 +51,9 @@ pub fn check_freeze_authority(mint: &Mint, field_name: &str) -> Result<()> {
         Ok(())
     } else {
         msg!("Mint {} must have freeze authority not set", field_name);
-        Err(Error::from(ProgramError::InvalidAccountData).with_source(source!()))
+        Err(Error::from(ProgramError::InvalidAccountData)
+            .with_account_name(field_name)
+            .with_source(source!()))
     }
 }
 
@@ -53,7 +62,10 @@ pub fn check_mint_empty(mint: &Mint, field_name: &str) -> Result<()> {
         Ok(())
     } else {
         msg!("Non empty mint {} supply: {}", field_name, mint.supply);
-        Err(Error::from(ProgramError::InvalidArgument).with_source(source!()))
+        Err(Error::from(ProgramError::InvalidArgument)
+            .with_account_name(field_name)
+            .with_values((mint.supply, 0))
+            .with_source(source!()))
     }
 }
 
@@ -67,7 +79,10 @@ pub fn check_token_mint(token: &TokenAccount, mint: &Pubkey, field_name: &str) -
             token.mint,
             mint
         );
-        Err(Error::from(ProgramError::InvalidAccountData).with_source(source!()))
+        Err(Error::from(ProgramError::InvalidAccountData)
+            .with_account_name(field_name)
+            .with_pubkeys((token.mint, *mint))
+            .with_source(source!()))
     }
 }
 
@@ -81,8 +96,74 @@ pub fn check_token_owner(token: &TokenAccount, owner: &Pubkey, field_name: &str)
             token.owner,
             owner
         );
-        Err(Error::from(ProgramError::InvalidAccountData).with_source(source!()))
+        Err(Error::from(ProgramError::InvalidAccountData)
+            .with_account_name(field_name)
+            .with_pubkeys((token.owner, *owner))
+            .with_source(source!()))
+    }
+}
+
+/// SOL can be sent to a system account or to a wrapped SOL token account.
+/// Returns true for wSOL, the token account must be synced after the lamports transfer
+pub fn check_sol_destination(account: &AccountInfo, field_name: &str) -> Result<bool> {
+    if *account.owner == system_program::ID {
+        Ok(false)
+    } else if *account.owner == spl_token::ID {
+        let token = spl_token::state::Account::unpack(&account.data.borrow())
+            .map_err(|e| Error::from(e).with_account_name(field_name))?;
+        if token.is_native() {
+            Ok(true)
+        } else {
+            msg!("Token account {} is not wSOL", field_name);
+            Err(Error::from(ProgramError::InvalidAccountData)
+                .with_account_name(field_name)
+                .with_source(source!()))
+        }
+    } else {
+        check_owner_program(account, &system_program::ID, field_name).map(|_| false)
+    }
+}
+
+/// check that the instruction just before the current one verified
+/// the `signer` ed25519 signature of `message` (see the ed25519 program instruction layout)
+pub fn check_ed25519_signature(
+    instructions: &AccountInfo,
+    signer: &Pubkey,
+    message: &[u8],
+) -> Result<()> {
+    const HEADER_LEN: usize = 2;
+    const OFFSETS_LEN: usize = 14;
+    let read_u16 = |data: &[u8], at: usize| u16::from_le_bytes([data[at], data[at + 1]]);
+
+    let instruction = get_instruction_relative(-1, instructions)?;
+    require_keys_eq!(
+        instruction.program_id,
+        ed25519_program::ID,
+        MarinadeError::InvalidSignature
+    );
+    let data = &instruction.data;
+    // exactly one signature with all the data inside the ed25519 instruction itself
+    require!(
+        data.len() >= HEADER_LEN + OFFSETS_LEN && data[0] == 1,
+        MarinadeError::InvalidSignature
+    );
+    let offsets = &data[HEADER_LEN..HEADER_LEN + OFFSETS_LEN];
+    for instruction_index_at in [2, 6, 12] {
+        require_eq!(
+            read_u16(offsets, instruction_index_at),
+            u16::MAX,
+            MarinadeError::InvalidSignature
+        );
     }
+    let public_key_offset = read_u16(offsets, 4) as usize;
+    let message_offset = read_u16(offsets, 8) as usize;
+    let message_size = read_u16(offsets, 10) as usize;
+    require!(
+        data.get(public_key_offset..public_key_offset + 32) == Some(&signer.to_bytes()[..])
+            && data.get(message_offset..message_offset + message_size) == Some(message),
+        MarinadeError::InvalidSignature
+    );
+    Ok(())
 }
 
 // check that the account is delegated and to the right validator
@@ -114,6 +195,16 @@ pub fn check_stake_amount_and_validator(
     Ok(())
 }
 
+/// msg! built only with the `verbose-logs` feature, for the values the events already carry
+#[macro_export]
+macro_rules! debug_msg {
+    ($($arg:tt)*) => {
+        if cfg!(feature = "verbose-logs") {
+            anchor_lang::prelude::msg!($($arg)*);
+        }
+    };
+}
+
 #[macro_export]
 macro_rules! require_lte {
     ($value1: expr, $value2: expr, $error_code: expr $(,)?) => {
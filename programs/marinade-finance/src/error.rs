@@ -270,4 +270,328 @@ pub enum MarinadeError {
 
     #[msg("Capacity of the list must be not less than it's current size")]
     ShrinkingListWithDeletingContents, // 6086 0x17c6
+
+    #[msg("Slippage exceeded")]
+    SlippageExceeded, // 6087 0x17c7
+
+    #[msg("LP mint supply is greater than the registered LP supply")]
+    UnexpectedLpSupply, // 6088 0x17c8
+
+    #[msg("Not enough LP tokens staked")]
+    NotEnoughStakedLp, // 6089 0x17c9
+
+    #[msg("Nothing to rebalance")]
+    NothingToRebalance, // 6090 0x17ca
+
+    #[msg("Liquid unstake volume for this epoch is capped")]
+    LiquidUnstakeIsCapped, // 6091 0x17cb
+
+    #[msg("Invalid fee curve")]
+    InvalidFeeCurve, // 6092 0x17cc
+
+    #[msg("Fee curve account is required")]
+    MissingFeeCurve, // 6093 0x17cd
+
+    #[msg("Invalid unlock slot")]
+    InvalidUnlockSlot, // 6094 0x17ce
+
+    #[msg("LP tokens are locked")]
+    LpIsLocked, // 6095 0x17cf
+
+    #[msg("LP lock fee vault account is required")]
+    MissingLpLockFeeVault, // 6096 0x17d0
+
+    #[msg("Lock fee boost is too high")]
+    LockFeeBoostIsTooHigh, // 6097 0x17d1
+
+    #[msg("Not enough LP tokens locked")]
+    NotEnoughLockedLp, // 6098 0x17d2
+
+    #[msg("LP global cap reached")]
+    LpGlobalCapReached, // 6099 0x17d3
+
+    #[msg("LP wallet cap reached")]
+    LpWalletCapReached, // 6100 0x17d4
+
+    #[msg("Flash loan is not enabled")]
+    FlashLoanIsNotEnabled, // 6101 0x17d5
+
+    #[msg("Flash loan in progress")]
+    FlashLoanInProgress, // 6102 0x17d6
+
+    #[msg("Flash loan not in progress")]
+    FlashLoanNotInProgress, // 6103 0x17d7
+
+    #[msg("flash_loan_end instruction not found")]
+    MissingFlashLoanEnd, // 6104 0x17d8
+
+    #[msg("Flash loan fee is too high")]
+    FlashLoanFeeIsTooHigh, // 6105 0x17d9
+
+    #[msg("Invalid ed25519 signature instruction")]
+    InvalidSignature, // 6106 0x17da
+
+    #[msg("Permit expired")]
+    PermitExpired, // 6107 0x17db
+
+    #[msg("LP minimum holding period not elapsed")]
+    LpHoldingPeriodNotElapsed, // 6108 0x17dc
+
+    #[msg("Ticket NFT accounts must be provided to claim a wrapped ticket")]
+    MissingTicketNftAccounts, // 6109 0x17dd
+
+    #[msg("Auto claim tip is too high")]
+    AutoClaimTipIsTooHigh, // 6110 0x17de
+
+    #[msg("Invalid unstake epoch")]
+    InvalidUnstakeEpoch, // 6111 0x17df
+
+    #[msg("Ticket was swept to the unclaimed tickets account")]
+    TicketIsSwept, // 6112 0x17e0
+
+    #[msg("Ticket is not stale yet")]
+    TicketIsNotStale, // 6113 0x17e1
+
+    #[msg("Referral share is too high")]
+    ReferralShareIsTooHigh, // 6114 0x17e2
+
+    #[msg("One validator index is required for every stake account")]
+    StakeAccountsMismatch, // 6115 0x17e3
+
+    #[msg("mSOL lock fee vault is required")]
+    MissingMsolLockFeeVault, // 6116 0x17e4
+
+    #[msg("Invalid mSOL lock tier")]
+    InvalidLockTier, // 6117 0x17e5
+
+    #[msg("mSOL lock has not expired yet")]
+    MsolIsLocked, // 6118 0x17e6
+
+    #[msg("mSOL lock fee share is too high")]
+    MsolLockFeeShareIsTooHigh, // 6119 0x17e7
+
+    #[msg("Accounts to create the associated token account are required")]
+    MissingAssociatedTokenAccounts, // 6120 0x17e8
+
+    #[msg("Invalid associated token account")]
+    InvalidAssociatedTokenAccount, // 6121 0x17e9
+
+    #[msg("Ticket rent refund must be approved by the claimer")]
+    RentRefundNotApproved, // 6122 0x17ea
+
+    #[msg("Config changes must be queued")]
+    ConfigChangeMustBeQueued, // 6123 0x17eb
+
+    #[msg("Config change delay is too high")]
+    ConfigChangeDelayIsTooHigh, // 6124 0x17ec
+
+    #[msg("Config change is not executable yet")]
+    ConfigChangeIsTimelocked, // 6125 0x17ed
+
+    #[msg("Invalid paused operations")]
+    InvalidPausedOperations, // 6126 0x17ee
+
+    #[msg("Invalid operator authority")]
+    InvalidOperatorAuthority, // 6127 0x17ef
+
+    #[msg("Invalid treasurer authority")]
+    InvalidTreasurerAuthority, // 6128 0x17f0
+
+    #[msg("Admin role can not be revoked")]
+    CanNotRevokeAdmin, // 6129 0x17f1
+
+    #[msg("Invalid guardian set")]
+    InvalidGuardianSet, // 6130 0x17f2
+
+    #[msg("Not enough guardian signatures")]
+    GuardianThresholdNotReached, // 6131 0x17f3
+
+    #[msg("Config change can not be vetoed anymore")]
+    VetoWindowIsClosed, // 6132 0x17f4
+
+    #[msg("Circuit breaker tripped, waiting for the admin ack")]
+    CircuitBreakerTripped, // 6133 0x17f5
+
+    #[msg("Circuit breaker is not tripped")]
+    CircuitBreakerNotTripped, // 6134 0x17f6
+
+    #[msg("Invalid rescue authority")]
+    InvalidRescueAuthority, // 6135 0x17f7
+
+    #[msg("mSOL and LP tokens can not be rescued")]
+    CanNotRescueProtocolTokens, // 6136 0x17f8
+
+    #[msg("Nothing to rescue")]
+    NothingToRescue, // 6137 0x17f9
+
+    #[msg("State must be migrated to the current version")]
+    StaleStateVersion, // 6138 0x17fa
+
+    #[msg("State is already at the current version")]
+    StateAlreadyMigrated, // 6139 0x17fb
+
+    #[msg("No validator scores")]
+    EmptyValidatorScores, // 6140 0x17fc
+
+    #[msg("Stake is directed to another validator, redirect it first")]
+    DirectedStakeValidatorMismatch, // 6141 0x17fd
+
+    #[msg("Stake is already directed to this validator")]
+    StakeAlreadyDirected, // 6142 0x17fe
+
+    #[msg("Wrong validator directed stake account")]
+    WrongValidatorDirectedStake, // 6143 0x17ff
+
+    #[msg("Invalid max commission")]
+    InvalidMaxCommission, // 6144 0x1800
+
+    #[msg("Invalid vote account")]
+    InvalidVoteAccount, // 6145 0x1801
+
+    #[msg("Wrong validator health account")]
+    WrongValidatorHealth, // 6146 0x1802
+
+    #[msg("Validator is flagged")]
+    ValidatorIsFlagged, // 6147 0x1803
+
+    #[msg("Validator commission is too high")]
+    ValidatorCommissionIsTooHigh, // 6148 0x1804
+
+    #[msg("Not enough activated stake on the validator")]
+    NotEnoughValidatorStake, // 6149 0x1805
+
+    #[msg("Stake accounts must be sorted by address")]
+    UnsortedStakeAccounts, // 6150 0x1806
+
+    #[msg("Validator bond is too low")]
+    ValidatorBondIsTooLow, // 6151 0x1807
+
+    #[msg("Validator is not flagged")]
+    ValidatorIsNotFlagged, // 6152 0x1808
+
+    #[msg("Validator bond already slashed in this epoch")]
+    ValidatorBondAlreadySlashed, // 6153 0x1809
+
+    #[msg("Wrong validator bond account")]
+    WrongValidatorBond, // 6154 0x180a
+
+    #[msg("Redelegating from a validator with score > 0")]
+    RedelegatingFromNonZeroScoredValidator, // 6155 0x180b
+
+    #[msg("Validator accounts do not match the validator indexes")]
+    ValidatorAccountsMismatch, // 6156 0x180c
+
+    #[msg("Epoch already reported")]
+    EpochAlreadyReported, // 6157 0x180d
+
+    #[msg("Not every stake account is updated in this epoch")]
+    EpochUpdateNotFinished, // 6158 0x180e
+
+    #[msg("Signer is not the validator identity")]
+    InvalidValidatorIdentity, // 6159 0x180f
+
+    #[msg("Wrong delegation strategy account")]
+    WrongDelegationStrategy, // 6160 0x1810
+
+    #[msg("Stake accounts do not match the stake indexes")]
+    StakeIndexesMismatch, // 6161 0x1811
+
+    #[msg("Source stake indexes must be descending and above the destination stake index")]
+    InvalidSourceStakeIndexes, // 6162 0x1812
+
+    #[msg("No mergeable stake accounts")]
+    NoMergeableStakes, // 6163 0x1813
+
+    #[msg("Split amount is lower than min stake")]
+    SplitAmountIsTooLow, // 6164 0x1814
+
+    #[msg("Split amount is above the unstake delta")]
+    SplitAmountAboveUnstakeDelta, // 6165 0x1815
+
+    #[msg("Validator identity is unchanged")]
+    ValidatorIdentityNotChanged, // 6166 0x1816
+
+    #[msg("Reward fee change is too high")]
+    RewardsFeeChangeIsTooHigh, // 6167 0x1817
+
+    #[msg("Reward fee already changed in this epoch")]
+    RewardsFeeAlreadyChanged, // 6168 0x1818
+
+    #[msg("Management fee is too high")]
+    ManagementFeeIsTooHigh, // 6169 0x1819
+
+    #[msg("Integrator partner must sign")]
+    MissingIntegratorSignature, // 6170 0x181a
+
+    #[msg("Insurance fund vault is required")]
+    MissingInsuranceFundVault, // 6171 0x181b
+
+    #[msg("Insurance fund fee share is too high")]
+    InsuranceFundFeeShareIsTooHigh, // 6172 0x181c
+
+    #[msg("Cover above the recorded losses")]
+    CoverAboveRecordedLosses, // 6173 0x181d
+
+    #[msg("Not enough mSOL in the insurance fund")]
+    InsufficientInsuranceFund, // 6174 0x181e
+
+    #[msg("Invalid treasury recipients")]
+    InvalidTreasuryRecipients, // 6175 0x181f
+
+    #[msg("Treasury recipient accounts mismatch")]
+    TreasuryRecipientsMismatch, // 6176 0x1820
+
+    #[msg("Deposit imbalance fee is too high")]
+    DepositImbalanceFeeIsTooHigh, // 6177 0x1821
+
+    #[msg("Add liquidity bonus share is too high")]
+    AddLiquidityBonusShareIsTooHigh, // 6178 0x1822
+
+    #[msg("Invalid holding discount tiers")]
+    InvalidHoldingDiscountTiers, // 6179 0x1823
+
+    #[msg("Holding record of another wallet")]
+    WrongHoldingRecord, // 6180 0x1824
+
+    #[msg("Buyback governance mint can not be changed")]
+    BuybackGovernanceMintChanged, // 6181 0x1825
+
+    #[msg("Buyback epoch budget exceeded")]
+    BuybackBudgetExceeded, // 6182 0x1826
+
+    #[msg("Invalid buyback swap")]
+    InvalidBuybackSwap, // 6183 0x1827
+
+    #[msg("mSOL price grew above the max epoch yield")]
+    MsolPriceAboveYieldBand, // 6184 0x1828
+
+    #[msg("Invalid or stale price feed")]
+    InvalidPriceFeed, // 6185 0x1829
+
+    #[msg("mSOL price deviates from the price feeds")]
+    MsolPriceFeedDeviation, // 6186 0x182a
+
+    #[msg("Not a program mint or its mint authority")]
+    InvalidMetadataMint, // 6187 0x182b
+
+    #[msg("Stake pool withdraw did not deliver a delegated stake account")]
+    StakePoolWithdrawFailed, // 6188 0x182c
+
+    #[msg("Vault amount is too low")]
+    VaultAmountTooLow, // 6189 0x182d
+
+    #[msg("Authority is not a signer of the governance")]
+    InvalidGovernanceAuthority, // 6190 0x182e
+
+    #[msg("Recorded balances do not match the accounts")]
+    InvariantViolation, // 6191 0x182f
+
+    #[msg("Not enough liquidity added to mint LP")]
+    NoLpSharesMinted, // 6192 0x1830
+
+    #[msg("User stats of another wallet")]
+    WrongUserStats, // 6193 0x1831
+
+    #[msg("Liq pool mSOL leg account is required")]
+    MissingLiqPoolMsolLeg, // 6194 0x1832
 }
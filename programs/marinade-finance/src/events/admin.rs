@@ -1,14 +1,29 @@
 use anchor_lang::prelude::*;
 
-use crate::instructions::InitializeData;
+use crate::{
+    instructions::{
+        ConfigCheck, ConfigMarinadeParams, GovernedAuthority, InitializeData, Invariant,
+        InvariantCheck, RescueAuthority,
+    },
+    state::{
+        fee_curve::{FeeCurvePoint, FeeSizeTier},
+        holding_record::HoldingDiscountTier,
+        roles::Role,
+        treasury_splitter::TreasuryRecipient,
+        Fee,
+    },
+};
 
 use super::{
-    BoolValueChange, FeeCentsValueChange, FeeValueChange, PubkeyValueChange, U64ValueChange,
+    BoolValueChange, FeeCentsValueChange, FeeValueChange, PubkeyValueChange, U32ValueChange,
+    U64ValueChange, U8ValueChange,
 };
 
 #[event]
 pub struct ChangeAuthorityEvent {
     pub state: Pubkey,
+    pub authority: Pubkey,
+    pub slot: u64,
     pub admin_change: Option<PubkeyValueChange>,
     pub validator_manager_change: Option<PubkeyValueChange>,
     pub operational_sol_account_change: Option<PubkeyValueChange>,
@@ -16,18 +31,160 @@ pub struct ChangeAuthorityEvent {
     pub pause_authority_change: Option<PubkeyValueChange>,
 }
 
+#[event]
+pub struct SetGovernanceAuthorityEvent {
+    pub state: Pubkey,
+    pub authority: Pubkey,
+    pub slot: u64,
+    pub governed_authority: GovernedAuthority,
+    pub realm: Pubkey,
+    pub governance: Pubkey,
+    pub authority_change: PubkeyValueChange,
+}
+
+#[event]
+pub struct CreateLookupTableEvent {
+    pub state: Pubkey,
+    pub authority: Pubkey,
+    pub slot: u64,
+    pub lookup_table_change: PubkeyValueChange,
+    pub addresses: Vec<Pubkey>,
+}
+
+#[event]
+pub struct ConfigFeeCurveEvent {
+    pub state: Pubkey,
+    pub authority: Pubkey,
+    pub slot: u64,
+    pub fee_curve_change: PubkeyValueChange,
+    pub points: Vec<FeeCurvePoint>,
+    pub size_tiers: Vec<FeeSizeTier>,
+}
+
 #[event]
 pub struct ConfigLpEvent {
     pub state: Pubkey,
+    pub authority: Pubkey,
+    pub slot: u64,
     pub min_fee_change: Option<FeeValueChange>,
     pub max_fee_change: Option<FeeValueChange>,
     pub liquidity_target_change: Option<U64ValueChange>,
     pub treasury_cut_change: Option<FeeValueChange>,
+    pub msol_leg_target_change: Option<FeeValueChange>,
+    pub lp_wallet_cap_change: Option<U64ValueChange>,
+    pub lp_global_cap_change: Option<U64ValueChange>,
+    pub flash_loan_enabled_change: Option<BoolValueChange>,
+    pub flash_loan_fee_change: Option<FeeValueChange>,
+    pub lp_min_holding_slots_change: Option<U64ValueChange>,
+    pub add_liquidity_bonus_share_change: Option<FeeValueChange>,
+}
+
+#[event]
+pub struct ConfigLpLockEvent {
+    pub state: Pubkey,
+    pub authority: Pubkey,
+    pub slot: u64,
+    pub lp_lock_vault: Pubkey,
+    pub lp_lock_fee_vault: Pubkey,
+    pub lock_fee_boost_change: FeeValueChange,
+}
+
+#[event]
+pub struct ConfigMsolLockEvent {
+    pub state: Pubkey,
+    pub authority: Pubkey,
+    pub slot: u64,
+    pub msol_lock_vault: Pubkey,
+    pub msol_lock_fee_vault: Pubkey,
+    pub fee_share_change: FeeValueChange,
+}
+
+#[event]
+pub struct ConfigHoldingDiscountEvent {
+    pub state: Pubkey,
+    pub authority: Pubkey,
+    pub slot: u64,
+    pub tiers: Vec<HoldingDiscountTier>,
+}
+
+#[event]
+pub struct ConfigTreasurySplitterEvent {
+    pub state: Pubkey,
+    pub authority: Pubkey,
+    pub slot: u64,
+    pub treasury_msol_account_change: PubkeyValueChange,
+    pub recipients: Vec<TreasuryRecipient>,
+}
+
+#[event]
+pub struct ConfigInsuranceFundEvent {
+    pub state: Pubkey,
+    pub authority: Pubkey,
+    pub slot: u64,
+    pub insurance_fund_vault: Pubkey,
+    pub fee_share_change: FeeValueChange,
+}
+
+#[event]
+pub struct ConfigBuybackEvent {
+    pub state: Pubkey,
+    pub authority: Pubkey,
+    pub slot: u64,
+    pub governance_mint: Pubkey,
+    pub amm_program_change: PubkeyValueChange,
+    pub epoch_budget_change: U64ValueChange,
+}
+
+#[event]
+pub struct BuybackAndBurnEvent {
+    pub state: Pubkey,
+    pub epoch: u64,
+    pub amm_program: Pubkey,
+    pub msol_spent: u64,
+    pub burned: u64,
+    pub spent_in_epoch: u64,
+    pub epoch_budget: u64,
+    pub total_msol_spent: u64,
+    pub total_burned: u64,
+}
+
+#[event]
+pub struct TokenMetadataEvent {
+    pub state: Pubkey,
+    pub authority: Pubkey,
+    pub slot: u64,
+    pub mint: Pubkey,
+    pub metadata: Pubkey,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+}
+
+#[event]
+pub struct CoverLossEvent {
+    pub state: Pubkey,
+    pub authority: Pubkey,
+    pub slot: u64,
+    pub lamports: u64,
+    pub msol_burned: u64,
+    pub recorded_losses: u64,
+    pub insurance_fund_balance: u64,
+}
+
+#[event]
+pub struct ConfigReferralEvent {
+    pub state: Pubkey,
+    pub authority: Pubkey,
+    pub slot: u64,
+    pub referral_vault: Pubkey,
+    pub referral_share_change: FeeValueChange,
 }
 
 #[event]
 pub struct ConfigMarinadeEvent {
     pub state: Pubkey,
+    pub authority: Pubkey,
+    pub slot: u64,
     pub rewards_fee_change: Option<FeeValueChange>,
     pub slots_for_stake_delta_change: Option<U64ValueChange>,
     pub min_stake_change: Option<U64ValueChange>,
@@ -39,9 +196,54 @@ pub struct ConfigMarinadeEvent {
     pub delayed_unstake_fee_change: Option<FeeCentsValueChange>,
     pub withdraw_stake_account_fee_change: Option<FeeCentsValueChange>,
     pub max_stake_moved_per_epoch_change: Option<FeeValueChange>,
+    pub max_liquid_unstake_per_epoch_change: Option<U64ValueChange>,
+    pub stale_ticket_epochs_change: Option<U64ValueChange>,
+    pub config_change_delay_slots_change: Option<U64ValueChange>,
+    pub msol_price_change_limit_change: Option<FeeValueChange>,
+    pub max_directed_stake_share_change: Option<FeeValueChange>,
+    pub max_commission_change: Option<U8ValueChange>,
+    pub delinquent_epochs_change: Option<U64ValueChange>,
+    pub max_validator_stake_share_change: Option<FeeValueChange>,
+    pub min_validator_stake_change: Option<U64ValueChange>,
+    pub min_validator_bond_change: Option<U64ValueChange>,
+    pub validator_bond_slash_change: Option<FeeValueChange>,
+    pub keeper_reward_change: Option<U64ValueChange>,
+    pub delegation_strategy_change: Option<PubkeyValueChange>,
+    pub deactivating_stake_discount_change: Option<FeeValueChange>,
+    pub management_fee_change: Option<FeeValueChange>,
+    pub deposit_imbalance_fee_change: Option<FeeValueChange>,
+    pub deposit_imbalance_threshold_change: Option<FeeValueChange>,
+    pub msol_usd_feed_change: Option<PubkeyValueChange>,
+    pub sol_usd_feed_change: Option<PubkeyValueChange>,
+    pub price_feed_tolerance_change: Option<FeeValueChange>,
+    pub max_epoch_yield_change: Option<FeeValueChange>,
+}
+
+#[event]
+pub struct QueueConfigChangeEvent {
+    pub state: Pubkey,
+    pub authority: Pubkey,
+    pub slot: u64,
+    pub queued_config_change: Pubkey,
+    pub params: ConfigMarinadeParams,
+    pub execute_slot: u64,
 }
 
-// TODO: ConfigValidatorSystemEvent?
+#[event]
+pub struct CancelConfigChangeEvent {
+    pub state: Pubkey,
+    pub authority: Pubkey,
+    pub slot: u64,
+    pub queued_config_change: Pubkey,
+}
+
+#[event]
+pub struct ConfigValidatorSystemEvent {
+    pub state: Pubkey,
+    pub authority: Pubkey,
+    pub slot: u64,
+    pub extra_stake_delta_runs_change: U32ValueChange,
+}
 
 #[event]
 pub struct InitializeEvent {
@@ -56,14 +258,77 @@ pub struct InitializeEvent {
     pub treasury_msol_account: Pubkey,
 }
 
+#[event]
+pub struct AckCircuitBreakerEvent {
+    pub state: Pubkey,
+    pub authority: Pubkey,
+    pub slot: u64,
+    /// PausedOperations bits left after the ack
+    pub paused: u8,
+}
+
+#[event]
+pub struct GrantRoleEvent {
+    pub state: Pubkey,
+    pub authority: Pubkey,
+    pub slot: u64,
+    pub role: Role,
+    pub authority_change: PubkeyValueChange,
+}
+
+#[event]
+pub struct RevokeRoleEvent {
+    pub state: Pubkey,
+    pub authority: Pubkey,
+    pub slot: u64,
+    pub role: Role,
+    pub old_authority: Pubkey,
+}
+
 #[event]
 pub struct EmergencyPauseEvent {
     pub state: Pubkey,
+    pub authority: Pubkey,
+    pub slot: u64,
 }
 
 #[event]
 pub struct ResumeEvent {
     pub state: Pubkey,
+    pub authority: Pubkey,
+    pub slot: u64,
+}
+
+#[event]
+pub struct SetPausedOperationsEvent {
+    pub state: Pubkey,
+    pub authority: Pubkey,
+    pub slot: u64,
+    pub operations: u8,
+    pub paused: bool,
+    // PausedOperations bits
+    pub old: u8,
+    pub new: u8,
+}
+
+#[event]
+pub struct CreateDelegationStrategyEvent {
+    pub state: Pubkey,
+    pub authority: Pubkey,
+    pub slot: u64,
+    pub delegation_strategy: Pubkey,
+    pub score_share: Fee,
+    pub min_score: u32,
+    pub max_validator_stake_share: Fee,
+    pub max_directed_stake_share: Fee,
+}
+
+#[event]
+pub struct InitKeeperPoolEvent {
+    pub state: Pubkey,
+    pub authority: Pubkey,
+    pub slot: u64,
+    pub keeper_pool: Pubkey,
 }
 
 #[event]
@@ -79,3 +344,58 @@ pub struct ReallocStakeListEvent {
     pub count: u32,
     pub new_capacity: u32,
 }
+
+#[event]
+pub struct ReconcileLpSupplyEvent {
+    pub state: Pubkey,
+    pub authority: Pubkey,
+    pub slot: u64,
+    pub lp_supply_change: U64ValueChange,
+}
+
+#[event]
+pub struct RescueTokensEvent {
+    pub state: Pubkey,
+    pub authority: RescueAuthority,
+    pub token_account: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub treasury_token_account: Pubkey,
+}
+
+#[event]
+pub struct RescueLamportsEvent {
+    pub state: Pubkey,
+    pub authority: RescueAuthority,
+    pub lamports: u64,
+    pub treasury_sol_account: Pubkey,
+}
+
+#[event]
+pub struct VerifyConfigEvent {
+    pub state: Pubkey,
+    pub checked: u8,
+    // empty when the config is sane
+    pub violations: Vec<ConfigCheck>,
+}
+
+#[event]
+pub struct VerifyInvariantsEvent {
+    pub state: Pubkey,
+    pub slot: u64,
+    pub tolerance: u64,
+    pub sol_leg_balance: u64,
+    pub msol_leg_balance: u64,
+    pub checks: Vec<InvariantCheck>,
+    // empty when every check is within tolerance
+    pub violations: Vec<Invariant>,
+}
+
+#[event]
+pub struct MigrateStateEvent {
+    pub state: Pubkey,
+    pub old_version: u8,
+    pub new_version: u8,
+    pub old_len: u32,
+    pub new_len: u32,
+}
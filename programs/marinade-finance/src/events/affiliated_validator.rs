@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+use super::FeeValueChange;
+
+#[event]
+pub struct SetAffiliatedValidatorEvent {
+    pub state: Pubkey,
+    pub authority: Pubkey,
+    pub slot: u64,
+    pub validator: Pubkey,
+    pub operator: Pubkey,
+    pub reserve_share: FeeValueChange,
+}
+
+#[event]
+pub struct SweepValidatorCommissionEvent {
+    pub state: Pubkey,
+    pub epoch: u64,
+    pub validator: Pubkey,
+    pub operator: Pubkey,
+    pub amount: u64,
+    pub reserve_amount: u64,
+    pub operator_amount: u64,
+    pub swept_lamports: u64,
+}
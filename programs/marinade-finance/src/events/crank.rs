@@ -1,6 +1,6 @@
 use anchor_lang::prelude::*;
 
-use crate::state::Fee;
+use crate::state::{epoch_report::EpochReport, Fee};
 
 use super::U64ValueChange;
 
@@ -88,6 +88,71 @@ pub struct StakeReserveEvent {
     pub amount: u64,
 }
 
+// update paused the program, see State::msol_price_change_limit
+#[event]
+pub struct CircuitBreakerEvent {
+    pub state: Pubkey,
+    pub epoch: u64,
+    pub reference_msol_price: u64,
+    pub msol_price: u64,
+    pub msol_price_change_limit: Fee,
+}
+
+// update paused the program, see State::max_epoch_yield
+#[event]
+pub struct EpochYieldBreakerEvent {
+    pub state: Pubkey,
+    pub epoch: u64,
+    pub epoch_start_msol_price: u64,
+    pub msol_price: u64,
+    pub max_epoch_yield: Fee,
+}
+
+// the reward fee taken by the update instructions, minted in mSOL to the treasury
+// the mSOL locks (msol_lock_cut) and the insurance fund (insurance_fund_cut)
+#[event]
+pub struct RewardFeeEvent {
+    pub state: Pubkey,
+    pub epoch: u64,
+    pub rewards: u64,
+    pub reward_fee: Fee,
+    pub fee_lamports: u64,
+    pub msol_fees: u64,
+    pub msol_lock_cut: u64,
+    pub insurance_fund_cut: u64,
+    pub treasury_msol_account: Pubkey,
+}
+
+// share of the protocol reward fee minted into the insurance fund vault
+#[event]
+pub struct InsuranceFundContributionEvent {
+    pub state: Pubkey,
+    pub epoch: u64,
+    pub msol_amount: u64,
+    pub total_contributed: u64,
+}
+
+// slashing recorded by update for cover_loss
+#[event]
+pub struct InsuranceFundLossEvent {
+    pub state: Pubkey,
+    pub epoch: u64,
+    pub stake_account: Pubkey,
+    pub lamports: u64,
+    pub recorded_losses: u64,
+}
+
+#[event]
+pub struct ManagementFeeEvent {
+    pub state: Pubkey,
+    pub epoch: u64,
+    pub elapsed_seconds: i64,
+    pub management_fee: Fee,
+    pub total_lamports_under_control: u64,
+    pub fee_lamports: u64,
+    pub msol_fees: u64,
+}
+
 #[event]
 pub struct UpdateActiveEvent {
     pub state: Pubkey,
@@ -109,6 +174,14 @@ pub struct UpdateActiveEvent {
     pub msol_supply: u64,
 }
 
+#[event]
+pub struct UpdateActiveBatchEvent {
+    pub state: Pubkey,
+    pub epoch: u64,
+    pub updated: u32,
+    pub next_stake_index: u32,
+}
+
 #[event]
 pub struct UpdateDeactivatedEvent {
     pub state: Pubkey,
@@ -125,3 +198,81 @@ pub struct UpdateDeactivatedEvent {
     pub total_virtual_staked_lamports: u64,
     pub msol_supply: u64,
 }
+
+#[event]
+pub struct RebalanceLiqPoolEvent {
+    pub state: Pubkey,
+    pub sol_leg_balance: u64,
+    pub msol_leg_balance: u64,
+    pub reserve_balance: u64,
+    pub msol_leg_target: Fee,
+    pub excess_value: u64,
+    pub msol_burned: u64,
+    pub lamports_swapped: u64,
+    // MSOL price used
+    pub total_virtual_staked_lamports: u64,
+    pub msol_supply: u64,
+}
+
+// the tips wait in the stake account for update_active
+#[event]
+pub struct ClaimMevTipsEvent {
+    pub state: Pubkey,
+    pub epoch: u64,
+    pub stake_index: u32,
+    pub stake_account: Pubkey,
+    pub tip_distribution_account: Pubkey,
+    pub amount: u64,
+}
+
+// lamports sent directly to the reserve, added as rewards without reward fee
+#[event]
+pub struct SweepReserveDonationsEvent {
+    pub state: Pubkey,
+    pub epoch: u64,
+    pub donated: u64,
+    pub available_reserve_balance: u64,
+    pub total_lamports_under_control: u64,
+}
+
+#[event]
+pub struct KeeperRewardEvent {
+    pub state: Pubkey,
+    pub keeper: Pubkey,
+    pub amount: u64,
+    pub paid_lamports: u64,
+}
+
+#[event]
+pub struct EpochReportEvent {
+    pub state: Pubkey,
+    pub report: EpochReport,
+}
+
+#[event]
+pub struct DistributeTreasuryEvent {
+    pub state: Pubkey,
+    pub msol_amount: u64,
+    pub distributed: u64,
+    pub total_distributed: u64,
+}
+
+#[event]
+pub struct UpdateMsolPriceOracleEvent {
+    pub state: Pubkey,
+    pub msol_price: u64,
+    pub msol_price_x64: u128,
+    pub confidence: u64,
+    pub msol_price_epoch: u64,
+    pub circuit_breaker_tripped: bool,
+}
+
+#[event]
+pub struct UpdateMsolPriceHistoryEvent {
+    pub state: Pubkey,
+    pub epoch: u64,
+    pub msol_price: u64,
+    pub count: u64,
+    /// false while the price was not refreshed in the epoch
+    pub recorded: bool,
+}
@@ -1,5 +1,7 @@
 use anchor_lang::prelude::*;
 
+use crate::state::Fee;
+
 #[event]
 pub struct ClaimEvent {
     pub state: Pubkey,
@@ -29,3 +31,83 @@ pub struct OrderUnstakeEvent {
     pub total_virtual_staked_lamports: u64,
     pub msol_supply: u64,
 }
+
+#[event]
+pub struct WrapTicketEvent {
+    pub state: Pubkey,
+    pub ticket: Pubkey,
+    pub old_beneficiary: Pubkey,
+    pub ticket_nft_mint: Pubkey,
+    pub nft_owner: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct SplitTicketEvent {
+    pub state: Pubkey,
+    pub ticket: Pubkey,
+    pub new_ticket: Pubkey,
+    pub beneficiary: Pubkey,
+    pub ticket_epoch: u64,
+    pub old_amount: u64,
+    pub split_amount: u64,
+    pub circulating_ticket_count: u64,
+}
+
+#[event]
+pub struct MergeTicketsEvent {
+    pub state: Pubkey,
+    pub ticket: Pubkey,
+    pub source_ticket: Pubkey,
+    pub beneficiary: Pubkey,
+    pub ticket_epoch: u64,
+    pub old_amount: u64,
+    pub merged_amount: u64,
+    pub circulating_ticket_count: u64,
+}
+
+#[event]
+pub struct ChangeTicketBeneficiaryEvent {
+    pub state: Pubkey,
+    pub ticket: Pubkey,
+    pub old_beneficiary: Pubkey,
+    pub new_beneficiary: Pubkey,
+}
+
+#[event]
+pub struct EnableAutoClaimEvent {
+    pub state: Pubkey,
+    pub ticket: Pubkey,
+    pub beneficiary: Pubkey,
+    pub tip: Fee,
+}
+
+#[event]
+pub struct CrankClaimEvent {
+    pub state: Pubkey,
+    pub epoch: u64,
+    pub ticket: Pubkey,
+    pub beneficiary: Pubkey,
+    pub cranker: Pubkey,
+    pub circulating_ticket_balance: u64,
+    pub circulating_ticket_count: u64,
+    pub reserve_balance: u64,
+    pub user_balance: u64,
+    pub amount: u64,
+    pub tip: u64,
+}
+
+#[event]
+pub struct SweepStaleTicketEvent {
+    pub state: Pubkey,
+    pub epoch: u64,
+    pub ticket: Pubkey,
+    pub beneficiary: Pubkey,
+    pub ticket_epoch: u64,
+    pub circulating_ticket_balance: u64,
+    pub circulating_ticket_count: u64,
+    pub reserve_balance: u64,
+    pub amount: u64,
+    pub unclaimed_lamports: u64,
+    pub unclaimed_ticket_count: u64,
+}
@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct DepositDirectedEvent {
+    pub state: Pubkey,
+    pub sol_owner: Pubkey,
+    pub validator: Pubkey,
+    pub lamports: u64,
+    pub owner_directed_lamports: u64,
+    pub validator_directed_lamports: u64,
+}
+
+#[event]
+pub struct RedirectStakeEvent {
+    pub state: Pubkey,
+    pub owner: Pubkey,
+    pub old_validator: Pubkey,
+    pub new_validator: Pubkey,
+    pub lamports: u64,
+    pub old_validator_directed_lamports: u64,
+    pub new_validator_directed_lamports: u64,
+}
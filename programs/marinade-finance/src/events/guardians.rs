@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct ConfigGuardiansEvent {
+    pub state: Pubkey,
+    pub guardian_set: Pubkey,
+    pub guardians: Vec<Pubkey>,
+    pub threshold: u8,
+    // empty when created by the admin
+    pub approvals: Vec<Pubkey>,
+}
+
+#[event]
+pub struct GuardianVetoEvent {
+    pub state: Pubkey,
+    pub queued_config_change: Pubkey,
+    pub approvals: Vec<Pubkey>,
+}
+
+#[event]
+pub struct GuardianPauseEvent {
+    pub state: Pubkey,
+    pub approvals: Vec<Pubkey>,
+}
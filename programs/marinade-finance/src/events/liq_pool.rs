@@ -12,11 +12,45 @@ pub struct AddLiquidityEvent {
     pub lp_supply: u64,
     pub sol_added_amount: u64,
     pub lp_minted: u64,
+    // lamports of LP fees added to sol_added_amount, see State::add_liquidity_bonus
+    pub bonus_lamports: u64,
     // MSOL price used
     pub total_virtual_staked_lamports: u64,
     pub msol_supply: u64,
 }
 
+#[event]
+pub struct AddLiquidityMsolEvent {
+    pub state: Pubkey,
+    pub msol_owner: Pubkey,
+    pub user_msol_balance: u64,
+    pub user_lp_balance: u64,
+    pub sol_leg_balance: u64,
+    pub msol_leg_balance: u64,
+    pub lp_supply: u64,
+    pub msol_added_amount: u64,
+    pub lp_minted: u64,
+    // MSOL price used
+    pub total_virtual_staked_lamports: u64,
+    pub msol_supply: u64,
+}
+
+#[event]
+pub struct AddLiquidityProportionalEvent {
+    pub state: Pubkey,
+    pub sol_owner: Pubkey,
+    pub msol_owner: Pubkey,
+    pub user_sol_balance: u64,
+    pub user_msol_balance: u64,
+    pub user_lp_balance: u64,
+    pub sol_leg_balance: u64,
+    pub msol_leg_balance: u64,
+    pub lp_supply: u64,
+    pub sol_added_amount: u64,
+    pub msol_added_amount: u64,
+    pub lp_minted: u64,
+}
+
 #[event]
 pub struct LiquidUnstakeEvent {
     pub state: Pubkey,
@@ -29,12 +63,15 @@ pub struct LiquidUnstakeEvent {
     pub msol_amount: u64,
     pub msol_fee: u64,
     pub treasury_msol_cut: u64,
+    pub lock_msol_cut: u64,
+    pub lp_msol_cut: u64,
     pub sol_amount: u64,
     // params used
     pub lp_liquidity_target: u64,
     pub lp_max_fee: Fee,
     pub lp_min_fee: Fee,
     pub treasury_cut: Fee,
+    pub holding_discount: Fee,
 }
 
 #[event]
@@ -50,3 +87,97 @@ pub struct RemoveLiquidityEvent {
     pub sol_out_amount: u64,
     pub msol_out_amount: u64,
 }
+
+#[event]
+pub struct RemoveLiquiditySolOnlyEvent {
+    pub state: Pubkey,
+    pub sol_leg_balance: u64,
+    pub msol_leg_balance: u64,
+    pub user_lp_balance: u64,
+    pub user_sol_balance: u64,
+    pub lp_mint_supply: u64,
+    pub lp_burned: u64,
+    pub sol_share_amount: u64,
+    pub msol_share_amount: u64,
+    pub msol_fee: u64,
+    pub treasury_msol_cut: u64,
+    pub lock_msol_cut: u64,
+    pub lp_msol_cut: u64,
+    /// SOL paid by the pool for the mSOL share, counted as liquid unstake volume
+    pub msol_swapped_sol_amount: u64,
+    pub sol_out_amount: u64,
+    // params used
+    pub lp_liquidity_target: u64,
+    pub lp_max_fee: Fee,
+    pub lp_min_fee: Fee,
+}
+
+#[event]
+pub struct RemoveLiquidityMsolOnlyEvent {
+    pub state: Pubkey,
+    pub sol_leg_balance: u64,
+    pub msol_leg_balance: u64,
+    pub user_lp_balance: u64,
+    pub user_msol_balance: u64,
+    pub lp_mint_supply: u64,
+    pub lp_burned: u64,
+    pub sol_share_amount: u64,
+    pub msol_share_amount: u64,
+    pub msol_out_amount: u64,
+    // MSOL price used
+    pub total_virtual_staked_lamports: u64,
+    pub msol_supply: u64,
+}
+
+#[event]
+pub struct LockLiquidityEvent {
+    pub state: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub locked_amount: u64,
+    pub unlock_slot: u64,
+    pub locked_lp_supply: u64,
+}
+
+#[event]
+pub struct UnlockLiquidityEvent {
+    pub state: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub locked_amount: u64,
+    pub locked_lp_supply: u64,
+}
+
+#[event]
+pub struct ClaimLpLockFeesEvent {
+    pub state: Pubkey,
+    pub owner: Pubkey,
+    pub msol_amount: u64,
+    pub locked_amount: u64,
+}
+
+#[event]
+pub struct FlashLoanBeginEvent {
+    pub state: Pubkey,
+    pub borrower: Pubkey,
+    pub sol_leg_balance: u64,
+    pub lamports: u64,
+}
+
+#[event]
+pub struct FlashLoanEndEvent {
+    pub state: Pubkey,
+    pub payer: Pubkey,
+    pub lamports: u64,
+    pub fee: u64,
+    pub sol_leg_balance: u64,
+}
+
+#[event]
+pub struct UpdateLpPriceEvent {
+    pub state: Pubkey,
+    pub lp_price: u64,
+    pub sol_leg_balance: u64,
+    pub msol_leg_balance: u64,
+    pub lp_supply: u64,
+}
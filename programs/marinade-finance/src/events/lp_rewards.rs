@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+
+use super::U64ValueChange;
+
+#[event]
+pub struct ConfigureLpRewardsEvent {
+    pub state: Pubkey,
+    pub authority: Pubkey,
+    pub slot: u64,
+    pub lp_rewards: Pubkey,
+    pub reward_mint: Pubkey,
+    pub reward_rate_per_slot_change: U64ValueChange,
+    pub end_slot_change: U64ValueChange,
+}
+
+#[event]
+pub struct StakeLpEvent {
+    pub state: Pubkey,
+    pub lp_rewards: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub staked_amount: u64,
+    pub total_staked: u64,
+    pub acc_reward_per_share: u128,
+}
+
+#[event]
+pub struct UnstakeLpEvent {
+    pub state: Pubkey,
+    pub lp_rewards: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub staked_amount: u64,
+    pub total_staked: u64,
+    pub acc_reward_per_share: u128,
+}
+
+#[event]
+pub struct ClaimLpRewardsEvent {
+    pub state: Pubkey,
+    pub lp_rewards: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub staked_amount: u64,
+    pub acc_reward_per_share: u128,
+}
@@ -10,6 +10,15 @@ pub struct AddValidatorEvent {
     pub score: u32,
 }
 
+#[event]
+pub struct AddValidatorPermissionlessEvent {
+    pub state: Pubkey,
+    pub validator: Pubkey,
+    pub index: u32,
+    pub commission: u8,
+    pub activated_stake: u64,
+}
+
 // TODO: EmergencyUnstake
 // TODO: PartialUnstake
 
@@ -21,6 +30,83 @@ pub struct RemoveValidatorEvent {
     pub operational_sol_balance: u64,
 }
 
+#[event]
+pub struct CheckValidatorCommissionEvent {
+    pub state: Pubkey,
+    pub validator: Pubkey,
+    pub index: u32,
+    pub commission: u8,
+    pub max_commission: u8,
+    pub flagged: bool,
+    pub old_score: u32,
+}
+
+#[event]
+pub struct CheckValidatorDelinquencyEvent {
+    pub state: Pubkey,
+    pub validator: Pubkey,
+    pub index: u32,
+    pub credits: u64,
+    pub stalled_epochs: u64,
+    pub delinquent_epochs: u64,
+    pub flagged: bool,
+    pub old_score: u32,
+}
+
+#[event]
+pub struct SplitStakeForWithdrawEvent {
+    pub state: Pubkey,
+    pub epoch: u64,
+    pub stake_index: u32,
+    pub stake_account: Pubkey,
+    pub last_update_stake_delegation: u64,
+    pub split_stake_account: Pubkey,
+    pub split_stake_index: u32,
+    pub validator_index: u32,
+    pub validator_vote: Pubkey,
+    pub total_unstake_delta: u64,
+    pub amount: u64,
+}
+
+#[event]
+pub struct SetValidatorBlockedEvent {
+    pub state: Pubkey,
+    pub authority: Pubkey,
+    pub slot: u64,
+    pub validator: Pubkey,
+    pub blocked: bool,
+}
+
+#[event]
+pub struct SetValidatorQuarantinedEvent {
+    pub state: Pubkey,
+    pub validator: Pubkey,
+    pub index: u32,
+    pub quarantined: bool,
+    pub old_score: u32,
+}
+
+#[event]
+pub struct QuarantineValidatorEvent {
+    pub state: Pubkey,
+    pub validator: Pubkey,
+    pub index: u32,
+    pub identity: Pubkey,
+    pub metadata_identity: Pubkey,
+    pub old_score: u32,
+}
+
+#[event]
+pub struct SetValidatorMetadataEvent {
+    pub state: Pubkey,
+    pub validator: Pubkey,
+    pub identity: Pubkey,
+    pub name_hash: [u8; 32],
+    pub website_hash: [u8; 32],
+    pub datacenter_hash: [u8; 32],
+    pub asn: u32,
+}
+
 #[event]
 pub struct SetValidatorScoreEvent {
     pub state: Pubkey,
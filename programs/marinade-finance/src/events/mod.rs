@@ -3,11 +3,19 @@ use anchor_lang::prelude::*;
 use crate::state::{fee::FeeCents, Fee};
 
 pub mod admin;
+pub mod affiliated_validator;
 pub mod crank;
 pub mod delayed_unstake;
+pub mod directed_stake;
+pub mod guardians;
 pub mod liq_pool;
+pub mod lp_rewards;
 pub mod management;
+pub mod msol_lock;
+pub mod referral;
+pub mod share_vault;
 pub mod user;
+pub mod validator_bond;
 
 #[derive(Clone, AnchorSerialize, AnchorDeserialize)]
 pub struct U64ValueChange {
@@ -21,6 +29,12 @@ pub struct U32ValueChange {
     pub new: u32,
 }
 
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct U8ValueChange {
+    pub old: u8,
+    pub new: u8,
+}
+
 #[derive(Clone, AnchorSerialize, AnchorDeserialize)]
 pub struct FeeValueChange {
     pub old: Fee,
@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct LockMsolEvent {
+    pub state: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub locked_amount: u64,
+    pub tier: u8,
+    pub unlock_epoch: u64,
+    pub weight: u64,
+    pub total_locked: u64,
+    pub total_weight: u64,
+}
+
+#[event]
+pub struct ExtendLockEvent {
+    pub state: Pubkey,
+    pub owner: Pubkey,
+    pub locked_amount: u64,
+    pub tier: u8,
+    pub unlock_epoch: u64,
+    pub weight: u64,
+    pub total_weight: u64,
+}
+
+#[event]
+pub struct UnlockMsolEvent {
+    pub state: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub msol_fees: u64,
+    pub total_locked: u64,
+    pub total_weight: u64,
+}
@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+
+use crate::{events::FeeValueChange, state::Fee};
+
+#[event]
+pub struct RegisterPartnerEvent {
+    pub state: Pubkey,
+    pub partner: Pubkey,
+    pub referral_partner: Pubkey,
+}
+
+#[event]
+pub struct DepositWithReferralEvent {
+    pub state: Pubkey,
+    pub partner: Pubkey,
+    pub sol_owner: Pubkey,
+    pub lamports: u64,
+    pub deposit_fee: u64,
+    pub msol_reward: u64,
+    pub referred_lamports: u64,
+    pub pending_msol: u64,
+}
+
+#[event]
+pub struct ClaimReferralRewardsEvent {
+    pub state: Pubkey,
+    pub partner: Pubkey,
+    pub msol_amount: u64,
+    pub pending_msol: u64,
+}
+
+#[event]
+pub struct RegisterIntegratorEvent {
+    pub state: Pubkey,
+    pub partner: Pubkey,
+    pub integrator_partner: Pubkey,
+    pub fee_rebate: Fee,
+}
+
+#[event]
+pub struct ConfigIntegratorEvent {
+    pub state: Pubkey,
+    pub partner: Pubkey,
+    pub fee_rebate_change: FeeValueChange,
+}
+
+#[event]
+pub struct IntegratorLiquidUnstakeEvent {
+    pub state: Pubkey,
+    pub partner: Pubkey,
+    pub lamports: u64,
+    pub msol_fee_rebate: u64,
+    pub unstaked_lamports: u64,
+    pub liquid_unstakes: u64,
+}
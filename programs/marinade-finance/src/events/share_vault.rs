@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+
+// field names of the ERC-4626 Deposit and Withdraw events
+
+#[event]
+pub struct InitShareVaultEvent {
+    pub state: Pubkey,
+    pub vault: Pubkey,
+    pub asset_vault: Pubkey,
+    pub share_mint: Pubkey,
+}
+
+#[event]
+pub struct VaultDepositEvent {
+    pub state: Pubkey,
+    pub vault: Pubkey,
+    pub sender: Pubkey,
+    /// owner of the share token account
+    pub owner: Pubkey,
+    pub assets: u64,
+    pub shares: u64,
+    pub total_assets: u64,
+    pub total_shares: u64,
+}
+
+#[event]
+pub struct VaultWithdrawEvent {
+    pub state: Pubkey,
+    pub vault: Pubkey,
+    pub sender: Pubkey,
+    /// owner of the mSOL token account receiving the assets
+    pub receiver: Pubkey,
+    /// owner of the burned shares
+    pub owner: Pubkey,
+    pub assets: u64,
+    pub shares: u64,
+    pub total_assets: u64,
+    pub total_shares: u64,
+}
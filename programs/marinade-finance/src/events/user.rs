@@ -16,6 +16,32 @@ pub struct DepositStakeAccountEvent {
     // MSOL price used
     pub total_virtual_staked_lamports: u64,
     pub msol_supply: u64,
+    // valued with State::deactivating_stake_discount and cooling down to the reserve
+    pub deactivating: bool,
+}
+
+// followed by the DepositStakeAccountEvent of the withdrawn stake account
+#[event]
+pub struct DepositStakePoolTokensEvent {
+    pub state: Pubkey,
+    pub stake_pool: Pubkey,
+    pub pool_mint: Pubkey,
+    pub pool_tokens: u64,
+    pub stake_account: Pubkey,
+    pub stake_account_lamports: u64,
+}
+
+#[event]
+pub struct DepositStakeAccountsEvent {
+    pub state: Pubkey,
+    pub stake_accounts: Vec<Pubkey>,
+    pub delegated: u64, // total lamports in the stake accounts delegations
+    pub total_active_balance: u64,
+    pub user_msol_balance: u64,
+    pub msol_minted: u64,
+    // MSOL price used
+    pub total_virtual_staked_lamports: u64,
+    pub msol_supply: u64,
 }
 
 #[event]
@@ -31,6 +57,8 @@ pub struct DepositEvent {
     pub msol_swapped: u64,
     pub sol_deposited: u64,
     pub msol_minted: u64,
+    // State::deposit_imbalance_fee paid to the SOL leg
+    pub deposit_fee: u64,
     // MSOL price used
     pub total_virtual_staked_lamports: u64,
     pub msol_supply: u64,
@@ -52,6 +80,7 @@ pub struct WithdrawStakeAccountEvent {
     pub split_stake: Pubkey, // output stake account
     pub beneficiary: Pubkey, // withdraw auth for the output stake account
     pub split_lamports: u64,
+    pub deactivated: bool,
     pub fee_bp_cents: u32,
     // MSOL price used
     pub total_virtual_staked_lamports: u64,
@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct FundValidatorBondEvent {
+    pub state: Pubkey,
+    pub validator: Pubkey,
+    pub funder: Pubkey,
+    pub amount: u64,
+    pub bond_amount: u64,
+}
+
+#[event]
+pub struct WithdrawValidatorBondEvent {
+    pub state: Pubkey,
+    pub validator: Pubkey,
+    pub authority: Pubkey,
+    pub amount: u64,
+    pub bond_amount: u64,
+}
+
+#[event]
+pub struct SlashValidatorBondEvent {
+    pub state: Pubkey,
+    pub validator: Pubkey,
+    pub flags: u8,
+    pub amount: u64,
+    pub bond_amount: u64,
+}
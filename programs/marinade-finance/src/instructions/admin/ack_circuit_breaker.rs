@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::MarinadeError, events::admin::AckCircuitBreakerEvent, State};
+
+#[derive(Accounts)]
+pub struct AckCircuitBreaker<'info> {
+    #[account(
+        mut,
+        has_one = admin_authority @ MarinadeError::InvalidAdminAuthority,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Account<'info, State>,
+    pub admin_authority: Signer<'info>,
+}
+
+impl<'info> AckCircuitBreaker<'info> {
+    // fn ack_circuit_breaker()
+    // accepts the current mSOL price and resumes the operations not paused before the trip
+    pub fn process(&mut self) -> Result<()> {
+        require!(
+            self.state.circuit_breaker_tripped,
+            MarinadeError::CircuitBreakerNotTripped
+        );
+        self.state.circuit_breaker_tripped = false;
+        self.state.msol_price_reference = self.state.msol_price;
+        self.state.paused = self.state.paused_before_circuit_breaker;
+        self.state.paused_before_circuit_breaker = 0;
+
+        emit!(AckCircuitBreakerEvent {
+            state: self.state.key(),
+            authority: self.admin_authority.key(),
+            slot: Clock::get()?.slot,
+            paused: self.state.paused,
+        });
+        Ok(())
+    }
+}
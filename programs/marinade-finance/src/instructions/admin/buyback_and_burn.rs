@@ -0,0 +1,177 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    instruction::{AccountMeta, Instruction},
+    program::invoke_signed,
+};
+use anchor_spl::token::{burn, Burn, Mint, Token, TokenAccount};
+
+use crate::{
+    error::MarinadeError,
+    events::admin::BuybackAndBurnEvent,
+    require_lte,
+    state::{buyback::BuybackConfig, paused_operations::PausedOperations, roles::Role},
+    State,
+};
+
+#[derive(Accounts)]
+pub struct BuybackAndBurn<'info> {
+    #[account(
+        constraint = state.has_role(Role::Treasurer, treasurer_authority.key)
+            @ MarinadeError::InvalidTreasurerAuthority,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+    pub treasurer_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = state,
+        has_one = governance_mint,
+        seeds = [
+            &state.key().to_bytes(),
+            BuybackConfig::SEED
+        ],
+        bump = buyback_config.bump,
+    )]
+    pub buyback_config: Box<Account<'info, BuybackConfig>>,
+    /// CHECK: PDA
+    #[account(
+        seeds = [
+            &state.key().to_bytes(),
+            BuybackConfig::AUTHORITY_SEED
+        ],
+        bump = buyback_config.authority_bump,
+    )]
+    pub buyback_authority: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = buyback_config.msol_vault
+    )]
+    pub msol_vault: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        address = buyback_config.governance_vault
+    )]
+    pub governance_vault: Box<Account<'info, TokenAccount>>,
+    #[account(mut)]
+    pub governance_mint: Box<Account<'info, Mint>>,
+
+    /// CHECK: whitelisted in buyback_config
+    #[account(
+        executable,
+        address = buyback_config.amm_program @ MarinadeError::InvalidBuybackSwap
+    )]
+    pub amm_program: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> BuybackAndBurn<'info> {
+    // fn buyback_and_burn()
+    // swaps up to `msol_amount` of the vault mSOL through the whitelisted AMM and burns the
+    // governance tokens received. `swap_data` is the AMM instruction data and the AMM accounts
+    // are passed in remaining_accounts, the buyback authority signs the swap.
+    // The balance deltas of the vaults are checked, not the AMM accounts
+    pub fn process(
+        &mut self,
+        swap_accounts: &[AccountInfo<'info>],
+        msol_amount: u64,
+        min_burn_amount: u64,
+        swap_data: Vec<u8>,
+    ) -> Result<()> {
+        self.state.check_paused(PausedOperations::VALIDATOR_OPS)?;
+        let epoch = Clock::get()?.epoch;
+        require_gt!(msol_amount, 0, MarinadeError::InvalidBuybackSwap);
+        require_lte!(
+            msol_amount,
+            self.buyback_config.remaining_budget(epoch),
+            MarinadeError::BuybackBudgetExceeded
+        );
+
+        let msol_before = self.msol_vault.amount;
+        let governance_before = self.governance_vault.amount;
+
+        let authority = self.buyback_authority.key();
+        let instruction = Instruction {
+            program_id: self.amm_program.key(),
+            accounts: swap_accounts
+                .iter()
+                .map(|account| AccountMeta {
+                    pubkey: *account.key,
+                    is_signer: account.is_signer || *account.key == authority,
+                    is_writable: account.is_writable,
+                })
+                .collect(),
+            data: swap_data,
+        };
+        let mut account_infos = swap_accounts.to_vec();
+        account_infos.push(self.amm_program.to_account_info());
+        invoke_signed(
+            &instruction,
+            &account_infos,
+            &[&[
+                &self.state.key().to_bytes(),
+                BuybackConfig::AUTHORITY_SEED,
+                &[self.buyback_config.authority_bump],
+            ]],
+        )?;
+
+        self.msol_vault.reload()?;
+        self.governance_vault.reload()?;
+        // the AMM must not take over the vaults
+        require_keys_eq!(
+            self.msol_vault.owner,
+            authority,
+            MarinadeError::InvalidBuybackSwap
+        );
+        require_keys_eq!(
+            self.governance_vault.owner,
+            authority,
+            MarinadeError::InvalidBuybackSwap
+        );
+        let msol_spent = msol_before
+            .checked_sub(self.msol_vault.amount)
+            .ok_or_else(|| error!(MarinadeError::InvalidBuybackSwap))?;
+        require_lte!(msol_spent, msol_amount, MarinadeError::InvalidBuybackSwap);
+        let bought = self
+            .governance_vault
+            .amount
+            .checked_sub(governance_before)
+            .ok_or_else(|| error!(MarinadeError::InvalidBuybackSwap))?;
+        require_gte!(bought, min_burn_amount, MarinadeError::SlippageExceeded);
+
+        if bought > 0 {
+            burn(
+                CpiContext::new_with_signer(
+                    self.token_program.to_account_info(),
+                    Burn {
+                        mint: self.governance_mint.to_account_info(),
+                        from: self.governance_vault.to_account_info(),
+                        authority: self.buyback_authority.to_account_info(),
+                    },
+                    &[&[
+                        &self.state.key().to_bytes(),
+                        BuybackConfig::AUTHORITY_SEED,
+                        &[self.buyback_config.authority_bump],
+                    ]],
+                ),
+                bought,
+            )?;
+        }
+        self.buyback_config.on_buyback(epoch, msol_spent, bought)?;
+
+        emit!(BuybackAndBurnEvent {
+            state: self.state.key(),
+            epoch,
+            amm_program: self.amm_program.key(),
+            msol_spent,
+            burned: bought,
+            spent_in_epoch: self.buyback_config.spent_in_epoch,
+            epoch_budget: self.buyback_config.epoch_budget,
+            total_msol_spent: self.buyback_config.total_msol_spent,
+            total_burned: self.buyback_config.total_burned,
+        });
+
+        Ok(())
+    }
+}
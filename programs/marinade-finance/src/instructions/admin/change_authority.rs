@@ -10,7 +10,8 @@ use crate::{
 pub struct ChangeAuthority<'info> {
     #[account(
         mut,
-        has_one = admin_authority @ MarinadeError::InvalidAdminAuthority
+        has_one = admin_authority @ MarinadeError::InvalidAdminAuthority,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
     )]
     pub state: Account<'info, State>,
     pub admin_authority: Signer<'info>,
@@ -83,6 +84,8 @@ impl<'info> ChangeAuthority<'info> {
 
         emit!(ChangeAuthorityEvent {
             state: self.state.key(),
+            authority: self.admin_authority.key(),
+            slot: Clock::get()?.slot,
             admin_change,
             validator_manager_change,
             operational_sol_account_change,
@@ -0,0 +1,132 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::system_program;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::{
+    error::MarinadeError,
+    events::{admin::ConfigBuybackEvent, PubkeyValueChange, U64ValueChange},
+    state::buyback::BuybackConfig,
+    State,
+};
+
+#[derive(Accounts)]
+pub struct ConfigBuyback<'info> {
+    #[account(
+        has_one = admin_authority @ MarinadeError::InvalidAdminAuthority,
+        has_one = msol_mint,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+    pub admin_authority: Signer<'info>,
+
+    pub msol_mint: Box<Account<'info, Mint>>,
+    pub governance_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init_if_needed,
+        payer = rent_payer,
+        space = BuybackConfig::LEN,
+        seeds = [
+            &state.key().to_bytes(),
+            BuybackConfig::SEED
+        ],
+        bump,
+    )]
+    pub buyback_config: Box<Account<'info, BuybackConfig>>,
+    /// CHECK: PDA
+    #[account(
+        seeds = [
+            &state.key().to_bytes(),
+            BuybackConfig::AUTHORITY_SEED
+        ],
+        bump,
+    )]
+    pub buyback_authority: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = rent_payer,
+        token::mint = msol_mint,
+        token::authority = buyback_authority,
+        seeds = [
+            &state.key().to_bytes(),
+            BuybackConfig::MSOL_VAULT_SEED
+        ],
+        bump,
+    )]
+    pub msol_vault: Box<Account<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = rent_payer,
+        token::mint = governance_mint,
+        token::authority = buyback_authority,
+        seeds = [
+            &state.key().to_bytes(),
+            BuybackConfig::GOVERNANCE_VAULT_SEED
+        ],
+        bump,
+    )]
+    pub governance_vault: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: whitelisted AMM, only its key is stored
+    #[account(executable)]
+    pub amm_program: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        owner = system_program::ID
+    )]
+    pub rent_payer: Signer<'info>,
+
+    pub rent: Sysvar<'info, Rent>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> ConfigBuyback<'info> {
+    // fn config_buyback()
+    // whitelists the AMM and sets the mSOL the buyback can spend per epoch.
+    // The governance mint is fixed once set, the governance vault is bound to it
+    pub fn process(&mut self, epoch_budget: u64, bump: u8, authority_bump: u8) -> Result<()> {
+        self.state.check_config_not_timelocked()?;
+        require!(
+            self.buyback_config.governance_mint == Pubkey::default()
+                || self.buyback_config.governance_mint == self.governance_mint.key(),
+            MarinadeError::BuybackGovernanceMintChanged
+        );
+        self.buyback_config.state = self.state.key();
+        self.buyback_config.bump = bump;
+        self.buyback_config.authority_bump = authority_bump;
+        self.buyback_config.governance_mint = self.governance_mint.key();
+        self.buyback_config.msol_vault = self.msol_vault.key();
+        self.buyback_config.governance_vault = self.governance_vault.key();
+
+        let amm_program_change = {
+            let old = self.buyback_config.amm_program;
+            self.buyback_config.amm_program = self.amm_program.key();
+            PubkeyValueChange {
+                old,
+                new: self.amm_program.key(),
+            }
+        };
+        let epoch_budget_change = {
+            let old = self.buyback_config.epoch_budget;
+            self.buyback_config.epoch_budget = epoch_budget;
+            U64ValueChange {
+                old,
+                new: epoch_budget,
+            }
+        };
+
+        emit!(ConfigBuybackEvent {
+            state: self.state.key(),
+            authority: self.admin_authority.key(),
+            slot: Clock::get()?.slot,
+            governance_mint: self.governance_mint.key(),
+            amm_program_change,
+            epoch_budget_change,
+        });
+
+        Ok(())
+    }
+}
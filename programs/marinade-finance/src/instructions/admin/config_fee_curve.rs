@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::system_program;
+
+use crate::{
+    events::{admin::ConfigFeeCurveEvent, PubkeyValueChange},
+    state::fee_curve::{FeeCurve, FeeCurvePoint, FeeSizeTier},
+    MarinadeError, State,
+};
+
+#[derive(Accounts)]
+pub struct ConfigFeeCurve<'info> {
+    #[account(
+        mut,
+        has_one = admin_authority @ MarinadeError::InvalidAdminAuthority,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+    pub admin_authority: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = rent_payer,
+        space = FeeCurve::LEN,
+        seeds = [
+            &state.key().to_bytes(),
+            FeeCurve::SEED
+        ],
+        bump,
+    )]
+    pub fee_curve: Box<Account<'info, FeeCurve>>,
+
+    #[account(
+        mut,
+        owner = system_program::ID
+    )]
+    pub rent_payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ConfigFeeCurve<'info> {
+    /// sets the liquid unstake fee curve and its size tiers, empty points return to the linear fee
+    pub fn process(
+        &mut self,
+        points: Vec<FeeCurvePoint>,
+        size_tiers: Vec<FeeSizeTier>,
+        bump: u8,
+    ) -> Result<()> {
+        self.state.check_config_not_timelocked()?;
+        self.fee_curve.state = self.state.key();
+        self.fee_curve.bump = bump;
+        let new_fee_curve = if points.is_empty() {
+            Pubkey::default()
+        } else {
+            self.fee_curve.set_points(&points)?;
+            self.fee_curve.set_size_tiers(&size_tiers)?;
+            self.fee_curve.key()
+        };
+
+        let fee_curve_change = {
+            let old = self.state.liq_pool_ext.fee_curve;
+            self.state.liq_pool_ext.fee_curve = new_fee_curve;
+            PubkeyValueChange {
+                old,
+                new: new_fee_curve,
+            }
+        };
+
+        emit!(ConfigFeeCurveEvent {
+            state: self.state.key(),
+            authority: self.admin_authority.key(),
+            slot: Clock::get()?.slot,
+            fee_curve_change,
+            points,
+            size_tiers,
+        });
+
+        Ok(())
+    }
+}
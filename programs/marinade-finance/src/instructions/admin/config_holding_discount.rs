@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::MarinadeError, events::admin::ConfigHoldingDiscountEvent,
+    state::holding_record::HoldingDiscountTier, State,
+};
+
+#[derive(Accounts)]
+pub struct ConfigHoldingDiscount<'info> {
+    #[account(
+        mut,
+        has_one = admin_authority @ MarinadeError::InvalidAdminAuthority,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+    pub admin_authority: Signer<'info>,
+}
+
+impl<'info> ConfigHoldingDiscount<'info> {
+    // fn config_holding_discount()
+    // sets the liquid unstake fee discount schedule by holding time, no tiers disable it
+    pub fn process(&mut self, tiers: Vec<HoldingDiscountTier>) -> Result<()> {
+        self.state.check_config_not_timelocked()?;
+        HoldingDiscountTier::validate(&tiers)?;
+        self.state.holding_discount_tiers =
+            [HoldingDiscountTier::default(); HoldingDiscountTier::MAX_TIERS];
+        self.state.holding_discount_tiers[..tiers.len()].copy_from_slice(&tiers);
+
+        emit!(ConfigHoldingDiscountEvent {
+            state: self.state.key(),
+            authority: self.admin_authority.key(),
+            slot: Clock::get()?.slot,
+            tiers,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,94 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::system_program;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::{
+    events::{admin::ConfigInsuranceFundEvent, FeeValueChange},
+    state::{insurance_fund::InsuranceFund, roles::Role, Fee},
+    MarinadeError, State,
+};
+
+#[derive(Accounts)]
+pub struct ConfigInsuranceFund<'info> {
+    #[account(
+        mut,
+        constraint = state.has_role(Role::Treasurer, treasurer_authority.key)
+            @ MarinadeError::InvalidTreasurerAuthority,
+        has_one = msol_mint,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+    pub treasurer_authority: Signer<'info>,
+
+    pub msol_mint: Box<Account<'info, Mint>>,
+
+    /// CHECK: PDA
+    #[account(
+        seeds = [
+            &state.key().to_bytes(),
+            InsuranceFund::AUTHORITY_SEED
+        ],
+        bump,
+    )]
+    pub insurance_fund_authority: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = rent_payer,
+        token::mint = msol_mint,
+        token::authority = insurance_fund_authority,
+        seeds = [
+            &state.key().to_bytes(),
+            InsuranceFund::VAULT_SEED
+        ],
+        bump,
+    )]
+    pub insurance_fund_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        owner = system_program::ID
+    )]
+    pub rent_payer: Signer<'info>,
+
+    pub rent: Sysvar<'info, Rent>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> ConfigInsuranceFund<'info> {
+    /// enables the insurance fund and sets the share of the protocol reward fee it gets
+    pub fn process(
+        &mut self,
+        fee_share: Fee,
+        insurance_fund_authority_bump_seed: u8,
+    ) -> Result<()> {
+        self.state.check_config_not_timelocked()?;
+        fee_share.check_max(
+            InsuranceFund::MAX_FEE_SHARE,
+            MarinadeError::InsuranceFundFeeShareIsTooHigh,
+        )?;
+
+        self.state.insurance_fund.authority_bump_seed = insurance_fund_authority_bump_seed;
+        self.state.insurance_fund.vault = self.insurance_fund_vault.key();
+
+        let fee_share_change = {
+            let old = self.state.insurance_fund.fee_share;
+            self.state.insurance_fund.fee_share = fee_share;
+            FeeValueChange {
+                old,
+                new: fee_share,
+            }
+        };
+
+        emit!(ConfigInsuranceFundEvent {
+            state: self.state.key(),
+            authority: self.treasurer_authority.key(),
+            slot: Clock::get()?.slot,
+            insurance_fund_vault: self.insurance_fund_vault.key(),
+            fee_share_change,
+        });
+
+        Ok(())
+    }
+}
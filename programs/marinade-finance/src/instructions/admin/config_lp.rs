@@ -1,7 +1,8 @@
 use anchor_lang::prelude::*;
 
 use crate::{
-    events::{admin::ConfigLpEvent, FeeValueChange, U64ValueChange},
+    events::{admin::ConfigLpEvent, BoolValueChange, FeeValueChange, U64ValueChange},
+    require_lte,
     state::Fee,
     MarinadeError, State,
 };
@@ -12,13 +13,21 @@ pub struct ConfigLpParams {
     pub max_fee: Option<Fee>,
     pub liquidity_target: Option<u64>,
     pub treasury_cut: Option<Fee>,
+    pub msol_leg_target: Option<Fee>,
+    pub lp_wallet_cap: Option<u64>,
+    pub lp_global_cap: Option<u64>,
+    pub flash_loan_enabled: Option<bool>,
+    pub flash_loan_fee: Option<Fee>,
+    pub lp_min_holding_slots: Option<u64>,
+    pub add_liquidity_bonus_share: Option<Fee>,
 }
 
 #[derive(Accounts)]
 pub struct ConfigLp<'info> {
     #[account(
         mut,
-        has_one = admin_authority @ MarinadeError::InvalidAdminAuthority
+        has_one = admin_authority @ MarinadeError::InvalidAdminAuthority,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
     )]
     pub state: Account<'info, State>,
     pub admin_authority: Signer<'info>,
@@ -32,8 +41,16 @@ impl<'info> ConfigLp<'info> {
             max_fee,
             liquidity_target,
             treasury_cut,
+            msol_leg_target,
+            lp_wallet_cap,
+            lp_global_cap,
+            flash_loan_enabled,
+            flash_loan_fee,
+            lp_min_holding_slots,
+            add_liquidity_bonus_share,
         }: ConfigLpParams,
     ) -> Result<()> {
+        self.state.check_config_not_timelocked()?;
         let min_fee_change = if let Some(min_fee) = min_fee {
             let old = self.state.liq_pool.lp_min_fee;
             self.state.liq_pool.lp_min_fee = min_fee;
@@ -72,14 +89,107 @@ impl<'info> ConfigLp<'info> {
             None
         };
 
+        let msol_leg_target_change = if let Some(msol_leg_target) = msol_leg_target {
+            let old = self.state.liq_pool_ext.msol_leg_target;
+            self.state.liq_pool_ext.msol_leg_target = msol_leg_target;
+            Some(FeeValueChange {
+                old,
+                new: msol_leg_target,
+            })
+        } else {
+            None
+        };
+
+        let lp_wallet_cap_change = if let Some(lp_wallet_cap) = lp_wallet_cap {
+            let old = self.state.liq_pool_ext.lp_wallet_cap;
+            self.state.liq_pool_ext.lp_wallet_cap = lp_wallet_cap;
+            Some(U64ValueChange {
+                old,
+                new: lp_wallet_cap,
+            })
+        } else {
+            None
+        };
+
+        let lp_global_cap_change = if let Some(lp_global_cap) = lp_global_cap {
+            let old = self.state.liq_pool_ext.lp_global_cap;
+            self.state.liq_pool_ext.lp_global_cap = lp_global_cap;
+            Some(U64ValueChange {
+                old,
+                new: lp_global_cap,
+            })
+        } else {
+            None
+        };
+
+        let flash_loan_enabled_change = if let Some(flash_loan_enabled) = flash_loan_enabled {
+            let old = self.state.liq_pool_ext.flash_loan_enabled;
+            self.state.liq_pool_ext.flash_loan_enabled = flash_loan_enabled;
+            Some(BoolValueChange {
+                old,
+                new: flash_loan_enabled,
+            })
+        } else {
+            None
+        };
+
+        let flash_loan_fee_change = if let Some(flash_loan_fee) = flash_loan_fee {
+            let old = self.state.liq_pool_ext.flash_loan_fee;
+            self.state.liq_pool_ext.flash_loan_fee = flash_loan_fee;
+            Some(FeeValueChange {
+                old,
+                new: flash_loan_fee,
+            })
+        } else {
+            None
+        };
+
+        let lp_min_holding_slots_change = if let Some(lp_min_holding_slots) = lp_min_holding_slots {
+            let old = self.state.liq_pool_ext.lp_min_holding_slots;
+            self.state.liq_pool_ext.lp_min_holding_slots = lp_min_holding_slots;
+            Some(U64ValueChange {
+                old,
+                new: lp_min_holding_slots,
+            })
+        } else {
+            None
+        };
+
+        let add_liquidity_bonus_share_change =
+            if let Some(add_liquidity_bonus_share) = add_liquidity_bonus_share {
+                require_lte!(
+                    add_liquidity_bonus_share,
+                    State::MAX_ADD_LIQUIDITY_BONUS_SHARE,
+                    MarinadeError::AddLiquidityBonusShareIsTooHigh
+                );
+                let old = self.state.add_liquidity_bonus_share;
+                self.state.add_liquidity_bonus_share = add_liquidity_bonus_share;
+                Some(FeeValueChange {
+                    old,
+                    new: add_liquidity_bonus_share,
+                })
+            } else {
+                None
+            };
+
         self.state.liq_pool.validate()?;
+        self.state.liq_pool_ext.validate()?;
 
         emit!(ConfigLpEvent {
             state: self.state.key(),
+            authority: self.admin_authority.key(),
+            slot: Clock::get()?.slot,
             min_fee_change,
             max_fee_change,
             liquidity_target_change,
-            treasury_cut_change
+            treasury_cut_change,
+            msol_leg_target_change,
+            lp_wallet_cap_change,
+            lp_global_cap_change,
+            flash_loan_enabled_change,
+            flash_loan_fee_change,
+            lp_min_holding_slots_change,
+            add_liquidity_bonus_share_change,
         });
         Ok(())
     }
@@ -0,0 +1,103 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::system_program;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::{
+    events::{admin::ConfigLpLockEvent, FeeValueChange},
+    state::{lp_lock::LpLock, roles::Role, Fee},
+    MarinadeError, State,
+};
+
+#[derive(Accounts)]
+pub struct ConfigLpLock<'info> {
+    #[account(
+        mut,
+        constraint = state.has_role(Role::Treasurer, treasurer_authority.key)
+            @ MarinadeError::InvalidTreasurerAuthority,
+        has_one = msol_mint,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+    pub treasurer_authority: Signer<'info>,
+
+    pub msol_mint: Box<Account<'info, Mint>>,
+    #[account(address = state.liq_pool.lp_mint)]
+    pub lp_mint: Box<Account<'info, Mint>>,
+
+    /// CHECK: PDA
+    #[account(
+        seeds = [
+            &state.key().to_bytes(),
+            LpLock::AUTHORITY_SEED
+        ],
+        bump,
+    )]
+    pub lp_lock_authority: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = rent_payer,
+        token::mint = lp_mint,
+        token::authority = lp_lock_authority,
+        seeds = [
+            &state.key().to_bytes(),
+            LpLock::VAULT_SEED
+        ],
+        bump,
+    )]
+    pub lp_lock_vault: Box<Account<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = rent_payer,
+        token::mint = msol_mint,
+        token::authority = lp_lock_authority,
+        seeds = [
+            &state.key().to_bytes(),
+            LpLock::FEE_VAULT_SEED
+        ],
+        bump,
+    )]
+    pub lp_lock_fee_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        owner = system_program::ID
+    )]
+    pub rent_payer: Signer<'info>,
+
+    pub rent: Sysvar<'info, Rent>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> ConfigLpLock<'info> {
+    /// enables the time-locked LP positions and sets the share of the liquid unstake LP fee they get
+    pub fn process(&mut self, lock_fee_boost: Fee, lp_lock_authority_bump_seed: u8) -> Result<()> {
+        self.state.check_config_not_timelocked()?;
+        self.state.liq_pool_ext.lp_lock_authority_bump_seed = lp_lock_authority_bump_seed;
+        self.state.liq_pool_ext.lp_lock_vault = self.lp_lock_vault.key();
+        self.state.liq_pool_ext.lp_lock_fee_vault = self.lp_lock_fee_vault.key();
+
+        let lock_fee_boost_change = {
+            let old = self.state.liq_pool_ext.lock_fee_boost;
+            self.state.liq_pool_ext.lock_fee_boost = lock_fee_boost;
+            FeeValueChange {
+                old,
+                new: lock_fee_boost,
+            }
+        };
+
+        self.state.liq_pool_ext.validate()?;
+
+        emit!(ConfigLpLockEvent {
+            state: self.state.key(),
+            authority: self.treasurer_authority.key(),
+            slot: Clock::get()?.slot,
+            lp_lock_vault: self.lp_lock_vault.key(),
+            lp_lock_fee_vault: self.lp_lock_fee_vault.key(),
+            lock_fee_boost_change,
+        });
+
+        Ok(())
+    }
+}
@@ -1,6 +1,6 @@
 use crate::events::{
     admin::ConfigMarinadeEvent, BoolValueChange, FeeCentsValueChange, FeeValueChange,
-    U64ValueChange,
+    PubkeyValueChange, U64ValueChange, U8ValueChange,
 };
 use crate::{
     require_lte,
@@ -22,13 +22,60 @@ pub struct ConfigMarinadeParams {
     pub delayed_unstake_fee: Option<FeeCents>,
     pub withdraw_stake_account_fee: Option<FeeCents>,
     pub max_stake_moved_per_epoch: Option<Fee>,
+    pub max_liquid_unstake_per_epoch: Option<u64>,
+    pub stale_ticket_epochs: Option<u64>,
+    pub config_change_delay_slots: Option<u64>,
+    pub msol_price_change_limit: Option<Fee>,
+    pub max_directed_stake_share: Option<Fee>,
+    pub max_commission: Option<u8>,
+    pub delinquent_epochs: Option<u64>,
+    pub max_validator_stake_share: Option<Fee>,
+    pub min_validator_stake: Option<u64>,
+    pub min_validator_bond: Option<u64>,
+    pub validator_bond_slash: Option<Fee>,
+    pub keeper_reward: Option<u64>,
+    pub delegation_strategy: Option<Pubkey>,
+    pub deactivating_stake_discount: Option<Fee>,
+    pub management_fee: Option<Fee>,
+    pub deposit_imbalance_fee: Option<Fee>,
+    pub deposit_imbalance_threshold: Option<Fee>,
+    pub msol_usd_feed: Option<Pubkey>,
+    pub sol_usd_feed: Option<Pubkey>,
+    pub price_feed_tolerance: Option<Fee>,
+    pub max_epoch_yield: Option<Fee>,
+}
+
+impl ConfigMarinadeParams {
+    // borsh size with all the values set
+    pub const MAX_LEN: usize = 5 // rewards_fee
+        + 9 * 6 // slots_for_stake_delta .. liquidity_sol_cap
+        + 2 // withdraw_stake_account_enabled
+        + 5 * 3 // delayed_unstake_fee, withdraw_stake_account_fee, max_stake_moved_per_epoch
+        + 9 * 3 // max_liquid_unstake_per_epoch, stale_ticket_epochs, config_change_delay_slots
+        + 5 * 2 // msol_price_change_limit, max_directed_stake_share
+        + 2 // max_commission
+        + 9 // delinquent_epochs
+        + 5 // max_validator_stake_share
+        + 9 * 2 // min_validator_stake, min_validator_bond
+        + 5 // validator_bond_slash
+        + 9 // keeper_reward
+        + 33 // delegation_strategy
+        + 5 // deactivating_stake_discount
+        + 5 // management_fee
+        + 5 // deposit_imbalance_fee
+        + 5 // deposit_imbalance_threshold
+        + 1 + 32 // msol_usd_feed
+        + 1 + 32 // sol_usd_feed
+        + 5 // price_feed_tolerance
+        + 5; // max_epoch_yield
 }
 
 #[derive(Accounts)]
 pub struct ConfigMarinade<'info> {
     #[account(
         mut,
-        has_one = admin_authority @ MarinadeError::InvalidAdminAuthority
+        has_one = admin_authority @ MarinadeError::InvalidAdminAuthority,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
     )]
     pub state: Account<'info, State>,
     pub admin_authority: Signer<'info>,
@@ -36,7 +83,12 @@ pub struct ConfigMarinade<'info> {
 
 impl<'info> ConfigMarinade<'info> {
     // fn config_marinade()
-    pub fn process(
+    pub fn process(&mut self, params: ConfigMarinadeParams) -> Result<()> {
+        self.state.check_config_not_timelocked()?;
+        self.apply(params)
+    }
+
+    pub(crate) fn apply(
         &mut self,
         ConfigMarinadeParams {
             rewards_fee,
@@ -50,6 +102,27 @@ impl<'info> ConfigMarinade<'info> {
             delayed_unstake_fee,
             withdraw_stake_account_fee,
             max_stake_moved_per_epoch,
+            max_liquid_unstake_per_epoch,
+            stale_ticket_epochs,
+            config_change_delay_slots,
+            msol_price_change_limit,
+            max_directed_stake_share,
+            max_commission,
+            delinquent_epochs,
+            max_validator_stake_share,
+            min_validator_stake,
+            min_validator_bond,
+            validator_bond_slash,
+            keeper_reward,
+            delegation_strategy,
+            deactivating_stake_discount,
+            management_fee,
+            deposit_imbalance_fee,
+            deposit_imbalance_threshold,
+            msol_usd_feed,
+            sol_usd_feed,
+            price_feed_tolerance,
+            max_epoch_yield,
         }: ConfigMarinadeParams,
     ) -> Result<()> {
         let rewards_fee_change = if let Some(rewards_fee) = rewards_fee {
@@ -59,7 +132,20 @@ impl<'info> ConfigMarinade<'info> {
                 MarinadeError::RewardsFeeIsTooHigh
             );
             let old = self.state.reward_fee;
+            require_lte!(
+                old.abs_diff(rewards_fee),
+                State::MAX_REWARD_FEE_CHANGE,
+                MarinadeError::RewardsFeeChangeIsTooHigh
+            );
+            let epoch = Clock::get()?.epoch;
+            // the initial value is 0 and the first epochs of a cluster are not a concern
+            require_gt!(
+                epoch,
+                self.state.reward_fee_change_epoch,
+                MarinadeError::RewardsFeeAlreadyChanged
+            );
             self.state.reward_fee = rewards_fee;
+            self.state.reward_fee_change_epoch = epoch;
             Some(FeeValueChange {
                 old,
                 new: rewards_fee,
@@ -214,8 +300,281 @@ impl<'info> ConfigMarinade<'info> {
                 None
             };
 
+        let max_liquid_unstake_per_epoch_change =
+            if let Some(max_liquid_unstake_per_epoch) = max_liquid_unstake_per_epoch {
+                let old = self.state.liq_pool_ext.max_liquid_unstake_per_epoch;
+                self.state.liq_pool_ext.max_liquid_unstake_per_epoch = max_liquid_unstake_per_epoch;
+                Some(U64ValueChange {
+                    old,
+                    new: max_liquid_unstake_per_epoch,
+                })
+            } else {
+                None
+            };
+
+        let stale_ticket_epochs_change = if let Some(stale_ticket_epochs) = stale_ticket_epochs {
+            let old = self.state.stale_ticket_epochs;
+            self.state.stale_ticket_epochs = stale_ticket_epochs;
+            Some(U64ValueChange {
+                old,
+                new: stale_ticket_epochs,
+            })
+        } else {
+            None
+        };
+
+        let config_change_delay_slots_change =
+            if let Some(config_change_delay_slots) = config_change_delay_slots {
+                require_lte!(
+                    config_change_delay_slots,
+                    State::MAX_CONFIG_CHANGE_DELAY_SLOTS,
+                    MarinadeError::ConfigChangeDelayIsTooHigh
+                );
+                let old = self.state.config_change_delay_slots;
+                self.state.config_change_delay_slots = config_change_delay_slots;
+                Some(U64ValueChange {
+                    old,
+                    new: config_change_delay_slots,
+                })
+            } else {
+                None
+            };
+
+        let msol_price_change_limit_change =
+            if let Some(msol_price_change_limit) = msol_price_change_limit {
+                msol_price_change_limit.check()?;
+                let old = self.state.msol_price_change_limit;
+                self.state.msol_price_change_limit = msol_price_change_limit;
+                Some(FeeValueChange {
+                    old,
+                    new: msol_price_change_limit,
+                })
+            } else {
+                None
+            };
+
+        let max_directed_stake_share_change =
+            if let Some(max_directed_stake_share) = max_directed_stake_share {
+                max_directed_stake_share.check()?;
+                let old = self.state.max_directed_stake_share;
+                self.state.max_directed_stake_share = max_directed_stake_share;
+                Some(FeeValueChange {
+                    old,
+                    new: max_directed_stake_share,
+                })
+            } else {
+                None
+            };
+
+        let max_commission_change = if let Some(max_commission) = max_commission {
+            require_lte!(
+                max_commission,
+                State::MAX_COMMISSION,
+                MarinadeError::InvalidMaxCommission
+            );
+            let old = self.state.max_commission;
+            self.state.max_commission = max_commission;
+            Some(U8ValueChange {
+                old,
+                new: max_commission,
+            })
+        } else {
+            None
+        };
+
+        let delinquent_epochs_change = if let Some(delinquent_epochs) = delinquent_epochs {
+            let old = self.state.delinquent_epochs;
+            self.state.delinquent_epochs = delinquent_epochs;
+            Some(U64ValueChange {
+                old,
+                new: delinquent_epochs,
+            })
+        } else {
+            None
+        };
+
+        let max_validator_stake_share_change =
+            if let Some(max_validator_stake_share) = max_validator_stake_share {
+                max_validator_stake_share.check()?;
+                let old = self.state.max_validator_stake_share;
+                self.state.max_validator_stake_share = max_validator_stake_share;
+                Some(FeeValueChange {
+                    old,
+                    new: max_validator_stake_share,
+                })
+            } else {
+                None
+            };
+
+        let min_validator_stake_change = if let Some(min_validator_stake) = min_validator_stake {
+            // u64::MAX disables add_validator_permissionless
+            let old = self.state.min_validator_stake;
+            self.state.min_validator_stake = min_validator_stake;
+            Some(U64ValueChange {
+                old,
+                new: min_validator_stake,
+            })
+        } else {
+            None
+        };
+
+        let min_validator_bond_change = if let Some(min_validator_bond) = min_validator_bond {
+            let old = self.state.min_validator_bond;
+            self.state.min_validator_bond = min_validator_bond;
+            Some(U64ValueChange {
+                old,
+                new: min_validator_bond,
+            })
+        } else {
+            None
+        };
+
+        let validator_bond_slash_change = if let Some(validator_bond_slash) = validator_bond_slash {
+            validator_bond_slash.check()?;
+            let old = self.state.validator_bond_slash;
+            self.state.validator_bond_slash = validator_bond_slash;
+            Some(FeeValueChange {
+                old,
+                new: validator_bond_slash,
+            })
+        } else {
+            None
+        };
+
+        let keeper_reward_change = if let Some(keeper_reward) = keeper_reward {
+            // 0 disables the keeper rewards
+            let old = self.state.keeper_reward;
+            self.state.keeper_reward = keeper_reward;
+            Some(U64ValueChange {
+                old,
+                new: keeper_reward,
+            })
+        } else {
+            None
+        };
+
+        let delegation_strategy_change = if let Some(delegation_strategy) = delegation_strategy {
+            // a DelegationStrategy of this state, Pubkey::default() goes back to the State parameters
+            let old = self.state.delegation_strategy;
+            self.state.delegation_strategy = delegation_strategy;
+            Some(PubkeyValueChange {
+                old,
+                new: delegation_strategy,
+            })
+        } else {
+            None
+        };
+
+        let deactivating_stake_discount_change =
+            if let Some(deactivating_stake_discount) = deactivating_stake_discount {
+                deactivating_stake_discount.check()?;
+                let old = self.state.deactivating_stake_discount;
+                self.state.deactivating_stake_discount = deactivating_stake_discount;
+                Some(FeeValueChange {
+                    old,
+                    new: deactivating_stake_discount,
+                })
+            } else {
+                None
+            };
+
+        let management_fee_change = if let Some(management_fee) = management_fee {
+            require_lte!(
+                management_fee,
+                State::MAX_MANAGEMENT_FEE,
+                MarinadeError::ManagementFeeIsTooHigh
+            );
+            // the new fee applies to the time since the last accrual
+            let old = self.state.management_fee;
+            self.state.management_fee = management_fee;
+            Some(FeeValueChange {
+                old,
+                new: management_fee,
+            })
+        } else {
+            None
+        };
+
+        let deposit_imbalance_fee_change =
+            if let Some(deposit_imbalance_fee) = deposit_imbalance_fee {
+                require_lte!(
+                    deposit_imbalance_fee,
+                    State::MAX_DEPOSIT_IMBALANCE_FEE,
+                    MarinadeError::DepositImbalanceFeeIsTooHigh
+                );
+                let old = self.state.deposit_imbalance_fee;
+                self.state.deposit_imbalance_fee = deposit_imbalance_fee;
+                Some(FeeValueChange {
+                    old,
+                    new: deposit_imbalance_fee,
+                })
+            } else {
+                None
+            };
+
+        let deposit_imbalance_threshold_change =
+            if let Some(deposit_imbalance_threshold) = deposit_imbalance_threshold {
+                deposit_imbalance_threshold.check()?;
+                let old = self.state.deposit_imbalance_threshold;
+                self.state.deposit_imbalance_threshold = deposit_imbalance_threshold;
+                Some(FeeValueChange {
+                    old,
+                    new: deposit_imbalance_threshold,
+                })
+            } else {
+                None
+            };
+
+        let msol_usd_feed_change = if let Some(msol_usd_feed) = msol_usd_feed {
+            let old = self.state.msol_usd_feed;
+            self.state.msol_usd_feed = msol_usd_feed;
+            Some(PubkeyValueChange {
+                old,
+                new: msol_usd_feed,
+            })
+        } else {
+            None
+        };
+
+        let sol_usd_feed_change = if let Some(sol_usd_feed) = sol_usd_feed {
+            let old = self.state.sol_usd_feed;
+            self.state.sol_usd_feed = sol_usd_feed;
+            Some(PubkeyValueChange {
+                old,
+                new: sol_usd_feed,
+            })
+        } else {
+            None
+        };
+
+        let price_feed_tolerance_change = if let Some(price_feed_tolerance) = price_feed_tolerance {
+            price_feed_tolerance.check()?;
+            let old = self.state.price_feed_tolerance;
+            self.state.price_feed_tolerance = price_feed_tolerance;
+            Some(FeeValueChange {
+                old,
+                new: price_feed_tolerance,
+            })
+        } else {
+            None
+        };
+
+        let max_epoch_yield_change = if let Some(max_epoch_yield) = max_epoch_yield {
+            max_epoch_yield.check()?;
+            let old = self.state.max_epoch_yield;
+            self.state.max_epoch_yield = max_epoch_yield;
+            Some(FeeValueChange {
+                old,
+                new: max_epoch_yield,
+            })
+        } else {
+            None
+        };
+
         emit!(ConfigMarinadeEvent {
             state: self.state.key(),
+            authority: self.admin_authority.key(),
+            slot: Clock::get()?.slot,
             rewards_fee_change,
             slots_for_stake_delta_change,
             min_stake_change,
@@ -227,6 +586,27 @@ impl<'info> ConfigMarinade<'info> {
             delayed_unstake_fee_change,
             withdraw_stake_account_fee_change,
             max_stake_moved_per_epoch_change,
+            max_liquid_unstake_per_epoch_change,
+            stale_ticket_epochs_change,
+            config_change_delay_slots_change,
+            msol_price_change_limit_change,
+            max_directed_stake_share_change,
+            max_commission_change,
+            delinquent_epochs_change,
+            max_validator_stake_share_change,
+            min_validator_stake_change,
+            min_validator_bond_change,
+            validator_bond_slash_change,
+            keeper_reward_change,
+            delegation_strategy_change,
+            deactivating_stake_discount_change,
+            management_fee_change,
+            deposit_imbalance_fee_change,
+            deposit_imbalance_threshold_change,
+            msol_usd_feed_change,
+            sol_usd_feed_change,
+            price_feed_tolerance_change,
+            max_epoch_yield_change,
         });
 
         Ok(())
@@ -0,0 +1,108 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::system_program;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::{
+    events::{admin::ConfigMsolLockEvent, FeeValueChange},
+    state::{
+        msol_lock::{MsolLock, MsolLockSystem},
+        roles::Role,
+        Fee,
+    },
+    MarinadeError, State,
+};
+
+#[derive(Accounts)]
+pub struct ConfigMsolLock<'info> {
+    #[account(
+        mut,
+        constraint = state.has_role(Role::Treasurer, treasurer_authority.key)
+            @ MarinadeError::InvalidTreasurerAuthority,
+        has_one = msol_mint,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+    pub treasurer_authority: Signer<'info>,
+
+    pub msol_mint: Box<Account<'info, Mint>>,
+
+    /// CHECK: PDA
+    #[account(
+        seeds = [
+            &state.key().to_bytes(),
+            MsolLock::AUTHORITY_SEED
+        ],
+        bump,
+    )]
+    pub msol_lock_authority: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = rent_payer,
+        token::mint = msol_mint,
+        token::authority = msol_lock_authority,
+        seeds = [
+            &state.key().to_bytes(),
+            MsolLock::VAULT_SEED
+        ],
+        bump,
+    )]
+    pub msol_lock_vault: Box<Account<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = rent_payer,
+        token::mint = msol_mint,
+        token::authority = msol_lock_authority,
+        seeds = [
+            &state.key().to_bytes(),
+            MsolLock::FEE_VAULT_SEED
+        ],
+        bump,
+    )]
+    pub msol_lock_fee_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        owner = system_program::ID
+    )]
+    pub rent_payer: Signer<'info>,
+
+    pub rent: Sysvar<'info, Rent>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> ConfigMsolLock<'info> {
+    /// enables the time-locked mSOL positions and sets the share of the protocol reward fee they get
+    pub fn process(&mut self, fee_share: Fee, msol_lock_authority_bump_seed: u8) -> Result<()> {
+        self.state.check_config_not_timelocked()?;
+        fee_share.check_max(
+            MsolLockSystem::MAX_FEE_SHARE,
+            MarinadeError::MsolLockFeeShareIsTooHigh,
+        )?;
+
+        self.state.msol_lock.authority_bump_seed = msol_lock_authority_bump_seed;
+        self.state.msol_lock.vault = self.msol_lock_vault.key();
+        self.state.msol_lock.fee_vault = self.msol_lock_fee_vault.key();
+
+        let fee_share_change = {
+            let old = self.state.msol_lock.fee_share;
+            self.state.msol_lock.fee_share = fee_share;
+            FeeValueChange {
+                old,
+                new: fee_share,
+            }
+        };
+
+        emit!(ConfigMsolLockEvent {
+            state: self.state.key(),
+            authority: self.treasurer_authority.key(),
+            slot: Clock::get()?.slot,
+            msol_lock_vault: self.msol_lock_vault.key(),
+            msol_lock_fee_vault: self.msol_lock_fee_vault.key(),
+            fee_share_change,
+        });
+
+        Ok(())
+    }
+}
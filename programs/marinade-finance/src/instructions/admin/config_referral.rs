@@ -0,0 +1,94 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::system_program;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::{
+    events::{admin::ConfigReferralEvent, FeeValueChange},
+    state::{referral::ReferralPartner, roles::Role, Fee},
+    MarinadeError, State,
+};
+
+#[derive(Accounts)]
+pub struct ConfigReferral<'info> {
+    #[account(
+        mut,
+        constraint = state.has_role(Role::Treasurer, treasurer_authority.key)
+            @ MarinadeError::InvalidTreasurerAuthority,
+        has_one = msol_mint,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+    pub treasurer_authority: Signer<'info>,
+
+    pub msol_mint: Box<Account<'info, Mint>>,
+
+    /// CHECK: PDA
+    #[account(
+        seeds = [
+            &state.key().to_bytes(),
+            ReferralPartner::VAULT_AUTHORITY_SEED
+        ],
+        bump,
+    )]
+    pub referral_vault_authority: UncheckedAccount<'info>,
+    // funded by the DAO from the treasury
+    #[account(
+        init_if_needed,
+        payer = rent_payer,
+        token::mint = msol_mint,
+        token::authority = referral_vault_authority,
+        seeds = [
+            &state.key().to_bytes(),
+            ReferralPartner::VAULT_SEED
+        ],
+        bump,
+    )]
+    pub referral_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        owner = system_program::ID
+    )]
+    pub rent_payer: Signer<'info>,
+
+    pub rent: Sysvar<'info, Rent>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> ConfigReferral<'info> {
+    /// enables the referral program, a zero share stops the accrual for new deposits
+    pub fn process(
+        &mut self,
+        referral_share: Fee,
+        referral_vault_authority_bump_seed: u8,
+    ) -> Result<()> {
+        self.state.check_config_not_timelocked()?;
+        referral_share.check_max(
+            ReferralPartner::MAX_REFERRAL_SHARE,
+            MarinadeError::ReferralShareIsTooHigh,
+        )?;
+        self.state.referral_vault_authority_bump_seed = referral_vault_authority_bump_seed;
+        self.state.referral_vault = self.referral_vault.key();
+
+        let referral_share_change = {
+            let old = self.state.referral_share;
+            self.state.referral_share = referral_share;
+            FeeValueChange {
+                old,
+                new: referral_share,
+            }
+        };
+
+        emit!(ConfigReferralEvent {
+            state: self.state.key(),
+            authority: self.treasurer_authority.key(),
+            slot: Clock::get()?.slot,
+            referral_vault: self.referral_vault.key(),
+            referral_share_change,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,106 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::system_program;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::{
+    error::MarinadeError,
+    events::{admin::ConfigTreasurySplitterEvent, PubkeyValueChange},
+    state::treasury_splitter::{TreasuryRecipient, TreasurySplitter},
+    State,
+};
+
+#[derive(Accounts)]
+pub struct ConfigTreasurySplitter<'info> {
+    #[account(
+        mut,
+        has_one = admin_authority @ MarinadeError::InvalidAdminAuthority,
+        has_one = msol_mint,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+    pub admin_authority: Signer<'info>,
+
+    pub msol_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init_if_needed,
+        payer = rent_payer,
+        space = TreasurySplitter::LEN,
+        seeds = [
+            &state.key().to_bytes(),
+            TreasurySplitter::SEED
+        ],
+        bump,
+    )]
+    pub treasury_splitter: Box<Account<'info, TreasurySplitter>>,
+    /// CHECK: PDA
+    #[account(
+        seeds = [
+            &state.key().to_bytes(),
+            TreasurySplitter::AUTHORITY_SEED
+        ],
+        bump,
+    )]
+    pub treasury_authority: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = rent_payer,
+        token::mint = msol_mint,
+        token::authority = treasury_authority,
+        seeds = [
+            &state.key().to_bytes(),
+            TreasurySplitter::VAULT_SEED
+        ],
+        bump,
+    )]
+    pub treasury_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        owner = system_program::ID
+    )]
+    pub rent_payer: Signer<'info>,
+
+    pub rent: Sysvar<'info, Rent>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> ConfigTreasurySplitter<'info> {
+    // fn config_treasury_splitter()
+    // sets the recipients and makes the splitter vault the treasury mSOL account,
+    // change_authority can point the treasury to a plain account again
+    pub fn process(
+        &mut self,
+        recipients: Vec<TreasuryRecipient>,
+        bump: u8,
+        authority_bump: u8,
+    ) -> Result<()> {
+        self.state.check_config_not_timelocked()?;
+        self.treasury_splitter.state = self.state.key();
+        self.treasury_splitter.bump = bump;
+        self.treasury_splitter.authority_bump = authority_bump;
+        self.treasury_splitter.vault = self.treasury_vault.key();
+        self.treasury_splitter.set_recipients(&recipients)?;
+
+        let treasury_msol_account_change = {
+            let old = self.state.treasury_msol_account;
+            self.state.treasury_msol_account = self.treasury_vault.key();
+            PubkeyValueChange {
+                old,
+                new: self.treasury_vault.key(),
+            }
+        };
+
+        emit!(ConfigTreasurySplitterEvent {
+            state: self.state.key(),
+            authority: self.admin_authority.key(),
+            slot: Clock::get()?.slot,
+            treasury_msol_account_change,
+            recipients,
+        });
+
+        Ok(())
+    }
+}
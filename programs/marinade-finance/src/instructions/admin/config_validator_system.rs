@@ -1,13 +1,20 @@
 use anchor_lang::prelude::*;
 
-use crate::{MarinadeError, State};
+use crate::{
+    events::{admin::ConfigValidatorSystemEvent, U32ValueChange},
+    state::roles::Role,
+    MarinadeError, State,
+};
 
 #[derive(Accounts)]
 pub struct ConfigValidatorSystem<'info> {
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
     pub state: Account<'info, State>,
     #[account(
-        address = state.validator_system.manager_authority
+        constraint = state.has_role(Role::ValidatorManager, manager_authority.key)
             @ MarinadeError::InvalidValidatorManager
     )]
     pub manager_authority: Signer<'info>,
@@ -15,7 +22,17 @@ pub struct ConfigValidatorSystem<'info> {
 
 impl<'info> ConfigValidatorSystem<'info> {
     pub fn process(&mut self, extra_runs: u32) -> Result<()> {
+        let old = self.state.stake_system.extra_stake_delta_runs;
         self.state.stake_system.extra_stake_delta_runs = extra_runs; // TODO: think about is it stake or validator thing?
+        emit!(ConfigValidatorSystemEvent {
+            state: self.state.key(),
+            authority: self.manager_authority.key(),
+            slot: Clock::get()?.slot,
+            extra_stake_delta_runs_change: U32ValueChange {
+                old,
+                new: extra_runs,
+            },
+        });
         Ok(())
     }
 }
@@ -0,0 +1,91 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{burn, Burn, Mint, Token, TokenAccount};
+
+use crate::{
+    events::admin::CoverLossEvent, require_lte, state::insurance_fund::InsuranceFund,
+    MarinadeError, State,
+};
+
+#[derive(Accounts)]
+pub struct CoverLoss<'info> {
+    #[account(
+        mut,
+        has_one = admin_authority @ MarinadeError::InvalidAdminAuthority,
+        has_one = msol_mint,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+    pub admin_authority: Signer<'info>,
+
+    #[account(mut)]
+    pub msol_mint: Box<Account<'info, Mint>>,
+
+    /// CHECK: PDA
+    #[account(
+        seeds = [
+            &state.key().to_bytes(),
+            InsuranceFund::AUTHORITY_SEED
+        ],
+        bump = state.insurance_fund.authority_bump_seed,
+    )]
+    pub insurance_fund_authority: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = state.insurance_fund.vault
+    )]
+    pub insurance_fund_vault: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> CoverLoss<'info> {
+    // fn cover_loss()
+    // the DAO covers `lamports` of the recorded losses by burning the insurance fund mSOL
+    // worth them, the SOL backing the burned mSOL stays in the pool for the stakers
+    pub fn process(&mut self, lamports: u64) -> Result<()> {
+        require_lte!(
+            lamports,
+            self.state.insurance_fund.recorded_losses,
+            MarinadeError::CoverAboveRecordedLosses
+        );
+        let msol_amount = self.state.calc_msol_from_lamports(lamports)?;
+        require_lte!(
+            msol_amount,
+            self.insurance_fund_vault.amount,
+            MarinadeError::InsufficientInsuranceFund
+        );
+
+        if msol_amount > 0 {
+            burn(
+                CpiContext::new_with_signer(
+                    self.token_program.to_account_info(),
+                    Burn {
+                        mint: self.msol_mint.to_account_info(),
+                        from: self.insurance_fund_vault.to_account_info(),
+                        authority: self.insurance_fund_authority.to_account_info(),
+                    },
+                    &[&[
+                        &self.state.key().to_bytes(),
+                        InsuranceFund::AUTHORITY_SEED,
+                        &[self.state.insurance_fund.authority_bump_seed],
+                    ]],
+                ),
+                msol_amount,
+            )?;
+            self.state.on_msol_burn(msol_amount);
+        }
+        self.state.insurance_fund.on_cover(lamports);
+
+        emit!(CoverLossEvent {
+            state: self.state.key(),
+            authority: self.admin_authority.key(),
+            slot: Clock::get()?.slot,
+            lamports,
+            msol_burned: msol_amount,
+            recorded_losses: self.state.insurance_fund.recorded_losses,
+            insurance_fund_balance: self.insurance_fund_vault.amount - msol_amount,
+        });
+
+        Ok(())
+    }
+}
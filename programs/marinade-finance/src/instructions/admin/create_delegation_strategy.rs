@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::system_program;
+
+use crate::{
+    error::MarinadeError, events::admin::CreateDelegationStrategyEvent,
+    state::delegation_strategy::DelegationStrategy, Fee, State,
+};
+
+#[derive(Accounts)]
+pub struct CreateDelegationStrategy<'info> {
+    #[account(
+        has_one = admin_authority @ MarinadeError::InvalidAdminAuthority,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+    pub admin_authority: Signer<'info>,
+
+    #[account(
+        init,
+        payer = rent_payer,
+        space = DelegationStrategy::LEN,
+    )]
+    pub delegation_strategy: Box<Account<'info, DelegationStrategy>>,
+
+    #[account(
+        mut,
+        owner = system_program::ID
+    )]
+    pub rent_payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateDelegationStrategy<'info> {
+    // fn create_delegation_strategy()
+    // the strategy is used once config_marinade sets it as State::delegation_strategy
+    pub fn process(
+        &mut self,
+        score_share: Fee,
+        min_score: u32,
+        max_validator_stake_share: Fee,
+        max_directed_stake_share: Fee,
+    ) -> Result<()> {
+        let delegation_strategy = DelegationStrategy {
+            state: self.state.key(),
+            score_share,
+            min_score,
+            max_validator_stake_share,
+            max_directed_stake_share,
+        };
+        delegation_strategy.check()?;
+        self.delegation_strategy.set_inner(delegation_strategy);
+
+        emit!(CreateDelegationStrategyEvent {
+            state: self.state.key(),
+            authority: self.admin_authority.key(),
+            slot: Clock::get()?.slot,
+            delegation_strategy: self.delegation_strategy.key(),
+            score_share,
+            min_score,
+            max_validator_stake_share,
+            max_directed_stake_share,
+        });
+
+        Ok(())
+    }
+}
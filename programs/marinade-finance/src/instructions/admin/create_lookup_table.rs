@@ -0,0 +1,161 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{program::invoke_signed, stake, system_program, sysvar};
+use anchor_spl::token::spl_token;
+
+use crate::{
+    address_lookup_table,
+    error::MarinadeError,
+    events::{admin::CreateLookupTableEvent, PubkeyValueChange},
+    state::{liq_pool::LiqPool, roles::Role, stake_system::StakeSystem},
+    State,
+};
+
+#[derive(Accounts)]
+pub struct CreateLookupTable<'info> {
+    #[account(
+        mut,
+        constraint = state.has_role(Role::Operator, operator_authority.key)
+            @ MarinadeError::InvalidOperatorAuthority,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+    pub operator_authority: Signer<'info>,
+
+    /// CHECK: created by the lookup table program, address checked in process
+    #[account(mut)]
+    pub lookup_table: UncheckedAccount<'info>,
+    /// CHECK: PDA
+    #[account(
+        seeds = [
+            &state.key().to_bytes(),
+            State::LOOKUP_TABLE_AUTHORITY_SEED
+        ],
+        bump,
+    )]
+    pub lookup_table_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        owner = system_program::ID
+    )]
+    pub rent_payer: Signer<'info>,
+
+    /// CHECK: program id checked
+    #[account(address = address_lookup_table::ID)]
+    pub address_lookup_table_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateLookupTable<'info> {
+    // the accounts of the deposit, liquid unstake, liquidity and crank instructions
+    pub fn protocol_addresses(&self) -> Vec<Pubkey> {
+        let state_address = self.state.key();
+        let mut addresses = vec![
+            state_address,
+            self.state.msol_mint,
+            State::find_msol_mint_authority(&state_address).0,
+            State::find_reserve_address(&state_address).0,
+            self.state.treasury_msol_account,
+            self.state.operational_sol_account,
+            *self.state.stake_system.stake_list_address(),
+            *self.state.validator_system.validator_list_address(),
+            StakeSystem::find_stake_deposit_authority(&state_address).0,
+            StakeSystem::find_stake_withdraw_authority(&state_address).0,
+            self.state.liq_pool.lp_mint,
+            LiqPool::find_lp_mint_authority(&state_address).0,
+            LiqPool::find_sol_leg_address(&state_address).0,
+            self.state.liq_pool.msol_leg,
+            LiqPool::find_msol_leg_authority(&state_address).0,
+            system_program::ID,
+            spl_token::ID,
+            stake::program::ID,
+            stake::config::ID,
+            sysvar::clock::ID,
+            sysvar::rent::ID,
+            sysvar::stake_history::ID,
+            sysvar::epoch_schedule::ID,
+        ];
+        // optional accounts, Pubkey::default() when not in use
+        for address in [
+            self.state.liq_pool_ext.fee_curve,
+            self.state.liq_pool_ext.lp_lock_fee_vault,
+            self.state.delegation_strategy,
+        ] {
+            if address != Pubkey::default() {
+                addresses.push(address);
+            }
+        }
+        addresses
+    }
+
+    // fn create_lookup_table()
+    // creates a lookup table owned by the lookup table authority PDA holding the
+    // protocol_addresses() and records it in State::lookup_table.
+    // `recent_slot` is a slot of the SlotHashes sysvar, it makes the table address
+    pub fn process(&mut self, recent_slot: u64, authority_bump: u8) -> Result<()> {
+        let (lookup_table, lookup_table_bump) = address_lookup_table::find_lookup_table_address(
+            self.lookup_table_authority.key,
+            recent_slot,
+        );
+        require_keys_eq!(
+            self.lookup_table.key(),
+            lookup_table,
+            ErrorCode::ConstraintAddress
+        );
+        let addresses = self.protocol_addresses();
+
+        let state_bytes = self.state.key().to_bytes();
+        let authority_seeds: &[&[u8]] = &[
+            &state_bytes,
+            State::LOOKUP_TABLE_AUTHORITY_SEED,
+            &[authority_bump],
+        ];
+        let account_infos = [
+            self.lookup_table.to_account_info(),
+            self.lookup_table_authority.to_account_info(),
+            self.rent_payer.to_account_info(),
+            self.system_program.to_account_info(),
+            self.address_lookup_table_program.to_account_info(),
+        ];
+        invoke_signed(
+            &address_lookup_table::create_lookup_table(
+                &lookup_table,
+                self.lookup_table_authority.key,
+                self.rent_payer.key,
+                recent_slot,
+                lookup_table_bump,
+            ),
+            &account_infos,
+            &[authority_seeds],
+        )?;
+        invoke_signed(
+            &address_lookup_table::extend_lookup_table(
+                &lookup_table,
+                self.lookup_table_authority.key,
+                self.rent_payer.key,
+                &addresses,
+            ),
+            &account_infos,
+            &[authority_seeds],
+        )?;
+
+        let lookup_table_change = {
+            let old = self.state.lookup_table;
+            self.state.lookup_table = lookup_table;
+            PubkeyValueChange {
+                old,
+                new: lookup_table,
+            }
+        };
+
+        emit!(CreateLookupTableEvent {
+            state: self.state.key(),
+            authority: self.operator_authority.key(),
+            slot: Clock::get()?.slot,
+            lookup_table_change,
+            addresses,
+        });
+
+        Ok(())
+    }
+}
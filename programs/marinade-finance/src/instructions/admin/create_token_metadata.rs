@@ -0,0 +1,143 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{program::invoke_signed, system_program};
+use anchor_spl::token::Mint;
+
+use crate::{
+    error::MarinadeError, events::admin::TokenMetadataEvent, state::liq_pool::LiqPool,
+    token_metadata, State, ID,
+};
+
+/// Program mints with token metadata, signed by their mint authority PDA
+#[derive(Clone, Copy, Debug, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub enum MetadataMint {
+    Msol,
+    Lp,
+}
+
+impl MetadataMint {
+    pub fn mint(&self, state: &State) -> Pubkey {
+        match self {
+            MetadataMint::Msol => state.msol_mint,
+            MetadataMint::Lp => state.liq_pool.lp_mint,
+        }
+    }
+
+    pub fn authority_seed_and_bump(&self, state: &State) -> (&'static [u8], u8) {
+        match self {
+            MetadataMint::Msol => (
+                State::MSOL_MINT_AUTHORITY_SEED,
+                state.msol_mint_authority_bump_seed,
+            ),
+            MetadataMint::Lp => (
+                LiqPool::LP_MINT_AUTHORITY_SEED,
+                state.liq_pool.lp_mint_authority_bump_seed,
+            ),
+        }
+    }
+
+    pub fn check_accounts(
+        &self,
+        state: &Pubkey,
+        state_data: &State,
+        mint: &Pubkey,
+        mint_authority: &Pubkey,
+    ) -> Result<()> {
+        require_keys_eq!(
+            *mint,
+            self.mint(state_data),
+            MarinadeError::InvalidMetadataMint
+        );
+        let (seed, bump) = self.authority_seed_and_bump(state_data);
+        let expected = Pubkey::create_program_address(&[&state.to_bytes(), seed, &[bump]], &ID)
+            .map_err(|_| error!(MarinadeError::InvalidMetadataMint))?;
+        require_keys_eq!(
+            *mint_authority,
+            expected,
+            MarinadeError::InvalidMetadataMint
+        );
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct CreateTokenMetadata<'info> {
+    #[account(
+        has_one = admin_authority @ MarinadeError::InvalidAdminAuthority,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+    pub admin_authority: Signer<'info>,
+
+    pub mint: Box<Account<'info, Mint>>,
+    /// CHECK: PDA, checked in code
+    pub mint_authority: UncheckedAccount<'info>,
+    /// CHECK: created by the metadata program
+    #[account(
+        mut,
+        address = token_metadata::metadata_address(&mint.key())
+    )]
+    pub metadata: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        owner = system_program::ID
+    )]
+    pub rent_payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    /// CHECK: CPI
+    #[account(address = token_metadata::ID)]
+    pub token_metadata_program: UncheckedAccount<'info>,
+}
+
+impl<'info> CreateTokenMetadata<'info> {
+    // fn create_token_metadata()
+    // wallets show the program mints by the name, symbol and uri of the metadata
+    pub fn process(
+        &mut self,
+        metadata_mint: MetadataMint,
+        name: String,
+        symbol: String,
+        uri: String,
+    ) -> Result<()> {
+        metadata_mint.check_accounts(
+            &self.state.key(),
+            &self.state,
+            &self.mint.key(),
+            self.mint_authority.key,
+        )?;
+        let (seed, bump) = metadata_mint.authority_seed_and_bump(&self.state);
+        invoke_signed(
+            &token_metadata::create_metadata_account(
+                self.metadata.key,
+                &self.mint.key(),
+                self.mint_authority.key,
+                self.rent_payer.key,
+                name.clone(),
+                symbol.clone(),
+                uri.clone(),
+            )?,
+            &[
+                self.token_metadata_program.to_account_info(),
+                self.metadata.to_account_info(),
+                self.mint.to_account_info(),
+                self.mint_authority.to_account_info(),
+                self.rent_payer.to_account_info(),
+                self.system_program.to_account_info(),
+            ],
+            &[&[&self.state.key().to_bytes(), seed, &[bump]]],
+        )?;
+
+        emit!(TokenMetadataEvent {
+            state: self.state.key(),
+            authority: self.admin_authority.key(),
+            slot: Clock::get()?.slot,
+            mint: self.mint.key(),
+            metadata: self.metadata.key(),
+            name,
+            symbol,
+            uri,
+        });
+        Ok(())
+    }
+}
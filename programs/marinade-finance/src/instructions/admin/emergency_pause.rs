@@ -2,16 +2,19 @@ use anchor_lang::prelude::*;
 
 use crate::{
     error::MarinadeError,
-    events::admin::{EmergencyPauseEvent, ResumeEvent},
+    events::admin::{EmergencyPauseEvent, ResumeEvent, SetPausedOperationsEvent},
+    state::{paused_operations::PausedOperations, roles::Role},
     State,
 };
 
-// this account struct is used for pause() and resume() instructions (see lib.rs)
+// this account struct is used for pause(), resume(), pause_operations() and resume_operations() instructions (see lib.rs)
 #[derive(Accounts)]
 pub struct EmergencyPause<'info> {
     #[account(
         mut,
-        has_one = pause_authority @ MarinadeError::InvalidPauseAuthority
+        constraint = state.has_role(Role::Pauser, pause_authority.key)
+            @ MarinadeError::InvalidPauseAuthority,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
     )]
     pub state: Account<'info, State>,
     pub pause_authority: Signer<'info>,
@@ -19,20 +22,58 @@ pub struct EmergencyPause<'info> {
 
 impl<'info> EmergencyPause<'info> {
     pub fn pause(&mut self) -> Result<()> {
-        require!(!self.state.paused, MarinadeError::AlreadyPaused);
-        self.state.paused = true;
+        require!(!self.state.is_fully_paused(), MarinadeError::AlreadyPaused);
+        self.state.paused = PausedOperations::ALL;
         emit!(EmergencyPauseEvent {
             state: self.state.key(),
+            authority: self.pause_authority.key(),
+            slot: Clock::get()?.slot,
         });
 
         Ok(())
     }
 
     pub fn resume(&mut self) -> Result<()> {
-        require!(self.state.paused, MarinadeError::NotPaused);
-        self.state.paused = false;
+        require_neq!(self.state.paused, 0, MarinadeError::NotPaused);
+        require!(
+            !self.state.circuit_breaker_tripped,
+            MarinadeError::CircuitBreakerTripped
+        );
+        self.state.paused = 0;
         emit!(ResumeEvent {
             state: self.state.key(),
+            authority: self.pause_authority.key(),
+            slot: Clock::get()?.slot,
+        });
+        Ok(())
+    }
+
+    // pause or resume only some groups of instructions
+    pub fn set_paused_operations(&mut self, operations: u8, paused: bool) -> Result<()> {
+        require!(
+            operations != 0 && operations & !PausedOperations::ALL == 0,
+            MarinadeError::InvalidPausedOperations
+        );
+        let old = self.state.paused;
+        if paused {
+            require_neq!(old & operations, operations, MarinadeError::AlreadyPaused);
+            self.state.paused |= operations;
+        } else {
+            require_neq!(old & operations, 0, MarinadeError::NotPaused);
+            require!(
+                !self.state.circuit_breaker_tripped,
+                MarinadeError::CircuitBreakerTripped
+            );
+            self.state.paused &= !operations;
+        }
+        emit!(SetPausedOperationsEvent {
+            state: self.state.key(),
+            authority: self.pause_authority.key(),
+            slot: Clock::get()?.slot,
+            operations,
+            paused,
+            old,
+            new: self.state.paused,
         });
         Ok(())
     }
@@ -0,0 +1,109 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::MarinadeError,
+    events::{admin::SetGovernanceAuthorityEvent, PubkeyValueChange},
+    spl_governance, State,
+};
+
+/// State authorities that can be handed to a Realms DAO
+#[derive(Clone, Copy, Debug, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub enum GovernedAuthority {
+    Admin,
+    ValidatorManager,
+}
+
+impl GovernedAuthority {
+    pub fn get(&self, state: &State) -> Pubkey {
+        match self {
+            GovernedAuthority::Admin => state.admin_authority,
+            GovernedAuthority::ValidatorManager => state.validator_system.manager_authority,
+        }
+    }
+
+    fn set(&self, state: &mut State, authority: Pubkey) {
+        match self {
+            GovernedAuthority::Admin => state.admin_authority = authority,
+            GovernedAuthority::ValidatorManager => {
+                state.validator_system.manager_authority = authority
+            }
+        }
+    }
+}
+
+#[derive(Accounts)]
+pub struct SetGovernanceAuthority<'info> {
+    #[account(
+        mut,
+        has_one = admin_authority @ MarinadeError::InvalidAdminAuthority,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+    pub admin_authority: Signer<'info>,
+
+    /// CHECK: checked in spl_governance::check_governance
+    pub realm: UncheckedAccount<'info>,
+    /// CHECK: checked in spl_governance::check_governance
+    pub governance: UncheckedAccount<'info>,
+    /// CHECK: the governance PDA or its native treasury
+    #[account(
+        constraint = spl_governance::is_governance_signer(governance.key, governance_authority.key)
+            @ MarinadeError::InvalidGovernanceAuthority
+    )]
+    pub governance_authority: UncheckedAccount<'info>,
+}
+
+impl<'info> SetGovernanceAuthority<'info> {
+    // fn set_governance_authority()
+    // same as change_authority, but the new authority must be a signer of a governance of `realm`
+    pub fn process(&mut self, authority: GovernedAuthority) -> Result<()> {
+        spl_governance::check_governance(&self.realm, &self.governance)
+            .map_err(|e| e.with_account_name("governance"))?;
+
+        let old = authority.get(&self.state);
+        authority.set(&mut self.state, self.governance_authority.key());
+
+        emit!(SetGovernanceAuthorityEvent {
+            state: self.state.key(),
+            authority: self.admin_authority.key(),
+            slot: Clock::get()?.slot,
+            governed_authority: authority,
+            realm: self.realm.key(),
+            governance: self.governance.key(),
+            authority_change: PubkeyValueChange {
+                old,
+                new: self.governance_authority.key(),
+            },
+        });
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct CheckGovernanceAuthority<'info> {
+    #[account(
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+
+    /// CHECK: checked in spl_governance::check_governance
+    pub realm: UncheckedAccount<'info>,
+    /// CHECK: checked in spl_governance::check_governance
+    pub governance: UncheckedAccount<'info>,
+}
+
+impl<'info> CheckGovernanceAuthority<'info> {
+    // fn check_governance_authority()
+    // fails unless `authority` is held by a governance of `realm`,
+    // lets a proposal or a client assert the DAO still controls the program
+    pub fn process(&self, authority: GovernedAuthority) -> Result<()> {
+        spl_governance::check_governance(&self.realm, &self.governance)
+            .map_err(|e| e.with_account_name("governance"))?;
+        require!(
+            spl_governance::is_governance_signer(self.governance.key, &authority.get(&self.state)),
+            MarinadeError::InvalidGovernanceAuthority
+        );
+        Ok(())
+    }
+}
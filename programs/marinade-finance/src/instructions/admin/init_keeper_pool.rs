@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::system_program;
+
+use crate::{
+    error::MarinadeError,
+    events::admin::InitKeeperPoolEvent,
+    state::{keeper_pool::KeeperPool, roles::Role},
+    State,
+};
+
+#[derive(Accounts)]
+pub struct InitKeeperPool<'info> {
+    #[account(
+        constraint = state.has_role(Role::Operator, operator_authority.key)
+            @ MarinadeError::InvalidOperatorAuthority,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+    pub operator_authority: Signer<'info>,
+
+    #[account(
+        init,
+        payer = rent_payer,
+        space = KeeperPool::LEN,
+        seeds = [
+            &state.key().to_bytes(),
+            KeeperPool::SEED
+        ],
+        bump,
+    )]
+    pub keeper_pool: Box<Account<'info, KeeperPool>>,
+
+    #[account(
+        mut,
+        owner = system_program::ID
+    )]
+    pub rent_payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitKeeperPool<'info> {
+    // fn init_keeper_pool()
+    pub fn process(&mut self, bump: u8) -> Result<()> {
+        self.keeper_pool.set_inner(KeeperPool {
+            state: self.state.key(),
+            bump,
+            paid_lamports: 0,
+        });
+
+        emit!(InitKeeperPoolEvent {
+            state: self.state.key(),
+            authority: self.operator_authority.key(),
+            slot: Clock::get()?.slot,
+            keeper_pool: self.keeper_pool.key(),
+        });
+
+        Ok(())
+    }
+}
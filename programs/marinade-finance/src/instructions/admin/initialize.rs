@@ -7,8 +7,16 @@ use crate::{
     events::admin::InitializeEvent,
     require_lte,
     state::{
-        fee::FeeCents, liq_pool::LiqPool, stake_system::StakeSystem,
-        validator_system::ValidatorSystem, Fee,
+        epoch_report::EpochStats,
+        fee::FeeCents,
+        holding_record::HoldingDiscountTier,
+        insurance_fund::InsuranceFund,
+        liq_pool::{LiqPool, LiqPoolExt},
+        msol_lock::MsolLockSystem,
+        stake_system::{StakeSystem, UpdateCursor},
+        unstake_schedule::UnstakeSchedule,
+        validator_system::ValidatorSystem,
+        Fee,
     },
     State, ID,
 };
@@ -181,13 +189,59 @@ impl<'info> Initialize<'info> {
             staking_sol_cap: std::u64::MAX, // Unlimited
             emergency_cooling_down: 0,
             pause_authority,
-            paused: false,
+            paused: 0,
             delayed_unstake_fee: FeeCents::from_bp_cents(0),
             withdraw_stake_account_fee: FeeCents::from_bp_cents(0),
             withdraw_stake_account_enabled: false,
             last_stake_move_epoch: 0,
             stake_moved: 0,
             max_stake_moved_per_epoch: Fee::from_basis_points(10000), // 100% of total_lamports_under_control
+            unstake_schedule: UnstakeSchedule::default(),
+            stale_ticket_epochs: std::u64::MAX, // never
+            referral_share: Fee::from_basis_points(0),
+            referral_vault: Pubkey::default(),
+            referral_vault_authority_bump_seed: 0,
+            msol_lock: MsolLockSystem::default(),
+            config_change_delay_slots: 0,           // no timelock
+            operator_authority: Pubkey::default(),  // admin
+            treasurer_authority: Pubkey::default(), // admin
+            msol_price_change_limit: Fee::from_basis_points(0), // no circuit breaker
+            msol_price_reference: State::PRICE_DENOMINATOR,
+            msol_price_reference_epoch: 0,
+            circuit_breaker_tripped: false,
+            version: State::VERSION,
+            max_directed_stake_share: Fee::from_basis_points(0),
+            max_commission: State::MAX_COMMISSION, // no cap
+            delinquent_epochs: 0,
+            max_validator_stake_share: Fee::from_basis_points(Fee::MAX_BASIS_POINTS), // no limit
+            min_validator_stake: std::u64::MAX, // no permissionless add_validator
+            min_validator_bond: 0,
+            validator_bond_slash: Fee::from_basis_points(0),
+            keeper_reward: 0,
+            epoch_stats: EpochStats::default(),
+            delegation_strategy: Pubkey::default(),
+            deactivating_stake_discount: Fee::from_basis_points(0),
+            reward_fee_change_epoch: 0,
+            management_fee: Fee::from_basis_points(0),
+            management_fee_accrued_at: 0,
+            management_fee_epoch: 0,
+            insurance_fund: InsuranceFund::default(),
+            deposit_imbalance_fee: Fee::from_basis_points(0),
+            deposit_imbalance_threshold: Fee::from_basis_points(0),
+            add_liquidity_bonus_share: Fee::from_basis_points(0),
+            add_liquidity_bonus_budget: 0,
+            holding_discount_tiers: [HoldingDiscountTier::default();
+                HoldingDiscountTier::MAX_TIERS],
+            msol_usd_feed: Pubkey::default(),
+            sol_usd_feed: Pubkey::default(),
+            price_feed_tolerance: Fee::from_basis_points(0),
+            max_epoch_yield: Fee::from_basis_points(0),
+            lookup_table: Pubkey::default(),
+            update_cursor: UpdateCursor::default(),
+            msol_price_x64: State::PRICE_X64_DENOMINATOR,
+            lp_minimum_liquidity: 0,
+            liq_pool_ext: LiqPoolExt::default(),
+            paused_before_circuit_breaker: 0,
         });
 
         emit!(InitializeEvent {
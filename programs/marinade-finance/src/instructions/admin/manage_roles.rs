@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::MarinadeError,
+    events::{
+        admin::{GrantRoleEvent, RevokeRoleEvent},
+        PubkeyValueChange,
+    },
+    state::roles::Role,
+    State,
+};
+
+// this account struct is used for grant_role() and revoke_role() instructions (see lib.rs)
+#[derive(Accounts)]
+pub struct ManageRoles<'info> {
+    #[account(
+        mut,
+        has_one = admin_authority @ MarinadeError::InvalidAdminAuthority,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Account<'info, State>,
+    pub admin_authority: Signer<'info>,
+}
+
+impl<'info> ManageRoles<'info> {
+    pub fn grant_role(&mut self, role: Role, authority: Pubkey) -> Result<()> {
+        // the admin role can be passed but never left empty
+        require_keys_neq!(authority, Pubkey::default());
+        let old = self.state.role_authority(role);
+        self.state.set_role_authority(role, authority);
+        emit!(GrantRoleEvent {
+            state: self.state.key(),
+            authority: self.admin_authority.key(),
+            slot: Clock::get()?.slot,
+            role,
+            authority_change: PubkeyValueChange {
+                old,
+                new: authority
+            },
+        });
+        Ok(())
+    }
+
+    // the revoked role goes back to the admin
+    pub fn revoke_role(&mut self, role: Role) -> Result<()> {
+        require!(role != Role::Admin, MarinadeError::CanNotRevokeAdmin);
+        let old = self.state.role_authority(role);
+        self.state.set_role_authority(role, Pubkey::default());
+        emit!(RevokeRoleEvent {
+            state: self.state.key(),
+            authority: self.admin_authority.key(),
+            slot: Clock::get()?.slot,
+            role,
+            old_authority: old,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,211 @@
+use anchor_lang::{prelude::*, system_program, Discriminator};
+
+use crate::{error::MarinadeError, events::admin::MigrateStateEvent, State, ID};
+
+#[derive(Accounts)]
+pub struct MigrateState<'info> {
+    /// CHECK: may have an old layout, deserialized in code
+    #[account(
+        mut,
+        owner = ID,
+    )]
+    pub state: UncheckedAccount<'info>,
+    pub admin_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        owner = system_program::ID,
+    )]
+    pub rent_funds: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateState<'info> {
+    // msol_mint goes first, this offset never changes
+    const ADMIN_AUTHORITY_OFFSET: usize = 8 + 32;
+
+    // fn migrate_state()
+    // grows the state account to the current layout and runs the migrations of the versions it missed
+    pub fn process(&mut self) -> Result<()> {
+        let state_info = self.state.to_account_info();
+        let old_len = state_info.data_len();
+        {
+            let data = state_info.try_borrow_data()?;
+            require!(
+                old_len >= Self::ADMIN_AUTHORITY_OFFSET + 32 && data[..8] == State::DISCRIMINATOR,
+                ErrorCode::AccountDiscriminatorMismatch
+            );
+            let admin_authority = Pubkey::try_from_slice(
+                &data[Self::ADMIN_AUTHORITY_OFFSET..Self::ADMIN_AUTHORITY_OFFSET + 32],
+            )?;
+            require_keys_eq!(
+                admin_authority,
+                self.admin_authority.key(),
+                MarinadeError::InvalidAdminAuthority
+            );
+        }
+
+        let new_len = State::serialized_len().max(old_len);
+        if new_len > old_len {
+            let rent_lamports = Rent::get()?
+                .minimum_balance(new_len)
+                .saturating_sub(state_info.lamports());
+            if rent_lamports > 0 {
+                system_program::transfer(
+                    CpiContext::new(
+                        self.system_program.to_account_info(),
+                        system_program::Transfer {
+                            from: self.rent_funds.to_account_info(),
+                            to: state_info.clone(),
+                        },
+                    ),
+                    rent_lamports,
+                )?;
+            }
+            // the new fields are zeroed
+            state_info.realloc(new_len, true)?;
+        }
+
+        let mut state = State::try_deserialize(&mut &state_info.try_borrow_data()?[..])?;
+        let old_version = state.migrate()?;
+        state.try_serialize(&mut &mut state_info.try_borrow_mut_data()?[..])?;
+
+        emit!(MigrateStateEvent {
+            state: self.state.key(),
+            old_version,
+            new_version: state.version,
+            old_len: old_len as u32,
+            new_len: new_len as u32,
+        });
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::mem::MaybeUninit;
+
+    use anchor_lang::{prelude::*, Discriminator};
+
+    use crate::{
+        state::{
+            paused_operations::PausedOperations, stake_system::StakeSystem,
+            validator_system::ValidatorSystem, Fee, FeeCents,
+        },
+        State,
+    };
+
+    // the layouts deployed before versioning, keep them frozen
+    #[derive(AnchorSerialize)]
+    struct LiqPoolV0 {
+        lp_mint: Pubkey,
+        lp_mint_authority_bump_seed: u8,
+        sol_leg_bump_seed: u8,
+        msol_leg_authority_bump_seed: u8,
+        msol_leg: Pubkey,
+        lp_liquidity_target: u64,
+        lp_max_fee: Fee,
+        lp_min_fee: Fee,
+        treasury_cut: Fee,
+        lp_supply: u64,
+        lent_from_sol_leg: u64,
+        liquidity_sol_cap: u64,
+    }
+
+    #[derive(AnchorSerialize)]
+    struct StateV0 {
+        msol_mint: Pubkey,
+        admin_authority: Pubkey,
+        operational_sol_account: Pubkey,
+        treasury_msol_account: Pubkey,
+        reserve_bump_seed: u8,
+        msol_mint_authority_bump_seed: u8,
+        rent_exempt_for_token_acc: u64,
+        reward_fee: Fee,
+        stake_system: StakeSystem,
+        validator_system: ValidatorSystem,
+        liq_pool: LiqPoolV0,
+        available_reserve_balance: u64,
+        msol_supply: u64,
+        msol_price: u64,
+        circulating_ticket_count: u64,
+        circulating_ticket_balance: u64,
+        lent_from_reserve: u64,
+        min_deposit: u64,
+        min_withdraw: u64,
+        staking_sol_cap: u64,
+        emergency_cooling_down: u64,
+        pause_authority: Pubkey,
+        paused: bool,
+        delayed_unstake_fee: FeeCents,
+        withdraw_stake_account_fee: FeeCents,
+        withdraw_stake_account_enabled: bool,
+        last_stake_move_epoch: u64,
+        stake_moved: u64,
+        max_stake_moved_per_epoch: Fee,
+    }
+
+    #[test]
+    fn test_migrate_baseline_state() {
+        let mut old: StateV0 = unsafe { MaybeUninit::zeroed().assume_init() };
+        old.msol_mint = Pubkey::new_unique();
+        old.admin_authority = Pubkey::new_unique();
+        old.reward_fee = Fee::from_basis_points(200);
+        old.liq_pool.lp_mint = Pubkey::new_unique();
+        old.liq_pool.lp_max_fee = Fee::from_basis_points(300);
+        old.liq_pool.lp_supply = 1_000;
+        old.liq_pool.liquidity_sol_cap = std::u64::MAX;
+        old.available_reserve_balance = 2_000;
+        old.msol_supply = 3_000;
+        old.msol_price = State::PRICE_DENOMINATOR + 1;
+        old.pause_authority = Pubkey::new_unique();
+        old.paused = true;
+        old.max_stake_moved_per_epoch = Fee::from_basis_points(1_000);
+
+        let mut data = State::DISCRIMINATOR.to_vec();
+        old.serialize(&mut data).unwrap();
+        // realloc of migrate_state
+        data.resize(State::serialized_len(), 0);
+
+        let mut state = State::try_deserialize(&mut &data[..]).unwrap();
+        assert_eq!(state.migrate().unwrap(), 0);
+        assert_eq!(state.version, State::VERSION);
+
+        assert_eq!(state.msol_mint, old.msol_mint);
+        assert_eq!(state.admin_authority, old.admin_authority);
+        assert_eq!(state.reward_fee, old.reward_fee);
+        assert_eq!(state.liq_pool.lp_mint, old.liq_pool.lp_mint);
+        assert_eq!(state.liq_pool.lp_max_fee, old.liq_pool.lp_max_fee);
+        assert_eq!(state.liq_pool.lp_supply, old.liq_pool.lp_supply);
+        assert_eq!(
+            state.liq_pool.liquidity_sol_cap,
+            old.liq_pool.liquidity_sol_cap
+        );
+        assert_eq!(
+            state.available_reserve_balance,
+            old.available_reserve_balance
+        );
+        assert_eq!(state.msol_supply, old.msol_supply);
+        assert_eq!(state.msol_price, old.msol_price);
+        assert_eq!(state.pause_authority, old.pause_authority);
+        assert_eq!(state.paused, PausedOperations::ALL);
+        assert_eq!(
+            state.max_stake_moved_per_epoch,
+            old.max_stake_moved_per_epoch
+        );
+
+        // new fields get the values initialize uses
+        assert_eq!(state.stale_ticket_epochs, std::u64::MAX);
+        assert_eq!(state.msol_price_reference, old.msol_price);
+        assert_eq!(state.msol_price_x64, (old.msol_price as u128) << 32);
+        assert_eq!(state.liq_pool_ext.msol_leg_target, Fee::FULL);
+        assert_eq!(state.liq_pool_ext.lp_global_cap, std::u64::MAX);
+        assert_eq!(
+            state.liq_pool_ext.max_liquid_unstake_per_epoch,
+            std::u64::MAX
+        );
+
+        assert!(state.migrate().is_err());
+    }
+}
@@ -1,17 +1,65 @@
+pub mod ack_circuit_breaker;
+pub mod buyback_and_burn;
 pub mod change_authority;
+pub mod config_buyback;
+pub mod config_fee_curve;
+pub mod config_holding_discount;
+pub mod config_insurance_fund;
 pub mod config_lp;
+pub mod config_lp_lock;
 pub mod config_marinade;
+pub mod config_msol_lock;
+pub mod config_referral;
+pub mod config_treasury_splitter;
 pub mod config_validator_system;
+pub mod cover_loss;
+pub mod create_delegation_strategy;
+pub mod create_lookup_table;
+pub mod create_token_metadata;
 pub mod emergency_pause;
+pub mod governance_authority;
+pub mod init_keeper_pool;
 pub mod initialize;
+pub mod manage_roles;
+pub mod migrate_state;
+pub mod queue_config_change;
 pub mod realloc_stake_list;
 pub mod realloc_validator_list;
+pub mod reconcile_lp_supply;
+pub mod rescue_tokens;
+pub mod update_token_metadata;
+pub mod verify_config;
+pub mod verify_invariants;
 
+pub use ack_circuit_breaker::*;
+pub use buyback_and_burn::*;
 pub use change_authority::*;
+pub use config_buyback::*;
+pub use config_fee_curve::*;
+pub use config_holding_discount::*;
+pub use config_insurance_fund::*;
 pub use config_lp::*;
+pub use config_lp_lock::*;
 pub use config_marinade::*;
+pub use config_msol_lock::*;
+pub use config_referral::*;
+pub use config_treasury_splitter::*;
 pub use config_validator_system::*;
+pub use cover_loss::*;
+pub use create_delegation_strategy::*;
+pub use create_lookup_table::*;
+pub use create_token_metadata::*;
 pub use emergency_pause::*;
+pub use governance_authority::*;
+pub use init_keeper_pool::*;
 pub use initialize::*;
+pub use manage_roles::*;
+pub use migrate_state::*;
+pub use queue_config_change::*;
 pub use realloc_stake_list::*;
 pub use realloc_validator_list::*;
+pub use reconcile_lp_supply::*;
+pub use rescue_tokens::*;
+pub use update_token_metadata::*;
+pub use verify_config::*;
+pub use verify_invariants::*;
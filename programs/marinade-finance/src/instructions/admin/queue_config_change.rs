@@ -0,0 +1,137 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::system_program;
+
+use crate::{
+    error::MarinadeError,
+    events::admin::{CancelConfigChangeEvent, QueueConfigChangeEvent},
+    instructions::{ConfigMarinade, ConfigMarinadeParams},
+    state::config_change::QueuedConfigChange,
+    State,
+};
+
+#[derive(Accounts)]
+pub struct QueueConfigChange<'info> {
+    #[account(
+        has_one = admin_authority @ MarinadeError::InvalidAdminAuthority,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+    pub admin_authority: Signer<'info>,
+
+    #[account(
+        init,
+        payer = rent_payer,
+        space = QueuedConfigChange::LEN,
+        seeds = [
+            &state.key().to_bytes(),
+            QueuedConfigChange::SEED
+        ],
+        bump,
+    )]
+    pub queued_config_change: Box<Account<'info, QueuedConfigChange>>,
+
+    #[account(
+        mut,
+        owner = system_program::ID
+    )]
+    pub rent_payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> QueueConfigChange<'info> {
+    // fn queue_config_change()
+    pub fn process(&mut self, params: ConfigMarinadeParams, bump: u8) -> Result<()> {
+        let queued_slot = Clock::get()?.slot;
+        let execute_slot = queued_slot + self.state.config_change_delay_slots;
+        self.queued_config_change.set_inner(QueuedConfigChange {
+            state: self.state.key(),
+            rent_payer: self.rent_payer.key(),
+            bump,
+            params,
+            queued_slot,
+            execute_slot,
+        });
+
+        emit!(QueueConfigChangeEvent {
+            state: self.state.key(),
+            authority: self.admin_authority.key(),
+            slot: Clock::get()?.slot,
+            queued_config_change: self.queued_config_change.key(),
+            params,
+            execute_slot,
+        });
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct ExecuteConfigChange<'info> {
+    pub config: ConfigMarinade<'info>,
+
+    #[account(
+        mut,
+        close = rent_payer,
+        has_one = rent_payer,
+        constraint = queued_config_change.state == config.state.key(),
+        seeds = [
+            &config.state.key().to_bytes(),
+            QueuedConfigChange::SEED
+        ],
+        bump = queued_config_change.bump,
+    )]
+    pub queued_config_change: Box<Account<'info, QueuedConfigChange>>,
+    #[account(mut)]
+    pub rent_payer: SystemAccount<'info>,
+}
+
+impl<'info> ExecuteConfigChange<'info> {
+    // fn execute_config_change()
+    pub fn process(&mut self) -> Result<()> {
+        require_gte!(
+            Clock::get()?.slot,
+            self.queued_config_change.execute_slot,
+            MarinadeError::ConfigChangeIsTimelocked
+        );
+        self.config.apply(self.queued_config_change.params)
+    }
+}
+
+#[derive(Accounts)]
+pub struct CancelConfigChange<'info> {
+    #[account(
+        has_one = admin_authority @ MarinadeError::InvalidAdminAuthority,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+    pub admin_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        close = rent_payer,
+        has_one = rent_payer,
+        has_one = state,
+        seeds = [
+            &state.key().to_bytes(),
+            QueuedConfigChange::SEED
+        ],
+        bump = queued_config_change.bump,
+    )]
+    pub queued_config_change: Box<Account<'info, QueuedConfigChange>>,
+    #[account(mut)]
+    pub rent_payer: SystemAccount<'info>,
+}
+
+impl<'info> CancelConfigChange<'info> {
+    // fn cancel_config_change()
+    pub fn process(&mut self) -> Result<()> {
+        emit!(CancelConfigChangeEvent {
+            state: self.state.key(),
+            authority: self.admin_authority.key(),
+            slot: Clock::get()?.slot,
+            queued_config_change: self.queued_config_change.key(),
+        });
+        Ok(())
+    }
+}
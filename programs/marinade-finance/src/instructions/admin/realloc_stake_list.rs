@@ -1,7 +1,9 @@
 use anchor_lang::{prelude::*, system_program, Discriminator};
 
 use crate::{
-    error::MarinadeError, events::admin::ReallocStakeListEvent, state::stake_system::StakeList,
+    error::MarinadeError,
+    events::admin::ReallocStakeListEvent,
+    state::{roles::Role, stake_system::StakeList},
     State,
 };
 
@@ -10,10 +12,12 @@ use crate::{
 pub struct ReallocStakeList<'info> {
     #[account(
         mut,
-        has_one = admin_authority @ MarinadeError::InvalidAdminAuthority,
+        constraint = state.has_role(Role::Operator, operator_authority.key)
+            @ MarinadeError::InvalidOperatorAuthority,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
     )]
     pub state: Account<'info, State>,
-    pub admin_authority: Signer<'info>,
+    pub operator_authority: Signer<'info>,
     #[account(
         mut,
         address = state.stake_system.stake_list.account,
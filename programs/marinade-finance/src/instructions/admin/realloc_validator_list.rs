@@ -1,8 +1,10 @@
 use anchor_lang::{prelude::*, system_program, Discriminator};
 
 use crate::{
-    error::MarinadeError, events::admin::ReallocValidatorListEvent,
-    state::validator_system::ValidatorList, State,
+    error::MarinadeError,
+    events::admin::ReallocValidatorListEvent,
+    state::{roles::Role, validator_system::ValidatorList},
+    State,
 };
 
 #[derive(Accounts)]
@@ -10,10 +12,12 @@ use crate::{
 pub struct ReallocValidatorList<'info> {
     #[account(
         mut,
-        has_one = admin_authority @ MarinadeError::InvalidAdminAuthority,
+        constraint = state.has_role(Role::Operator, operator_authority.key)
+            @ MarinadeError::InvalidOperatorAuthority,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
     )]
     pub state: Account<'info, State>,
-    pub admin_authority: Signer<'info>,
+    pub operator_authority: Signer<'info>,
     #[account(
         mut,
         address = state.validator_system.validator_list.account,
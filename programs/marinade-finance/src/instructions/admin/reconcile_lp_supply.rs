@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+
+use crate::{
+    error::MarinadeError,
+    events::{admin::ReconcileLpSupplyEvent, U64ValueChange},
+    state::roles::Role,
+    State,
+};
+
+#[derive(Accounts)]
+pub struct ReconcileLpSupply<'info> {
+    #[account(
+        mut,
+        constraint = state.has_role(Role::Operator, operator_authority.key)
+            @ MarinadeError::InvalidOperatorAuthority,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Account<'info, State>,
+    pub operator_authority: Signer<'info>,
+
+    #[account(address = state.liq_pool.lp_mint)]
+    pub lp_mint: Account<'info, Mint>,
+}
+
+impl<'info> ReconcileLpSupply<'info> {
+    /// resync the virtual lp_supply with the real lp_mint supply
+    /// after an UnexpectedLpSupply error has been investigated
+    pub fn process(&mut self) -> Result<()> {
+        let lp_supply_change = {
+            let old = self.state.liq_pool.lp_supply;
+            let new = self.state.lp_supply_of(self.lp_mint.supply);
+            self.state.liq_pool.lp_supply = new;
+            U64ValueChange { old, new }
+        };
+
+        emit!(ReconcileLpSupplyEvent {
+            state: self.state.key(),
+            authority: self.operator_authority.key(),
+            slot: Clock::get()?.slot,
+            lp_supply_change,
+        });
+
+        Ok(())
+    }
+}
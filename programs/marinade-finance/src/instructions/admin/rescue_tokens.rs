@@ -0,0 +1,179 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::token::{
+    transfer as transfer_tokens, Token, TokenAccount, Transfer as TransferTokens,
+};
+
+use crate::{
+    error::MarinadeError,
+    events::admin::{RescueLamportsEvent, RescueTokensEvent},
+    state::liq_pool::LiqPool,
+    State, ID,
+};
+
+/// Program PDAs users send tokens and SOL to by mistake
+#[derive(Clone, Copy, Debug, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub enum RescueAuthority {
+    Reserve,
+    LiqPoolSolLeg,
+    LiqPoolMsolLegAuthority,
+}
+
+impl RescueAuthority {
+    pub fn seed_and_bump(&self, state: &State) -> (&'static [u8], u8) {
+        match self {
+            RescueAuthority::Reserve => (State::RESERVE_SEED, state.reserve_bump_seed),
+            RescueAuthority::LiqPoolSolLeg => {
+                (LiqPool::SOL_LEG_SEED, state.liq_pool.sol_leg_bump_seed)
+            }
+            RescueAuthority::LiqPoolMsolLegAuthority => (
+                LiqPool::MSOL_LEG_AUTHORITY_SEED,
+                state.liq_pool.msol_leg_authority_bump_seed,
+            ),
+        }
+    }
+
+    /// the SOL leg lamports are liquidity and the SOL sent to the reserve goes
+    /// to the mSOL holders (see sweep_reserve_donations), none of them can be rescued
+    pub fn can_rescue_lamports(&self) -> bool {
+        match self {
+            RescueAuthority::Reserve | RescueAuthority::LiqPoolSolLeg => false,
+            RescueAuthority::LiqPoolMsolLegAuthority => true, // never holds SOL
+        }
+    }
+
+    pub fn check_address(
+        &self,
+        state: &Pubkey,
+        state_data: &State,
+        address: &Pubkey,
+    ) -> Result<()> {
+        let (seed, bump) = self.seed_and_bump(state_data);
+        let expected = Pubkey::create_program_address(&[&state.to_bytes(), seed, &[bump]], &ID)
+            .map_err(|_| error!(MarinadeError::InvalidRescueAuthority))?;
+        require_keys_eq!(*address, expected, MarinadeError::InvalidRescueAuthority);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct RescueTokens<'info> {
+    #[account(
+        has_one = admin_authority @ MarinadeError::InvalidAdminAuthority,
+        has_one = treasury_msol_account,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+    pub admin_authority: Signer<'info>,
+
+    /// CHECK: PDA, checked in code
+    pub pda_authority: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        token::authority = pda_authority,
+        constraint = token_account.mint != state.msol_mint
+            @ MarinadeError::CanNotRescueProtocolTokens,
+        constraint = token_account.mint != state.liq_pool.lp_mint
+            @ MarinadeError::CanNotRescueProtocolTokens,
+    )]
+    pub token_account: Box<Account<'info, TokenAccount>>,
+
+    // the rescued tokens go to the owner of the treasury mSOL account
+    pub treasury_msol_account: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        token::mint = token_account.mint,
+        token::authority = treasury_msol_account.owner,
+    )]
+    pub treasury_token_account: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> RescueTokens<'info> {
+    // fn rescue_tokens()
+    pub fn process(&mut self, authority: RescueAuthority) -> Result<()> {
+        authority.check_address(&self.state.key(), &self.state, self.pda_authority.key)?;
+        let (seed, bump) = authority.seed_and_bump(&self.state);
+        let amount = self.token_account.amount;
+        transfer_tokens(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                TransferTokens {
+                    from: self.token_account.to_account_info(),
+                    to: self.treasury_token_account.to_account_info(),
+                    authority: self.pda_authority.to_account_info(),
+                },
+                &[&[&self.state.key().to_bytes(), seed, &[bump]]],
+            ),
+            amount,
+        )?;
+
+        emit!(RescueTokensEvent {
+            state: self.state.key(),
+            authority,
+            token_account: self.token_account.key(),
+            mint: self.token_account.mint,
+            amount,
+            treasury_token_account: self.treasury_token_account.key(),
+        });
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct RescueLamports<'info> {
+    #[account(
+        has_one = admin_authority @ MarinadeError::InvalidAdminAuthority,
+        has_one = treasury_msol_account,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+    pub admin_authority: Signer<'info>,
+
+    // PDA, checked in code
+    #[account(mut)]
+    pub pda_authority: SystemAccount<'info>,
+
+    pub treasury_msol_account: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        address = treasury_msol_account.owner
+    )]
+    pub treasury_sol_account: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RescueLamports<'info> {
+    // fn rescue_lamports()
+    pub fn process(&mut self, authority: RescueAuthority) -> Result<()> {
+        require!(
+            authority.can_rescue_lamports(),
+            MarinadeError::InvalidRescueAuthority
+        );
+        authority.check_address(&self.state.key(), &self.state, self.pda_authority.key)?;
+        let (seed, bump) = authority.seed_and_bump(&self.state);
+        let excess = self.pda_authority.lamports();
+        require_gt!(excess, 0, MarinadeError::NothingToRescue);
+        transfer(
+            CpiContext::new_with_signer(
+                self.system_program.to_account_info(),
+                Transfer {
+                    from: self.pda_authority.to_account_info(),
+                    to: self.treasury_sol_account.to_account_info(),
+                },
+                &[&[&self.state.key().to_bytes(), seed, &[bump]]],
+            ),
+            excess,
+        )?;
+
+        emit!(RescueLamportsEvent {
+            state: self.state.key(),
+            authority,
+            lamports: excess,
+            treasury_sol_account: self.treasury_sol_account.key(),
+        });
+        Ok(())
+    }
+}
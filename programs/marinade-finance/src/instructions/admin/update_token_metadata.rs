@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::invoke_signed;
+
+use crate::{
+    error::MarinadeError, events::admin::TokenMetadataEvent,
+    instructions::admin::create_token_metadata::MetadataMint, token_metadata, State,
+};
+
+#[derive(Accounts)]
+pub struct UpdateTokenMetadata<'info> {
+    #[account(
+        has_one = admin_authority @ MarinadeError::InvalidAdminAuthority,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+    pub admin_authority: Signer<'info>,
+
+    /// CHECK: checked in code
+    pub mint: UncheckedAccount<'info>,
+    /// CHECK: PDA, checked in code
+    pub mint_authority: UncheckedAccount<'info>,
+    /// CHECK: owned by the metadata program
+    #[account(
+        mut,
+        address = token_metadata::metadata_address(mint.key)
+    )]
+    pub metadata: UncheckedAccount<'info>,
+
+    /// CHECK: CPI
+    #[account(address = token_metadata::ID)]
+    pub token_metadata_program: UncheckedAccount<'info>,
+}
+
+impl<'info> UpdateTokenMetadata<'info> {
+    // fn update_token_metadata()
+    pub fn process(
+        &mut self,
+        metadata_mint: MetadataMint,
+        name: String,
+        symbol: String,
+        uri: String,
+    ) -> Result<()> {
+        metadata_mint.check_accounts(
+            &self.state.key(),
+            &self.state,
+            self.mint.key,
+            self.mint_authority.key,
+        )?;
+        let (seed, bump) = metadata_mint.authority_seed_and_bump(&self.state);
+        invoke_signed(
+            &token_metadata::update_metadata_account(
+                self.metadata.key,
+                self.mint_authority.key,
+                name.clone(),
+                symbol.clone(),
+                uri.clone(),
+            )?,
+            &[
+                self.token_metadata_program.to_account_info(),
+                self.metadata.to_account_info(),
+                self.mint_authority.to_account_info(),
+            ],
+            &[&[&self.state.key().to_bytes(), seed, &[bump]]],
+        )?;
+
+        emit!(TokenMetadataEvent {
+            state: self.state.key(),
+            authority: self.admin_authority.key(),
+            slot: Clock::get()?.slot,
+            mint: self.mint.key(),
+            metadata: self.metadata.key(),
+            name,
+            symbol,
+            uri,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,203 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, TokenAccount};
+
+use crate::{
+    events::admin::VerifyConfigEvent,
+    state::{
+        liq_pool::LiqPool, msol_lock::MsolLockSystem, paused_operations::PausedOperations,
+        referral::ReferralPartner, stake_system::StakeSystem, Fee,
+    },
+    State,
+};
+
+/// Invariants checked by verify_config
+#[derive(Clone, Copy, Debug, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub enum ConfigCheck {
+    RewardFee,
+    LpFees,
+    LpLiquidityTarget,
+    TreasuryCut,
+    LockFeeBoost,
+    FlashLoanFee,
+    MsolLegTarget,
+    MinStake,
+    SlotsForStakeDelta,
+    MinWithdraw,
+    DelayedUnstakeFee,
+    WithdrawStakeAccountFee,
+    ReferralShare,
+    MsolLockFeeShare,
+    ConfigChangeDelay,
+    MsolPriceChangeLimit,
+    PausedOperations,
+    BumpSeeds,
+    MsolMintAuthority,
+    LpMintAuthority,
+    MsolLeg,
+    StateVersion,
+    MaxDirectedStakeShare,
+    MaxCommission,
+    MaxValidatorStakeShare,
+    ValidatorBondSlash,
+}
+
+#[derive(Accounts)]
+pub struct VerifyConfig<'info> {
+    #[account(has_one = msol_mint)]
+    pub state: Box<Account<'info, State>>,
+    pub msol_mint: Box<Account<'info, Mint>>,
+    #[account(address = state.liq_pool.lp_mint)]
+    pub lp_mint: Box<Account<'info, Mint>>,
+    #[account(address = state.liq_pool.msol_leg)]
+    pub liq_pool_msol_leg: Box<Account<'info, TokenAccount>>,
+}
+
+impl<'info> VerifyConfig<'info> {
+    // fn verify_config()
+    // permissionless, reports the violated invariants instead of failing
+    pub fn process(&mut self) -> Result<()> {
+        let state = &self.state;
+        let state_address = state.key();
+        let liq_pool = &state.liq_pool;
+        let liq_pool_ext = &state.liq_pool_ext;
+        let full = Fee::FULL;
+
+        let bumps_ok = State::find_reserve_address(&state_address).1 == state.reserve_bump_seed
+            && State::find_msol_mint_authority(&state_address).1
+                == state.msol_mint_authority_bump_seed
+            && StakeSystem::find_stake_deposit_authority(&state_address).1
+                == state.stake_system.stake_deposit_bump_seed
+            && StakeSystem::find_stake_withdraw_authority(&state_address).1
+                == state.stake_system.stake_withdraw_bump_seed
+            && LiqPool::find_lp_mint_authority(&state_address).1
+                == liq_pool.lp_mint_authority_bump_seed
+            && LiqPool::find_sol_leg_address(&state_address).1 == liq_pool.sol_leg_bump_seed
+            && LiqPool::find_msol_leg_authority(&state_address).1
+                == liq_pool.msol_leg_authority_bump_seed;
+
+        let checks = [
+            (
+                ConfigCheck::RewardFee,
+                state.reward_fee <= State::MAX_REWARD_FEE,
+            ),
+            (
+                ConfigCheck::LpFees,
+                liq_pool.lp_min_fee <= liq_pool.lp_max_fee
+                    && liq_pool.lp_max_fee <= LiqPool::MAX_FEE,
+            ),
+            (
+                ConfigCheck::LpLiquidityTarget,
+                liq_pool.lp_liquidity_target >= LiqPool::MIN_LIQUIDITY_TARGET,
+            ),
+            (
+                ConfigCheck::TreasuryCut,
+                liq_pool.treasury_cut <= LiqPool::MAX_TREASURY_CUT,
+            ),
+            (
+                ConfigCheck::LockFeeBoost,
+                liq_pool_ext.lock_fee_boost <= LiqPool::MAX_LOCK_FEE_BOOST,
+            ),
+            (
+                ConfigCheck::FlashLoanFee,
+                liq_pool_ext.flash_loan_fee <= LiqPool::MAX_FLASH_LOAN_FEE,
+            ),
+            (
+                ConfigCheck::MsolLegTarget,
+                liq_pool_ext.msol_leg_target <= full,
+            ),
+            (
+                ConfigCheck::MinStake,
+                state.stake_system.min_stake >= State::MIN_STAKE_LOWER_LIMIT,
+            ),
+            (
+                ConfigCheck::SlotsForStakeDelta,
+                state.stake_system.slots_for_stake_delta >= StakeSystem::MIN_UPDATE_WINDOW,
+            ),
+            (
+                ConfigCheck::MinWithdraw,
+                state.min_withdraw <= State::MAX_WITHDRAW_ATOM,
+            ),
+            (
+                ConfigCheck::DelayedUnstakeFee,
+                state.delayed_unstake_fee <= State::MAX_DELAYED_UNSTAKE_FEE,
+            ),
+            (
+                ConfigCheck::WithdrawStakeAccountFee,
+                state.withdraw_stake_account_fee <= State::MAX_WITHDRAW_STAKE_ACCOUNT_FEE,
+            ),
+            (
+                ConfigCheck::ReferralShare,
+                state.referral_share <= ReferralPartner::MAX_REFERRAL_SHARE,
+            ),
+            (
+                ConfigCheck::MsolLockFeeShare,
+                state.msol_lock.fee_share <= MsolLockSystem::MAX_FEE_SHARE,
+            ),
+            (
+                ConfigCheck::ConfigChangeDelay,
+                state.config_change_delay_slots <= State::MAX_CONFIG_CHANGE_DELAY_SLOTS,
+            ),
+            (
+                ConfigCheck::MsolPriceChangeLimit,
+                state.msol_price_change_limit <= full,
+            ),
+            (
+                ConfigCheck::PausedOperations,
+                state.paused & !PausedOperations::ALL == 0,
+            ),
+            (ConfigCheck::BumpSeeds, bumps_ok),
+            (
+                ConfigCheck::MsolMintAuthority,
+                self.msol_mint
+                    .mint_authority
+                    .contains(&State::find_msol_mint_authority(&state_address).0)
+                    && self.msol_mint.supply <= state.msol_supply,
+            ),
+            (
+                ConfigCheck::LpMintAuthority,
+                self.lp_mint
+                    .mint_authority
+                    .contains(&LiqPool::find_lp_mint_authority(&state_address).0),
+            ),
+            (
+                ConfigCheck::MsolLeg,
+                self.liq_pool_msol_leg.mint == state.msol_mint
+                    && self.liq_pool_msol_leg.owner
+                        == LiqPool::find_msol_leg_authority(&state_address).0,
+            ),
+            (ConfigCheck::StateVersion, state.check_version()),
+            (
+                ConfigCheck::MaxDirectedStakeShare,
+                state.max_directed_stake_share <= full,
+            ),
+            (
+                ConfigCheck::MaxCommission,
+                state.max_commission <= State::MAX_COMMISSION,
+            ),
+            (
+                ConfigCheck::MaxValidatorStakeShare,
+                state.max_validator_stake_share <= full,
+            ),
+            (
+                ConfigCheck::ValidatorBondSlash,
+                state.validator_bond_slash <= full,
+            ),
+        ];
+
+        let violations: Vec<ConfigCheck> = checks
+            .iter()
+            .filter(|(_, ok)| !ok)
+            .map(|(check, _)| *check)
+            .collect();
+        for violation in &violations {
+            msg!("Config check failed: {:?}", violation);
+        }
+
+        emit!(VerifyConfigEvent {
+            state: state_address,
+            checked: checks.len() as u8,
+            violations,
+        });
+        Ok(())
+    }
+}
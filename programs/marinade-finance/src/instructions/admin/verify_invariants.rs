@@ -0,0 +1,164 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, TokenAccount};
+
+use crate::{
+    error::MarinadeError,
+    events::admin::VerifyInvariantsEvent,
+    state::{
+        liq_pool::LiqPool,
+        stake_system::{StakeList, StakeRecord},
+        validator_system::ValidatorList,
+    },
+    State,
+};
+
+/// Balances recomputed by verify_invariants
+#[derive(Clone, Copy, Debug, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub enum Invariant {
+    /// sum of the validator active balances vs ValidatorSystem::total_active_balance
+    TotalActiveBalance,
+    /// sum of the stake records vs total_active_balance + total_cooling_down
+    StakeBalance,
+    /// reserve lamports above rent vs State::available_reserve_balance
+    ReserveBalance,
+    /// mSOL mint supply vs State::msol_supply
+    MsolSupply,
+    /// LP mint supply and the minimum liquidity vs LiqPool::lp_supply
+    LpSupply,
+    /// recomputed vs State::total_lamports_under_control
+    TotalLamportsUnderControl,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub struct InvariantCheck {
+    pub invariant: Invariant,
+    pub recorded: u64,
+    pub computed: u64,
+}
+
+impl InvariantCheck {
+    pub fn holds(&self, tolerance: u64) -> bool {
+        self.recorded.abs_diff(self.computed) <= tolerance
+    }
+}
+
+#[derive(Accounts)]
+pub struct VerifyInvariants<'info> {
+    #[account(
+        has_one = msol_mint,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+    pub msol_mint: Box<Account<'info, Mint>>,
+    #[account(address = state.liq_pool.lp_mint)]
+    pub lp_mint: Box<Account<'info, Mint>>,
+    #[account(
+        seeds = [
+            &state.key().to_bytes(),
+            State::RESERVE_SEED
+        ],
+        bump = state.reserve_bump_seed
+    )]
+    pub reserve_pda: SystemAccount<'info>,
+    #[account(
+        seeds = [
+            &state.key().to_bytes(),
+            LiqPool::SOL_LEG_SEED
+        ],
+        bump = state.liq_pool.sol_leg_bump_seed
+    )]
+    pub liq_pool_sol_leg_pda: SystemAccount<'info>,
+    #[account(address = state.liq_pool.msol_leg)]
+    pub liq_pool_msol_leg: Box<Account<'info, TokenAccount>>,
+    #[account(address = state.stake_system.stake_list.account)]
+    pub stake_list: Account<'info, StakeList>,
+    #[account(address = state.validator_system.validator_list.account)]
+    pub validator_list: Account<'info, ValidatorList>,
+}
+
+impl<'info> VerifyInvariants<'info> {
+    // fn verify_invariants()
+    // permissionless, recomputes the balances the State records from the lists, the reserve
+    // and the mints. Emits every check and fails when one is off by more than `tolerance`
+    // lamports, the event is in the logs of a simulation anyway
+    pub fn process(&self, tolerance: u64) -> Result<()> {
+        let state = &self.state;
+
+        // full scans of the lists read in place, the totals must come from a single transaction
+        let mut total_active_balance: u64 = 0;
+        {
+            let validator_list_data = self.validator_list.to_account_info();
+            let validator_list_data = validator_list_data.data.borrow();
+            for index in 0..state.validator_system.validator_count() {
+                total_active_balance += state
+                    .validator_system
+                    .get(&validator_list_data, index)?
+                    .active_balance;
+            }
+        }
+        let mut stake_balance: u64 = 0;
+        {
+            let stake_list_data = self.stake_list.to_account_info();
+            let stake_list_data = stake_list_data.data.borrow();
+            for index in 0..state.stake_system.stake_count() {
+                let stake: StakeRecord =
+                    state.stake_system.stake_list.get(&stake_list_data, index)?;
+                stake_balance += stake.last_update_delegated_lamports;
+            }
+        }
+        let reserve_balance = self
+            .reserve_pda
+            .lamports()
+            .saturating_sub(state.rent_exempt_for_token_acc);
+
+        let checks = [
+            InvariantCheck {
+                invariant: Invariant::TotalActiveBalance,
+                recorded: state.validator_system.total_active_balance,
+                computed: total_active_balance,
+            },
+            InvariantCheck {
+                invariant: Invariant::StakeBalance,
+                recorded: state.validator_system.total_active_balance + state.total_cooling_down(),
+                computed: stake_balance,
+            },
+            InvariantCheck {
+                invariant: Invariant::ReserveBalance,
+                recorded: state.available_reserve_balance,
+                computed: reserve_balance,
+            },
+            InvariantCheck {
+                invariant: Invariant::MsolSupply,
+                recorded: state.msol_supply,
+                computed: self.msol_mint.supply,
+            },
+            InvariantCheck {
+                invariant: Invariant::LpSupply,
+                recorded: state.liq_pool.lp_supply,
+                computed: state.lp_supply_of(self.lp_mint.supply),
+            },
+            InvariantCheck {
+                invariant: Invariant::TotalLamportsUnderControl,
+                recorded: state.total_lamports_under_control(),
+                computed: total_active_balance + state.total_cooling_down() + reserve_balance,
+            },
+        ];
+        let violations: Vec<Invariant> = checks
+            .iter()
+            .filter(|check| !check.holds(tolerance))
+            .map(|check| check.invariant)
+            .collect();
+
+        emit!(VerifyInvariantsEvent {
+            state: state.key(),
+            slot: Clock::get()?.slot,
+            tolerance,
+            sol_leg_balance: self.liq_pool_sol_leg_pda.lamports(),
+            msol_leg_balance: self.liq_pool_msol_leg.amount,
+            checks: checks.to_vec(),
+            violations: violations.clone(),
+        });
+        require!(violations.is_empty(), MarinadeError::InvariantViolation);
+        Ok(())
+    }
+}
@@ -0,0 +1,5 @@
+pub mod set_affiliated_validator;
+pub mod sweep_validator_commission;
+
+pub use set_affiliated_validator::*;
+pub use sweep_validator_commission::*;
@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::system_program;
+
+use crate::{
+    error::MarinadeError,
+    events::{affiliated_validator::SetAffiliatedValidatorEvent, FeeValueChange},
+    state::{affiliated_validator::AffiliatedValidator, paused_operations::PausedOperations},
+    Fee, State,
+};
+
+#[derive(Accounts)]
+pub struct SetAffiliatedValidator<'info> {
+    #[account(
+        has_one = admin_authority @ MarinadeError::InvalidAdminAuthority,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+    pub admin_authority: Signer<'info>,
+
+    /// CHECK: only the address is used
+    pub validator_vote: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = rent_payer,
+        space = AffiliatedValidator::LEN,
+        seeds = [
+            &state.key().to_bytes(),
+            AffiliatedValidator::SEED,
+            &validator_vote.key().to_bytes()
+        ],
+        bump,
+    )]
+    pub affiliated_validator: Box<Account<'info, AffiliatedValidator>>,
+
+    #[account(
+        mut,
+        owner = system_program::ID
+    )]
+    pub rent_payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SetAffiliatedValidator<'info> {
+    // fn set_affiliated_validator()
+    // marks a protocol operated validator, the sweep works once the vote account
+    // withdraw authority is moved to the AffiliatedValidator::WITHDRAW_AUTHORITY_SEED PDA
+    pub fn process(&mut self, operator: Pubkey, reserve_share: Fee, bump: u8) -> Result<()> {
+        self.state.check_paused(PausedOperations::VALIDATOR_OPS)?;
+        reserve_share.check()?;
+
+        if self.affiliated_validator.state == Pubkey::default() {
+            self.affiliated_validator.set_inner(AffiliatedValidator {
+                state: self.state.key(),
+                validator: self.validator_vote.key(),
+                bump,
+                operator,
+                reserve_share: Fee::from_basis_points(0),
+                swept_lamports: 0,
+            });
+        }
+        let old_reserve_share = self.affiliated_validator.reserve_share;
+        self.affiliated_validator.operator = operator;
+        self.affiliated_validator.reserve_share = reserve_share;
+
+        emit!(SetAffiliatedValidatorEvent {
+            state: self.state.key(),
+            authority: self.admin_authority.key(),
+            slot: Clock::get()?.slot,
+            validator: self.validator_vote.key(),
+            operator,
+            reserve_share: FeeValueChange {
+                old: old_reserve_share,
+                new: reserve_share,
+            },
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,144 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{program::invoke_signed, vote},
+};
+
+use crate::{
+    error::MarinadeError,
+    events::affiliated_validator::SweepValidatorCommissionEvent,
+    state::{affiliated_validator::AffiliatedValidator, paused_operations::PausedOperations},
+    State,
+};
+
+#[derive(Accounts)]
+pub struct SweepValidatorCommission<'info> {
+    #[account(
+        mut,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+
+    /// CHECK: CPI
+    #[account(
+        mut,
+        owner = vote::program::ID
+    )]
+    pub validator_vote: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        has_one = state,
+        has_one = operator,
+        seeds = [
+            &state.key().to_bytes(),
+            AffiliatedValidator::SEED,
+            &validator_vote.key().to_bytes()
+        ],
+        bump = affiliated_validator.bump,
+    )]
+    pub affiliated_validator: Box<Account<'info, AffiliatedValidator>>,
+    /// CHECK: PDA, the vote account withdraw authority
+    #[account(
+        seeds = [
+            &state.key().to_bytes(),
+            AffiliatedValidator::WITHDRAW_AUTHORITY_SEED
+        ],
+        bump,
+    )]
+    pub vote_withdraw_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            &state.key().to_bytes(),
+            State::RESERVE_SEED
+        ],
+        bump = state.reserve_bump_seed
+    )]
+    pub reserve_pda: SystemAccount<'info>,
+    #[account(mut)]
+    pub operator: SystemAccount<'info>,
+
+    pub clock: Sysvar<'info, Clock>,
+    pub rent: Sysvar<'info, Rent>,
+    /// CHECK: CPI
+    #[account(address = vote::program::ID)]
+    pub vote_program: UncheckedAccount<'info>,
+}
+
+impl<'info> SweepValidatorCommission<'info> {
+    // fn sweep_validator_commission()
+    // permissionless, withdraws the vote account balance above the rent exempt reserve,
+    // AffiliatedValidator::reserve_share of it goes to the reserve and raises the mSOL price,
+    // the rest goes to the operator
+    pub fn process(&mut self, vote_withdraw_authority_bump: u8) -> Result<()> {
+        self.state.check_paused(PausedOperations::VALIDATOR_OPS)?;
+
+        let validator_vote = self.validator_vote.to_account_info();
+        let amount = validator_vote
+            .lamports()
+            .saturating_sub(self.rent.minimum_balance(validator_vote.data_len()));
+        if amount == 0 {
+            msg!("No commission to sweep");
+            return Ok(()); // Not an error. Don't fail other instructions in tx
+        }
+        let reserve_amount = self.affiliated_validator.reserve_share.apply(amount);
+        let operator_amount = amount - reserve_amount;
+
+        self.withdraw_commission(
+            &self.reserve_pda.to_account_info(),
+            reserve_amount,
+            vote_withdraw_authority_bump,
+        )?;
+        self.state.on_transfer_to_reserve(reserve_amount);
+        self.withdraw_commission(
+            &self.operator.to_account_info(),
+            operator_amount,
+            vote_withdraw_authority_bump,
+        )?;
+        self.affiliated_validator.swept_lamports += amount;
+
+        emit!(SweepValidatorCommissionEvent {
+            state: self.state.key(),
+            epoch: self.clock.epoch,
+            validator: self.validator_vote.key(),
+            operator: self.operator.key(),
+            amount,
+            reserve_amount,
+            operator_amount,
+            swept_lamports: self.affiliated_validator.swept_lamports,
+        });
+
+        Ok(())
+    }
+
+    fn withdraw_commission(
+        &self,
+        to: &AccountInfo<'info>,
+        amount: u64,
+        vote_withdraw_authority_bump: u8,
+    ) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+        invoke_signed(
+            &vote::instruction::withdraw(
+                self.validator_vote.key,
+                self.vote_withdraw_authority.key,
+                amount,
+                to.key,
+            ),
+            &[
+                self.vote_program.to_account_info(),
+                self.validator_vote.to_account_info(),
+                to.clone(),
+                self.vote_withdraw_authority.to_account_info(),
+            ],
+            &[&[
+                &self.state.key().to_bytes(),
+                AffiliatedValidator::WITHDRAW_AUTHORITY_SEED,
+                &[vote_withdraw_authority_bump],
+            ]],
+        )?;
+        Ok(())
+    }
+}
@@ -0,0 +1,108 @@
+use anchor_lang::{prelude::*, solana_program::program::invoke};
+use anchor_spl::stake::StakeAccount;
+
+use crate::{
+    error::MarinadeError,
+    events::crank::ClaimMevTipsEvent,
+    state::{paused_operations::PausedOperations, stake_system::StakeList},
+    tip_distribution, State,
+};
+
+#[derive(Accounts)]
+pub struct ClaimMevTips<'info> {
+    #[account(
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+    #[account(
+        address = state.stake_system.stake_list.account,
+    )]
+    pub stake_list: Account<'info, StakeList>,
+    // the claimant of the tips
+    #[account(mut)]
+    pub stake_account: Box<Account<'info, StakeAccount>>,
+
+    /// CHECK: CPI
+    pub tip_distribution_config: UncheckedAccount<'info>,
+    /// CHECK: CPI
+    #[account(
+        mut,
+        owner = tip_distribution::ID
+    )]
+    pub tip_distribution_account: UncheckedAccount<'info>,
+    /// CHECK: CPI, created by the tip distribution program
+    #[account(mut)]
+    pub claim_status: UncheckedAccount<'info>,
+    // pays the rent of claim_status
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub clock: Sysvar<'info, Clock>,
+    pub system_program: Program<'info, System>,
+    /// CHECK: CPI
+    #[account(address = tip_distribution::ID)]
+    pub tip_distribution_program: UncheckedAccount<'info>,
+}
+
+impl<'info> ClaimMevTips<'info> {
+    // fn claim_mev_tips()
+    // permissionless, claims the MEV tips of one of our stake accounts.
+    // The tips land in the stake account as extra lamports, update_active moves them to the reserve
+    // taking the reward fee, so they are part of the mSOL price instead of expiring unclaimed
+    pub fn process(
+        &mut self,
+        stake_index: u32,
+        claim_status_bump: u8,
+        amount: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        self.state.check_paused(PausedOperations::VALIDATOR_OPS)?;
+        self.state
+            .stake_system
+            .get_checked(
+                &self.stake_list.to_account_info().data.as_ref().borrow(),
+                stake_index,
+                self.stake_account.to_account_info().key,
+            )
+            .map_err(|e| e.with_account_name("stake_account"))?;
+
+        let stake_balance = self.stake_account.to_account_info().lamports();
+        invoke(
+            &tip_distribution::claim(
+                self.tip_distribution_config.key,
+                self.tip_distribution_account.key,
+                self.claim_status.key,
+                self.stake_account.to_account_info().key,
+                self.payer.key,
+                claim_status_bump,
+                amount,
+                proof,
+            )?,
+            &[
+                self.tip_distribution_program.to_account_info(),
+                self.tip_distribution_config.to_account_info(),
+                self.tip_distribution_account.to_account_info(),
+                self.claim_status.to_account_info(),
+                self.stake_account.to_account_info(),
+                self.payer.to_account_info(),
+                self.system_program.to_account_info(),
+            ],
+        )?;
+        let claimed = self
+            .stake_account
+            .to_account_info()
+            .lamports()
+            .saturating_sub(stake_balance);
+
+        emit!(ClaimMevTipsEvent {
+            state: self.state.key(),
+            epoch: self.clock.epoch,
+            stake_index,
+            stake_account: self.stake_account.key(),
+            tip_distribution_account: self.tip_distribution_account.key(),
+            amount: claimed,
+        });
+
+        Ok(())
+    }
+}
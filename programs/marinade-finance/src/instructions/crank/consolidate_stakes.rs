@@ -0,0 +1,176 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::stake_history;
+use anchor_spl::stake::{Stake, StakeAccount};
+
+use crate::instructions::crank::{keeper_reward::pay_keeper_reward, merge_stakes::merge_stake};
+use crate::state::keeper_pool::KeeperPool;
+use crate::state::stake_system::StakeList;
+use crate::state::validator_system::ValidatorList;
+use crate::{
+    error::MarinadeError,
+    state::{paused_operations::PausedOperations, stake_system::StakeSystem},
+    State,
+};
+
+#[derive(Accounts)]
+pub struct ConsolidateStakes<'info> {
+    #[account(
+        mut,
+        has_one = operational_sol_account,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+    #[account(
+        mut,
+        address = state.stake_system.stake_list.account,
+    )]
+    pub stake_list: Account<'info, StakeList>,
+    #[account(
+        mut,
+        address = state.validator_system.validator_list.account,
+    )]
+    pub validator_list: Account<'info, ValidatorList>,
+    #[account(mut)]
+    pub destination_stake: Box<Account<'info, StakeAccount>>,
+    /// CHECK: PDA
+    #[account(
+        seeds = [
+            &state.key().to_bytes(),
+            StakeSystem::STAKE_DEPOSIT_SEED
+        ],
+        bump = state.stake_system.stake_deposit_bump_seed
+    )]
+    pub stake_deposit_authority: UncheckedAccount<'info>,
+    /// CHECK: PDA
+    #[account(
+        seeds = [
+            &state.key().to_bytes(),
+            StakeSystem::STAKE_WITHDRAW_SEED
+        ],
+        bump = state.stake_system.stake_withdraw_bump_seed
+    )]
+    pub stake_withdraw_authority: UncheckedAccount<'info>,
+    /// CHECK: not important
+    #[account(mut)]
+    pub operational_sol_account: UncheckedAccount<'info>,
+
+    pub clock: Sysvar<'info, Clock>,
+    /// CHECK: have no CPU budget to parse
+    #[account(address = stake_history::ID)]
+    pub stake_history: UncheckedAccount<'info>,
+
+    pub stake_program: Program<'info, Stake>,
+
+    // pays State::keeper_reward to keeper, see KeeperPool
+    #[account(
+        mut,
+        has_one = state
+    )]
+    pub keeper_pool: Option<Box<Account<'info, KeeperPool>>>,
+    #[account(mut)]
+    pub keeper: Option<SystemAccount<'info>>,
+}
+
+impl<'info> ConsolidateStakes<'info> {
+    // fn consolidate_stakes()
+    // permissionless defragmentation of the stake list: merges the stake accounts in
+    // `source_stakes` into the destination stake one by one as merge_stakes does.
+    // `source_stake_indexes` must be descending and above the destination index, so the
+    // removals from the stake list don't move the accounts not merged yet.
+    // Source accounts that can't be merged (activating, deactivating, not updated or on other
+    // validator) are skipped
+    pub fn process(
+        &mut self,
+        source_stakes: &[AccountInfo<'info>],
+        destination_stake_index: u32,
+        source_stake_indexes: Vec<u32>,
+        validator_index: u32,
+    ) -> Result<()> {
+        self.state.check_paused(PausedOperations::VALIDATOR_OPS)?;
+        require_gt!(
+            source_stake_indexes.len(),
+            0,
+            MarinadeError::StakeIndexesMismatch
+        );
+        require_eq!(
+            source_stakes.len(),
+            source_stake_indexes.len(),
+            MarinadeError::StakeIndexesMismatch
+        );
+        let mut previous_index = u32::MAX;
+        for source_stake_index in source_stake_indexes.iter() {
+            require_gt!(
+                previous_index,
+                *source_stake_index,
+                MarinadeError::InvalidSourceStakeIndexes
+            );
+            previous_index = *source_stake_index;
+        }
+        require_gt!(
+            previous_index,
+            destination_stake_index,
+            MarinadeError::InvalidSourceStakeIndexes
+        );
+
+        let state_address = self.state.key();
+        let validator_vote = self
+            .state
+            .validator_system
+            .get(
+                &self.validator_list.to_account_info().data.as_ref().borrow(),
+                validator_index,
+            )?
+            .validator_account;
+        let mut merged: u32 = 0;
+        for (source_stake, source_stake_index) in source_stakes.iter().zip(source_stake_indexes) {
+            let source_stake = Account::<StakeAccount>::try_from(source_stake)?;
+            let source_stake_info = self.state.stake_system.get_checked(
+                &self.stake_list.to_account_info().data.as_ref().borrow(),
+                source_stake_index,
+                source_stake.to_account_info().key,
+            )?;
+            let mergeable = match source_stake.delegation() {
+                Some(delegation) => {
+                    delegation.voter_pubkey == validator_vote
+                        && delegation.activation_epoch < self.clock.epoch
+                        && delegation.deactivation_epoch == std::u64::MAX
+                        && source_stake_info.last_update_delegated_lamports == delegation.stake
+                        && source_stake.to_account_info().lamports()
+                            == delegation.stake + source_stake.meta().unwrap().rent_exempt_reserve
+                }
+                None => false,
+            };
+            if !mergeable {
+                msg!("Stake {} is not mergeable", source_stake.key());
+                continue;
+            }
+
+            merge_stake(
+                &mut self.state,
+                &state_address,
+                &self.stake_list.to_account_info(),
+                &self.validator_list.to_account_info(),
+                &mut self.destination_stake,
+                destination_stake_index,
+                &source_stake,
+                source_stake_index,
+                validator_index,
+                &self.stake_deposit_authority.to_account_info(),
+                &self.stake_withdraw_authority.to_account_info(),
+                &self.operational_sol_account.to_account_info(),
+                &self.clock,
+                &self.stake_history.to_account_info(),
+                &self.stake_program.to_account_info(),
+            )?;
+            merged += 1;
+        }
+        require_gt!(merged, 0, MarinadeError::NoMergeableStakes);
+
+        pay_keeper_reward(
+            &self.state,
+            self.keeper_pool.as_deref_mut(),
+            self.keeper.as_ref().map(|keeper| keeper.to_account_info()),
+        )?;
+        Ok(())
+    }
+}
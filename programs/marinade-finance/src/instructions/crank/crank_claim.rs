@@ -0,0 +1,147 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+
+use crate::events::delayed_unstake::CrankClaimEvent;
+use crate::instructions::delayed_unstake::claim::check_ticket_due;
+use crate::state::delayed_unstake_ticket::{TicketAccountData, TicketAutoClaim};
+use crate::state::paused_operations::PausedOperations;
+use crate::MarinadeError;
+use crate::State;
+
+#[derive(Accounts)]
+pub struct CrankClaim<'info> {
+    #[account(
+        mut,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Account<'info, State>,
+    #[account(
+        mut,
+        seeds = [
+            &state.key().to_bytes(),
+            State::RESERVE_SEED
+        ],
+        bump = state.reserve_bump_seed
+    )]
+    pub reserve_pda: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        close = beneficiary,
+    )]
+    pub ticket_account: Account<'info, TicketAccountData>,
+    #[account(
+        mut,
+        close = beneficiary,
+        seeds = [
+            &state.key().to_bytes(),
+            TicketAutoClaim::SEED,
+            &ticket_account.key().to_bytes()
+        ],
+        bump = auto_claim.bump,
+        has_one = beneficiary @ MarinadeError::WrongBeneficiary,
+    )]
+    pub auto_claim: Account<'info, TicketAutoClaim>,
+
+    #[account(
+        mut,
+        address = ticket_account.beneficiary @ MarinadeError::WrongBeneficiary
+    )]
+    pub beneficiary: SystemAccount<'info>,
+
+    // receives the tip
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+
+    pub clock: Sysvar<'info, Clock>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CrankClaim<'info> {
+    fn transfer_from_reserve(&mut self, to: AccountInfo<'info>, lamports: u64) -> Result<()> {
+        transfer(
+            CpiContext::new_with_signer(
+                self.system_program.to_account_info(),
+                Transfer {
+                    from: self.reserve_pda.to_account_info(),
+                    to,
+                },
+                &[&[
+                    &self.state.key().to_bytes(),
+                    State::RESERVE_SEED,
+                    &[self.state.reserve_bump_seed],
+                ]],
+            ),
+            lamports,
+        )?;
+        self.state.on_transfer_from_reserve(lamports);
+        Ok(())
+    }
+
+    // fn crank_claim()
+    // permissionless claim of a due ticket whose beneficiary opted in with enable_auto_claim
+    pub fn process(&mut self) -> Result<()> {
+        self.state.check_paused(PausedOperations::DELAYED_UNSTAKE)?;
+        require_keys_eq!(
+            self.ticket_account.state_address,
+            self.state.key(),
+            MarinadeError::InvalidDelayedUnstakeTicket
+        );
+        // not used
+        require_neq!(
+            self.ticket_account.lamports_amount,
+            0,
+            MarinadeError::ReusingDelayedUnstakeTicket
+        );
+        check_ticket_due(&self.ticket_account, &self.clock)
+            .map_err(|e| e.with_account_name("ticket_account"))?;
+
+        // record for event, use real balance not virtual field
+        let user_balance = self.beneficiary.lamports();
+        let reserve_balance = self.reserve_pda.lamports();
+        let lamports = self.ticket_account.lamports_amount;
+
+        // use real balance not virtual field
+        let available_for_claim = reserve_balance - self.state.rent_exempt_for_token_acc;
+        if lamports > available_for_claim {
+            msg!(
+                "Requested to claim {} when only {} ready. Wait a few hours and retry",
+                lamports,
+                available_for_claim
+            );
+            // Error: "Wait a few hours and retry"
+            return err!(MarinadeError::TicketNotReady);
+        }
+
+        // record for event and then update
+        let circulating_ticket_balance = self.state.circulating_ticket_balance;
+        let circulating_ticket_count = self.state.circulating_ticket_count;
+        self.state.circulating_ticket_balance -= lamports;
+        self.state.circulating_ticket_count -= 1;
+        // disable ticket-account
+        self.ticket_account.lamports_amount = 0;
+
+        let tip = self.auto_claim.tip.apply(lamports);
+        self.transfer_from_reserve(self.beneficiary.to_account_info(), lamports - tip)?;
+        if tip > 0 {
+            self.transfer_from_reserve(self.cranker.to_account_info(), tip)?;
+        }
+
+        emit!(CrankClaimEvent {
+            state: self.state.key(),
+            epoch: self.clock.epoch,
+            ticket: self.ticket_account.key(),
+            beneficiary: self.beneficiary.key(),
+            cranker: self.cranker.key(),
+            circulating_ticket_balance,
+            circulating_ticket_count,
+            reserve_balance,
+            user_balance,
+            amount: lamports,
+            tip,
+        });
+
+        Ok(())
+    }
+}
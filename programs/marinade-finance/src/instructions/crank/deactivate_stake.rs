@@ -1,8 +1,12 @@
 use crate::{
     error::MarinadeError,
     events::crank::{DeactivateStakeEvent, SplitStakeAccountInfo},
+    instructions::crank::keeper_reward::pay_keeper_reward,
     require_lt,
     state::{
+        directed_stake::ValidatorDirectedStake,
+        keeper_pool::KeeperPool,
+        paused_operations::PausedOperations,
         stake_system::{StakeList, StakeSystem},
         validator_system::ValidatorList,
     },
@@ -23,7 +27,10 @@ use crate::checks::check_stake_amount_and_validator;
 
 #[derive(Accounts)]
 pub struct DeactivateStake<'info> {
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
     pub state: Box<Account<'info, State>>,
     // Readonly. For stake delta calculation
     #[account(
@@ -77,6 +84,19 @@ pub struct DeactivateStake<'info> {
 
     pub system_program: Program<'info, System>,
     pub stake_program: Program<'info, Stake>,
+
+    // directed stake of the validator, keeps it from being unstaked below its directed stake target
+    #[account(has_one = state)]
+    pub validator_directed_stake: Option<Box<Account<'info, ValidatorDirectedStake>>>,
+
+    // pays State::keeper_reward to keeper, see KeeperPool
+    #[account(
+        mut,
+        has_one = state
+    )]
+    pub keeper_pool: Option<Box<Account<'info, KeeperPool>>>,
+    #[account(mut)]
+    pub keeper: Option<SystemAccount<'info>>,
 }
 
 impl<'info> DeactivateStake<'info> {
@@ -84,7 +104,7 @@ impl<'info> DeactivateStake<'info> {
     // fn deactivate_stake()
     //
     pub fn process(&mut self, stake_index: u32, validator_index: u32) -> Result<()> {
-        require!(!self.state.paused, MarinadeError::ProgramIsPaused);
+        self.state.check_paused(PausedOperations::VALIDATOR_OPS)?;
 
         let mut stake = self.state.stake_system.get_checked(
             &self.stake_list.to_account_info().data.as_ref().borrow(),
@@ -115,7 +135,9 @@ impl<'info> DeactivateStake<'info> {
         );
 
         // compute total required stake delta (i128, must be negative)
-        let total_stake_delta_i128 = self.state.stake_delta(self.reserve_pda.lamports());
+        let total_stake_delta_i128 = self
+            .state
+            .stake_delta(self.reserve_pda.lamports(), self.clock.epoch);
         msg!("total_stake_delta_i128 {}", total_stake_delta_i128);
         require_lt!(
             total_stake_delta_i128,
@@ -137,10 +159,21 @@ impl<'info> DeactivateStake<'info> {
         )?;
 
         // compute target for this particular validator (total_stake_target * score/total_score)
-        let validator_stake_target = self
+        let mut validator_stake_target = self
             .state
             .validator_system
             .validator_stake_target(&validator, total_stake_target)?;
+        if let Some(validator_directed_stake) = &self.validator_directed_stake {
+            require_keys_eq!(
+                validator_directed_stake.validator,
+                validator.validator_account,
+                MarinadeError::WrongValidatorDirectedStake
+            );
+            validator_stake_target = validator_stake_target.max(
+                validator_directed_stake
+                    .stake_target(self.state.max_directed_stake_share, total_stake_target),
+            );
+        }
 
         // compute how much we should unstake from this validator
         let validator_active_balance = validator.active_balance; // record for event
@@ -331,6 +364,11 @@ impl<'info> DeactivateStake<'info> {
             total_unstake_delta,
             unstaked_amount,
         });
+        pay_keeper_reward(
+            &self.state,
+            self.keeper_pool.as_deref_mut(),
+            self.keeper.as_ref().map(|keeper| keeper.to_account_info()),
+        )?;
 
         Ok(())
     }
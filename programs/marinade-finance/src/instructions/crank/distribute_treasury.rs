@@ -0,0 +1,107 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{transfer, Token, TokenAccount, Transfer};
+
+use crate::{
+    error::MarinadeError,
+    events::crank::DistributeTreasuryEvent,
+    state::{paused_operations::PausedOperations, treasury_splitter::TreasurySplitter},
+    State,
+};
+
+#[derive(Accounts)]
+pub struct DistributeTreasury<'info> {
+    #[account(
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+
+    #[account(
+        mut,
+        has_one = state,
+        seeds = [
+            &state.key().to_bytes(),
+            TreasurySplitter::SEED
+        ],
+        bump = treasury_splitter.bump,
+    )]
+    pub treasury_splitter: Box<Account<'info, TreasurySplitter>>,
+    /// CHECK: PDA
+    #[account(
+        seeds = [
+            &state.key().to_bytes(),
+            TreasurySplitter::AUTHORITY_SEED
+        ],
+        bump = treasury_splitter.authority_bump,
+    )]
+    pub treasury_authority: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = treasury_splitter.vault
+    )]
+    pub treasury_vault: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> DistributeTreasury<'info> {
+    // fn distribute_treasury()
+    // permissionless, splits the vault mSOL between the recipients by weight.
+    // The recipient token accounts are passed in remaining_accounts in the order of the splitter
+    pub fn process(&mut self, recipient_accounts: &[AccountInfo<'info>]) -> Result<()> {
+        self.state.check_paused(PausedOperations::VALIDATOR_OPS)?;
+        let recipients = self.treasury_splitter.recipients().to_vec();
+        require_eq!(
+            recipient_accounts.len(),
+            recipients.len(),
+            MarinadeError::TreasuryRecipientsMismatch
+        );
+
+        let msol_amount = self.treasury_vault.amount;
+        let mut distributed = 0;
+        for (recipient, recipient_account) in recipients.iter().zip(recipient_accounts) {
+            require_keys_eq!(
+                *recipient_account.key,
+                recipient.token_account,
+                MarinadeError::TreasuryRecipientsMismatch
+            );
+            let token_account = Account::<TokenAccount>::try_from(recipient_account)?;
+            require_keys_eq!(
+                token_account.mint,
+                self.state.msol_mint,
+                MarinadeError::TreasuryRecipientsMismatch
+            );
+            let share = self
+                .treasury_splitter
+                .recipient_share(recipient, msol_amount)?;
+            if share > 0 {
+                transfer(
+                    CpiContext::new_with_signer(
+                        self.token_program.to_account_info(),
+                        Transfer {
+                            from: self.treasury_vault.to_account_info(),
+                            to: recipient_account.clone(),
+                            authority: self.treasury_authority.to_account_info(),
+                        },
+                        &[&[
+                            &self.state.key().to_bytes(),
+                            TreasurySplitter::AUTHORITY_SEED,
+                            &[self.treasury_splitter.authority_bump],
+                        ]],
+                    ),
+                    share,
+                )?;
+                distributed += share;
+            }
+        }
+        self.treasury_splitter.total_distributed += distributed;
+
+        emit!(DistributeTreasuryEvent {
+            state: self.state.key(),
+            msol_amount,
+            distributed,
+            total_distributed: self.treasury_splitter.total_distributed,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+
+use crate::{events::crank::KeeperRewardEvent, state::keeper_pool::KeeperPool, State};
+
+/// Pays State::keeper_reward from the keeper pool to `keeper` when both are passed,
+/// as much as the pool has above its rent exempt reserve
+pub(crate) fn pay_keeper_reward<'info>(
+    state: &Account<'info, State>,
+    keeper_pool: Option<&mut Account<'info, KeeperPool>>,
+    keeper: Option<AccountInfo<'info>>,
+) -> Result<()> {
+    let (keeper_pool, keeper) = match (keeper_pool, keeper) {
+        (Some(keeper_pool), Some(keeper)) => (keeper_pool, keeper),
+        _ => return Ok(()),
+    };
+    let keeper_pool_info = keeper_pool.to_account_info();
+    let available = keeper_pool_info
+        .lamports()
+        .saturating_sub(Rent::get()?.minimum_balance(keeper_pool_info.data_len()));
+    let amount = state.keeper_reward.min(available);
+    if amount == 0 {
+        return Ok(());
+    }
+
+    **keeper_pool_info.try_borrow_mut_lamports()? -= amount;
+    **keeper.try_borrow_mut_lamports()? += amount;
+    keeper_pool.paid_lamports += amount;
+
+    emit!(KeeperRewardEvent {
+        state: state.key(),
+        keeper: keeper.key(),
+        amount,
+        paid_lamports: keeper_pool.paid_lamports,
+    });
+    Ok(())
+}
@@ -4,15 +4,22 @@ use anchor_lang::solana_program::{program::invoke_signed, stake};
 use anchor_spl::stake::{withdraw, Stake, StakeAccount, Withdraw};
 
 use crate::events::crank::MergeStakesEvent;
+use crate::instructions::crank::keeper_reward::pay_keeper_reward;
+use crate::state::keeper_pool::KeeperPool;
 use crate::state::stake_system::StakeList;
 use crate::state::validator_system::ValidatorList;
-use crate::{error::MarinadeError, state::stake_system::StakeSystem, State};
+use crate::{
+    error::MarinadeError,
+    state::{paused_operations::PausedOperations, stake_system::StakeSystem},
+    State,
+};
 
 #[derive(Accounts)]
 pub struct MergeStakes<'info> {
     #[account(
         mut,
-        has_one = operational_sol_account
+        has_one = operational_sol_account,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
     )]
     pub state: Box<Account<'info, State>>,
     #[account(
@@ -57,6 +64,15 @@ pub struct MergeStakes<'info> {
     pub stake_history: UncheckedAccount<'info>,
 
     pub stake_program: Program<'info, Stake>,
+
+    // pays State::keeper_reward to keeper, see KeeperPool
+    #[account(
+        mut,
+        has_one = state
+    )]
+    pub keeper_pool: Option<Box<Account<'info, KeeperPool>>>,
+    #[account(mut)]
+    pub keeper: Option<SystemAccount<'info>>,
 }
 
 impl<'info> MergeStakes<'info> {
@@ -66,183 +82,222 @@ impl<'info> MergeStakes<'info> {
         source_stake_index: u32,
         validator_index: u32,
     ) -> Result<()> {
-        require!(!self.state.paused, MarinadeError::ProgramIsPaused);
+        self.state.check_paused(PausedOperations::VALIDATOR_OPS)?;
 
-        let mut validator = self.state.validator_system.get(
-            &self.validator_list.to_account_info().data.as_ref().borrow(),
+        let state_address = self.state.key();
+        merge_stake(
+            &mut self.state,
+            &state_address,
+            &self.stake_list.to_account_info(),
+            &self.validator_list.to_account_info(),
+            &mut self.destination_stake,
+            destination_stake_index,
+            &self.source_stake,
+            source_stake_index,
             validator_index,
+            &self.stake_deposit_authority.to_account_info(),
+            &self.stake_withdraw_authority.to_account_info(),
+            &self.operational_sol_account.to_account_info(),
+            &self.clock,
+            &self.stake_history.to_account_info(),
+            &self.stake_program.to_account_info(),
+        )?;
+        pay_keeper_reward(
+            &self.state,
+            self.keeper_pool.as_deref_mut(),
+            self.keeper.as_ref().map(|keeper| keeper.to_account_info()),
         )?;
+        Ok(())
+    }
+}
 
-        // record for event
-        let validator_active_balance = validator.active_balance;
-        let total_active_balance = self.state.validator_system.total_active_balance;
-        let operational_sol_balance = self.operational_sol_account.lamports();
+// merges source_stake into destination_stake, both active on the validator and updated,
+// and removes the source from the stake list. Used by merge_stakes and consolidate_stakes
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn merge_stake<'info>(
+    state: &mut State,
+    state_address: &Pubkey,
+    stake_list: &AccountInfo<'info>,
+    validator_list: &AccountInfo<'info>,
+    destination_stake: &mut Account<'info, StakeAccount>,
+    destination_stake_index: u32,
+    source_stake: &Account<'info, StakeAccount>,
+    source_stake_index: u32,
+    validator_index: u32,
+    stake_deposit_authority: &AccountInfo<'info>,
+    stake_withdraw_authority: &AccountInfo<'info>,
+    operational_sol_account: &AccountInfo<'info>,
+    clock: &Sysvar<'info, Clock>,
+    stake_history: &AccountInfo<'info>,
+    stake_program: &AccountInfo<'info>,
+) -> Result<()> {
+    let mut validator = state
+        .validator_system
+        .get(&validator_list.data.as_ref().borrow(), validator_index)?;
 
-        let mut destination_stake_info = self.state.stake_system.get_checked(
-            &self.stake_list.to_account_info().data.as_ref().borrow(),
-            destination_stake_index,
-            self.destination_stake.to_account_info().key,
-        )?;
-        let last_update_destination_stake_delegation =
-            destination_stake_info.last_update_delegated_lamports;
-        let destination_delegation = if let Some(delegation) = self.destination_stake.delegation() {
-            delegation
-        } else {
-            return err!(MarinadeError::DestinationStakeMustBeDelegated)
-                .map_err(|e| e.with_account_name("destination_stake"));
-        };
-        require_eq!(
-            destination_delegation.deactivation_epoch,
-            std::u64::MAX,
-            MarinadeError::DestinationStakeMustNotBeDeactivating
-        );
-        require_eq!(
-            destination_stake_info.last_update_delegated_lamports,
-            destination_delegation.stake,
-            MarinadeError::DestinationStakeMustBeUpdated
-        );
+    // record for event
+    let validator_active_balance = validator.active_balance;
+    let total_active_balance = state.validator_system.total_active_balance;
+    let operational_sol_balance = operational_sol_account.lamports();
 
-        require_keys_eq!(
-            destination_delegation.voter_pubkey,
-            validator.validator_account,
-            MarinadeError::InvalidDestinationStakeDelegation
-        );
+    let mut destination_stake_info = state.stake_system.get_checked(
+        &stake_list.data.as_ref().borrow(),
+        destination_stake_index,
+        destination_stake.to_account_info().key,
+    )?;
+    let last_update_destination_stake_delegation =
+        destination_stake_info.last_update_delegated_lamports;
+    let destination_delegation = if let Some(delegation) = destination_stake.delegation() {
+        delegation
+    } else {
+        return err!(MarinadeError::DestinationStakeMustBeDelegated)
+            .map_err(|e| e.with_account_name("destination_stake"));
+    };
+    require_eq!(
+        destination_delegation.deactivation_epoch,
+        std::u64::MAX,
+        MarinadeError::DestinationStakeMustNotBeDeactivating
+    );
+    require_eq!(
+        destination_stake_info.last_update_delegated_lamports,
+        destination_delegation.stake,
+        MarinadeError::DestinationStakeMustBeUpdated
+    );
 
-        // Source stake
-        let source_stake_info = self.state.stake_system.get_checked(
-            &self.stake_list.to_account_info().data.as_ref().borrow(),
-            source_stake_index,
-            self.source_stake.to_account_info().key,
-        )?;
-        let source_delegation = if let Some(delegation) = self.source_stake.delegation() {
-            delegation
-        } else {
-            return err!(MarinadeError::SourceStakeMustBeDelegated)
-                .map_err(|e| e.with_account_name("source_stake"));
-        };
-        require_eq!(
-            source_delegation.deactivation_epoch,
-            std::u64::MAX,
-            MarinadeError::SourceStakeMustNotBeDeactivating
-        );
-        require_eq!(
-            source_stake_info.last_update_delegated_lamports,
-            source_delegation.stake,
-            MarinadeError::SourceStakeMustBeUpdated
-        );
+    require_keys_eq!(
+        destination_delegation.voter_pubkey,
+        validator.validator_account,
+        MarinadeError::InvalidDestinationStakeDelegation
+    );
 
-        require_eq!(
-            self.source_stake.to_account_info().lamports(),
-            source_delegation.stake + self.source_stake.meta().unwrap().rent_exempt_reserve,
-            MarinadeError::SourceStakeMustBeUpdated
-        );
+    // Source stake
+    let source_stake_info = state.stake_system.get_checked(
+        &stake_list.data.as_ref().borrow(),
+        source_stake_index,
+        source_stake.to_account_info().key,
+    )?;
+    let source_delegation = if let Some(delegation) = source_stake.delegation() {
+        delegation
+    } else {
+        return err!(MarinadeError::SourceStakeMustBeDelegated)
+            .map_err(|e| e.with_account_name("source_stake"));
+    };
+    require_eq!(
+        source_delegation.deactivation_epoch,
+        std::u64::MAX,
+        MarinadeError::SourceStakeMustNotBeDeactivating
+    );
+    require_eq!(
+        source_stake_info.last_update_delegated_lamports,
+        source_delegation.stake,
+        MarinadeError::SourceStakeMustBeUpdated
+    );
 
-        require_keys_eq!(
-            source_delegation.voter_pubkey,
-            validator.validator_account,
-            MarinadeError::InvalidSourceStakeDelegation
-        );
-        invoke_signed(
-            &stake::instruction::merge(
-                self.destination_stake.to_account_info().key,
-                self.source_stake.to_account_info().key,
-                self.stake_deposit_authority.to_account_info().key,
-            )[0],
-            &[
-                self.stake_program.to_account_info(),
-                self.destination_stake.to_account_info(),
-                self.source_stake.to_account_info(),
-                self.clock.to_account_info(),
-                self.stake_history.to_account_info(),
-                self.stake_deposit_authority.to_account_info(),
-            ],
-            &[&[
-                &self.state.key().to_bytes(),
-                StakeSystem::STAKE_DEPOSIT_SEED,
-                &[self.state.stake_system.stake_deposit_bump_seed],
-            ]],
-        )?;
-        // reread stake after merging to properly compute extra_delegated
-        self.destination_stake.reload()?;
-        // extra_delegated = dest.delegation.stake after merge - (dest.last_update_delegated_lamports + source.last_update_delegated_lamports)
-        let extra_delegated = self.destination_stake.delegation().unwrap().stake
-            - destination_stake_info.last_update_delegated_lamports
-            - source_stake_info.last_update_delegated_lamports;
-        // Note: if the merge is invoked with 2 activating accounts, or a new account -> activating account,
-        // the source account *rent-lamports* are added to the destination account on top of the delegation (extra-delegated).
-        // This is not normal operation for the bot, but this instruction is permissionless so anyone can call any time,
-        // and so we should consider the case.
-        // In normal cases (the bot merging to active accounts) the *rent-lamports* go to dest account *native lamports*,
-        // so the destination account will have double the rent-exempt lamports
-        let returned_stake_rent =
-            self.source_stake.meta().unwrap().rent_exempt_reserve - extra_delegated;
-        // update validator.active_balance
-        validator.active_balance += extra_delegated;
-        // store in list
-        self.state.validator_system.set(
-            &mut self
-                .validator_list
-                .to_account_info()
-                .data
-                .as_ref()
-                .borrow_mut(),
-            validator_index,
-            validator,
-        )?;
-        // update also total_active_balance
-        self.state.validator_system.total_active_balance += extra_delegated;
+    require_eq!(
+        source_stake.to_account_info().lamports(),
+        source_delegation.stake + source_stake.meta().unwrap().rent_exempt_reserve,
+        MarinadeError::SourceStakeMustBeUpdated
+    );
 
-        destination_stake_info.last_update_delegated_lamports =
-            self.destination_stake.delegation().unwrap().stake;
-        self.state.stake_system.set(
-            &mut self.stake_list.to_account_info().data.as_ref().borrow_mut(),
-            destination_stake_index,
-            destination_stake_info,
-        )?;
-        // Call this last because of index invalidation
-        self.state.stake_system.remove(
-            &mut self.stake_list.to_account_info().data.as_ref().borrow_mut(),
-            source_stake_index,
-        )?;
-        if returned_stake_rent > 0 {
-            // withdraw the rent-exempt lamports part of merged stake to operational_sol_account for the future recreation of this slot's account
-            withdraw(
-                CpiContext::new_with_signer(
-                    self.stake_program.to_account_info(),
-                    Withdraw {
-                        stake: self.destination_stake.to_account_info(),
-                        withdrawer: self.stake_withdraw_authority.to_account_info(),
-                        to: self.operational_sol_account.to_account_info(),
-                        clock: self.clock.to_account_info(),
-                        stake_history: self.stake_history.to_account_info(),
-                    },
-                    &[&[
-                        &self.state.key().to_bytes(),
-                        StakeSystem::STAKE_WITHDRAW_SEED,
-                        &[self.state.stake_system.stake_withdraw_bump_seed],
-                    ]],
-                ),
-                returned_stake_rent,
-                None,
-            )?;
-        }
-        emit!(MergeStakesEvent {
-            state: self.state.key(),
-            epoch: self.clock.epoch,
-            destination_stake_index,
-            destination_stake_account: destination_stake_info.stake_account,
-            last_update_destination_stake_delegation,
-            source_stake_index,
-            source_stake_account: source_stake_info.stake_account,
-            last_update_source_stake_delegation: source_stake_info.last_update_delegated_lamports,
-            validator_index,
-            validator_vote: validator.validator_account,
-            extra_delegated,
+    require_keys_eq!(
+        source_delegation.voter_pubkey,
+        validator.validator_account,
+        MarinadeError::InvalidSourceStakeDelegation
+    );
+    invoke_signed(
+        &stake::instruction::merge(
+            destination_stake.to_account_info().key,
+            source_stake.to_account_info().key,
+            stake_deposit_authority.key,
+        )[0],
+        &[
+            stake_program.clone(),
+            destination_stake.to_account_info(),
+            source_stake.to_account_info(),
+            clock.to_account_info(),
+            stake_history.clone(),
+            stake_deposit_authority.clone(),
+        ],
+        &[&[
+            &state_address.to_bytes(),
+            StakeSystem::STAKE_DEPOSIT_SEED,
+            &[state.stake_system.stake_deposit_bump_seed],
+        ]],
+    )?;
+    // reread stake after merging to properly compute extra_delegated
+    destination_stake.reload()?;
+    // extra_delegated = dest.delegation.stake after merge - (dest.last_update_delegated_lamports + source.last_update_delegated_lamports)
+    let extra_delegated = destination_stake.delegation().unwrap().stake
+        - destination_stake_info.last_update_delegated_lamports
+        - source_stake_info.last_update_delegated_lamports;
+    // Note: if the merge is invoked with 2 activating accounts, or a new account -> activating account,
+    // the source account *rent-lamports* are added to the destination account on top of the delegation (extra-delegated).
+    // This is not normal operation for the bot, but this instruction is permissionless so anyone can call any time,
+    // and so we should consider the case.
+    // In normal cases (the bot merging to active accounts) the *rent-lamports* go to dest account *native lamports*,
+    // so the destination account will have double the rent-exempt lamports
+    let returned_stake_rent = source_stake.meta().unwrap().rent_exempt_reserve - extra_delegated;
+    // update validator.active_balance
+    validator.active_balance += extra_delegated;
+    // store in list
+    state.validator_system.set(
+        &mut validator_list.data.as_ref().borrow_mut(),
+        validator_index,
+        validator,
+    )?;
+    // update also total_active_balance
+    state.validator_system.total_active_balance += extra_delegated;
+
+    destination_stake_info.last_update_delegated_lamports =
+        destination_stake.delegation().unwrap().stake;
+    state.stake_system.set(
+        &mut stake_list.data.as_ref().borrow_mut(),
+        destination_stake_index,
+        destination_stake_info,
+    )?;
+    // Call this last because of index invalidation
+    state.stake_system.remove(
+        &mut stake_list.data.as_ref().borrow_mut(),
+        source_stake_index,
+    )?;
+    if returned_stake_rent > 0 {
+        // withdraw the rent-exempt lamports part of merged stake to operational_sol_account for the future recreation of this slot's account
+        withdraw(
+            CpiContext::new_with_signer(
+                stake_program.clone(),
+                Withdraw {
+                    stake: destination_stake.to_account_info(),
+                    withdrawer: stake_withdraw_authority.clone(),
+                    to: operational_sol_account.clone(),
+                    clock: clock.to_account_info(),
+                    stake_history: stake_history.clone(),
+                },
+                &[&[
+                    &state_address.to_bytes(),
+                    StakeSystem::STAKE_WITHDRAW_SEED,
+                    &[state.stake_system.stake_withdraw_bump_seed],
+                ]],
+            ),
             returned_stake_rent,
-            validator_active_balance,
-            total_active_balance,
-            operational_sol_balance,
-        });
-        Ok(())
+            None,
+        )?;
     }
+    emit!(MergeStakesEvent {
+        state: *state_address,
+        epoch: clock.epoch,
+        destination_stake_index,
+        destination_stake_account: destination_stake_info.stake_account,
+        last_update_destination_stake_delegation,
+        source_stake_index,
+        source_stake_account: source_stake_info.stake_account,
+        last_update_source_stake_delegation: source_stake_info.last_update_delegated_lamports,
+        validator_index,
+        validator_vote: validator.validator_account,
+        extra_delegated,
+        returned_stake_rent,
+        validator_active_balance,
+        total_active_balance,
+        operational_sol_balance,
+    });
+    Ok(())
 }
@@ -1,11 +1,32 @@
+pub mod claim_mev_tips;
+pub mod consolidate_stakes;
+pub mod crank_claim;
 pub mod deactivate_stake;
+pub mod distribute_treasury;
+pub mod keeper_reward;
 pub mod merge_stakes;
+pub mod rebalance_liq_pool;
 pub mod redelegate;
+pub mod report_epoch;
 pub mod stake_reserve;
+pub mod stake_reserve_batch;
+pub mod sweep_reserve_donations;
 pub mod update;
+pub mod update_msol_price_history;
+pub mod update_msol_price_oracle;
 
+pub use claim_mev_tips::*;
+pub use consolidate_stakes::*;
+pub use crank_claim::*;
 pub use deactivate_stake::*;
+pub use distribute_treasury::*;
 pub use merge_stakes::*;
+pub use rebalance_liq_pool::*;
 pub use redelegate::*;
+pub use report_epoch::*;
 pub use stake_reserve::*;
+pub use stake_reserve_batch::*;
+pub use sweep_reserve_donations::*;
 pub use update::*;
+pub use update_msol_price_history::*;
+pub use update_msol_price_oracle::*;
@@ -0,0 +1,168 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::token::{burn, Burn, Mint, Token, TokenAccount};
+
+use crate::{
+    error::MarinadeError,
+    events::crank::RebalanceLiqPoolEvent,
+    state::{
+        liq_pool::LiqPool, lp_price_oracle::LpPriceOracle, paused_operations::PausedOperations,
+    },
+    State,
+};
+
+#[derive(Accounts)]
+pub struct RebalanceLiqPool<'info> {
+    #[account(
+        mut,
+        has_one = msol_mint,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+
+    #[account(mut)]
+    pub msol_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [
+            &state.key().to_bytes(),
+            LiqPool::SOL_LEG_SEED
+        ],
+        bump = state.liq_pool.sol_leg_bump_seed
+    )]
+    pub liq_pool_sol_leg_pda: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        address = state.liq_pool.msol_leg
+    )]
+    pub liq_pool_msol_leg: Box<Account<'info, TokenAccount>>,
+    /// CHECK: PDA
+    #[account(
+        seeds = [
+            &state.key().to_bytes(),
+            LiqPool::MSOL_LEG_AUTHORITY_SEED
+        ],
+        bump = state.liq_pool.msol_leg_authority_bump_seed
+    )]
+    pub liq_pool_msol_leg_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            &state.key().to_bytes(),
+            State::RESERVE_SEED
+        ],
+        bump = state.reserve_bump_seed
+    )]
+    pub reserve_pda: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+
+    #[account(
+        mut,
+        has_one = state
+    )]
+    pub lp_price_oracle: Option<Box<Account<'info, LpPriceOracle>>>,
+}
+
+impl<'info> RebalanceLiqPool<'info> {
+    // fn rebalance_liq_pool()
+    // Permissionless crank. When the mSOL leg holds more than msol_leg_target of the pool value,
+    // the excess mSOL is burned and replaced by the same SOL value taken from the reserve.
+    // It is done at the mSOL price, so neither the LPs nor the mSOL holders lose value
+    pub fn process(&mut self) -> Result<()> {
+        self.state.check_paused(PausedOperations::LIQUIDITY)?;
+        self.state.liq_pool.check_no_flash_loan()?;
+
+        let sol_leg_balance = self.liq_pool_sol_leg_pda.lamports();
+        let sol_leg_available_balance = sol_leg_balance - self.state.rent_exempt_for_token_acc;
+        let msol_leg_balance = self.liq_pool_msol_leg.amount;
+        let msol_leg_value = self.state.msol_to_sol(msol_leg_balance)?;
+
+        let excess_value = self
+            .state
+            .liq_pool_ext
+            .msol_leg_excess_value(sol_leg_available_balance, msol_leg_value);
+        // SOL in the reserve not reserved for the delayed unstake tickets
+        let reserve_balance = self.reserve_pda.lamports();
+        let reserve_free_balance = reserve_balance
+            .saturating_sub(self.state.rent_exempt_for_token_acc)
+            .saturating_sub(self.state.circulating_ticket_balance);
+
+        let msol_amount = self
+            .state
+            .calc_msol_from_lamports(excess_value.min(reserve_free_balance))?
+            .min(msol_leg_balance);
+        let lamports = self.state.msol_to_sol(msol_amount)?;
+        msg!(
+            "mSOL leg excess value:{}, reserve free balance:{}, mSOL swapped:{} for {} lamports",
+            excess_value,
+            reserve_free_balance,
+            msol_amount,
+            lamports
+        );
+        require_gt!(lamports, 0, MarinadeError::NothingToRebalance);
+
+        burn(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                Burn {
+                    mint: self.msol_mint.to_account_info(),
+                    from: self.liq_pool_msol_leg.to_account_info(),
+                    authority: self.liq_pool_msol_leg_authority.to_account_info(),
+                },
+                &[&[
+                    &self.state.key().to_bytes(),
+                    LiqPool::MSOL_LEG_AUTHORITY_SEED,
+                    &[self.state.liq_pool.msol_leg_authority_bump_seed],
+                ]],
+            ),
+            msol_amount,
+        )?;
+        self.state.on_msol_burn(msol_amount);
+
+        transfer(
+            CpiContext::new_with_signer(
+                self.system_program.to_account_info(),
+                Transfer {
+                    from: self.reserve_pda.to_account_info(),
+                    to: self.liq_pool_sol_leg_pda.to_account_info(),
+                },
+                &[&[
+                    &self.state.key().to_bytes(),
+                    State::RESERVE_SEED,
+                    &[self.state.reserve_bump_seed],
+                ]],
+            ),
+            lamports,
+        )?;
+        self.state.on_transfer_from_reserve(lamports);
+
+        if let Some(lp_price_oracle) = &mut self.lp_price_oracle {
+            lp_price_oracle.update(
+                &self.state,
+                self.liq_pool_sol_leg_pda.lamports(),
+                msol_leg_balance - msol_amount,
+                Clock::get()?.slot,
+            )?;
+        }
+
+        emit!(RebalanceLiqPoolEvent {
+            state: self.state.key(),
+            sol_leg_balance,
+            msol_leg_balance,
+            reserve_balance,
+            msol_leg_target: self.state.liq_pool_ext.msol_leg_target,
+            excess_value,
+            msol_burned: msol_amount,
+            lamports_swapped: lamports,
+            total_virtual_staked_lamports: self.state.total_virtual_staked_lamports(),
+            msol_supply: self.state.msol_supply,
+        });
+
+        Ok(())
+    }
+}
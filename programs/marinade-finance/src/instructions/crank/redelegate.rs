@@ -3,6 +3,7 @@ use crate::{
     error::MarinadeError,
     events::crank::{RedelegateEvent, SplitStakeAccountInfo},
     state::{
+        paused_operations::PausedOperations,
         stake_system::{StakeList, StakeRecord, StakeSystem},
         validator_system::ValidatorList,
     },
@@ -20,7 +21,10 @@ use anchor_spl::stake::{withdraw, Stake, StakeAccount, Withdraw};
 
 #[derive(Accounts)]
 pub struct ReDelegate<'info> {
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
     pub state: Box<Account<'info, State>>,
     #[account(
         mut,
@@ -105,14 +109,6 @@ impl<'info> ReDelegate<'info> {
         source_validator_index: u32,
         dest_validator_index: u32,
     ) -> Result<()> {
-        require!(!self.state.paused, MarinadeError::ProgramIsPaused);
-
-        require_neq!(
-            source_validator_index,
-            dest_validator_index,
-            MarinadeError::SourceAndDestValidatorsAreTheSame
-        );
-
         // only allow redelegation in the stake/unstake window at the end of the epoch
         {
             let last_slot = EpochSchedule::get()
@@ -125,6 +121,24 @@ impl<'info> ReDelegate<'info> {
             );
         }
 
+        self.redelegate(stake_index, source_validator_index, dest_validator_index)
+    }
+
+    /// redelegates the stake above the source validator target to the dest validator, up to its target
+    pub(crate) fn redelegate(
+        &mut self,
+        stake_index: u32,
+        source_validator_index: u32,
+        dest_validator_index: u32,
+    ) -> Result<()> {
+        self.state.check_paused(PausedOperations::VALIDATOR_OPS)?;
+
+        require_neq!(
+            source_validator_index,
+            dest_validator_index,
+            MarinadeError::SourceAndDestValidatorsAreTheSame
+        );
+
         let mut stake = self.state.stake_system.get_checked(
             &self.stake_list.to_account_info().data.as_ref().borrow(),
             stake_index,
@@ -154,7 +168,9 @@ impl<'info> ReDelegate<'info> {
         )?;
 
         // compute total required stake delta (i128, can be positive or negative)
-        let total_stake_delta_i128 = self.state.stake_delta(self.reserve_pda.lamports());
+        let total_stake_delta_i128 = self
+            .state
+            .stake_delta(self.reserve_pda.lamports(), self.clock.epoch);
         // compute total target stake (current total active stake +/- delta)
         let total_stake_target_i128 =
             self.state.validator_system.total_active_balance as i128 + total_stake_delta_i128;
@@ -206,7 +222,8 @@ impl<'info> ReDelegate<'info> {
         let dest_validator_stake_target = self
             .state
             .validator_system
-            .validator_stake_target(&dest_validator, total_stake_target)?;
+            .validator_stake_target(&dest_validator, total_stake_target)?
+            .min(self.state.max_validator_stake());
         // verify: dest validator must be under target
         if dest_validator.active_balance + self.state.stake_system.min_stake
             > dest_validator_stake_target
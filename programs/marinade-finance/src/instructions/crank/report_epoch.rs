@@ -0,0 +1,106 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::system_program;
+
+use crate::{
+    error::MarinadeError,
+    events::crank::EpochReportEvent,
+    state::{
+        epoch_report::{EpochReport, EpochReports},
+        stake_system::StakeList,
+    },
+    State,
+};
+
+#[derive(Accounts)]
+pub struct ReportEpoch<'info> {
+    #[account(
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+    #[account(
+        address = state.stake_system.stake_list.account,
+    )]
+    pub stake_list: Account<'info, StakeList>,
+    #[account(
+        seeds = [
+            &state.key().to_bytes(),
+            State::RESERVE_SEED
+        ],
+        bump = state.reserve_bump_seed
+    )]
+    pub reserve_pda: SystemAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = rent_payer,
+        space = EpochReports::LEN,
+        seeds = [
+            &state.key().to_bytes(),
+            EpochReports::SEED
+        ],
+        bump,
+    )]
+    pub epoch_reports: Box<Account<'info, EpochReports>>,
+    #[account(
+        mut,
+        owner = system_program::ID
+    )]
+    pub rent_payer: Signer<'info>,
+
+    pub clock: Sysvar<'info, Clock>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ReportEpoch<'info> {
+    // fn report_epoch()
+    // permissionless, once per epoch when every stake account is updated:
+    // stores the report of the epoch in the ring buffer and emits it
+    pub fn process(&mut self, bump: u8) -> Result<()> {
+        let epoch = self.clock.epoch;
+        if self.epoch_reports.state == Pubkey::default() {
+            self.epoch_reports.state = self.state.key();
+            self.epoch_reports.bump = bump;
+        }
+        if let Some(last_report) = self.epoch_reports.last() {
+            require_gt!(
+                epoch,
+                last_report.epoch,
+                MarinadeError::EpochAlreadyReported
+            );
+        }
+        require!(
+            self.state.stake_system.is_updated(
+                &self.stake_list.to_account_info().data.as_ref().borrow(),
+                epoch
+            )?,
+            MarinadeError::EpochUpdateNotFinished
+        );
+
+        let epoch_stats = self.state.epoch_stats.of_epoch(epoch);
+        let start_msol_price = if self.state.msol_price_reference_epoch == epoch {
+            self.state.msol_price_reference
+        } else {
+            // no price update in the epoch
+            self.state.msol_price
+        };
+        let report = EpochReport {
+            epoch,
+            start_msol_price,
+            end_msol_price: self.state.msol_price,
+            rewards: epoch_stats.rewards,
+            msol_fees: epoch_stats.msol_fees,
+            stake_delta: self.state.stake_delta(self.reserve_pda.lamports(), epoch),
+            validator_count: self.state.validator_system.validator_count(),
+            total_active_balance: self.state.validator_system.total_active_balance,
+            msol_supply: self.state.msol_supply,
+        };
+        self.epoch_reports.push(report);
+
+        emit!(EpochReportEvent {
+            state: self.state.key(),
+            report,
+        });
+
+        Ok(())
+    }
+}
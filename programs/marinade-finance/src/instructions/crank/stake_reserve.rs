@@ -1,9 +1,16 @@
 use crate::{
     error::MarinadeError,
     events::crank::StakeReserveEvent,
+    instructions::crank::keeper_reward::pay_keeper_reward,
     state::{
+        delegation_strategy::DelegationStrategy,
+        directed_stake::ValidatorDirectedStake,
+        keeper_pool::KeeperPool,
+        paused_operations::PausedOperations,
         stake_system::{StakeList, StakeSystem},
-        validator_system::ValidatorList,
+        validator_bond::ValidatorBond,
+        validator_health::ValidatorHealth,
+        validator_system::{ValidatorList, ValidatorRecord},
     },
     State, ID,
 };
@@ -28,7 +35,10 @@ use std::convert::TryFrom;
 
 #[derive(Accounts)]
 pub struct StakeReserve<'info> {
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
     pub state: Box<Account<'info, State>>,
     #[account(
         mut,
@@ -43,6 +53,16 @@ pub struct StakeReserve<'info> {
     /// CHECK: CPI
     #[account(mut)]
     pub validator_vote: UncheckedAccount<'info>,
+    /// CHECK: ValidatorHealth of validator_vote, may not exist
+    #[account(
+        seeds = [
+            &state.key().to_bytes(),
+            ValidatorHealth::SEED,
+            &validator_vote.key().to_bytes()
+        ],
+        bump,
+    )]
+    pub validator_health: UncheckedAccount<'info>,
     #[account(
         mut,
         seeds = [
@@ -86,6 +106,38 @@ pub struct StakeReserve<'info> {
 
     pub system_program: Program<'info, System>,
     pub stake_program: Program<'info, Stake>,
+
+    // directed stake of validator_vote, raises its stake target
+    #[account(
+        has_one = state,
+        constraint = validator_directed_stake.validator == validator_vote.key()
+            @ MarinadeError::WrongValidatorDirectedStake
+    )]
+    pub validator_directed_stake: Option<Box<Account<'info, ValidatorDirectedStake>>>,
+
+    // required when State::min_validator_bond is set
+    #[account(
+        has_one = state,
+        constraint = validator_bond.validator == validator_vote.key()
+            @ MarinadeError::WrongValidatorBond
+    )]
+    pub validator_bond: Option<Box<Account<'info, ValidatorBond>>>,
+
+    // pays State::keeper_reward to keeper, see KeeperPool
+    #[account(
+        mut,
+        has_one = state
+    )]
+    pub keeper_pool: Option<Box<Account<'info, KeeperPool>>>,
+    #[account(mut)]
+    pub keeper: Option<SystemAccount<'info>>,
+
+    // required while State::delegation_strategy is set
+    #[account(
+        has_one = state,
+        address = state.delegation_strategy @ MarinadeError::WrongDelegationStrategy
+    )]
+    pub delegation_strategy: Option<Box<Account<'info, DelegationStrategy>>>,
 }
 
 impl<'info> StakeReserve<'info> {
@@ -94,35 +146,17 @@ impl<'info> StakeReserve<'info> {
     /// stakes from available delta-stake in data.validator_index
     /// pub fn stake_reserve()
     pub fn process(&mut self, validator_index: u32) -> Result<()> {
-        require!(!self.state.paused, MarinadeError::ProgramIsPaused);
+        self.state.check_paused(PausedOperations::VALIDATOR_OPS)?;
+        self.state
+            .check_delegation_strategy(self.delegation_strategy.is_some())?;
 
         sol_log_compute_units();
 
         // record for event
         let total_active_balance = self.state.validator_system.total_active_balance;
 
-        let staker = Pubkey::create_program_address(
-            &[
-                &self.state.key().to_bytes(),
-                StakeSystem::STAKE_DEPOSIT_SEED,
-                &[self.state.stake_system.stake_deposit_bump_seed],
-            ],
-            &ID,
-        )
-        .unwrap();
-
-        let withdrawer = Pubkey::create_program_address(
-            &[
-                &self.state.key().to_bytes(),
-                StakeSystem::STAKE_WITHDRAW_SEED,
-                &[self.state.stake_system.stake_withdraw_bump_seed],
-            ],
-            &ID,
-        )
-        .unwrap();
-
         let reserve_balance = self.reserve_pda.lamports();
-        let stake_delta = self.state.stake_delta(reserve_balance);
+        let stake_delta = self.state.stake_delta(reserve_balance, self.clock.epoch);
         if stake_delta <= 0 {
             if stake_delta < 0 {
                 msg!(
@@ -150,6 +184,29 @@ impl<'info> StakeReserve<'info> {
         // record for event
         let validator_active_balance = validator.active_balance;
 
+        // the directed stake would still go to a quarantined validator with score 0
+        if ValidatorHealth::is_quarantined(&self.validator_health)? {
+            msg!("Validator {} is quarantined", validator.validator_account);
+            self.return_unused_stake_account_rent()?;
+            return Ok(()); // Not an error. Don't fail other instructions in tx
+        }
+
+        // skin in the game: no stake for validators without the bond
+        let bond_amount = self
+            .validator_bond
+            .as_ref()
+            .map_or(0, |validator_bond| validator_bond.amount);
+        if bond_amount < self.state.min_validator_bond {
+            msg!(
+                "Validator {} bond {} is lower than min bond {}",
+                validator.validator_account,
+                bond_amount,
+                self.state.min_validator_bond
+            );
+            self.return_unused_stake_account_rent()?;
+            return Ok(()); // Not an error. Don't fail other instructions in tx
+        }
+
         if validator.last_stake_delta_epoch == self.clock.epoch {
             // check if we have some extra stake runs allowed
             if self.state.stake_system.extra_stake_delta_runs == 0 {
@@ -174,112 +231,40 @@ impl<'info> StakeReserve<'info> {
             MarinadeError::TooEarlyForStakeDelta
         );
 
-        let validator_stake_target = self
-            .state
-            .validator_system
-            .validator_stake_target(&validator, total_stake_target)?;
-
-        //verify the validator is under-staked
-        if validator_active_balance >= validator_stake_target {
-            msg!(
-                    "Validator {} has already reached stake target {}. Please stake into another validator",
-                    validator.validator_account,
-                    validator_stake_target
-                );
-            self.return_unused_stake_account_rent()?;
-            return Ok(()); // Not an error. Don't fail other instructions in tx
-        }
-
-        // compute stake_target
-        // stake_target = validator_target - validator_actual_balance, at most total_stake_delta
-        let stake_target = validator_stake_target
-            .saturating_sub(validator_active_balance)
-            .min(total_stake_delta);
-
-        // if what's left in stake_delta after this operation is < state.min_stake, take all the remainder from stake_delta
-        let stake_target = if total_stake_delta - stake_target < self.state.stake_system.min_stake {
-            total_stake_delta
-        } else {
-            stake_target
+        let ReserveStakeTarget {
+            validator_stake_target,
+            amount: stake_target,
+        } = match reserve_stake_target(
+            &self.state,
+            &validator,
+            self.validator_directed_stake.as_deref().map(|v| &**v),
+            self.delegation_strategy.as_deref().map(|d| &**d),
+            total_stake_delta,
+            total_stake_target,
+        )? {
+            Some(target) => target,
+            None => {
+                self.return_unused_stake_account_rent()?;
+                return Ok(()); // Not an error. Don't fail other instructions in tx
+            }
         };
 
-        // if the amount to stake is < stake_system.min_stake (e.g. less than 1 SOL)
-        // we don't stake to avoid creating a stake account with less than 1 SOL
-        if stake_target < self.state.stake_system.min_stake {
-            msg!(
-                "Resulting stake {} is lower than min stake allowed {}",
-                stake_target,
-                self.state.stake_system.min_stake
-            );
-            self.return_unused_stake_account_rent()?;
-            return Ok(()); // Not an error. Don't fail other instructions in tx
-        }
-
-        // transfer SOL from reserve_pda to the stake-account
-        sol_log_compute_units();
-        msg!("Transfer to stake account");
-        transfer(
-            CpiContext::new_with_signer(
-                self.system_program.to_account_info(),
-                Transfer {
-                    from: self.reserve_pda.to_account_info(),
-                    to: self.stake_account.to_account_info(),
-                },
-                &[&[
-                    &self.state.key().to_bytes(),
-                    State::RESERVE_SEED,
-                    &[self.state.reserve_bump_seed],
-                ]],
-            ),
-            stake_target,
-        )?;
-        self.state.on_transfer_from_reserve(stake_target);
-
-        sol_log_compute_units();
-        msg!("Initialize stake");
-        invoke(
-            &stake::instruction::initialize(
-                &self.stake_account.key(),
-                &Authorized { staker, withdrawer },
-                &Lockup::default(),
-            ),
-            &[
-                self.stake_program.to_account_info(),
-                self.stake_account.to_account_info(),
-                self.rent.to_account_info(),
-            ],
-        )?;
-
-        sol_log_compute_units();
-        msg!("Delegate stake");
-        invoke_signed(
-            &stake::instruction::delegate_stake(
-                &self.stake_account.key(),
-                &staker,
-                self.validator_vote.key,
-            ),
-            &[
-                self.stake_program.to_account_info(),
-                self.stake_account.to_account_info(),
-                self.stake_deposit_authority.to_account_info(),
-                self.validator_vote.to_account_info(),
-                self.clock.to_account_info(),
-                self.stake_history.to_account_info(),
-                self.stake_config.to_account_info(),
-            ],
-            &[&[
-                &self.state.key().to_bytes(),
-                StakeSystem::STAKE_DEPOSIT_SEED,
-                &[self.state.stake_system.stake_deposit_bump_seed],
-            ]],
-        )?;
-
-        self.state.stake_system.add(
-            &mut self.stake_list.to_account_info().data.as_ref().borrow_mut(),
-            &self.stake_account.key(),
-            stake_target,
+        let state_address = self.state.key();
+        stake_from_reserve(
+            &mut self.state,
+            &state_address,
+            &self.reserve_pda.to_account_info(),
+            &self.stake_account.to_account_info(),
+            &self.validator_vote.to_account_info(),
+            &self.stake_deposit_authority.to_account_info(),
+            &self.stake_list.to_account_info(),
             &self.clock,
-            0, // is_emergency_unstaking? no
+            &self.rent,
+            &self.stake_history.to_account_info(),
+            &self.stake_config.to_account_info(),
+            &self.system_program.to_account_info(),
+            &self.stake_program.to_account_info(),
+            stake_target,
         )?;
 
         // update validator record and store in list
@@ -315,6 +300,11 @@ impl<'info> StakeReserve<'info> {
             validator_active_balance,
             total_stake_delta,
         });
+        pay_keeper_reward(
+            &self.state,
+            self.keeper_pool.as_deref_mut(),
+            self.keeper.as_ref().map(|keeper| keeper.to_account_info()),
+        )?;
         Ok(())
     }
 
@@ -336,3 +326,196 @@ impl<'info> StakeReserve<'info> {
         )
     }
 }
+
+pub(crate) struct ReserveStakeTarget {
+    pub validator_stake_target: u64,
+    pub amount: u64,
+}
+
+/// Computes how much of `total_stake_delta` goes to `validator`,
+/// None when the validator must not get stake now
+pub(crate) fn reserve_stake_target(
+    state: &State,
+    validator: &ValidatorRecord,
+    validator_directed_stake: Option<&ValidatorDirectedStake>,
+    delegation_strategy: Option<&DelegationStrategy>,
+    total_stake_delta: u64,
+    total_stake_target: u64,
+) -> Result<Option<ReserveStakeTarget>> {
+    let (mut validator_stake_target, max_directed_stake_share, max_validator_stake_share) =
+        match delegation_strategy {
+            Some(delegation_strategy) => (
+                delegation_strategy.validator_stake_target(
+                    &state.validator_system,
+                    validator,
+                    total_stake_target,
+                )?,
+                delegation_strategy.max_directed_stake_share,
+                delegation_strategy.max_validator_stake_share,
+            ),
+            None => (
+                state
+                    .validator_system
+                    .validator_stake_target(validator, total_stake_target)?,
+                state.max_directed_stake_share,
+                state.max_validator_stake_share,
+            ),
+        };
+    if let Some(validator_directed_stake) = validator_directed_stake {
+        // the directed stake goes to the validator even above its score share
+        validator_stake_target = validator_stake_target.max(
+            validator_directed_stake.stake_target(max_directed_stake_share, total_stake_target),
+        );
+    }
+    // decentralization: no validator gets more than max_validator_stake_share of the TVL
+    let max_validator_stake = max_validator_stake_share.apply(state.total_lamports_under_control());
+    validator_stake_target = validator_stake_target.min(max_validator_stake);
+
+    //verify the validator is under-staked
+    if validator.active_balance >= validator_stake_target {
+        msg!(
+            "Validator {} has already reached stake target {}. Please stake into another validator",
+            validator.validator_account,
+            validator_stake_target
+        );
+        return Ok(None);
+    }
+
+    // compute stake_target
+    // stake_target = validator_target - validator_actual_balance, at most total_stake_delta
+    let stake_target = validator_stake_target
+        .saturating_sub(validator.active_balance)
+        .min(total_stake_delta);
+
+    // if what's left in stake_delta after this operation is < state.min_stake, take all the remainder from stake_delta
+    // unless it breaks the validator stake cap
+    let stake_target = if total_stake_delta - stake_target < state.stake_system.min_stake
+        && validator.active_balance + total_stake_delta <= max_validator_stake
+    {
+        total_stake_delta
+    } else {
+        stake_target
+    };
+
+    // if the amount to stake is < stake_system.min_stake (e.g. less than 1 SOL)
+    // we don't stake to avoid creating a stake account with less than 1 SOL
+    if stake_target < state.stake_system.min_stake {
+        msg!(
+            "Resulting stake {} is lower than min stake allowed {}",
+            stake_target,
+            state.stake_system.min_stake
+        );
+        return Ok(None);
+    }
+
+    Ok(Some(ReserveStakeTarget {
+        validator_stake_target,
+        amount: stake_target,
+    }))
+}
+
+/// Moves `amount` from the reserve to the rent exempt `stake_account`,
+/// delegates it to `validator_vote` and registers it in the stake list.
+/// The caller is responsible for the validator record and total_active_balance
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn stake_from_reserve<'info>(
+    state: &mut State,
+    state_address: &Pubkey,
+    reserve_pda: &AccountInfo<'info>,
+    stake_account: &AccountInfo<'info>,
+    validator_vote: &AccountInfo<'info>,
+    stake_deposit_authority: &AccountInfo<'info>,
+    stake_list: &AccountInfo<'info>,
+    clock: &Sysvar<'info, Clock>,
+    rent: &Sysvar<'info, Rent>,
+    stake_history: &AccountInfo<'info>,
+    stake_config: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    stake_program: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    let staker = Pubkey::create_program_address(
+        &[
+            &state_address.to_bytes(),
+            StakeSystem::STAKE_DEPOSIT_SEED,
+            &[state.stake_system.stake_deposit_bump_seed],
+        ],
+        &ID,
+    )
+    .unwrap();
+
+    let withdrawer = Pubkey::create_program_address(
+        &[
+            &state_address.to_bytes(),
+            StakeSystem::STAKE_WITHDRAW_SEED,
+            &[state.stake_system.stake_withdraw_bump_seed],
+        ],
+        &ID,
+    )
+    .unwrap();
+
+    // transfer SOL from reserve_pda to the stake-account
+    sol_log_compute_units();
+    msg!("Transfer to stake account");
+    transfer(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            Transfer {
+                from: reserve_pda.clone(),
+                to: stake_account.clone(),
+            },
+            &[&[
+                &state_address.to_bytes(),
+                State::RESERVE_SEED,
+                &[state.reserve_bump_seed],
+            ]],
+        ),
+        amount,
+    )?;
+    state.on_transfer_from_reserve(amount);
+
+    sol_log_compute_units();
+    msg!("Initialize stake");
+    invoke(
+        &stake::instruction::initialize(
+            stake_account.key,
+            &Authorized { staker, withdrawer },
+            &Lockup::default(),
+        ),
+        &[
+            stake_program.clone(),
+            stake_account.clone(),
+            rent.to_account_info(),
+        ],
+    )?;
+
+    sol_log_compute_units();
+    msg!("Delegate stake");
+    invoke_signed(
+        &stake::instruction::delegate_stake(stake_account.key, &staker, validator_vote.key),
+        &[
+            stake_program.clone(),
+            stake_account.clone(),
+            stake_deposit_authority.clone(),
+            validator_vote.clone(),
+            clock.to_account_info(),
+            stake_history.clone(),
+            stake_config.clone(),
+        ],
+        &[&[
+            &state_address.to_bytes(),
+            StakeSystem::STAKE_DEPOSIT_SEED,
+            &[state.stake_system.stake_deposit_bump_seed],
+        ]],
+    )?;
+
+    state.stake_system.add(
+        &mut stake_list.data.as_ref().borrow_mut(),
+        stake_account.key,
+        amount,
+        clock,
+        0, // is_emergency_unstaking? no
+    )?;
+
+    Ok(())
+}
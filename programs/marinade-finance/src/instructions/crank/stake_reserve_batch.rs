@@ -0,0 +1,338 @@
+use crate::{
+    error::MarinadeError,
+    events::crank::StakeReserveEvent,
+    instructions::crank::{
+        keeper_reward::pay_keeper_reward,
+        stake_reserve::{reserve_stake_target, stake_from_reserve, ReserveStakeTarget},
+    },
+    state::{
+        delegation_strategy::DelegationStrategy,
+        directed_stake::ValidatorDirectedStake,
+        keeper_pool::KeeperPool,
+        paused_operations::PausedOperations,
+        stake_system::{StakeList, StakeSystem},
+        validator_bond::ValidatorBond,
+        validator_health::ValidatorHealth,
+        validator_system::ValidatorList,
+    },
+    State, ID,
+};
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        stake::{self, state::StakeState},
+        sysvar::stake_history,
+    },
+    system_program::{self, create_account, CreateAccount},
+};
+use anchor_spl::stake::Stake;
+use std::convert::TryFrom;
+
+#[derive(Accounts)]
+pub struct StakeReserveBatch<'info> {
+    #[account(
+        mut,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+    #[account(
+        mut,
+        address = state.validator_system.validator_list.account,
+    )]
+    pub validator_list: Account<'info, ValidatorList>,
+    #[account(
+        mut,
+        address = state.stake_system.stake_list.account,
+    )]
+    pub stake_list: Account<'info, StakeList>,
+    #[account(
+        mut,
+        seeds = [
+            &state.key().to_bytes(),
+            State::RESERVE_SEED
+        ],
+        bump = state.reserve_bump_seed
+    )]
+    pub reserve_pda: SystemAccount<'info>,
+    /// CHECK: PDA
+    #[account(
+        seeds = [
+            &state.key().to_bytes(),
+            StakeSystem::STAKE_DEPOSIT_SEED
+        ],
+        bump = state.stake_system.stake_deposit_bump_seed
+    )]
+    pub stake_deposit_authority: UncheckedAccount<'info>,
+    // pays the rent of the stake accounts created
+    #[account(
+        mut,
+        owner = system_program::ID
+    )]
+    pub rent_payer: Signer<'info>,
+
+    pub clock: Sysvar<'info, Clock>,
+    pub epoch_schedule: Sysvar<'info, EpochSchedule>,
+    pub rent: Sysvar<'info, Rent>,
+    /// CHECK: have no CPU budget to parse
+    #[account(address = stake_history::ID)]
+    pub stake_history: UncheckedAccount<'info>,
+    /// CHECK: CPI
+    #[account(address = stake::config::ID)]
+    pub stake_config: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub stake_program: Program<'info, Stake>,
+
+    // pays State::keeper_reward to keeper, see KeeperPool
+    #[account(
+        mut,
+        has_one = state
+    )]
+    pub keeper_pool: Option<Box<Account<'info, KeeperPool>>>,
+    #[account(mut)]
+    pub keeper: Option<SystemAccount<'info>>,
+
+    // required while State::delegation_strategy is set
+    #[account(
+        has_one = state,
+        address = state.delegation_strategy @ MarinadeError::WrongDelegationStrategy
+    )]
+    pub delegation_strategy: Option<Box<Account<'info, DelegationStrategy>>>,
+}
+
+impl<'info> StakeReserveBatch<'info> {
+    // accounts of every validator in remaining_accounts:
+    // validator_vote, stake_account (new keypair, signer), validator_health (PDA, may not exist),
+    // validator_directed_stake, validator_bond.
+    // Like in any optional account the program id stands for a missing directed stake or bond
+    pub const ACCOUNTS_PER_VALIDATOR: usize = 5;
+
+    // fn stake_reserve_batch()
+    // Same as stake_reserve for every validator in `validator_accounts`,
+    // `validator_indexes[i]` is the index of the i-th validator.
+    // Validators that can't get stake are skipped and their stake account is not created,
+    // it stops when the stake delta is used up
+    pub fn process(
+        &mut self,
+        validator_accounts: &[AccountInfo<'info>],
+        validator_indexes: Vec<u32>,
+    ) -> Result<()> {
+        self.state.check_paused(PausedOperations::VALIDATOR_OPS)?;
+        self.state
+            .check_delegation_strategy(self.delegation_strategy.is_some())?;
+        require_gt!(
+            validator_indexes.len(),
+            0,
+            MarinadeError::ValidatorAccountsMismatch
+        );
+        require_eq!(
+            validator_accounts.len(),
+            validator_indexes.len() * Self::ACCOUNTS_PER_VALIDATOR,
+            MarinadeError::ValidatorAccountsMismatch
+        );
+
+        let last_slot = self.epoch_schedule.get_last_slot_in_epoch(self.clock.epoch);
+        require_gte!(
+            self.clock.slot,
+            last_slot.saturating_sub(self.state.stake_system.slots_for_stake_delta),
+            MarinadeError::TooEarlyForStakeDelta
+        );
+
+        let state_address = self.state.key();
+        let stake_account_rent = self.rent.minimum_balance(std::mem::size_of::<StakeState>());
+        for (accounts, validator_index) in validator_accounts
+            .chunks(Self::ACCOUNTS_PER_VALIDATOR)
+            .zip(validator_indexes)
+        {
+            let validator_vote = &accounts[0];
+            let stake_account = &accounts[1];
+
+            // record for event, every stake changes the totals
+            let total_active_balance = self.state.validator_system.total_active_balance;
+            let reserve_balance = self.reserve_pda.lamports();
+            let stake_delta = self.state.stake_delta(reserve_balance, self.clock.epoch);
+            if stake_delta <= 0 {
+                msg!("No stake delta left");
+                break;
+            }
+            let total_stake_delta = u64::try_from(stake_delta).expect("Stake delta overflow");
+            let total_stake_target = total_active_balance.saturating_add(total_stake_delta);
+
+            let mut validator = self
+                .state
+                .validator_system
+                .get_checked(
+                    &self.validator_list.to_account_info().data.as_ref().borrow(),
+                    validator_index,
+                    validator_vote.key,
+                )
+                .map_err(|e| e.with_account_name("validator_vote"))?;
+            // record for event
+            let validator_active_balance = validator.active_balance;
+
+            let (validator_health_address, _) = Pubkey::find_program_address(
+                &[
+                    &state_address.to_bytes(),
+                    ValidatorHealth::SEED,
+                    &validator_vote.key.to_bytes(),
+                ],
+                &ID,
+            );
+            require_keys_eq!(
+                *accounts[2].key,
+                validator_health_address,
+                MarinadeError::WrongValidatorHealth
+            );
+            if ValidatorHealth::is_quarantined(&accounts[2])? {
+                msg!("Validator {} is quarantined", validator.validator_account);
+                continue;
+            }
+
+            let validator_directed_stake = if *accounts[3].key == ID {
+                None
+            } else {
+                let validator_directed_stake =
+                    Account::<ValidatorDirectedStake>::try_from(&accounts[3])?;
+                require_keys_eq!(
+                    validator_directed_stake.state,
+                    state_address,
+                    MarinadeError::WrongValidatorDirectedStake
+                );
+                require_keys_eq!(
+                    validator_directed_stake.validator,
+                    *validator_vote.key,
+                    MarinadeError::WrongValidatorDirectedStake
+                );
+                Some(validator_directed_stake)
+            };
+
+            // skin in the game: no stake for validators without the bond
+            let bond_amount = if *accounts[4].key == ID {
+                0
+            } else {
+                let validator_bond = Account::<ValidatorBond>::try_from(&accounts[4])?;
+                require_keys_eq!(
+                    validator_bond.state,
+                    state_address,
+                    MarinadeError::WrongValidatorBond
+                );
+                require_keys_eq!(
+                    validator_bond.validator,
+                    *validator_vote.key,
+                    MarinadeError::WrongValidatorBond
+                );
+                validator_bond.amount
+            };
+            if bond_amount < self.state.min_validator_bond {
+                msg!(
+                    "Validator {} bond {} is lower than min bond {}",
+                    validator.validator_account,
+                    bond_amount,
+                    self.state.min_validator_bond
+                );
+                continue;
+            }
+
+            if validator.last_stake_delta_epoch == self.clock.epoch {
+                // check if we have some extra stake runs allowed
+                if self.state.stake_system.extra_stake_delta_runs == 0 {
+                    msg!(
+                        "Double delta stake command for validator {} in epoch {}",
+                        validator.validator_account,
+                        self.clock.epoch
+                    );
+                    continue;
+                } else {
+                    // some extra runs allowed. Use one
+                    self.state.stake_system.extra_stake_delta_runs -= 1;
+                }
+            }
+
+            let ReserveStakeTarget {
+                validator_stake_target,
+                amount: stake_target,
+            } = match reserve_stake_target(
+                &self.state,
+                &validator,
+                validator_directed_stake.as_deref(),
+                self.delegation_strategy.as_deref().map(|d| &**d),
+                total_stake_delta,
+                total_stake_target,
+            )? {
+                Some(target) => target,
+                None => continue,
+            };
+
+            // the rent is paid only for the stake accounts used
+            create_account(
+                CpiContext::new(
+                    self.system_program.to_account_info(),
+                    CreateAccount {
+                        from: self.rent_payer.to_account_info(),
+                        to: stake_account.clone(),
+                    },
+                ),
+                stake_account_rent,
+                std::mem::size_of::<StakeState>() as u64,
+                &stake::program::ID,
+            )?;
+            stake_from_reserve(
+                &mut self.state,
+                &state_address,
+                &self.reserve_pda.to_account_info(),
+                stake_account,
+                validator_vote,
+                &self.stake_deposit_authority.to_account_info(),
+                &self.stake_list.to_account_info(),
+                &self.clock,
+                &self.rent,
+                &self.stake_history.to_account_info(),
+                &self.stake_config.to_account_info(),
+                &self.system_program.to_account_info(),
+                &self.stake_program.to_account_info(),
+                stake_target,
+            )?;
+
+            // update validator record and store in list
+            validator.active_balance += stake_target;
+            validator.last_stake_delta_epoch = self.clock.epoch;
+            // Any stake-delta activity must activate stake delta mode
+            self.state.stake_system.last_stake_delta_epoch = self.clock.epoch;
+            self.state.validator_system.set(
+                &mut self
+                    .validator_list
+                    .to_account_info()
+                    .data
+                    .as_ref()
+                    .borrow_mut(),
+                validator_index,
+                validator,
+            )?;
+            // update also total_active_balance
+            self.state.validator_system.total_active_balance += stake_target;
+
+            emit!(StakeReserveEvent {
+                state: state_address,
+                epoch: self.clock.epoch,
+                stake_index: self.state.stake_system.stake_count() - 1,
+                stake_account: *stake_account.key,
+                validator_index,
+                validator_vote: *validator_vote.key,
+                amount: stake_target,
+                total_stake_target,
+                validator_stake_target,
+                reserve_balance,
+                total_active_balance,
+                validator_active_balance,
+                total_stake_delta,
+            });
+            pay_keeper_reward(
+                &self.state,
+                self.keeper_pool.as_deref_mut(),
+                self.keeper.as_ref().map(|keeper| keeper.to_account_info()),
+            )?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::MarinadeError, events::crank::SweepReserveDonationsEvent,
+    state::paused_operations::PausedOperations, State,
+};
+
+#[derive(Accounts)]
+pub struct SweepReserveDonations<'info> {
+    #[account(
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+    #[account(
+        seeds = [
+            &state.key().to_bytes(),
+            State::RESERVE_SEED
+        ],
+        bump = state.reserve_bump_seed
+    )]
+    pub reserve_pda: SystemAccount<'info>,
+}
+
+impl<'info> SweepReserveDonations<'info> {
+    // fn sweep_reserve_donations()
+    // permissionless, reports the lamports sent directly to the reserve.
+    // They are not added to available_reserve_balance here: the next update_* syncs the reserve
+    // and prices them through the max_epoch_yield and msol_price_change_limit circuit breakers.
+    // The liq pool legs need no sweep, their balances are read as the LP value
+    pub fn process(&mut self) -> Result<()> {
+        self.state.check_paused(PausedOperations::VALIDATOR_OPS)?;
+        let donated = self
+            .reserve_pda
+            .lamports()
+            .saturating_sub(self.state.rent_exempt_for_token_acc)
+            .saturating_sub(self.state.available_reserve_balance);
+        if donated == 0 {
+            msg!("No donation to sweep");
+            return Ok(()); // Not an error, an update may have synced the reserve first
+        }
+        let epoch = Clock::get()?.epoch;
+
+        emit!(SweepReserveDonationsEvent {
+            state: self.state.key(),
+            epoch,
+            donated,
+            available_reserve_balance: self.state.available_reserve_balance,
+            total_lamports_under_control: self.state.total_lamports_under_control(),
+        });
+
+        Ok(())
+    }
+}
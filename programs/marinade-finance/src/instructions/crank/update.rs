@@ -6,15 +6,27 @@ use anchor_lang::prelude::*;
 use anchor_lang::solana_program::sysvar::stake_history;
 use anchor_lang::system_program::{transfer, Transfer};
 use anchor_spl::stake::{withdraw, Stake, StakeAccount, Withdraw};
-use anchor_spl::token::{mint_to, Mint, MintTo, Token};
+use anchor_spl::token::{mint_to, Mint, MintTo, Token, TokenAccount};
 
-use crate::events::crank::{UpdateActiveEvent, UpdateDeactivatedEvent};
+use crate::events::crank::{
+    CircuitBreakerEvent, EpochYieldBreakerEvent, InsuranceFundContributionEvent,
+    InsuranceFundLossEvent, ManagementFeeEvent, RewardFeeEvent, UpdateActiveBatchEvent,
+    UpdateActiveEvent, UpdateDeactivatedEvent,
+};
 use crate::events::U64ValueChange;
+use crate::instructions::crank::keeper_reward::pay_keeper_reward;
+use crate::state::keeper_pool::KeeperPool;
+use crate::state::msol_price_history::MsolPriceHistory;
+use crate::state::msol_price_oracle::MsolPriceOracle;
+use crate::state::price_feed::FeedPrice;
 use crate::state::stake_system::StakeList;
 use crate::state::validator_system::ValidatorList;
 use crate::{
     error::MarinadeError,
-    state::stake_system::{StakeRecord, StakeSystem},
+    state::{
+        paused_operations::PausedOperations,
+        stake_system::{StakeRecord, StakeSystem},
+    },
     State,
 };
 
@@ -23,7 +35,8 @@ pub struct UpdateCommon<'info> {
     #[account(
         mut,
         has_one = treasury_msol_account,
-        has_one = msol_mint
+        has_one = msol_mint,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
     )]
     pub state: Box<Account<'info, State>>,
     #[account(
@@ -84,6 +97,46 @@ pub struct UpdateActive<'info> {
         address = common.state.validator_system.validator_list.account,
     )]
     pub validator_list: Account<'info, ValidatorList>,
+
+    #[account(
+        mut,
+        address = common.state.msol_lock.fee_vault
+    )]
+    pub msol_lock_fee_vault: Option<Box<Account<'info, TokenAccount>>>,
+
+    // pays State::keeper_reward to keeper for the first update of the stake in the epoch, see KeeperPool
+    #[account(
+        mut,
+        constraint = keeper_pool.state == common.state.key()
+    )]
+    pub keeper_pool: Option<Box<Account<'info, KeeperPool>>>,
+    #[account(mut)]
+    pub keeper: Option<SystemAccount<'info>>,
+
+    #[account(
+        mut,
+        address = common.state.insurance_fund.vault
+    )]
+    pub insurance_fund_vault: Option<Box<Account<'info, TokenAccount>>>,
+
+    #[account(
+        mut,
+        constraint = msol_price_oracle.state == common.state.key()
+    )]
+    pub msol_price_oracle: Option<Box<Account<'info, MsolPriceOracle>>>,
+
+    /// CHECK: parsed as a Pyth price account
+    #[account(address = common.state.msol_usd_feed @ MarinadeError::InvalidPriceFeed)]
+    pub msol_usd_feed: Option<UncheckedAccount<'info>>,
+    /// CHECK: parsed as a Pyth price account
+    #[account(address = common.state.sol_usd_feed @ MarinadeError::InvalidPriceFeed)]
+    pub sol_usd_feed: Option<UncheckedAccount<'info>>,
+
+    #[account(
+        mut,
+        constraint = msol_price_history.state == common.state.key()
+    )]
+    pub msol_price_history: Option<Box<Account<'info, MsolPriceHistory>>>,
 }
 
 impl<'info> Deref for UpdateActive<'info> {
@@ -112,6 +165,37 @@ pub struct UpdateDeactivated<'info> {
     pub operational_sol_account: UncheckedAccount<'info>,
 
     pub system_program: Program<'info, System>,
+
+    #[account(
+        mut,
+        address = common.state.msol_lock.fee_vault
+    )]
+    pub msol_lock_fee_vault: Option<Box<Account<'info, TokenAccount>>>,
+
+    #[account(
+        mut,
+        address = common.state.insurance_fund.vault
+    )]
+    pub insurance_fund_vault: Option<Box<Account<'info, TokenAccount>>>,
+
+    #[account(
+        mut,
+        constraint = msol_price_oracle.state == common.state.key()
+    )]
+    pub msol_price_oracle: Option<Box<Account<'info, MsolPriceOracle>>>,
+
+    /// CHECK: parsed as a Pyth price account
+    #[account(address = common.state.msol_usd_feed @ MarinadeError::InvalidPriceFeed)]
+    pub msol_usd_feed: Option<UncheckedAccount<'info>>,
+    /// CHECK: parsed as a Pyth price account
+    #[account(address = common.state.sol_usd_feed @ MarinadeError::InvalidPriceFeed)]
+    pub sol_usd_feed: Option<UncheckedAccount<'info>>,
+
+    #[account(
+        mut,
+        constraint = msol_price_history.state == common.state.key()
+    )]
+    pub msol_price_history: Option<Box<Account<'info, MsolPriceHistory>>>,
 }
 
 impl<'info> Deref for UpdateDeactivated<'info> {
@@ -211,13 +295,17 @@ impl<'info> UpdateCommon<'info> {
     }
 
     pub fn mint_to_treasury(&mut self, msol_lamports: u64) -> Result<()> {
+        self.mint_msol(self.treasury_msol_account.to_account_info(), msol_lamports)
+    }
+
+    fn mint_msol(&mut self, to: AccountInfo<'info>, msol_lamports: u64) -> Result<()> {
         if msol_lamports > 0 {
             mint_to(
                 CpiContext::new_with_signer(
                     self.token_program.to_account_info(),
                     MintTo {
                         mint: self.msol_mint.to_account_info(),
-                        to: self.treasury_msol_account.to_account_info(),
+                        to,
                         authority: self.msol_mint_authority.to_account_info(),
                     },
                     &[&[
@@ -233,13 +321,68 @@ impl<'info> UpdateCommon<'info> {
         Ok(())
     }
 
+    // the price feeds are optional accounts of the update instructions,
+    // required while State::price_feed_tolerance is set
     #[inline]
-    pub fn update_msol_price(&mut self) -> Result<U64ValueChange> {
+    pub fn update_msol_price(
+        &mut self,
+        msol_usd_feed: Option<&AccountInfo<'info>>,
+        sol_usd_feed: Option<&AccountInfo<'info>>,
+    ) -> Result<U64ValueChange> {
         // price is computed as:
         // total_active_balance + total_cooling_down + reserve - circulating_ticket_balance
         // DIVIDED by msol_supply
         let old = self.state.msol_price;
-        self.state.msol_price = self.state.msol_to_sol(State::PRICE_DENOMINATOR)?; // store binary-denominated mSOL price
+        let msol_price_x64 = self.state.calc_msol_price_x64();
+        let msol_price = State::msol_price_from_x64(msol_price_x64)?; // store binary-denominated mSOL price
+        let epoch = self.clock.epoch;
+        let epoch_start_msol_price = self.state.epoch_start_msol_price(epoch);
+        if self.state.on_epoch_yield(msol_price, epoch) {
+            // not failing, the pause must be stored
+            msg!(
+                "mSOL price grew from {} to {} in epoch {}, above the yield band, pausing",
+                epoch_start_msol_price,
+                msol_price,
+                epoch
+            );
+            emit!(EpochYieldBreakerEvent {
+                state: self.state.key(),
+                epoch,
+                epoch_start_msol_price,
+                msol_price,
+                max_epoch_yield: self.state.max_epoch_yield,
+            });
+        }
+        if !self.state.price_feed_tolerance.is_zero() {
+            match (msol_usd_feed, sol_usd_feed) {
+                (Some(msol_usd_feed), Some(sol_usd_feed)) => {
+                    let msol_usd = FeedPrice::load(msol_usd_feed, self.clock.slot)
+                        .map_err(|e| e.with_account_name("msol_usd_feed"))?;
+                    let sol_usd = FeedPrice::load(sol_usd_feed, self.clock.slot)
+                        .map_err(|e| e.with_account_name("sol_usd_feed"))?;
+                    self.state
+                        .check_price_feeds(msol_price, &msol_usd, &sol_usd)?;
+                }
+                _ => return err!(MarinadeError::InvalidPriceFeed),
+            }
+        }
+        self.state.msol_price_x64 = msol_price_x64;
+        if self.state.on_msol_price_update(msol_price, epoch) {
+            // not failing, the pause must be stored
+            msg!(
+                "mSOL price moved from {} to {} in epoch {}, pausing",
+                self.state.msol_price_reference,
+                msol_price,
+                epoch
+            );
+            emit!(CircuitBreakerEvent {
+                state: self.state.key(),
+                epoch,
+                reference_msol_price: self.state.msol_price_reference,
+                msol_price,
+                msol_price_change_limit: self.state.msol_price_change_limit,
+            });
+        }
         Ok(U64ValueChange {
             old,
             new: self.state.msol_price,
@@ -247,15 +390,138 @@ impl<'info> UpdateCommon<'info> {
     }
 
     // returns fees in msol
-    pub fn mint_protocol_fees(&mut self, lamports_incoming: u64) -> Result<u64> {
+    // the msol_lock_fee_vault share of the fees goes to the mSOL locks
+    // and the insurance_fund_vault share to the insurance fund
+    pub fn mint_protocol_fees(
+        &mut self,
+        lamports_incoming: u64,
+        msol_lock_fee_vault: Option<&AccountInfo<'info>>,
+        insurance_fund_vault: Option<&AccountInfo<'info>>,
+    ) -> Result<u64> {
         // apply x% protocol fee on staking rewards (do this before updating validators' balance, so it's 1% at old, lower, price)
         let protocol_rewards_fee = self.state.reward_fee.apply(lamports_incoming);
         msg!("protocol_rewards_fee {}", protocol_rewards_fee);
         // compute mSOL amount for protocol_rewards_fee
         let fee_as_msol_amount = self.state.calc_msol_from_lamports(protocol_rewards_fee)?;
-        self.mint_to_treasury(fee_as_msol_amount)?;
+        let msol_lock_cut = match msol_lock_fee_vault {
+            Some(msol_lock_fee_vault) => {
+                let msol_lock_cut = self.state.msol_lock.fee_cut(fee_as_msol_amount);
+                if msol_lock_cut > 0 {
+                    self.mint_msol(msol_lock_fee_vault.clone(), msol_lock_cut)?;
+                    self.state.msol_lock.on_fee(msol_lock_cut);
+                }
+                msol_lock_cut
+            }
+            None => 0,
+        };
+        let insurance_fund_cut = match insurance_fund_vault {
+            Some(insurance_fund_vault) => {
+                let insurance_fund_cut = self.state.insurance_fund.fee_cut(fee_as_msol_amount);
+                if insurance_fund_cut > 0 {
+                    self.mint_msol(insurance_fund_vault.clone(), insurance_fund_cut)?;
+                    self.state
+                        .insurance_fund
+                        .on_contribution(insurance_fund_cut);
+                    emit!(InsuranceFundContributionEvent {
+                        state: self.state.key(),
+                        epoch: self.clock.epoch,
+                        msol_amount: insurance_fund_cut,
+                        total_contributed: self.state.insurance_fund.total_contributed,
+                    });
+                }
+                insurance_fund_cut
+            }
+            None => 0,
+        };
+        self.mint_to_treasury(fee_as_msol_amount - msol_lock_cut - insurance_fund_cut)?;
+        emit!(RewardFeeEvent {
+            state: self.state.key(),
+            epoch: self.clock.epoch,
+            rewards: lamports_incoming,
+            reward_fee: self.state.reward_fee,
+            fee_lamports: protocol_rewards_fee,
+            msol_fees: fee_as_msol_amount,
+            msol_lock_cut,
+            insurance_fund_cut,
+            treasury_msol_account: self.treasury_msol_account.key(),
+        });
         Ok(fee_as_msol_amount)
     }
+
+    // mints State::management_fee for the time since the last accrual, once per epoch.
+    // The time is recorded also while the fee is 0 so it never applies to the past
+    fn accrue_management_fee(&mut self, is_treasury_msol_ready_for_transfer: bool) -> Result<()> {
+        if self.state.management_fee_epoch == self.clock.epoch {
+            return Ok(());
+        }
+        let elapsed = self
+            .clock
+            .unix_timestamp
+            .saturating_sub(self.state.management_fee_accrued_at);
+        if !self.state.management_fee.is_zero() && self.state.management_fee_accrued_at > 0 {
+            if !is_treasury_msol_ready_for_transfer {
+                // accrued on the next update with a ready treasury
+                return Ok(());
+            }
+            let total_lamports_under_control = self.state.total_lamports_under_control();
+            let fee_lamports = self.state.management_fee.apply_prorated(
+                total_lamports_under_control,
+                elapsed as u64,
+                State::SECONDS_PER_YEAR,
+            );
+            let msol_fees = self.state.calc_msol_from_lamports(fee_lamports)?;
+            self.mint_to_treasury(msol_fees)?;
+            self.on_rewards(0, Some(msol_fees));
+            emit!(ManagementFeeEvent {
+                state: self.state.key(),
+                epoch: self.clock.epoch,
+                elapsed_seconds: elapsed,
+                management_fee: self.state.management_fee,
+                total_lamports_under_control,
+                fee_lamports,
+                msol_fees,
+            });
+        }
+        self.state.management_fee_accrued_at = self.clock.unix_timestamp;
+        self.state.management_fee_epoch = self.clock.epoch;
+        Ok(())
+    }
+
+    // rewards and fees for the epoch report
+    fn on_rewards(&mut self, rewards: u64, msol_fees: Option<u64>) {
+        let epoch = self.clock.epoch;
+        self.state
+            .epoch_stats
+            .on_rewards(epoch, rewards, msol_fees.unwrap_or(0));
+    }
+
+    /// the mSOL lock fee vault is an optional account of the update instructions
+    fn check_msol_lock_fee_vault(&self, provided: bool) -> Result<()> {
+        self.state
+            .msol_lock
+            .check_fee_vault(provided)
+            .map_err(|e| e.with_account_name("msol_lock_fee_vault"))
+    }
+
+    /// the insurance fund vault is an optional account of the update instructions
+    fn check_insurance_fund_vault(&self, provided: bool) -> Result<()> {
+        self.state
+            .insurance_fund
+            .check_vault(provided)
+            .map_err(|e| e.with_account_name("insurance_fund_vault"))
+    }
+
+    // slashing to be covered by the insurance fund
+    fn on_loss(&mut self, lamports: u64) {
+        self.state.insurance_fund.on_loss(lamports);
+        emit!(InsuranceFundLossEvent {
+            state: self.state.key(),
+            epoch: self.clock.epoch,
+            stake_account: self.stake_account.key(),
+            lamports,
+            recorded_losses: self.state.insurance_fund.recorded_losses,
+        });
+    }
 }
 
 impl<'info> UpdateActive<'info> {
@@ -267,7 +533,7 @@ impl<'info> UpdateActive<'info> {
     //
     // fn update_active()
     pub fn process(&mut self, stake_index: u32, validator_index: u32) -> Result<()> {
-        require!(!self.state.paused, MarinadeError::ProgramIsPaused);
+        self.state.check_paused(PausedOperations::VALIDATOR_OPS)?;
 
         let total_virtual_staked_lamports = self.state.total_virtual_staked_lamports();
         let msol_supply = self.state.msol_supply;
@@ -275,6 +541,19 @@ impl<'info> UpdateActive<'info> {
             mut stake,
             is_treasury_msol_ready_for_transfer,
         } = self.begin(stake_index)?;
+        self.accrue_management_fee(is_treasury_msol_ready_for_transfer)?;
+        // only the first update of the epoch is rewarded
+        let is_keeper_rewarded = stake.last_update_epoch != self.clock.epoch;
+        self.check_msol_lock_fee_vault(self.msol_lock_fee_vault.is_some())?;
+        let msol_lock_fee_vault = self
+            .msol_lock_fee_vault
+            .as_ref()
+            .map(|msol_lock_fee_vault| msol_lock_fee_vault.to_account_info());
+        self.check_insurance_fund_vault(self.insurance_fund_vault.is_some())?;
+        let insurance_fund_vault = self
+            .insurance_fund_vault
+            .as_ref()
+            .map(|insurance_fund_vault| insurance_fund_vault.to_account_info());
 
         let delegation = self.stake_account.delegation().ok_or_else(|| {
             error!(MarinadeError::RequiredDelegatedStake).with_account_name("stake_account")
@@ -312,7 +591,11 @@ impl<'info> UpdateActive<'info> {
             self.withdraw_to_reserve(extra_lamports)?;
             // after sending to reserve, we take protocol_fees as minted mSOL
             if is_treasury_msol_ready_for_transfer {
-                Some(self.mint_protocol_fees(extra_lamports)?)
+                Some(self.mint_protocol_fees(
+                    extra_lamports,
+                    msol_lock_fee_vault.as_ref(),
+                    insurance_fund_vault.as_ref(),
+                )?)
             } else {
                 None
             }
@@ -323,6 +606,7 @@ impl<'info> UpdateActive<'info> {
                 None
             }
         };
+        self.on_rewards(extra_lamports, extra_msol_fees);
 
         msg!("current staked lamports {}", delegated_lamports);
         let delegation_growth_msol_fees =
@@ -332,10 +616,15 @@ impl<'info> UpdateActive<'info> {
                 msg!("Staking rewards: {}", rewards);
 
                 let delegation_growth_msol_fees = if is_treasury_msol_ready_for_transfer {
-                    Some(self.mint_protocol_fees(rewards)?)
+                    Some(self.mint_protocol_fees(
+                        rewards,
+                        msol_lock_fee_vault.as_ref(),
+                        insurance_fund_vault.as_ref(),
+                    )?)
                 } else {
                     None
                 };
+                self.on_rewards(rewards, delegation_growth_msol_fees);
 
                 // validator active balance is updated with rewards
                 validator.active_balance += rewards;
@@ -346,6 +635,7 @@ impl<'info> UpdateActive<'info> {
                 //slashed
                 let slashed = stake.last_update_delegated_lamports - delegated_lamports;
                 msg!("slashed {}", slashed);
+                self.on_loss(slashed);
                 //validator balance is updated with slashed
                 validator.active_balance = validator.active_balance.saturating_sub(slashed);
                 self.state.validator_system.total_active_balance =
@@ -381,13 +671,23 @@ impl<'info> UpdateActive<'info> {
         )?;
 
         // set new mSOL price
-        let msol_price_change = self.update_msol_price()?;
+        let msol_price_change = self
+            .common
+            .update_msol_price(self.msol_usd_feed.as_deref(), self.sol_usd_feed.as_deref())?;
+        if let Some(msol_price_oracle) = &mut self.msol_price_oracle {
+            msol_price_oracle.update(&self.common.state, &self.common.clock);
+        }
+        if let Some(msol_price_history) = &mut self.msol_price_history {
+            msol_price_history.update(&self.common.state, &self.common.clock);
+        }
         // save stake record
         self.state.stake_system.set(
             &mut self.stake_list.to_account_info().data.as_ref().borrow_mut(),
             stake_index,
             stake,
         )?;
+        let epoch = self.clock.epoch;
+        self.state.update_cursor.on_update(epoch, stake_index);
 
         assert_eq!(
             self.state.available_reserve_balance + self.state.rent_exempt_for_token_acc,
@@ -411,6 +711,79 @@ impl<'info> UpdateActive<'info> {
             total_virtual_staked_lamports,
             msol_supply,
         });
+        if is_keeper_rewarded {
+            pay_keeper_reward(
+                &self.common.state,
+                self.keeper_pool.as_deref_mut(),
+                self.keeper.as_ref().map(|keeper| keeper.to_account_info()),
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl<'info> UpdateActive<'info> {
+    // bounds the compute of update_active_batch, every update mints fees and
+    // writes the lists. Larger batches would fail on the compute budget
+    pub const MAX_BATCH_LEN: usize = 8;
+
+    // fn update_active_batch()
+    // Same as update_active for the stake account of the accounts and for every stake account
+    // of `stake_accounts` passed in remaining_accounts, `stake_indexes[i]` and
+    // `validator_indexes[i]` are the indexes of the i-th one, the stake account of the
+    // accounts going first. It stops after the update that trips the circuit breaker so
+    // the pause is stored, the next call resumes from State::update_cursor
+    pub fn process_batch(
+        &mut self,
+        stake_accounts: &[AccountInfo<'info>],
+        stake_indexes: Vec<u32>,
+        validator_indexes: Vec<u32>,
+    ) -> Result<()> {
+        require_gt!(stake_indexes.len(), 0, MarinadeError::StakeIndexesMismatch);
+        require_gte!(
+            Self::MAX_BATCH_LEN,
+            stake_indexes.len(),
+            MarinadeError::StakeIndexesMismatch
+        );
+        require_eq!(
+            stake_indexes.len(),
+            validator_indexes.len(),
+            MarinadeError::StakeIndexesMismatch
+        );
+        require_eq!(
+            stake_accounts.len() + 1,
+            stake_indexes.len(),
+            MarinadeError::StakeIndexesMismatch
+        );
+
+        let mut updated: u32 = 0;
+        for (i, (&stake_index, &validator_index)) in stake_indexes
+            .iter()
+            .zip(validator_indexes.iter())
+            .enumerate()
+        {
+            if i > 0 {
+                if self
+                    .state
+                    .check_paused(PausedOperations::VALIDATOR_OPS)
+                    .is_err()
+                {
+                    break;
+                }
+                let stake_account = &stake_accounts[i - 1];
+                require!(stake_account.is_writable, ErrorCode::ConstraintMut);
+                self.common.stake_account = Box::new(Account::try_from(stake_account)?);
+            }
+            self.process(stake_index, validator_index)?;
+            updated += 1;
+        }
+
+        emit!(UpdateActiveBatchEvent {
+            state: self.state.key(),
+            epoch: self.clock.epoch,
+            updated,
+            next_stake_index: self.state.update_cursor.next_stake_index,
+        });
         Ok(())
     }
 }
@@ -422,7 +795,7 @@ impl<'info> UpdateDeactivated<'info> {
     /// Optional Future Expansion: Partial: If the stake-account is a fully-deactivated stake account ready to withdraw,
     /// (cool-down period is complete) delete-withdraw the stake-account, send SOL to reserve-account
     pub fn process(&mut self, stake_index: u32) -> Result<()> {
-        require!(!self.state.paused, MarinadeError::ProgramIsPaused);
+        self.state.check_paused(PausedOperations::VALIDATOR_OPS)?;
 
         let total_virtual_staked_lamports = self.state.total_virtual_staked_lamports();
         let msol_supply = self.state.msol_supply;
@@ -431,6 +804,17 @@ impl<'info> UpdateDeactivated<'info> {
             stake,
             is_treasury_msol_ready_for_transfer,
         } = self.begin(stake_index)?;
+        self.accrue_management_fee(is_treasury_msol_ready_for_transfer)?;
+        self.check_msol_lock_fee_vault(self.msol_lock_fee_vault.is_some())?;
+        let msol_lock_fee_vault = self
+            .msol_lock_fee_vault
+            .as_ref()
+            .map(|msol_lock_fee_vault| msol_lock_fee_vault.to_account_info());
+        self.check_insurance_fund_vault(self.insurance_fund_vault.is_some())?;
+        let insurance_fund_vault = self
+            .insurance_fund_vault
+            .as_ref()
+            .map(|insurance_fund_vault| insurance_fund_vault.to_account_info());
 
         let delegation = self.stake_account.delegation().ok_or_else(|| {
             error!(MarinadeError::RequiredDelegatedStake).with_account_name("stake_account")
@@ -463,15 +847,22 @@ impl<'info> UpdateDeactivated<'info> {
             // Note: this includes any extra lamports in the stake-account (MEV rewards mostly)
             let rewards = stake_balance_without_rent - stake.last_update_delegated_lamports;
             msg!("Staking rewards: {}", rewards);
-            if is_treasury_msol_ready_for_transfer {
-                Some(self.mint_protocol_fees(rewards)?)
+            let msol_fees = if is_treasury_msol_ready_for_transfer {
+                Some(self.mint_protocol_fees(
+                    rewards,
+                    msol_lock_fee_vault.as_ref(),
+                    insurance_fund_vault.as_ref(),
+                )?)
             } else {
                 None
-            }
+            };
+            self.on_rewards(rewards, msol_fees);
+            msol_fees
         } else {
             // less than observed last time
             let slashed = stake.last_update_delegated_lamports - stake_balance_without_rent;
             msg!("Slashed {}", slashed);
+            self.common.on_loss(slashed);
             if is_treasury_msol_ready_for_transfer {
                 Some(0)
             } else {
@@ -514,7 +905,15 @@ impl<'info> UpdateDeactivated<'info> {
         // We update mSOL price in case we receive "extra deactivating rewards" after the start of Delayed-unstake.
         // Those rewards went into reserve_pda, are part of mSOL price (benefit all stakers) and even might be re-staked
         // set new mSOL price
-        let msol_price_change = self.update_msol_price()?;
+        let msol_price_change = self
+            .common
+            .update_msol_price(self.msol_usd_feed.as_deref(), self.sol_usd_feed.as_deref())?;
+        if let Some(msol_price_oracle) = &mut self.msol_price_oracle {
+            msol_price_oracle.update(&self.common.state, &self.common.clock);
+        }
+        if let Some(msol_price_history) = &mut self.msol_price_history {
+            msol_price_history.update(&self.common.state, &self.common.clock);
+        }
 
         //remove deleted stake-account from our list
         self.common.state.stake_system.remove(
@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::system_program;
+
+use crate::{
+    error::MarinadeError, events::crank::UpdateMsolPriceHistoryEvent,
+    state::msol_price_history::MsolPriceHistory, State,
+};
+
+#[derive(Accounts)]
+pub struct UpdateMsolPriceHistory<'info> {
+    #[account(
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+
+    #[account(
+        init_if_needed,
+        payer = rent_payer,
+        space = MsolPriceHistory::LEN,
+        seeds = [
+            &state.key().to_bytes(),
+            MsolPriceHistory::SEED
+        ],
+        bump,
+    )]
+    pub msol_price_history: Box<Account<'info, MsolPriceHistory>>,
+
+    #[account(
+        mut,
+        owner = system_program::ID
+    )]
+    pub rent_payer: Signer<'info>,
+
+    pub clock: Sysvar<'info, Clock>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> UpdateMsolPriceHistory<'info> {
+    // fn update_msol_price_history()
+    // permissionless, creates the history account on the first call.
+    // Records nothing until the first update of the epoch refreshed the price
+    pub fn process(&mut self, bump: u8) -> Result<()> {
+        self.msol_price_history.state = self.state.key();
+        self.msol_price_history.bump = bump;
+        let recorded = self.msol_price_history.update(&self.state, &self.clock);
+
+        emit!(UpdateMsolPriceHistoryEvent {
+            state: self.state.key(),
+            epoch: self.clock.epoch,
+            msol_price: self.state.msol_price,
+            count: self.msol_price_history.count,
+            recorded,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::system_program;
+
+use crate::{
+    error::MarinadeError, events::crank::UpdateMsolPriceOracleEvent,
+    state::msol_price_oracle::MsolPriceOracle, State,
+};
+
+#[derive(Accounts)]
+pub struct UpdateMsolPriceOracle<'info> {
+    #[account(
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+
+    #[account(
+        init_if_needed,
+        payer = rent_payer,
+        space = MsolPriceOracle::LEN,
+        seeds = [
+            &state.key().to_bytes(),
+            MsolPriceOracle::SEED
+        ],
+        bump,
+    )]
+    pub msol_price_oracle: Box<Account<'info, MsolPriceOracle>>,
+
+    #[account(
+        mut,
+        owner = system_program::ID
+    )]
+    pub rent_payer: Signer<'info>,
+
+    pub clock: Sysvar<'info, Clock>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> UpdateMsolPriceOracle<'info> {
+    // fn update_msol_price_oracle()
+    // permissionless, creates the oracle account on the first call
+    pub fn process(&mut self, bump: u8) -> Result<()> {
+        self.msol_price_oracle.state = self.state.key();
+        self.msol_price_oracle.bump = bump;
+        self.msol_price_oracle.update(&self.state, &self.clock);
+
+        emit!(UpdateMsolPriceOracleEvent {
+            state: self.state.key(),
+            msol_price: self.msol_price_oracle.msol_price,
+            msol_price_x64: self.msol_price_oracle.msol_price_x64,
+            confidence: self.msol_price_oracle.confidence,
+            msol_price_epoch: self.msol_price_oracle.msol_price_epoch,
+            circuit_breaker_tripped: self.msol_price_oracle.circuit_breaker_tripped,
+        });
+
+        Ok(())
+    }
+}
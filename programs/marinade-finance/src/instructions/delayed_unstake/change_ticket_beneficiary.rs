@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::MarinadeError,
+    events::delayed_unstake::ChangeTicketBeneficiaryEvent,
+    state::{delayed_unstake_ticket::TicketAccountData, paused_operations::PausedOperations},
+    State,
+};
+
+#[derive(Accounts)]
+pub struct ChangeTicketBeneficiary<'info> {
+    #[account(
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+
+    #[account(
+        mut,
+        has_one = beneficiary @ MarinadeError::WrongBeneficiary,
+        constraint = ticket_account.state_address == state.key()
+            @ MarinadeError::InvalidDelayedUnstakeTicket,
+    )]
+    pub ticket_account: Box<Account<'info, TicketAccountData>>,
+    pub beneficiary: Signer<'info>,
+}
+
+impl<'info> ChangeTicketBeneficiary<'info> {
+    // fn change_ticket_beneficiary()
+    pub fn process(&mut self, new_beneficiary: Pubkey) -> Result<()> {
+        self.state.check_paused(PausedOperations::DELAYED_UNSTAKE)?;
+        require_neq!(
+            self.ticket_account.lamports_amount,
+            0,
+            MarinadeError::ReusingDelayedUnstakeTicket
+        );
+
+        self.ticket_account.beneficiary = new_beneficiary;
+
+        emit!(ChangeTicketBeneficiaryEvent {
+            state: self.state.key(),
+            ticket: self.ticket_account.key(),
+            old_beneficiary: self.beneficiary.key(),
+            new_beneficiary,
+        });
+
+        Ok(())
+    }
+}
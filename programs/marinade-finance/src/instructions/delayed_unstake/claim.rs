@@ -1,19 +1,26 @@
 use anchor_lang::prelude::*;
 use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::token::{burn, Burn, Mint, Token, TokenAccount};
+
+use crate::checks::check_token_source_account;
 
 use crate::events::delayed_unstake::ClaimEvent;
-use crate::state::delayed_unstake_ticket::TicketAccountData;
+use crate::state::delayed_unstake_ticket::{TicketAccountData, UnclaimedTickets};
+use crate::state::paused_operations::PausedOperations;
 use crate::MarinadeError;
 use crate::State;
 
 ///How many epochs to wats for ticket. e.g.: Ticket created on epoch 14, ticket is due on epoch 15
-const WAIT_EPOCHS: u64 = 1;
+pub const WAIT_EPOCHS: u64 = 1;
 ///Wait 30 extra minutes from epochs start so the bot has time to withdraw SOL from inactive stake-accounts
 const EXTRA_WAIT_SECONDS: i64 = 30 * 60;
 
 #[derive(Accounts)]
 pub struct Claim<'info> {
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
     pub state: Account<'info, State>,
     #[account(
         mut,
@@ -25,22 +32,72 @@ pub struct Claim<'info> {
     )]
     pub reserve_pda: SystemAccount<'info>,
 
+    // at the end of this instruction, all lamports from ticket_account go to transfer_sol_to,
+    // or to rent_refund_to if provided
+    #[account(mut)]
+    pub ticket_account: Account<'info, TicketAccountData>,
+
+    // must be the ticket beneficiary, or any account chosen by the NFT holder if the ticket is wrapped
+    #[account(mut)]
+    pub transfer_sol_to: SystemAccount<'info>,
+
+    pub clock: Sysvar<'info, Clock>,
+
+    pub system_program: Program<'info, System>,
+
+    // wrapped tickets only
     #[account(
         mut,
-        close = transfer_sol_to,
-        // at the end of this instruction, all lamports from ticket_account go to transfer_sol_to
+        address = ticket_account.beneficiary @ MarinadeError::WrongBeneficiary
     )]
-    pub ticket_account: Account<'info, TicketAccountData>,
+    pub ticket_nft_mint: Option<Box<Account<'info, Mint>>>,
+    #[account(
+        mut,
+        token::mint = ticket_account.beneficiary
+    )]
+    pub ticket_nft_account: Option<Box<Account<'info, TokenAccount>>>,
+    pub ticket_nft_authority: Option<Signer<'info>>,
+    pub token_program: Option<Program<'info, Token>>,
 
+    // swept tickets only
     #[account(
         mut,
-        address = ticket_account.beneficiary @ MarinadeError::WrongBeneficiary
+        seeds = [
+            &state.key().to_bytes(),
+            UnclaimedTickets::SEED
+        ],
+        bump = unclaimed_tickets.bump
     )]
-    pub transfer_sol_to: SystemAccount<'info>,
+    pub unclaimed_tickets: Option<Box<Account<'info, UnclaimedTickets>>>,
 
-    pub clock: Sysvar<'info, Clock>,
+    // gets the ticket rent back instead of transfer_sol_to, e.g. the sponsor that paid for the ticket account.
+    // transfer_sol_to must sign to agree
+    #[account(mut)]
+    pub rent_refund_to: Option<SystemAccount<'info>>,
+}
 
-    pub system_program: Program<'info, System>,
+pub fn check_ticket_due(ticket: &TicketAccountData, clock: &Clock) -> Result<()> {
+    if ticket.is_swept() {
+        return err!(MarinadeError::TicketIsSwept)
+            .map_err(|e| e.with_account_name("ticket_account"));
+    }
+    //check if ticket is due
+    require_gte!(
+        clock.epoch,
+        ticket.created_epoch + WAIT_EPOCHS,
+        MarinadeError::TicketNotDue
+    );
+
+    // Wait X MORE HOURS FROM THE beginning of the EPOCH to give the bot time to withdraw inactive-stake-accounts
+    if ticket.created_epoch + WAIT_EPOCHS == clock.epoch {
+        require_gte!(
+            clock.unix_timestamp - clock.epoch_start_timestamp,
+            EXTRA_WAIT_SECONDS,
+            MarinadeError::TicketNotReady
+        );
+    }
+
+    Ok(())
 }
 
 /// Claim instruction: a user claims a Ticket-account
@@ -66,31 +123,110 @@ impl<'info> Claim<'info> {
             MarinadeError::ReusingDelayedUnstakeTicket
         );
 
-        //check if ticket is due
-        require_gte!(
-            self.clock.epoch,
-            self.ticket_account.created_epoch + WAIT_EPOCHS,
-            MarinadeError::TicketNotDue
-        );
+        // a swept ticket was due long ago
+        if self.ticket_account.is_swept() {
+            return Ok(());
+        }
+        check_ticket_due(&self.ticket_account, &self.clock)
+    }
+
+    fn close_ticket(&mut self) -> Result<()> {
+        let rent_to = match &self.rent_refund_to {
+            Some(rent_refund_to) => {
+                if !self.transfer_sol_to.is_signer {
+                    return err!(MarinadeError::RentRefundNotApproved)
+                        .map_err(|e| e.with_account_name("transfer_sol_to"));
+                }
+                rent_refund_to.to_account_info()
+            }
+            None => self.transfer_sol_to.to_account_info(),
+        };
+        self.ticket_account.close(rent_to)
+    }
+
+    /// the lamports of a swept ticket are no longer in the reserve nor in circulating_ticket_balance
+    fn claim_swept(&mut self) -> Result<()> {
+        let unclaimed_tickets = self
+            .unclaimed_tickets
+            .as_mut()
+            .ok_or_else(|| error!(MarinadeError::TicketIsSwept))?;
+        let user_balance = self.transfer_sol_to.lamports();
+        let lamports = self.ticket_account.lamports_amount;
+
+        unclaimed_tickets.lamports_amount -= lamports;
+        unclaimed_tickets.ticket_count -= 1;
+        // disable ticket-account
+        self.ticket_account.lamports_amount = 0;
+        **unclaimed_tickets
+            .to_account_info()
+            .try_borrow_mut_lamports()? -= lamports;
+        **self.transfer_sol_to.try_borrow_mut_lamports()? += lamports;
 
-        // Wait X MORE HOURS FROM THE beginning of the EPOCH to give the bot time to withdraw inactive-stake-accounts
-        if self.ticket_account.created_epoch + WAIT_EPOCHS == self.clock.epoch {
-            require_gte!(
-                self.clock.unix_timestamp - self.clock.epoch_start_timestamp,
-                EXTRA_WAIT_SECONDS,
-                MarinadeError::TicketNotReady
+        emit!(ClaimEvent {
+            state: self.state.key(),
+            epoch: self.clock.epoch,
+            ticket: self.ticket_account.key(),
+            beneficiary: self.ticket_account.beneficiary,
+            circulating_ticket_balance: self.state.circulating_ticket_balance,
+            circulating_ticket_count: self.state.circulating_ticket_count,
+            reserve_balance: self.reserve_pda.lamports(),
+            user_balance,
+            amount: lamports,
+        });
+
+        self.close_ticket()
+    }
+
+    /// the holder of a wrapped ticket burns the NFT to claim,
+    /// otherwise the SOL can only go to the beneficiary
+    fn check_beneficiary(&self) -> Result<()> {
+        if !self.ticket_account.is_wrapped(&self.ticket_account.key()) {
+            require_keys_eq!(
+                self.transfer_sol_to.key(),
+                self.ticket_account.beneficiary,
+                MarinadeError::WrongBeneficiary
             );
+            return Ok(());
+        }
+        if let (
+            Some(ticket_nft_mint),
+            Some(ticket_nft_account),
+            Some(ticket_nft_authority),
+            Some(token_program),
+        ) = (
+            &self.ticket_nft_mint,
+            &self.ticket_nft_account,
+            &self.ticket_nft_authority,
+            &self.token_program,
+        ) {
+            check_token_source_account(ticket_nft_account, ticket_nft_authority.key, 1)
+                .map_err(|e| e.with_account_name("ticket_nft_account"))?;
+            burn(
+                CpiContext::new(
+                    token_program.to_account_info(),
+                    Burn {
+                        mint: ticket_nft_mint.to_account_info(),
+                        from: ticket_nft_account.to_account_info(),
+                        authority: ticket_nft_authority.to_account_info(),
+                    },
+                ),
+                1,
+            )
+        } else {
+            err!(MarinadeError::MissingTicketNftAccounts)
         }
-
-        Ok(())
     }
 
     // fn claim()
     pub fn process(&mut self) -> Result<()> {
-        require!(!self.state.paused, MarinadeError::ProgramIsPaused);
+        self.state.check_paused(PausedOperations::DELAYED_UNSTAKE)?;
 
         self.check_ticket_account()
             .map_err(|e| e.with_account_name("ticket_account"))?;
+        self.check_beneficiary()?;
+        if self.ticket_account.is_swept() {
+            return self.claim_swept();
+        }
 
         // record for event, use real balance not virtual field
         let user_balance = self.transfer_sol_to.lamports();
@@ -106,7 +242,8 @@ impl<'info> Claim<'info> {
                 available_for_claim
             );
             // Error: "Wait a few hours and retry"
-            return err!(MarinadeError::TicketNotReady);
+            return err!(MarinadeError::TicketNotReady)
+                .map_err(|e| e.with_values((lamports, available_for_claim)));
         }
 
         // record for event and then update
@@ -148,6 +285,6 @@ impl<'info> Claim<'info> {
             amount: lamports,
         });
 
-        Ok(())
+        self.close_ticket()
     }
 }
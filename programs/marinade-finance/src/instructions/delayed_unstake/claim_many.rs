@@ -0,0 +1,124 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+
+use super::claim::check_ticket_due;
+use crate::events::delayed_unstake::ClaimEvent;
+use crate::state::delayed_unstake_ticket::TicketAccountData;
+use crate::state::paused_operations::PausedOperations;
+use crate::MarinadeError;
+use crate::State;
+
+#[derive(Accounts)]
+pub struct ClaimMany<'info> {
+    #[account(
+        mut,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Account<'info, State>,
+    #[account(
+        mut,
+        seeds = [
+            &state.key().to_bytes(),
+            State::RESERVE_SEED
+        ],
+        bump = state.reserve_bump_seed
+    )]
+    pub reserve_pda: SystemAccount<'info>,
+
+    // beneficiary of all the tickets, receives the SOL and the rent of the closed tickets
+    #[account(mut)]
+    pub transfer_sol_to: SystemAccount<'info>,
+
+    pub clock: Sysvar<'info, Clock>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ClaimMany<'info> {
+    // fn claim_many()
+    // Claims and closes every due ticket in `tickets`, the tickets not due yet are skipped.
+    // Wrapped tickets must be claimed one by one with claim
+    pub fn process(&mut self, tickets: &[AccountInfo<'info>]) -> Result<()> {
+        self.state.check_paused(PausedOperations::DELAYED_UNSTAKE)?;
+
+        let mut claimed_count = 0;
+        for ticket_info in tickets {
+            if !ticket_info.is_writable {
+                return err!(MarinadeError::InvalidDelayedUnstakeTicket)
+                    .map_err(|e| e.with_account_name("tickets"));
+            }
+            let ticket_account = Account::<TicketAccountData>::try_from(ticket_info)?;
+            require_keys_eq!(
+                ticket_account.state_address,
+                self.state.key(),
+                MarinadeError::InvalidDelayedUnstakeTicket
+            );
+            require_keys_eq!(
+                ticket_account.beneficiary,
+                self.transfer_sol_to.key(),
+                MarinadeError::WrongBeneficiary
+            );
+            if check_ticket_due(&ticket_account, &self.clock).is_err() {
+                msg!("Ticket {} is not due yet", ticket_info.key);
+                continue;
+            }
+
+            // record for event, use real balance not virtual field
+            let user_balance = self.transfer_sol_to.lamports();
+            let reserve_balance = self.reserve_pda.lamports();
+            let lamports = ticket_account.lamports_amount;
+            let available_for_claim = reserve_balance - self.state.rent_exempt_for_token_acc;
+            if lamports > available_for_claim {
+                msg!(
+                    "Ticket {} requests {} when only {} ready",
+                    ticket_info.key,
+                    lamports,
+                    available_for_claim
+                );
+                continue;
+            }
+
+            // record for event and then update
+            let circulating_ticket_balance = self.state.circulating_ticket_balance;
+            let circulating_ticket_count = self.state.circulating_ticket_count;
+            self.state.circulating_ticket_balance -= lamports;
+            self.state.circulating_ticket_count -= 1;
+
+            transfer(
+                CpiContext::new_with_signer(
+                    self.system_program.to_account_info(),
+                    Transfer {
+                        from: self.reserve_pda.to_account_info(),
+                        to: self.transfer_sol_to.to_account_info(),
+                    },
+                    &[&[
+                        &self.state.key().to_bytes(),
+                        State::RESERVE_SEED,
+                        &[self.state.reserve_bump_seed],
+                    ]],
+                ),
+                lamports,
+            )?;
+            self.state.on_transfer_from_reserve(lamports);
+            // all lamports from the ticket account go to transfer_sol_to
+            ticket_account.close(self.transfer_sol_to.to_account_info())?;
+            claimed_count += 1;
+
+            emit!(ClaimEvent {
+                state: self.state.key(),
+                epoch: self.clock.epoch,
+                ticket: ticket_info.key(),
+                beneficiary: self.transfer_sol_to.key(),
+                circulating_ticket_balance,
+                circulating_ticket_count,
+                reserve_balance,
+                user_balance,
+                amount: lamports,
+            });
+        }
+        // "Wait a few hours and retry"
+        require_gt!(claimed_count, 0, MarinadeError::TicketNotReady);
+
+        Ok(())
+    }
+}
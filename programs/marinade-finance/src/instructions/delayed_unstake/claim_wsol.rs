@@ -0,0 +1,140 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::token::{spl_token, sync_native, Mint, SyncNative, Token, TokenAccount};
+
+use super::claim::check_ticket_due;
+use crate::events::delayed_unstake::ClaimEvent;
+use crate::state::delayed_unstake_ticket::TicketAccountData;
+use crate::state::paused_operations::PausedOperations;
+use crate::MarinadeError;
+use crate::State;
+
+#[derive(Accounts)]
+pub struct ClaimWsol<'info> {
+    #[account(
+        mut,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Account<'info, State>,
+    #[account(
+        mut,
+        seeds = [
+            &state.key().to_bytes(),
+            State::RESERVE_SEED
+        ],
+        bump = state.reserve_bump_seed
+    )]
+    pub reserve_pda: SystemAccount<'info>,
+
+    // closed in code, all lamports from ticket_account go to transfer_wsol_to
+    #[account(mut)]
+    pub ticket_account: Account<'info, TicketAccountData>,
+
+    // any wSOL token account of the beneficiary, e.g. its associated token account
+    // created in the same transaction
+    #[account(
+        mut,
+        token::mint = native_mint,
+        token::authority = ticket_account.beneficiary @ MarinadeError::WrongBeneficiary
+    )]
+    pub transfer_wsol_to: Box<Account<'info, TokenAccount>>,
+    #[account(address = spl_token::native_mint::ID)]
+    pub native_mint: Box<Account<'info, Mint>>,
+
+    pub clock: Sysvar<'info, Clock>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> ClaimWsol<'info> {
+    // fn claim_wsol()
+    // same as claim, but the lamports are delivered as wSOL to a token account of the beneficiary
+    pub fn process(&mut self) -> Result<()> {
+        self.state.check_paused(PausedOperations::DELAYED_UNSTAKE)?;
+
+        require_keys_eq!(
+            self.ticket_account.state_address,
+            self.state.key(),
+            MarinadeError::InvalidDelayedUnstakeTicket
+        );
+        // not used
+        require_neq!(
+            self.ticket_account.lamports_amount,
+            0,
+            MarinadeError::ReusingDelayedUnstakeTicket
+        );
+        // a token account owned by the ticket NFT mint would lock the SOL forever
+        if self.ticket_account.is_wrapped(&self.ticket_account.key()) {
+            return err!(MarinadeError::WrongBeneficiary)
+                .map_err(|e| e.with_account_name("ticket_account"));
+        }
+        check_ticket_due(&self.ticket_account, &self.clock)
+            .map_err(|e| e.with_account_name("ticket_account"))?;
+
+        // record for event, use real balance not virtual field
+        let user_balance = self.transfer_wsol_to.amount;
+        let reserve_balance = self.reserve_pda.lamports();
+        let lamports = self.ticket_account.lamports_amount;
+
+        // use real balance not virtual field
+        let available_for_claim = reserve_balance - self.state.rent_exempt_for_token_acc;
+        if lamports > available_for_claim {
+            msg!(
+                "Requested to claim {} when only {} ready. Wait a few hours and retry",
+                lamports,
+                available_for_claim
+            );
+            // Error: "Wait a few hours and retry"
+            return err!(MarinadeError::TicketNotReady)
+                .map_err(|e| e.with_values((lamports, available_for_claim)));
+        }
+
+        // record for event and then update
+        let circulating_ticket_balance = self.state.circulating_ticket_balance;
+        let circulating_ticket_count = self.state.circulating_ticket_count;
+        self.state.circulating_ticket_balance -= lamports;
+        self.state.circulating_ticket_count -= 1;
+
+        // transfer sol from reserve_pda to the user wSOL account
+        transfer(
+            CpiContext::new_with_signer(
+                self.system_program.to_account_info(),
+                Transfer {
+                    from: self.reserve_pda.to_account_info(),
+                    to: self.transfer_wsol_to.to_account_info(),
+                },
+                &[&[
+                    &self.state.key().to_bytes(),
+                    State::RESERVE_SEED,
+                    &[self.state.reserve_bump_seed],
+                ]],
+            ),
+            lamports,
+        )?;
+        self.state.on_transfer_from_reserve(lamports);
+        // close the ticket before the sync so its rent is wrapped too
+        self.ticket_account
+            .close(self.transfer_wsol_to.to_account_info())?;
+        sync_native(CpiContext::new(
+            self.token_program.to_account_info(),
+            SyncNative {
+                account: self.transfer_wsol_to.to_account_info(),
+            },
+        ))?;
+
+        emit!(ClaimEvent {
+            state: self.state.key(),
+            epoch: self.clock.epoch,
+            ticket: self.ticket_account.key(),
+            beneficiary: self.ticket_account.beneficiary,
+            circulating_ticket_balance,
+            circulating_ticket_count,
+            reserve_balance,
+            user_balance,
+            amount: lamports,
+        });
+
+        Ok(())
+    }
+}
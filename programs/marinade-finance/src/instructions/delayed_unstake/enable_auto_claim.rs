@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::system_program;
+
+use crate::{
+    error::MarinadeError,
+    events::delayed_unstake::EnableAutoClaimEvent,
+    state::{
+        delayed_unstake_ticket::{TicketAccountData, TicketAutoClaim},
+        Fee,
+    },
+    State,
+};
+
+#[derive(Accounts)]
+pub struct EnableAutoClaim<'info> {
+    #[account(
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+
+    #[account(
+        has_one = beneficiary @ MarinadeError::WrongBeneficiary,
+        constraint = ticket_account.state_address == state.key()
+            @ MarinadeError::InvalidDelayedUnstakeTicket,
+    )]
+    pub ticket_account: Box<Account<'info, TicketAccountData>>,
+    #[account(
+        mut,
+        owner = system_program::ID
+    )]
+    pub beneficiary: Signer<'info>,
+
+    // closed into the beneficiary by crank_claim
+    #[account(
+        init_if_needed,
+        payer = beneficiary,
+        space = TicketAutoClaim::LEN,
+        seeds = [
+            &state.key().to_bytes(),
+            TicketAutoClaim::SEED,
+            &ticket_account.key().to_bytes()
+        ],
+        bump,
+    )]
+    pub auto_claim: Box<Account<'info, TicketAutoClaim>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> EnableAutoClaim<'info> {
+    // fn enable_auto_claim()
+    // can be called again to change the tip
+    pub fn process(&mut self, tip: Fee, bump: u8) -> Result<()> {
+        tip.check_max(
+            TicketAutoClaim::MAX_TIP,
+            MarinadeError::AutoClaimTipIsTooHigh,
+        )?;
+
+        self.auto_claim.ticket = self.ticket_account.key();
+        self.auto_claim.beneficiary = self.beneficiary.key();
+        self.auto_claim.tip = tip;
+        self.auto_claim.bump = bump;
+
+        emit!(EnableAutoClaimEvent {
+            state: self.state.key(),
+            ticket: self.ticket_account.key(),
+            beneficiary: self.beneficiary.key(),
+            tip,
+        });
+
+        Ok(())
+    }
+}
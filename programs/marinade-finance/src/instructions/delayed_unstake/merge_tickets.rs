@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::MarinadeError,
+    events::delayed_unstake::MergeTicketsEvent,
+    state::{delayed_unstake_ticket::TicketAccountData, paused_operations::PausedOperations},
+    State,
+};
+
+#[derive(Accounts)]
+pub struct MergeTickets<'info> {
+    #[account(
+        mut,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+
+    #[account(
+        mut,
+        has_one = beneficiary @ MarinadeError::WrongBeneficiary,
+        constraint = ticket_account.state_address == state.key()
+            @ MarinadeError::InvalidDelayedUnstakeTicket,
+    )]
+    pub ticket_account: Box<Account<'info, TicketAccountData>>,
+
+    // merged into ticket_account and closed, the rent goes to the beneficiary
+    #[account(
+        mut,
+        close = beneficiary,
+        has_one = beneficiary @ MarinadeError::WrongBeneficiary,
+        constraint = source_ticket_account.state_address == state.key()
+            @ MarinadeError::InvalidDelayedUnstakeTicket,
+        constraint = source_ticket_account.created_epoch == ticket_account.created_epoch
+            @ MarinadeError::InvalidDelayedUnstakeTicket,
+        constraint = source_ticket_account.key() != ticket_account.key()
+            @ MarinadeError::InvalidDelayedUnstakeTicket,
+    )]
+    pub source_ticket_account: Box<Account<'info, TicketAccountData>>,
+
+    #[account(mut)]
+    pub beneficiary: Signer<'info>,
+}
+
+impl<'info> MergeTickets<'info> {
+    // fn merge_tickets()
+    pub fn process(&mut self) -> Result<()> {
+        self.state.check_paused(PausedOperations::DELAYED_UNSTAKE)?;
+        if self.ticket_account.is_swept() {
+            return err!(MarinadeError::TicketIsSwept)
+                .map_err(|e| e.with_account_name("ticket_account"));
+        }
+
+        let old_amount = self.ticket_account.lamports_amount;
+        let merged_amount = self.source_ticket_account.lamports_amount;
+        self.ticket_account.lamports_amount += merged_amount;
+        // disable the source ticket-account
+        self.source_ticket_account.lamports_amount = 0;
+        // circulating_ticket_balance does not change
+        self.state.circulating_ticket_count -= 1;
+
+        emit!(MergeTicketsEvent {
+            state: self.state.key(),
+            ticket: self.ticket_account.key(),
+            source_ticket: self.source_ticket_account.key(),
+            beneficiary: self.beneficiary.key(),
+            ticket_epoch: self.ticket_account.created_epoch,
+            old_amount,
+            merged_amount,
+            circulating_ticket_count: self.state.circulating_ticket_count,
+        });
+
+        Ok(())
+    }
+}
@@ -1,5 +1,21 @@
+pub mod change_ticket_beneficiary;
 pub mod claim;
+pub mod claim_many;
+pub mod claim_wsol;
+pub mod enable_auto_claim;
+pub mod merge_tickets;
 pub mod order_unstake;
+pub mod split_ticket;
+pub mod sweep_stale_ticket;
+pub mod wrap_ticket;
 
+pub use change_ticket_beneficiary::*;
 pub use claim::*;
+pub use claim_many::*;
+pub use claim_wsol::*;
+pub use enable_auto_claim::*;
+pub use merge_tickets::*;
 pub use order_unstake::*;
+pub use split_ticket::*;
+pub use sweep_stale_ticket::*;
+pub use wrap_ticket::*;
@@ -2,8 +2,13 @@ use anchor_lang::prelude::*;
 use anchor_spl::token::{burn, Burn, Mint, Token, TokenAccount};
 
 use crate::{
-    checks::check_token_source_account, error::MarinadeError,
-    events::delayed_unstake::OrderUnstakeEvent, state::delayed_unstake_ticket::TicketAccountData,
+    checks::check_token_source_account,
+    error::MarinadeError,
+    events::delayed_unstake::OrderUnstakeEvent,
+    state::{
+        delayed_unstake_ticket::TicketAccountData, paused_operations::PausedOperations,
+        user_stats::UserStats,
+    },
     State,
 };
 
@@ -11,7 +16,8 @@ use crate::{
 pub struct OrderUnstake<'info> {
     #[account(
         mut,
-        has_one = msol_mint
+        has_one = msol_mint,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
     )]
     pub state: Box<Account<'info, State>>,
     #[account(mut)]
@@ -26,6 +32,8 @@ pub struct OrderUnstake<'info> {
 
     pub burn_msol_authority: Signer<'info>, // burn_msol_from acc must be pre-delegated with enough amount to this key or input owner signature here
 
+    // created just before by any rent payer, so a sponsor can pay for the ticket of a user holding only mSOL.
+    // The sponsor gets the rent back if the user claims with rent_refund_to
     #[account(
         zero,
         rent_exempt = enforce
@@ -35,12 +43,21 @@ pub struct OrderUnstake<'info> {
     pub clock: Sysvar<'info, Clock>,
     pub rent: Sysvar<'info, Rent>,
     pub token_program: Program<'info, Token>,
+
+    // staking statistics of the burn_msol_from owner
+    #[account(
+        mut,
+        has_one = state
+    )]
+    pub user_stats: Option<Box<Account<'info, UserStats>>>,
 }
 
 impl<'info> OrderUnstake<'info> {
     // fn order_unstake() // create delayed-unstake Ticket-account
-    pub fn process(&mut self, msol_amount: u64) -> Result<()> {
-        require!(!self.state.paused, MarinadeError::ProgramIsPaused);
+    // `created_epoch` schedules the ticket for a later epoch: it is due on created_epoch + 1
+    // and the protocol does not start unstaking for it before created_epoch
+    pub fn process(&mut self, msol_amount: u64, created_epoch: Option<u64>) -> Result<()> {
+        self.state.check_paused(PausedOperations::DELAYED_UNSTAKE)?;
 
         check_token_source_account(
             &self.burn_msol_from,
@@ -61,7 +78,7 @@ impl<'info> OrderUnstake<'info> {
         let delay_unstake_fee_lamports = self
             .state
             .delayed_unstake_fee
-            .apply(sol_value_of_msol_burned);
+            .apply_ceil(sol_value_of_msol_burned);
         // the fee value will be burned but not delivered, thus increasing mSOL value slightly for all mSOL holders
         let lamports_for_user = sol_value_of_msol_burned - delay_unstake_fee_lamports;
 
@@ -93,12 +110,39 @@ impl<'info> OrderUnstake<'info> {
         self.state.on_msol_burn(msol_amount);
 
         // initialize new_ticket_account
-        let created_epoch = self.clock.epoch
+        let min_created_epoch = self.clock.epoch
             + if self.clock.epoch == self.state.stake_system.last_stake_delta_epoch {
                 1
             } else {
                 0
             };
+        let created_epoch = match created_epoch {
+            Some(created_epoch) if created_epoch > min_created_epoch => {
+                self.state.unstake_schedule.schedule(
+                    created_epoch,
+                    lamports_for_user,
+                    self.clock.epoch,
+                )?;
+                created_epoch
+            }
+            Some(created_epoch) => {
+                require_eq!(
+                    created_epoch,
+                    min_created_epoch,
+                    MarinadeError::InvalidUnstakeEpoch
+                );
+                created_epoch
+            }
+            None => min_created_epoch,
+        };
+        if let Some(user_stats) = &mut self.user_stats {
+            require_keys_eq!(
+                user_stats.owner,
+                self.burn_msol_from.owner,
+                MarinadeError::WrongUserStats
+            );
+            user_stats.on_unstake(msol_amount, lamports_for_user)?;
+        }
         self.new_ticket_account.set_inner(TicketAccountData {
             state_address: self.state.key(),
             beneficiary: ticket_beneficiary,
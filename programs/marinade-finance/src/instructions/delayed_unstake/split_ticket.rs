@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::MarinadeError,
+    events::delayed_unstake::SplitTicketEvent,
+    state::{delayed_unstake_ticket::TicketAccountData, paused_operations::PausedOperations},
+    State,
+};
+
+#[derive(Accounts)]
+pub struct SplitTicket<'info> {
+    #[account(
+        mut,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+
+    #[account(
+        mut,
+        has_one = beneficiary @ MarinadeError::WrongBeneficiary,
+        constraint = ticket_account.state_address == state.key()
+            @ MarinadeError::InvalidDelayedUnstakeTicket,
+    )]
+    pub ticket_account: Box<Account<'info, TicketAccountData>>,
+    pub beneficiary: Signer<'info>,
+
+    #[account(
+        zero,
+        rent_exempt = enforce
+    )]
+    pub new_ticket_account: Box<Account<'info, TicketAccountData>>,
+
+    pub rent: Sysvar<'info, Rent>,
+}
+
+impl<'info> SplitTicket<'info> {
+    // fn split_ticket()
+    // moves `amount` lamports of the ticket to a new ticket with the same beneficiary and due epoch
+    pub fn process(&mut self, amount: u64) -> Result<()> {
+        self.state.check_paused(PausedOperations::DELAYED_UNSTAKE)?;
+        if self.ticket_account.is_swept() {
+            return err!(MarinadeError::TicketIsSwept)
+                .map_err(|e| e.with_account_name("ticket_account"));
+        }
+
+        let lamports_amount = self.ticket_account.lamports_amount;
+        require_gte!(
+            amount,
+            self.state.min_withdraw,
+            MarinadeError::WithdrawAmountIsTooLow
+        );
+        require_gte!(
+            lamports_amount.saturating_sub(amount),
+            self.state.min_withdraw,
+            MarinadeError::WithdrawAmountIsTooLow
+        );
+
+        self.ticket_account.lamports_amount -= amount;
+        self.new_ticket_account.set_inner(TicketAccountData {
+            state_address: self.state.key(),
+            beneficiary: self.ticket_account.beneficiary,
+            lamports_amount: amount,
+            created_epoch: self.ticket_account.created_epoch,
+        });
+        // circulating_ticket_balance does not change
+        self.state.circulating_ticket_count += 1;
+
+        emit!(SplitTicketEvent {
+            state: self.state.key(),
+            ticket: self.ticket_account.key(),
+            new_ticket: self.new_ticket_account.key(),
+            beneficiary: self.ticket_account.beneficiary,
+            ticket_epoch: self.ticket_account.created_epoch,
+            old_amount: lamports_amount,
+            split_amount: amount,
+            circulating_ticket_count: self.state.circulating_ticket_count,
+        });
+
+        Ok(())
+    }
+}
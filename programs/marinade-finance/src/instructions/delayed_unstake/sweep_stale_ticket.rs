@@ -0,0 +1,131 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::system_program;
+use anchor_lang::system_program::{transfer, Transfer};
+
+use super::claim::WAIT_EPOCHS;
+use crate::events::delayed_unstake::SweepStaleTicketEvent;
+use crate::require_lte;
+use crate::state::delayed_unstake_ticket::{TicketAccountData, UnclaimedTickets};
+use crate::state::paused_operations::PausedOperations;
+use crate::MarinadeError;
+use crate::State;
+
+#[derive(Accounts)]
+pub struct SweepStaleTicket<'info> {
+    #[account(
+        mut,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+    #[account(
+        mut,
+        seeds = [
+            &state.key().to_bytes(),
+            State::RESERVE_SEED
+        ],
+        bump = state.reserve_bump_seed
+    )]
+    pub reserve_pda: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = ticket_account.state_address == state.key()
+            @ MarinadeError::InvalidDelayedUnstakeTicket,
+    )]
+    pub ticket_account: Box<Account<'info, TicketAccountData>>,
+
+    #[account(
+        init_if_needed,
+        payer = rent_payer,
+        space = UnclaimedTickets::LEN,
+        seeds = [
+            &state.key().to_bytes(),
+            UnclaimedTickets::SEED
+        ],
+        bump,
+    )]
+    pub unclaimed_tickets: Box<Account<'info, UnclaimedTickets>>,
+
+    #[account(
+        mut,
+        owner = system_program::ID
+    )]
+    pub rent_payer: Signer<'info>,
+
+    pub clock: Sysvar<'info, Clock>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SweepStaleTicket<'info> {
+    // fn sweep_stale_ticket()
+    // permissionless, moves the lamports of a ticket unclaimed for stale_ticket_epochs
+    // after its due epoch out of the reserve
+    pub fn process(&mut self, bump: u8) -> Result<()> {
+        self.state.check_paused(PausedOperations::DELAYED_UNSTAKE)?;
+        if self.ticket_account.is_swept() {
+            return err!(MarinadeError::TicketIsSwept)
+                .map_err(|e| e.with_account_name("ticket_account"));
+        }
+        let lamports = self.ticket_account.lamports_amount;
+        require_neq!(lamports, 0, MarinadeError::ReusingDelayedUnstakeTicket);
+        let created_epoch = self.ticket_account.created_epoch;
+        require_gte!(
+            self.clock.epoch,
+            created_epoch
+                .saturating_add(WAIT_EPOCHS)
+                .saturating_add(self.state.stale_ticket_epochs),
+            MarinadeError::TicketIsNotStale
+        );
+
+        // use real balance not virtual field
+        let reserve_balance = self.reserve_pda.lamports();
+        let available_for_claim = reserve_balance - self.state.rent_exempt_for_token_acc;
+        require_lte!(lamports, available_for_claim, MarinadeError::TicketNotReady);
+
+        // record for event and then update
+        let circulating_ticket_balance = self.state.circulating_ticket_balance;
+        let circulating_ticket_count = self.state.circulating_ticket_count;
+        self.state.circulating_ticket_balance -= lamports;
+        self.state.circulating_ticket_count -= 1;
+
+        transfer(
+            CpiContext::new_with_signer(
+                self.system_program.to_account_info(),
+                Transfer {
+                    from: self.reserve_pda.to_account_info(),
+                    to: self.unclaimed_tickets.to_account_info(),
+                },
+                &[&[
+                    &self.state.key().to_bytes(),
+                    State::RESERVE_SEED,
+                    &[self.state.reserve_bump_seed],
+                ]],
+            ),
+            lamports,
+        )?;
+        self.state.on_transfer_from_reserve(lamports);
+
+        self.unclaimed_tickets.state = self.state.key();
+        self.unclaimed_tickets.bump = bump;
+        self.unclaimed_tickets.lamports_amount += lamports;
+        self.unclaimed_tickets.ticket_count += 1;
+        self.ticket_account.created_epoch = TicketAccountData::SWEPT_EPOCH;
+
+        emit!(SweepStaleTicketEvent {
+            state: self.state.key(),
+            epoch: self.clock.epoch,
+            ticket: self.ticket_account.key(),
+            beneficiary: self.ticket_account.beneficiary,
+            ticket_epoch: created_epoch,
+            circulating_ticket_balance,
+            circulating_ticket_count,
+            reserve_balance,
+            amount: lamports,
+            unclaimed_lamports: self.unclaimed_tickets.lamports_amount,
+            unclaimed_ticket_count: self.unclaimed_tickets.ticket_count,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,113 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::system_program;
+use anchor_spl::token::{
+    mint_to, set_authority, spl_token::instruction::AuthorityType, Mint, MintTo, SetAuthority,
+    Token, TokenAccount,
+};
+
+use crate::{
+    error::MarinadeError,
+    events::delayed_unstake::WrapTicketEvent,
+    state::{delayed_unstake_ticket::TicketAccountData, paused_operations::PausedOperations},
+    State,
+};
+
+#[derive(Accounts)]
+pub struct WrapTicket<'info> {
+    #[account(
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+
+    #[account(
+        mut,
+        has_one = beneficiary @ MarinadeError::WrongBeneficiary,
+        constraint = ticket_account.state_address == state.key()
+            @ MarinadeError::InvalidDelayedUnstakeTicket,
+    )]
+    pub ticket_account: Box<Account<'info, TicketAccountData>>,
+    pub beneficiary: Signer<'info>,
+
+    // the minting is done by the beneficiary, the mint authority is removed right after
+    #[account(
+        init,
+        payer = rent_payer,
+        mint::decimals = 0,
+        mint::authority = beneficiary,
+        seeds = [
+            &state.key().to_bytes(),
+            TicketAccountData::NFT_MINT_SEED,
+            &ticket_account.key().to_bytes()
+        ],
+        bump,
+    )]
+    pub ticket_nft_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        token::mint = ticket_nft_mint
+    )]
+    pub mint_nft_to: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        owner = system_program::ID
+    )]
+    pub rent_payer: Signer<'info>,
+
+    pub rent: Sysvar<'info, Rent>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> WrapTicket<'info> {
+    // fn wrap_ticket()
+    // Mints a single token for the ticket and makes its mint the ticket beneficiary,
+    // from then on the ticket is claimed by whoever holds the token
+    pub fn process(&mut self) -> Result<()> {
+        self.state.check_paused(PausedOperations::DELAYED_UNSTAKE)?;
+        require_neq!(
+            self.ticket_account.lamports_amount,
+            0,
+            MarinadeError::ReusingDelayedUnstakeTicket
+        );
+
+        mint_to(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                MintTo {
+                    mint: self.ticket_nft_mint.to_account_info(),
+                    to: self.mint_nft_to.to_account_info(),
+                    authority: self.beneficiary.to_account_info(),
+                },
+            ),
+            1,
+        )?;
+        // fix the supply to 1
+        set_authority(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                SetAuthority {
+                    current_authority: self.beneficiary.to_account_info(),
+                    account_or_mint: self.ticket_nft_mint.to_account_info(),
+                },
+            ),
+            AuthorityType::MintTokens,
+            None,
+        )?;
+
+        let old_beneficiary = self.ticket_account.beneficiary;
+        self.ticket_account.beneficiary = self.ticket_nft_mint.key();
+
+        emit!(WrapTicketEvent {
+            state: self.state.key(),
+            ticket: self.ticket_account.key(),
+            old_beneficiary,
+            ticket_nft_mint: self.ticket_nft_mint.key(),
+            nft_owner: self.mint_nft_to.owner,
+            amount: self.ticket_account.lamports_amount,
+        });
+
+        Ok(())
+    }
+}
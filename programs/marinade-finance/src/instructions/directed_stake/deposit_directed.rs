@@ -0,0 +1,97 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::MarinadeError,
+    events::directed_stake::DepositDirectedEvent,
+    instructions::user::deposit::Deposit,
+    state::directed_stake::{DirectedStake, ValidatorDirectedStake},
+};
+
+#[derive(Accounts)]
+pub struct DepositDirected<'info> {
+    pub deposit: Deposit<'info>,
+
+    /// CHECK: vote account of the chosen validator, only the address is recorded
+    pub validator_vote: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = deposit.transfer_from,
+        space = DirectedStake::LEN,
+        seeds = [
+            &deposit.state.key().to_bytes(),
+            DirectedStake::SEED,
+            &deposit.transfer_from.key().to_bytes()
+        ],
+        bump,
+    )]
+    pub directed_stake: Box<Account<'info, DirectedStake>>,
+
+    #[account(
+        init_if_needed,
+        payer = deposit.transfer_from,
+        space = ValidatorDirectedStake::LEN,
+        seeds = [
+            &deposit.state.key().to_bytes(),
+            ValidatorDirectedStake::SEED,
+            &validator_vote.key().to_bytes()
+        ],
+        bump,
+    )]
+    pub validator_directed_stake: Box<Account<'info, ValidatorDirectedStake>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> DepositDirected<'info> {
+    // fn deposit_directed()
+    // a regular deposit with the lamports attributed to the validator_vote preference
+    pub fn process(
+        &mut self,
+        lamports: u64,
+        min_msol_out: u64,
+        directed_stake_bump: u8,
+        validator_directed_stake_bump: u8,
+    ) -> Result<()> {
+        if self.directed_stake.state == Pubkey::default() {
+            self.directed_stake.set_inner(DirectedStake {
+                state: self.deposit.state.key(),
+                owner: self.deposit.transfer_from.key(),
+                bump: directed_stake_bump,
+                validator: self.validator_vote.key(),
+                lamports: 0,
+            });
+        } else {
+            require_keys_eq!(
+                self.directed_stake.validator,
+                self.validator_vote.key(),
+                MarinadeError::DirectedStakeValidatorMismatch
+            );
+        }
+        if self.validator_directed_stake.state == Pubkey::default() {
+            self.validator_directed_stake
+                .set_inner(ValidatorDirectedStake {
+                    state: self.deposit.state.key(),
+                    validator: self.validator_vote.key(),
+                    bump: validator_directed_stake_bump,
+                    directed_lamports: 0,
+                });
+        }
+
+        self.deposit.process(lamports, min_msol_out)?;
+
+        self.directed_stake.lamports += lamports;
+        self.validator_directed_stake.directed_lamports += lamports;
+
+        emit!(DepositDirectedEvent {
+            state: self.deposit.state.key(),
+            sol_owner: self.deposit.transfer_from.key(),
+            validator: self.validator_vote.key(),
+            lamports,
+            owner_directed_lamports: self.directed_stake.lamports,
+            validator_directed_lamports: self.validator_directed_stake.directed_lamports,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,5 @@
+pub mod deposit_directed;
+pub mod redirect_stake;
+
+pub use deposit_directed::*;
+pub use redirect_stake::*;
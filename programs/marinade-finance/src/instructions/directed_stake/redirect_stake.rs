@@ -0,0 +1,103 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::system_program;
+
+use crate::{
+    error::MarinadeError,
+    events::directed_stake::RedirectStakeEvent,
+    state::{
+        directed_stake::{DirectedStake, ValidatorDirectedStake},
+        paused_operations::PausedOperations,
+    },
+    State,
+};
+
+#[derive(Accounts)]
+pub struct RedirectStake<'info> {
+    #[account(
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+
+    #[account(
+        mut,
+        has_one = owner,
+        seeds = [
+            &state.key().to_bytes(),
+            DirectedStake::SEED,
+            &owner.key().to_bytes()
+        ],
+        bump = directed_stake.bump,
+        constraint = directed_stake.validator != validator_vote.key()
+            @ MarinadeError::StakeAlreadyDirected,
+    )]
+    pub directed_stake: Box<Account<'info, DirectedStake>>,
+    #[account(
+        mut,
+        owner = system_program::ID
+    )]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            &state.key().to_bytes(),
+            ValidatorDirectedStake::SEED,
+            &directed_stake.validator.to_bytes()
+        ],
+        bump = old_validator_directed_stake.bump,
+    )]
+    pub old_validator_directed_stake: Box<Account<'info, ValidatorDirectedStake>>,
+
+    /// CHECK: vote account of the new validator, only the address is recorded
+    pub validator_vote: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = ValidatorDirectedStake::LEN,
+        seeds = [
+            &state.key().to_bytes(),
+            ValidatorDirectedStake::SEED,
+            &validator_vote.key().to_bytes()
+        ],
+        bump,
+    )]
+    pub new_validator_directed_stake: Box<Account<'info, ValidatorDirectedStake>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RedirectStake<'info> {
+    // fn redirect_stake()
+    // moves all the lamports directed by the owner to validator_vote
+    pub fn process(&mut self, new_validator_directed_stake_bump: u8) -> Result<()> {
+        self.state.check_paused(PausedOperations::DEPOSITS)?;
+
+        if self.new_validator_directed_stake.state == Pubkey::default() {
+            self.new_validator_directed_stake
+                .set_inner(ValidatorDirectedStake {
+                    state: self.state.key(),
+                    validator: self.validator_vote.key(),
+                    bump: new_validator_directed_stake_bump,
+                    directed_lamports: 0,
+                });
+        }
+
+        let lamports = self.directed_stake.lamports;
+        let old_validator = self.directed_stake.validator;
+        self.old_validator_directed_stake.directed_lamports -= lamports;
+        self.new_validator_directed_stake.directed_lamports += lamports;
+        self.directed_stake.validator = self.validator_vote.key();
+
+        emit!(RedirectStakeEvent {
+            state: self.state.key(),
+            owner: self.owner.key(),
+            old_validator,
+            new_validator: self.validator_vote.key(),
+            lamports,
+            old_validator_directed_lamports: self.old_validator_directed_stake.directed_lamports,
+            new_validator_directed_lamports: self.new_validator_directed_stake.directed_lamports,
+        });
+
+        Ok(())
+    }
+}
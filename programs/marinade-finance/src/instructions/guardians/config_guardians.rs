@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::MarinadeError, events::guardians::ConfigGuardiansEvent, state::guardians::GuardianSet,
+    State,
+};
+
+#[derive(Accounts)]
+pub struct ConfigGuardians<'info> {
+    #[account(
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+
+    #[account(
+        mut,
+        has_one = state,
+        seeds = [
+            &state.key().to_bytes(),
+            GuardianSet::SEED
+        ],
+        bump = guardian_set.bump,
+    )]
+    pub guardian_set: Box<Account<'info, GuardianSet>>,
+}
+
+impl<'info> ConfigGuardians<'info> {
+    // fn config_guardians()
+    // the current guardians signing in `signers`
+    pub fn process(
+        &mut self,
+        signers: &[AccountInfo<'info>],
+        guardians: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        let approvals = self.guardian_set.check_approvals(signers)?;
+        GuardianSet::check_guardians(&guardians, threshold)?;
+        self.guardian_set.guardians = guardians.clone();
+        self.guardian_set.threshold = threshold;
+
+        emit!(ConfigGuardiansEvent {
+            state: self.state.key(),
+            guardian_set: self.guardian_set.key(),
+            guardians,
+            threshold,
+            approvals,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::MarinadeError,
+    events::guardians::GuardianPauseEvent,
+    state::{guardians::GuardianSet, paused_operations::PausedOperations},
+    State,
+};
+
+#[derive(Accounts)]
+pub struct GuardianPause<'info> {
+    #[account(
+        mut,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+
+    #[account(
+        has_one = state,
+        seeds = [
+            &state.key().to_bytes(),
+            GuardianSet::SEED
+        ],
+        bump = guardian_set.bump,
+    )]
+    pub guardian_set: Box<Account<'info, GuardianSet>>,
+}
+
+impl<'info> GuardianPause<'info> {
+    // fn guardian_pause()
+    // same as the emergency pause, resumed by the pause authority
+    pub fn process(&mut self, signers: &[AccountInfo<'info>]) -> Result<()> {
+        require!(!self.state.is_fully_paused(), MarinadeError::AlreadyPaused);
+        let approvals = self.guardian_set.check_approvals(signers)?;
+        self.state.paused = PausedOperations::ALL;
+
+        emit!(GuardianPauseEvent {
+            state: self.state.key(),
+            approvals,
+        });
+
+        Ok(())
+    }
+}
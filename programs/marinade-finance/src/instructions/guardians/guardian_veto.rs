@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::MarinadeError,
+    events::guardians::GuardianVetoEvent,
+    state::{config_change::QueuedConfigChange, guardians::GuardianSet},
+    State,
+};
+
+#[derive(Accounts)]
+pub struct GuardianVeto<'info> {
+    #[account(
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+
+    #[account(
+        has_one = state,
+        seeds = [
+            &state.key().to_bytes(),
+            GuardianSet::SEED
+        ],
+        bump = guardian_set.bump,
+    )]
+    pub guardian_set: Box<Account<'info, GuardianSet>>,
+
+    #[account(
+        mut,
+        close = rent_payer,
+        has_one = rent_payer,
+        has_one = state,
+        seeds = [
+            &state.key().to_bytes(),
+            QueuedConfigChange::SEED
+        ],
+        bump = queued_config_change.bump,
+    )]
+    pub queued_config_change: Box<Account<'info, QueuedConfigChange>>,
+    #[account(mut)]
+    pub rent_payer: SystemAccount<'info>,
+}
+
+impl<'info> GuardianVeto<'info> {
+    // fn guardian_veto()
+    // cancels the queued config change, only while it is timelocked
+    pub fn process(&mut self, signers: &[AccountInfo<'info>]) -> Result<()> {
+        require_gt!(
+            self.queued_config_change.execute_slot,
+            Clock::get()?.slot,
+            MarinadeError::VetoWindowIsClosed
+        );
+        let approvals = self.guardian_set.check_approvals(signers)?;
+
+        emit!(GuardianVetoEvent {
+            state: self.state.key(),
+            queued_config_change: self.queued_config_change.key(),
+            approvals,
+        });
+
+        Ok(())
+    }
+}
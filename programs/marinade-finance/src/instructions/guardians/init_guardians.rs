@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::system_program;
+
+use crate::{
+    error::MarinadeError, events::guardians::ConfigGuardiansEvent, state::guardians::GuardianSet,
+    State,
+};
+
+#[derive(Accounts)]
+pub struct InitGuardians<'info> {
+    #[account(
+        has_one = admin_authority @ MarinadeError::InvalidAdminAuthority,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+    pub admin_authority: Signer<'info>,
+
+    #[account(
+        init,
+        payer = rent_payer,
+        space = GuardianSet::LEN,
+        seeds = [
+            &state.key().to_bytes(),
+            GuardianSet::SEED
+        ],
+        bump,
+    )]
+    pub guardian_set: Box<Account<'info, GuardianSet>>,
+
+    #[account(
+        mut,
+        owner = system_program::ID
+    )]
+    pub rent_payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitGuardians<'info> {
+    // fn init_guardians()
+    // once created, only the guardians can change the set
+    pub fn process(&mut self, guardians: Vec<Pubkey>, threshold: u8, bump: u8) -> Result<()> {
+        GuardianSet::check_guardians(&guardians, threshold)?;
+        self.guardian_set.set_inner(GuardianSet {
+            state: self.state.key(),
+            bump,
+            threshold,
+            guardians: guardians.clone(),
+        });
+
+        emit!(ConfigGuardiansEvent {
+            state: self.state.key(),
+            guardian_set: self.guardian_set.key(),
+            guardians,
+            threshold,
+            approvals: Vec::new(),
+        });
+
+        Ok(())
+    }
+}
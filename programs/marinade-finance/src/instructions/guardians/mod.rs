@@ -0,0 +1,9 @@
+pub mod config_guardians;
+pub mod guardian_pause;
+pub mod guardian_veto;
+pub mod init_guardians;
+
+pub use config_guardians::*;
+pub use guardian_pause::*;
+pub use guardian_veto::*;
+pub use init_guardians::*;
@@ -1,7 +1,10 @@
-use crate::calc::shares_from_value;
+use crate::associated_token::{self, init_token_account_if_needed, CreateAssociatedTokenAccounts};
 use crate::error::MarinadeError;
 use crate::events::liq_pool::AddLiquidityEvent;
-use crate::state::liq_pool::LiqPool;
+use crate::state::{
+    fee_curve::FeeCurve, liq_pool::LiqPool, lp_deposit_record::LpDepositRecord,
+    lp_price_oracle::LpPriceOracle, paused_operations::PausedOperations,
+};
 use crate::{require_lte, State};
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::system_program;
@@ -10,7 +13,10 @@ use anchor_spl::token::{mint_to, Mint, MintTo, Token, TokenAccount};
 
 #[derive(Accounts)]
 pub struct AddLiquidity<'info> {
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
     pub state: Box<Account<'info, State>>,
 
     #[account(
@@ -49,21 +55,50 @@ pub struct AddLiquidity<'info> {
     )]
     pub transfer_from: Signer<'info>,
 
-    // user SPL-Token account to send the newly minted LP tokens
+    /// CHECK: user SPL-Token account to send the newly minted LP tokens, created in code if needed
+    #[account(mut)]
+    pub mint_to: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = transfer_from,
+        space = LpDepositRecord::LEN,
+        seeds = [
+            &state.key().to_bytes(),
+            LpDepositRecord::SEED,
+            &mint_to.key().to_bytes()
+        ],
+        bump,
+    )]
+    pub lp_deposit_record: Box<Account<'info, LpDepositRecord>>,
+
     #[account(
         mut,
-        token::mint = state.liq_pool.lp_mint
+        has_one = state
     )]
-    pub mint_to: Box<Account<'info, TokenAccount>>,
+    pub lp_price_oracle: Option<Box<Account<'info, LpPriceOracle>>>,
 
     pub system_program: Program<'info, System>,
     pub token_program: Program<'info, Token>,
+
+    // required only to create mint_to as the mint_to_owner associated token account
+    /// CHECK: any owner for the new LP token account
+    pub mint_to_owner: Option<UncheckedAccount<'info>>,
+    #[account(mut)]
+    pub ata_rent_payer: Option<Signer<'info>>,
+    /// CHECK: program id
+    #[account(address = associated_token::program::ID)]
+    pub associated_token_program: Option<UncheckedAccount<'info>>,
+
+    // required while State::add_liquidity_bonus_share and LiqPoolExt::fee_curve are set
+    #[account(address = state.liq_pool_ext.fee_curve)]
+    pub fee_curve: Option<Box<Account<'info, FeeCurve>>>,
 }
 
 impl<'info> AddLiquidity<'info> {
     // fn add_liquidity()
     pub fn process(&mut self, lamports: u64) -> Result<()> {
-        require!(!self.state.paused, MarinadeError::ProgramIsPaused);
+        self.state.check_paused(PausedOperations::LIQUIDITY)?;
+        self.state.liq_pool.check_no_flash_loan()?;
 
         require_gte!(
             lamports,
@@ -76,6 +111,18 @@ impl<'info> AddLiquidity<'info> {
             user_sol_balance,
             MarinadeError::NotEnoughUserFunds
         );
+        let mint_to_account = init_token_account_if_needed(
+            &self.mint_to.to_account_info(),
+            &self.lp_mint.to_account_info(),
+            CreateAssociatedTokenAccounts::from_optional_accounts(
+                &self.mint_to_owner,
+                &self.ata_rent_payer,
+                &self.associated_token_program,
+                &self.system_program,
+                &self.token_program,
+            ),
+            "mint_to",
+        )?;
         self.state
             .liq_pool
             .check_liquidity_cap(lamports, self.liq_pool_sol_leg_pda.lamports())?;
@@ -83,13 +130,14 @@ impl<'info> AddLiquidity<'info> {
         // Update virtual lp_supply by real one
 
         // if self.state.liq_pool.lp_supply < self.lp_mint.supply, Someone minted lp tokens without our permission or bug found
+        let synced_lp_supply = self.state.lp_supply_of(self.lp_mint.supply);
         require_lte!(
-            self.lp_mint.supply,
+            synced_lp_supply,
             self.state.liq_pool.lp_supply,
             MarinadeError::UnregisteredLPMinted
         );
 
-        self.state.liq_pool.lp_supply = self.lp_mint.supply;
+        self.state.liq_pool.lp_supply = synced_lp_supply;
         // save msol price source
         let total_virtual_staked_lamports = self.state.total_virtual_staked_lamports();
         let msol_supply = self.state.msol_supply;
@@ -100,20 +148,36 @@ impl<'info> AddLiquidity<'info> {
 
         // compute current liq-pool total value BEFORE adding user's deposit
         let sol_leg_balance = self.liq_pool_sol_leg_pda.lamports();
-        let sol_leg_available_balance = sol_leg_balance - self.state.rent_exempt_for_token_acc;
-        let msol_leg_value = self.state.msol_to_sol(self.liq_pool_msol_leg.amount)?;
-        let total_liq_pool_value = sol_leg_available_balance + msol_leg_value;
-        msg!(
-            "liq_pool SOL:{}, liq_pool mSOL value:{} liq_pool_value:{}",
-            sol_leg_available_balance,
-            msol_leg_value,
-            total_liq_pool_value
-        );
-
         let lp_supply = self.state.liq_pool.lp_supply;
-        let shares_for_user = shares_from_value(lamports, total_liq_pool_value, lp_supply)?;
+        // the bonus is paid by the LPs from the fees they accrued
+        let bonus_lamports = if !self.state.add_liquidity_bonus_share.is_zero() {
+            self.state
+                .liq_pool_ext
+                .check_fee_curve(self.fee_curve.is_some())
+                .map_err(|e| e.with_account_name("fee_curve"))?;
+            self.state.add_liquidity_bonus(
+                lamports,
+                sol_leg_balance,
+                self.fee_curve.as_ref().map(|fee_curve| &***fee_curve),
+            )
+        } else {
+            0
+        };
+        msg!("Add liquidity bonus {}", bonus_lamports);
+        let shares_for_user = self.state.quote_add_liquidity(
+            lamports + bonus_lamports,
+            sol_leg_balance,
+            self.liq_pool_msol_leg.amount,
+            lp_supply,
+        )?;
+        let shares_for_user = self.state.lp_shares_to_mint(shares_for_user)?;
 
         msg!("LP for user {}", shares_for_user);
+        self.state.liq_pool_ext.check_lp_caps(
+            self.state.liq_pool.lp_supply,
+            shares_for_user,
+            mint_to_account.amount,
+        )?;
 
         // we start with a transfer instruction so the user can verify the SOL amount they're staking while approving the transaction
         // transfer sol into liq-pool sol leg
@@ -129,7 +193,7 @@ impl<'info> AddLiquidity<'info> {
         )?;
 
         // mint liq-pool shares (mSOL-SOL-LP tokens) for the user
-        let user_lp_balance = self.mint_to.amount;
+        let user_lp_balance = mint_to_account.amount;
         mint_to(
             CpiContext::new_with_signer(
                 self.token_program.to_account_info(),
@@ -147,6 +211,21 @@ impl<'info> AddLiquidity<'info> {
             shares_for_user,
         )?;
         self.state.liq_pool.on_lp_mint(shares_for_user);
+        self.state.add_liquidity_bonus_budget -= bonus_lamports;
+        self.lp_deposit_record.on_add_liquidity(
+            self.state.key(),
+            self.mint_to.key(),
+            Clock::get()?.slot,
+        );
+
+        if let Some(lp_price_oracle) = &mut self.lp_price_oracle {
+            lp_price_oracle.update(
+                &self.state,
+                self.liq_pool_sol_leg_pda.lamports(),
+                self.liq_pool_msol_leg.amount,
+                Clock::get()?.slot,
+            )?;
+        }
 
         emit!(AddLiquidityEvent {
             state: self.state.key(),
@@ -157,6 +236,7 @@ impl<'info> AddLiquidity<'info> {
             lp_supply,
             sol_added_amount: lamports,
             lp_minted: shares_for_user,
+            bonus_lamports,
             // msol price components
             total_virtual_staked_lamports,
             msol_supply,
@@ -0,0 +1,227 @@
+use crate::calc::{shares_from_value, Rounding};
+use crate::checks::check_token_source_account;
+use crate::error::MarinadeError;
+use crate::events::liq_pool::AddLiquidityMsolEvent;
+use crate::state::{
+    liq_pool::LiqPool, lp_deposit_record::LpDepositRecord, lp_price_oracle::LpPriceOracle,
+    paused_operations::PausedOperations,
+};
+use crate::{require_lte, State};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::system_program;
+use anchor_spl::token::{
+    mint_to, transfer as transfer_token, Mint, MintTo, Token, TokenAccount,
+    Transfer as TransferToken,
+};
+
+#[derive(Accounts)]
+pub struct AddLiquidityMsol<'info> {
+    #[account(
+        mut,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+
+    #[account(
+        mut,
+        address = state.liq_pool.lp_mint
+    )]
+    pub lp_mint: Box<Account<'info, Mint>>,
+
+    /// CHECK: PDA
+    #[account(
+        seeds = [
+            &state.key().to_bytes(),
+            LiqPool::LP_MINT_AUTHORITY_SEED
+        ],
+        bump = state.liq_pool.lp_mint_authority_bump_seed
+    )]
+    pub lp_mint_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        address = state.liq_pool.msol_leg
+    )]
+    pub liq_pool_msol_leg: Box<Account<'info, TokenAccount>>,
+
+    // liq_pool_sol_leg_pda to be able to compute current SOL value in liq_pool
+    #[account(
+        seeds = [
+            &state.key().to_bytes(),
+            LiqPool::SOL_LEG_SEED
+        ],
+        bump = state.liq_pool.sol_leg_bump_seed
+    )]
+    pub liq_pool_sol_leg_pda: SystemAccount<'info>,
+
+    // user mSOL Token account to take the mSOL from
+    #[account(
+        mut,
+        token::mint = state.msol_mint
+    )]
+    pub transfer_from: Box<Account<'info, TokenAccount>>,
+    pub transfer_from_authority: Signer<'info>, // transfer_from owner or delegate_authority
+
+    // user SPL-Token account to send the newly minted LP tokens
+    #[account(
+        mut,
+        token::mint = state.liq_pool.lp_mint
+    )]
+    pub mint_to: Box<Account<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = rent_payer,
+        space = LpDepositRecord::LEN,
+        seeds = [
+            &state.key().to_bytes(),
+            LpDepositRecord::SEED,
+            &mint_to.key().to_bytes()
+        ],
+        bump,
+    )]
+    pub lp_deposit_record: Box<Account<'info, LpDepositRecord>>,
+    #[account(
+        mut,
+        owner = system_program::ID
+    )]
+    pub rent_payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+
+    #[account(
+        mut,
+        has_one = state
+    )]
+    pub lp_price_oracle: Option<Box<Account<'info, LpPriceOracle>>>,
+}
+
+impl<'info> AddLiquidityMsol<'info> {
+    // fn add_liquidity_msol()
+    pub fn process(&mut self, msol_amount: u64) -> Result<()> {
+        self.state.check_paused(PausedOperations::LIQUIDITY)?;
+        self.state.liq_pool.check_no_flash_loan()?;
+
+        check_token_source_account(
+            &self.transfer_from,
+            self.transfer_from_authority.key,
+            msol_amount,
+        )
+        .map_err(|e| e.with_account_name("transfer_from"))?;
+
+        // deposit is valued at the current mSOL price
+        let msol_value = self.state.msol_to_sol(msol_amount)?;
+        require_gte!(
+            msol_value,
+            self.state.min_deposit,
+            MarinadeError::DepositAmountIsTooLow
+        );
+
+        // Update virtual lp_supply by real one
+
+        // if self.state.liq_pool.lp_supply < self.lp_mint.supply, Someone minted lp tokens without our permission or bug found
+        let synced_lp_supply = self.state.lp_supply_of(self.lp_mint.supply);
+        require_lte!(
+            synced_lp_supply,
+            self.state.liq_pool.lp_supply,
+            MarinadeError::UnregisteredLPMinted
+        );
+
+        self.state.liq_pool.lp_supply = synced_lp_supply;
+        // save msol price source
+        let total_virtual_staked_lamports = self.state.total_virtual_staked_lamports();
+        let msol_supply = self.state.msol_supply;
+
+        // compute current liq-pool total value BEFORE adding user's deposit
+        let sol_leg_balance = self.liq_pool_sol_leg_pda.lamports();
+        let sol_leg_available_balance = sol_leg_balance - self.state.rent_exempt_for_token_acc;
+        let msol_leg_balance = self.liq_pool_msol_leg.amount;
+        let msol_leg_value = self
+            .state
+            .msol_to_sol_rounded(msol_leg_balance, Rounding::Up)?;
+        let total_liq_pool_value = sol_leg_available_balance + msol_leg_value;
+        msg!(
+            "liq_pool SOL:{}, liq_pool mSOL value:{} liq_pool_value:{}",
+            sol_leg_available_balance,
+            msol_leg_value,
+            total_liq_pool_value
+        );
+
+        let lp_supply = self.state.liq_pool.lp_supply;
+        let shares_for_user =
+            shares_from_value(msol_value, total_liq_pool_value, lp_supply, Rounding::Down)?;
+        let shares_for_user = self.state.lp_shares_to_mint(shares_for_user)?;
+
+        msg!("LP for user {}", shares_for_user);
+        self.state.liq_pool_ext.check_lp_caps(
+            self.state.liq_pool.lp_supply,
+            shares_for_user,
+            self.mint_to.amount,
+        )?;
+
+        // transfer mSOL into liq-pool mSOL leg
+        let user_msol_balance = self.transfer_from.amount;
+        transfer_token(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                TransferToken {
+                    from: self.transfer_from.to_account_info(),
+                    to: self.liq_pool_msol_leg.to_account_info(),
+                    authority: self.transfer_from_authority.to_account_info(),
+                },
+            ),
+            msol_amount,
+        )?;
+
+        // mint liq-pool shares (mSOL-SOL-LP tokens) for the user
+        let user_lp_balance = self.mint_to.amount;
+        mint_to(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                MintTo {
+                    mint: self.lp_mint.to_account_info(),
+                    to: self.mint_to.to_account_info(),
+                    authority: self.lp_mint_authority.to_account_info(),
+                },
+                &[&[
+                    &self.state.key().to_bytes(),
+                    LiqPool::LP_MINT_AUTHORITY_SEED,
+                    &[self.state.liq_pool.lp_mint_authority_bump_seed],
+                ]],
+            ),
+            shares_for_user,
+        )?;
+        self.state.liq_pool.on_lp_mint(shares_for_user);
+        self.lp_deposit_record.on_add_liquidity(
+            self.state.key(),
+            self.mint_to.key(),
+            Clock::get()?.slot,
+        );
+
+        if let Some(lp_price_oracle) = &mut self.lp_price_oracle {
+            lp_price_oracle.update(
+                &self.state,
+                self.liq_pool_sol_leg_pda.lamports(),
+                msol_leg_balance + msol_amount,
+                Clock::get()?.slot,
+            )?;
+        }
+
+        emit!(AddLiquidityMsolEvent {
+            state: self.state.key(),
+            msol_owner: self.transfer_from.owner,
+            user_msol_balance,
+            user_lp_balance,
+            sol_leg_balance,
+            msol_leg_balance,
+            lp_supply,
+            msol_added_amount: msol_amount,
+            lp_minted: shares_for_user,
+            // msol price components
+            total_virtual_staked_lamports,
+            msol_supply,
+        });
+
+        Ok(())
+    }
+}
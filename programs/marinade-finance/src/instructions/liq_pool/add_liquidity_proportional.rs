@@ -0,0 +1,264 @@
+use crate::calc::{proportional, Rounding};
+use crate::checks::check_token_source_account;
+use crate::error::MarinadeError;
+use crate::events::liq_pool::AddLiquidityProportionalEvent;
+use crate::state::{
+    liq_pool::LiqPool, lp_deposit_record::LpDepositRecord, lp_price_oracle::LpPriceOracle,
+    paused_operations::PausedOperations,
+};
+use crate::{require_lte, State};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::system_program;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::token::{
+    mint_to, transfer as transfer_token, Mint, MintTo, Token, TokenAccount,
+    Transfer as TransferToken,
+};
+
+#[derive(Accounts)]
+pub struct AddLiquidityProportional<'info> {
+    #[account(
+        mut,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+
+    #[account(
+        mut,
+        address = state.liq_pool.lp_mint
+    )]
+    pub lp_mint: Box<Account<'info, Mint>>,
+
+    /// CHECK: PDA
+    #[account(
+        seeds = [
+            &state.key().to_bytes(),
+            LiqPool::LP_MINT_AUTHORITY_SEED
+        ],
+        bump = state.liq_pool.lp_mint_authority_bump_seed
+    )]
+    pub lp_mint_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        address = state.liq_pool.msol_leg
+    )]
+    pub liq_pool_msol_leg: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [
+            &state.key().to_bytes(),
+            LiqPool::SOL_LEG_SEED
+        ],
+        bump = state.liq_pool.sol_leg_bump_seed
+    )]
+    pub liq_pool_sol_leg_pda: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        owner = system_program::ID
+    )]
+    pub transfer_from: Signer<'info>,
+
+    // user mSOL Token account to take the mSOL from
+    #[account(
+        mut,
+        token::mint = state.msol_mint
+    )]
+    pub transfer_msol_from: Box<Account<'info, TokenAccount>>,
+    pub transfer_msol_from_authority: Signer<'info>, // transfer_msol_from owner or delegate_authority
+
+    // user SPL-Token account to send the newly minted LP tokens
+    #[account(
+        mut,
+        token::mint = state.liq_pool.lp_mint
+    )]
+    pub mint_to: Box<Account<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = transfer_from,
+        space = LpDepositRecord::LEN,
+        seeds = [
+            &state.key().to_bytes(),
+            LpDepositRecord::SEED,
+            &mint_to.key().to_bytes()
+        ],
+        bump,
+    )]
+    pub lp_deposit_record: Box<Account<'info, LpDepositRecord>>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+
+    #[account(
+        mut,
+        has_one = state
+    )]
+    pub lp_price_oracle: Option<Box<Account<'info, LpPriceOracle>>>,
+}
+
+impl<'info> AddLiquidityProportional<'info> {
+    // fn add_liquidity_proportional()
+    // Deposits SOL and mSOL in the current ratio of the legs, so the LP share price does not move
+    pub fn process(&mut self, lamports: u64, max_msol_amount: u64) -> Result<()> {
+        self.state.check_paused(PausedOperations::LIQUIDITY)?;
+        self.state.liq_pool.check_no_flash_loan()?;
+
+        require_gte!(
+            lamports,
+            self.state.min_deposit,
+            MarinadeError::DepositAmountIsTooLow
+        );
+        let user_sol_balance = self.transfer_from.lamports();
+        require_lte!(
+            lamports,
+            user_sol_balance,
+            MarinadeError::NotEnoughUserFunds
+        );
+        self.state
+            .liq_pool
+            .check_liquidity_cap(lamports, self.liq_pool_sol_leg_pda.lamports())?;
+
+        // Update virtual lp_supply by real one
+
+        // if self.state.liq_pool.lp_supply < self.lp_mint.supply, Someone minted lp tokens without our permission or bug found
+        let synced_lp_supply = self.state.lp_supply_of(self.lp_mint.supply);
+        require_lte!(
+            synced_lp_supply,
+            self.state.liq_pool.lp_supply,
+            MarinadeError::UnregisteredLPMinted
+        );
+
+        self.state.liq_pool.lp_supply = synced_lp_supply;
+
+        let sol_leg_balance = self.liq_pool_sol_leg_pda.lamports();
+        let sol_leg_available_balance = sol_leg_balance - self.state.rent_exempt_for_token_acc;
+        let msol_leg_balance = self.liq_pool_msol_leg.amount;
+        let lp_supply = self.state.liq_pool.lp_supply;
+        // an empty pool has no ratio to follow, the first deposit must be done with add_liquidity
+        require_neq!(lp_supply, 0, MarinadeError::InsufficientLiquidity);
+        require_neq!(
+            sol_leg_available_balance,
+            0,
+            MarinadeError::InsufficientLiquidity
+        );
+
+        // mSOL to add keeping the legs ratio
+        let msol_amount = proportional(
+            lamports,
+            msol_leg_balance,
+            sol_leg_available_balance,
+            Rounding::Up,
+        )?;
+        require_lte!(
+            msol_amount,
+            max_msol_amount,
+            MarinadeError::SlippageExceeded
+        );
+        check_token_source_account(
+            &self.transfer_msol_from,
+            self.transfer_msol_from_authority.key,
+            msol_amount,
+        )
+        .map_err(|e| e.with_account_name("transfer_msol_from"))?;
+
+        // the share of each leg is the same, so the shares can be computed from the SOL leg alone
+        let shares_for_user = proportional(
+            lamports,
+            lp_supply,
+            sol_leg_available_balance,
+            Rounding::Down,
+        )?;
+        let shares_for_user = self.state.lp_shares_to_mint(shares_for_user)?;
+        msg!(
+            "SOL added:{}, mSOL added:{}, LP for user {}",
+            lamports,
+            msol_amount,
+            shares_for_user
+        );
+        self.state.liq_pool_ext.check_lp_caps(
+            self.state.liq_pool.lp_supply,
+            shares_for_user,
+            self.mint_to.amount,
+        )?;
+
+        // we start with a transfer instruction so the user can verify the SOL amount they're adding while approving the transaction
+        transfer(
+            CpiContext::new(
+                self.system_program.to_account_info(),
+                Transfer {
+                    from: self.transfer_from.to_account_info(),
+                    to: self.liq_pool_sol_leg_pda.to_account_info(),
+                },
+            ),
+            lamports,
+        )?;
+
+        let user_msol_balance = self.transfer_msol_from.amount;
+        if msol_amount > 0 {
+            transfer_token(
+                CpiContext::new(
+                    self.token_program.to_account_info(),
+                    TransferToken {
+                        from: self.transfer_msol_from.to_account_info(),
+                        to: self.liq_pool_msol_leg.to_account_info(),
+                        authority: self.transfer_msol_from_authority.to_account_info(),
+                    },
+                ),
+                msol_amount,
+            )?;
+        }
+
+        // mint liq-pool shares (mSOL-SOL-LP tokens) for the user
+        let user_lp_balance = self.mint_to.amount;
+        mint_to(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                MintTo {
+                    mint: self.lp_mint.to_account_info(),
+                    to: self.mint_to.to_account_info(),
+                    authority: self.lp_mint_authority.to_account_info(),
+                },
+                &[&[
+                    &self.state.key().to_bytes(),
+                    LiqPool::LP_MINT_AUTHORITY_SEED,
+                    &[self.state.liq_pool.lp_mint_authority_bump_seed],
+                ]],
+            ),
+            shares_for_user,
+        )?;
+        self.state.liq_pool.on_lp_mint(shares_for_user);
+        self.lp_deposit_record.on_add_liquidity(
+            self.state.key(),
+            self.mint_to.key(),
+            Clock::get()?.slot,
+        );
+
+        if let Some(lp_price_oracle) = &mut self.lp_price_oracle {
+            lp_price_oracle.update(
+                &self.state,
+                self.liq_pool_sol_leg_pda.lamports(),
+                msol_leg_balance + msol_amount,
+                Clock::get()?.slot,
+            )?;
+        }
+
+        emit!(AddLiquidityProportionalEvent {
+            state: self.state.key(),
+            sol_owner: self.transfer_from.key(),
+            msol_owner: self.transfer_msol_from.owner,
+            user_sol_balance,
+            user_msol_balance,
+            user_lp_balance,
+            sol_leg_balance,
+            msol_leg_balance,
+            lp_supply,
+            sol_added_amount: lamports,
+            msol_added_amount: msol_amount,
+            lp_minted: shares_for_user,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,274 @@
+use crate::calc::{shares_from_value, Rounding};
+use crate::checks::check_token_source_account;
+use crate::error::MarinadeError;
+use crate::events::liq_pool::AddLiquidityEvent;
+use crate::state::{
+    liq_pool::LiqPool, lp_deposit_record::LpDepositRecord, lp_price_oracle::LpPriceOracle,
+    paused_operations::PausedOperations,
+};
+use crate::{require_lte, State};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::system_program;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::token::{
+    close_account, mint_to, spl_token, transfer as transfer_token, CloseAccount, Mint, MintTo,
+    Token, TokenAccount, Transfer as TransferToken,
+};
+
+#[derive(Accounts)]
+pub struct AddLiquidityWsol<'info> {
+    #[account(
+        mut,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+
+    #[account(
+        mut,
+        address = state.liq_pool.lp_mint
+    )]
+    pub lp_mint: Box<Account<'info, Mint>>,
+
+    /// CHECK: PDA
+    #[account(
+        seeds = [
+            &state.key().to_bytes(),
+            LiqPool::LP_MINT_AUTHORITY_SEED
+        ],
+        bump = state.liq_pool.lp_mint_authority_bump_seed
+    )]
+    pub lp_mint_authority: UncheckedAccount<'info>,
+
+    // liq_pool_msol_leg to be able to compute current msol value in liq_pool
+    #[account(address = state.liq_pool.msol_leg)]
+    pub liq_pool_msol_leg: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [
+            &state.key().to_bytes(),
+            LiqPool::SOL_LEG_SEED
+        ],
+        bump = state.liq_pool.sol_leg_bump_seed
+    )]
+    pub liq_pool_sol_leg_pda: SystemAccount<'info>,
+
+    // user wSOL token account to take the SOL from
+    #[account(
+        mut,
+        token::mint = native_mint
+    )]
+    pub transfer_from: Box<Account<'info, TokenAccount>>,
+    pub transfer_from_authority: Signer<'info>, // transfer_from owner or delegate_authority
+
+    #[account(address = spl_token::native_mint::ID)]
+    pub native_mint: Box<Account<'info, Mint>>,
+    // temporary wSOL account closed into the SOL leg in the same instruction
+    #[account(
+        init,
+        payer = rent_payer,
+        token::mint = native_mint,
+        token::authority = liq_pool_sol_leg_pda,
+        seeds = [
+            &state.key().to_bytes(),
+            LiqPool::WSOL_UNWRAP_SEED
+        ],
+        bump,
+    )]
+    pub wsol_unwrap: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        owner = system_program::ID
+    )]
+    pub rent_payer: Signer<'info>, // gets the wsol_unwrap rent back
+
+    // user SPL-Token account to send the newly minted LP tokens
+    #[account(
+        mut,
+        token::mint = state.liq_pool.lp_mint
+    )]
+    pub mint_to: Box<Account<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = rent_payer,
+        space = LpDepositRecord::LEN,
+        seeds = [
+            &state.key().to_bytes(),
+            LpDepositRecord::SEED,
+            &mint_to.key().to_bytes()
+        ],
+        bump,
+    )]
+    pub lp_deposit_record: Box<Account<'info, LpDepositRecord>>,
+
+    pub rent: Sysvar<'info, Rent>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+
+    #[account(
+        mut,
+        has_one = state
+    )]
+    pub lp_price_oracle: Option<Box<Account<'info, LpPriceOracle>>>,
+}
+
+impl<'info> AddLiquidityWsol<'info> {
+    // fn add_liquidity_wsol()
+    // Same as add_liquidity but the SOL is taken from a wrapped SOL token account
+    pub fn process(&mut self, lamports: u64) -> Result<()> {
+        self.state.check_paused(PausedOperations::LIQUIDITY)?;
+        self.state.liq_pool.check_no_flash_loan()?;
+
+        require_gte!(
+            lamports,
+            self.state.min_deposit,
+            MarinadeError::DepositAmountIsTooLow
+        );
+        check_token_source_account(
+            &self.transfer_from,
+            self.transfer_from_authority.key,
+            lamports,
+        )
+        .map_err(|e| e.with_account_name("transfer_from"))?;
+        let user_sol_balance = self.transfer_from.amount;
+        self.state
+            .liq_pool
+            .check_liquidity_cap(lamports, self.liq_pool_sol_leg_pda.lamports())?;
+
+        // Update virtual lp_supply by real one
+
+        // if self.state.liq_pool.lp_supply < self.lp_mint.supply, Someone minted lp tokens without our permission or bug found
+        let synced_lp_supply = self.state.lp_supply_of(self.lp_mint.supply);
+        require_lte!(
+            synced_lp_supply,
+            self.state.liq_pool.lp_supply,
+            MarinadeError::UnregisteredLPMinted
+        );
+
+        self.state.liq_pool.lp_supply = synced_lp_supply;
+        // save msol price source
+        let total_virtual_staked_lamports = self.state.total_virtual_staked_lamports();
+        let msol_supply = self.state.msol_supply;
+
+        // compute current liq-pool total value BEFORE adding user's deposit
+        let sol_leg_balance = self.liq_pool_sol_leg_pda.lamports();
+        let sol_leg_available_balance = sol_leg_balance - self.state.rent_exempt_for_token_acc;
+        let msol_leg_value = self
+            .state
+            .msol_to_sol_rounded(self.liq_pool_msol_leg.amount, Rounding::Up)?;
+        let total_liq_pool_value = sol_leg_available_balance + msol_leg_value;
+        msg!(
+            "liq_pool SOL:{}, liq_pool mSOL value:{} liq_pool_value:{}",
+            sol_leg_available_balance,
+            msol_leg_value,
+            total_liq_pool_value
+        );
+
+        let lp_supply = self.state.liq_pool.lp_supply;
+        let shares_for_user =
+            shares_from_value(lamports, total_liq_pool_value, lp_supply, Rounding::Down)?;
+        let shares_for_user = self.state.lp_shares_to_mint(shares_for_user)?;
+
+        msg!("LP for user {}", shares_for_user);
+        self.state.liq_pool_ext.check_lp_caps(
+            self.state.liq_pool.lp_supply,
+            shares_for_user,
+            self.mint_to.amount,
+        )?;
+
+        // unwrap: move the wSOL to the temporary account and close it into the SOL leg
+        transfer_token(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                TransferToken {
+                    from: self.transfer_from.to_account_info(),
+                    to: self.wsol_unwrap.to_account_info(),
+                    authority: self.transfer_from_authority.to_account_info(),
+                },
+            ),
+            lamports,
+        )?;
+        let wsol_unwrap_rent = self.wsol_unwrap.to_account_info().lamports();
+        close_account(CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            CloseAccount {
+                account: self.wsol_unwrap.to_account_info(),
+                destination: self.liq_pool_sol_leg_pda.to_account_info(),
+                authority: self.liq_pool_sol_leg_pda.to_account_info(),
+            },
+            &[&[
+                &self.state.key().to_bytes(),
+                LiqPool::SOL_LEG_SEED,
+                &[self.state.liq_pool.sol_leg_bump_seed],
+            ]],
+        ))?;
+        // return the temporary account rent
+        transfer(
+            CpiContext::new_with_signer(
+                self.system_program.to_account_info(),
+                Transfer {
+                    from: self.liq_pool_sol_leg_pda.to_account_info(),
+                    to: self.rent_payer.to_account_info(),
+                },
+                &[&[
+                    &self.state.key().to_bytes(),
+                    LiqPool::SOL_LEG_SEED,
+                    &[self.state.liq_pool.sol_leg_bump_seed],
+                ]],
+            ),
+            wsol_unwrap_rent,
+        )?;
+
+        // mint liq-pool shares (mSOL-SOL-LP tokens) for the user
+        let user_lp_balance = self.mint_to.amount;
+        mint_to(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                MintTo {
+                    mint: self.lp_mint.to_account_info(),
+                    to: self.mint_to.to_account_info(),
+                    authority: self.lp_mint_authority.to_account_info(),
+                },
+                &[&[
+                    &self.state.key().to_bytes(),
+                    LiqPool::LP_MINT_AUTHORITY_SEED,
+                    &[self.state.liq_pool.lp_mint_authority_bump_seed],
+                ]],
+            ),
+            shares_for_user,
+        )?;
+        self.state.liq_pool.on_lp_mint(shares_for_user);
+        self.lp_deposit_record.on_add_liquidity(
+            self.state.key(),
+            self.mint_to.key(),
+            Clock::get()?.slot,
+        );
+
+        if let Some(lp_price_oracle) = &mut self.lp_price_oracle {
+            lp_price_oracle.update(
+                &self.state,
+                self.liq_pool_sol_leg_pda.lamports(),
+                self.liq_pool_msol_leg.amount,
+                Clock::get()?.slot,
+            )?;
+        }
+
+        emit!(AddLiquidityEvent {
+            state: self.state.key(),
+            sol_owner: self.transfer_from.owner,
+            user_sol_balance,
+            user_lp_balance,
+            sol_leg_balance,
+            lp_supply,
+            sol_added_amount: lamports,
+            lp_minted: shares_for_user,
+            bonus_lamports: 0,
+            // msol price components
+            total_virtual_staked_lamports,
+            msol_supply,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,93 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{transfer, Token, TokenAccount, Transfer};
+
+use crate::{
+    error::MarinadeError,
+    events::liq_pool::ClaimLpLockFeesEvent,
+    state::{lp_lock::LpLock, paused_operations::PausedOperations},
+    State,
+};
+
+#[derive(Accounts)]
+pub struct ClaimLpLockFees<'info> {
+    #[account(
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+
+    #[account(
+        mut,
+        has_one = state,
+        has_one = owner,
+        seeds = [
+            &state.key().to_bytes(),
+            LpLock::SEED,
+            &owner.key().to_bytes()
+        ],
+        bump = lp_lock.bump
+    )]
+    pub lp_lock: Box<Account<'info, LpLock>>,
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        address = state.liq_pool_ext.lp_lock_fee_vault
+    )]
+    pub lp_lock_fee_vault: Box<Account<'info, TokenAccount>>,
+    /// CHECK: PDA
+    #[account(
+        seeds = [
+            &state.key().to_bytes(),
+            LpLock::AUTHORITY_SEED
+        ],
+        bump = state.liq_pool_ext.lp_lock_authority_bump_seed
+    )]
+    pub lp_lock_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        token::mint = state.msol_mint
+    )]
+    pub transfer_msol_to: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> ClaimLpLockFees<'info> {
+    // fn claim_lp_lock_fees()
+    pub fn process(&mut self) -> Result<()> {
+        self.state.check_paused(PausedOperations::LIQUIDITY)?;
+
+        self.lp_lock
+            .settle(self.state.liq_pool_ext.lock_acc_fee_per_share)?;
+        let msol_amount = self.lp_lock.pending_fees;
+        if msol_amount > 0 {
+            transfer(
+                CpiContext::new_with_signer(
+                    self.token_program.to_account_info(),
+                    Transfer {
+                        from: self.lp_lock_fee_vault.to_account_info(),
+                        to: self.transfer_msol_to.to_account_info(),
+                        authority: self.lp_lock_authority.to_account_info(),
+                    },
+                    &[&[
+                        &self.state.key().to_bytes(),
+                        LpLock::AUTHORITY_SEED,
+                        &[self.state.liq_pool_ext.lp_lock_authority_bump_seed],
+                    ]],
+                ),
+                msol_amount,
+            )?;
+            self.lp_lock.pending_fees = 0;
+        }
+
+        emit!(ClaimLpLockFeesEvent {
+            state: self.state.key(),
+            owner: self.owner.key(),
+            msol_amount,
+            locked_amount: self.lp_lock.amount,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,197 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    system_program,
+    sysvar::instructions::{load_current_index_checked, load_instruction_at_checked},
+};
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_lang::Discriminator;
+use anchor_spl::token::TokenAccount;
+
+use crate::{
+    error::MarinadeError,
+    events::liq_pool::{FlashLoanBeginEvent, FlashLoanEndEvent},
+    instruction::FlashLoanEnd as FlashLoanEndInstruction,
+    require_lte,
+    state::{
+        liq_pool::LiqPool, lp_price_oracle::LpPriceOracle, paused_operations::PausedOperations,
+    },
+    State, ID,
+};
+
+#[derive(Accounts)]
+pub struct FlashLoanBegin<'info> {
+    #[account(
+        mut,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+
+    #[account(
+        mut,
+        seeds = [
+            &state.key().to_bytes(),
+            LiqPool::SOL_LEG_SEED
+        ],
+        bump = state.liq_pool.sol_leg_bump_seed
+    )]
+    pub liq_pool_sol_leg_pda: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub transfer_sol_to: SystemAccount<'info>,
+
+    /// CHECK: sysvar, used to find the flash_loan_end instruction
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> FlashLoanBegin<'info> {
+    // fn flash_loan_begin()
+    // lends SOL from the SOL leg, a flash_loan_end for the same state must follow in the same transaction
+    pub fn process(&mut self, lamports: u64) -> Result<()> {
+        self.state.check_paused(PausedOperations::LIQUIDITY)?;
+        require!(
+            self.state.liq_pool_ext.flash_loan_enabled,
+            MarinadeError::FlashLoanIsNotEnabled
+        );
+        self.state.liq_pool.check_no_flash_loan()?;
+
+        let sol_leg_balance = self.liq_pool_sol_leg_pda.lamports();
+        require_lte!(
+            lamports + self.state.rent_exempt_for_token_acc,
+            sol_leg_balance,
+            MarinadeError::InsufficientLiquidity
+        );
+
+        self.check_flash_loan_end()?;
+
+        transfer(
+            CpiContext::new_with_signer(
+                self.system_program.to_account_info(),
+                Transfer {
+                    from: self.liq_pool_sol_leg_pda.to_account_info(),
+                    to: self.transfer_sol_to.to_account_info(),
+                },
+                &[&[
+                    &self.state.key().to_bytes(),
+                    LiqPool::SOL_LEG_SEED,
+                    &[self.state.liq_pool.sol_leg_bump_seed],
+                ]],
+            ),
+            lamports,
+        )?;
+        self.state.liq_pool.lent_from_sol_leg = lamports;
+
+        emit!(FlashLoanBeginEvent {
+            state: self.state.key(),
+            borrower: self.transfer_sol_to.key(),
+            sol_leg_balance,
+            lamports,
+        });
+
+        Ok(())
+    }
+
+    /// the repayment is enforced by a top-level flash_loan_end instruction later in the transaction
+    fn check_flash_loan_end(&self) -> Result<()> {
+        let instructions = self.instructions.to_account_info();
+        let mut index = load_current_index_checked(&instructions)? as usize + 1;
+        while let Ok(instruction) = load_instruction_at_checked(index, &instructions) {
+            if instruction.program_id == ID
+                && instruction
+                    .data
+                    .starts_with(&FlashLoanEndInstruction::DISCRIMINATOR)
+                && instruction
+                    .accounts
+                    .first()
+                    .map_or(false, |state| state.pubkey == self.state.key())
+            {
+                return Ok(());
+            }
+            index += 1;
+        }
+        err!(MarinadeError::MissingFlashLoanEnd)
+    }
+}
+
+#[derive(Accounts)]
+pub struct FlashLoanEnd<'info> {
+    #[account(
+        mut,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+
+    #[account(
+        mut,
+        seeds = [
+            &state.key().to_bytes(),
+            LiqPool::SOL_LEG_SEED
+        ],
+        bump = state.liq_pool.sol_leg_bump_seed
+    )]
+    pub liq_pool_sol_leg_pda: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        owner = system_program::ID
+    )]
+    pub transfer_from: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    // required only with lp_price_oracle, to value the mSOL leg
+    #[account(address = state.liq_pool.msol_leg)]
+    pub liq_pool_msol_leg: Option<Box<Account<'info, TokenAccount>>>,
+    #[account(
+        mut,
+        has_one = state
+    )]
+    pub lp_price_oracle: Option<Box<Account<'info, LpPriceOracle>>>,
+}
+
+impl<'info> FlashLoanEnd<'info> {
+    // fn flash_loan_end()
+    // repays the loan plus the fee, the fee stays in the SOL leg for the LPs
+    pub fn process(&mut self) -> Result<()> {
+        let lamports = self.state.liq_pool.lent_from_sol_leg;
+        require_gt!(lamports, 0, MarinadeError::FlashLoanNotInProgress);
+        let fee = self.state.liq_pool_ext.flash_loan_fee.apply_ceil(lamports);
+
+        transfer(
+            CpiContext::new(
+                self.system_program.to_account_info(),
+                Transfer {
+                    from: self.transfer_from.to_account_info(),
+                    to: self.liq_pool_sol_leg_pda.to_account_info(),
+                },
+            ),
+            lamports + fee,
+        )?;
+        self.state.liq_pool.lent_from_sol_leg = 0;
+
+        if let Some(lp_price_oracle) = &mut self.lp_price_oracle {
+            let liq_pool_msol_leg = self
+                .liq_pool_msol_leg
+                .as_ref()
+                .ok_or_else(|| error!(MarinadeError::MissingLiqPoolMsolLeg))?;
+            lp_price_oracle.update(
+                &self.state,
+                self.liq_pool_sol_leg_pda.lamports(),
+                liq_pool_msol_leg.amount,
+                Clock::get()?.slot,
+            )?;
+        }
+
+        emit!(FlashLoanEndEvent {
+            state: self.state.key(),
+            payer: self.transfer_from.key(),
+            lamports,
+            fee,
+            sol_leg_balance: self.liq_pool_sol_leg_pda.lamports(),
+        });
+
+        Ok(())
+    }
+}
@@ -5,8 +5,16 @@ use anchor_spl::token::{
 };
 
 use crate::{
-    checks::check_token_source_account, events::liq_pool::LiquidUnstakeEvent,
-    state::liq_pool::LiqPool, MarinadeError, State,
+    checks::check_token_source_account,
+    debug_msg,
+    events::{liq_pool::LiquidUnstakeEvent, referral::IntegratorLiquidUnstakeEvent},
+    require_lte,
+    state::{
+        fee_curve::FeeCurve, holding_record::HoldingRecord, integrator::IntegratorPartner,
+        liq_pool::LiqPool, lp_price_oracle::LpPriceOracle, paused_operations::PausedOperations,
+        quote::LiquidUnstakeQuote, user_stats::UserStats, Fee,
+    },
+    MarinadeError, State,
 };
 
 #[derive(Accounts)]
@@ -14,7 +22,8 @@ pub struct LiquidUnstake<'info> {
     #[account(
         mut,
         has_one = treasury_msol_account,
-        has_one = msol_mint
+        has_one = msol_mint,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
     )]
     pub state: Box<Account<'info, State>>,
 
@@ -51,14 +60,73 @@ pub struct LiquidUnstake<'info> {
     #[account(mut)]
     pub transfer_sol_to: SystemAccount<'info>,
 
+    #[account(address = state.liq_pool_ext.fee_curve)]
+    pub fee_curve: Option<Box<Account<'info, FeeCurve>>>,
+
+    #[account(
+        mut,
+        address = state.liq_pool_ext.lp_lock_fee_vault
+    )]
+    pub lp_lock_fee_vault: Option<Box<Account<'info, TokenAccount>>>,
+
+    pub clock: Sysvar<'info, Clock>,
+
+    #[account(
+        mut,
+        has_one = state
+    )]
+    pub lp_price_oracle: Option<Box<Account<'info, LpPriceOracle>>>,
+
     pub system_program: Program<'info, System>,
     pub token_program: Program<'info, Token>,
+
+    // whitelisted integrator routing the unstake, gets IntegratorPartner::fee_rebate
+    #[account(
+        mut,
+        has_one = state
+    )]
+    pub integrator_partner: Option<Box<Account<'info, IntegratorPartner>>>,
+    pub integrator_authority: Option<Signer<'info>>,
+
+    // holding time of the get_msol_from owner deposits for State::holding_discount_tiers
+    #[account(
+        mut,
+        has_one = state
+    )]
+    pub holding_record: Option<Box<Account<'info, HoldingRecord>>>,
+
+    // staking statistics of the get_msol_from owner
+    #[account(
+        mut,
+        has_one = state
+    )]
+    pub user_stats: Option<Box<Account<'info, UserStats>>>,
 }
 
 impl<'info> LiquidUnstake<'info> {
     // fn liquid_unstake()
     pub fn process(&mut self, msol_amount: u64) -> Result<()> {
-        require!(!self.state.paused, MarinadeError::ProgramIsPaused);
+        self.state.check_paused(PausedOperations::LIQUID_UNSTAKE)?;
+        self.state.liq_pool.check_no_flash_loan()?;
+        self.state
+            .liq_pool_ext
+            .check_fee_curve(self.fee_curve.is_some())
+            .map_err(|e| e.with_account_name("fee_curve"))?;
+        self.state
+            .liq_pool_ext
+            .check_lp_lock_fee_vault(self.lp_lock_fee_vault.is_some())
+            .map_err(|e| e.with_account_name("lp_lock_fee_vault"))?;
+
+        if let Some(integrator_partner) = &self.integrator_partner {
+            match &self.integrator_authority {
+                Some(integrator_authority) => require_keys_eq!(
+                    integrator_authority.key(),
+                    integrator_partner.partner,
+                    MarinadeError::MissingIntegratorSignature
+                ),
+                None => return err!(MarinadeError::MissingIntegratorSignature),
+            }
+        }
 
         check_token_source_account(
             &self.get_msol_from,
@@ -66,6 +134,22 @@ impl<'info> LiquidUnstake<'info> {
             msol_amount,
         )
         .map_err(|e| e.with_account_name("get_msol_from"))?;
+        let holding_discount = match &self.holding_record {
+            Some(holding_record) => {
+                require_keys_eq!(
+                    holding_record.owner,
+                    self.get_msol_from.owner,
+                    MarinadeError::WrongHoldingRecord
+                );
+                holding_record.discount(
+                    &self.state.holding_discount_tiers,
+                    msol_amount,
+                    self.clock.unix_timestamp,
+                )
+            }
+            None => Fee::ZERO,
+        };
+        debug_msg!("holding_discount {}", holding_discount);
         let user_sol_balance = self.transfer_sol_to.lamports();
         let user_msol_balance = self.get_msol_from.amount;
         let treasury_msol_balance = self
@@ -74,39 +158,37 @@ impl<'info> LiquidUnstake<'info> {
 
         let liq_pool_msol_balance = self.liq_pool_msol_leg.amount;
         let liq_pool_sol_balance = self.liq_pool_sol_leg_pda.lamports();
-        let liq_pool_available_sol_balance =
-            liq_pool_sol_balance.saturating_sub(self.state.rent_exempt_for_token_acc);
-
-        // fee is computed based on the liquidity *after* the user takes the sol
-        let user_remove_lamports = self.state.msol_to_sol(msol_amount)?;
-        let liquid_unstake_fee = if user_remove_lamports >= liq_pool_available_sol_balance {
-            // user is removing all liquidity
-            self.state.liq_pool.lp_max_fee
-        } else {
-            let after_lamports = liq_pool_available_sol_balance - user_remove_lamports; //how much will be left?
-            self.state.liq_pool.linear_fee(after_lamports)
-        };
-
-        // compute fee in msol
-        let msol_fee = liquid_unstake_fee.apply(msol_amount);
-        msg!("msol_fee {}", msol_fee);
 
         // fee goes into treasury & LPs, so the user receives lamport value of data.msol_amount - msol_fee
-        // compute how many lamports the msol_amount the user is "selling" (minus fee) is worth
-        let working_lamports_value = self.state.msol_to_sol(msol_amount - msol_fee)?;
+        let LiquidUnstakeQuote {
+            msol_fee,
+            sol_out: working_lamports_value,
+            msol_fee_rebate,
+        } = self.state.quote_liquid_unstake(
+            msol_amount,
+            liq_pool_sol_balance,
+            self.fee_curve.as_ref().map(|fee_curve| &***fee_curve),
+            self.integrator_partner.as_deref().map(|p| &**p),
+            holding_discount,
+        )?;
+        debug_msg!("msol_fee {}", msol_fee);
 
         // it can't be more than what's in the LiqPool
-        if working_lamports_value + self.state.rent_exempt_for_token_acc
-            > self.liq_pool_sol_leg_pda.lamports()
-        {
-            return err!(MarinadeError::InsufficientLiquidity);
-        }
+        require_lte!(
+            working_lamports_value + self.state.rent_exempt_for_token_acc,
+            self.liq_pool_sol_leg_pda.lamports(),
+            MarinadeError::InsufficientLiquidity
+        );
 
         require_gte!(
             working_lamports_value,
             self.state.min_withdraw,
             MarinadeError::WithdrawAmountIsTooLow
         );
+        // circuit breaker against the pool being drained in a single epoch
+        self.state
+            .liq_pool_ext
+            .on_liquid_unstake(working_lamports_value, &self.clock)?;
 
         //transfer SOL from the liq-pool to the user
         if working_lamports_value > 0 {
@@ -127,13 +209,26 @@ impl<'info> LiquidUnstake<'info> {
             )?;
         }
 
-        // cut 25% from the fee for the treasury
+        // cut treasury_cut (25% by default) from the fee for the treasury
         let treasury_msol_cut = if treasury_msol_balance.is_some() {
             self.state.liq_pool.treasury_cut.apply(msol_fee)
         } else {
             0
         };
-        msg!("treasury_msol_cut {}", treasury_msol_cut);
+        debug_msg!("treasury_msol_cut {}", treasury_msol_cut);
+
+        // boost for the time-locked LP from the LP part of the fee
+        let lock_msol_cut = if self.lp_lock_fee_vault.is_some() {
+            self.state
+                .liq_pool_ext
+                .lock_fee_cut(msol_fee - treasury_msol_cut)
+        } else {
+            0
+        };
+        debug_msg!("lock_msol_cut {}", lock_msol_cut);
+        // the rest of the fee stays in the mSOL leg for the LPs
+        let lp_msol_cut = msol_fee - treasury_msol_cut - lock_msol_cut;
+        self.state.fund_add_liquidity_bonus(lp_msol_cut)?;
 
         //transfer mSOL to the liq-pool
         transfer_token(
@@ -145,7 +240,7 @@ impl<'info> LiquidUnstake<'info> {
                     authority: self.get_msol_from_authority.to_account_info(),
                 },
             ),
-            msol_amount - treasury_msol_cut,
+            msol_amount - treasury_msol_cut - lock_msol_cut,
         )?;
 
         //transfer treasury cut to treasury_msol_account
@@ -163,6 +258,33 @@ impl<'info> LiquidUnstake<'info> {
             )?;
         }
 
+        //transfer lock boost cut to lp_lock_fee_vault
+        if lock_msol_cut > 0 {
+            if let Some(lp_lock_fee_vault) = &self.lp_lock_fee_vault {
+                transfer_token(
+                    CpiContext::new(
+                        self.token_program.to_account_info(),
+                        TransferToken {
+                            from: self.get_msol_from.to_account_info(),
+                            to: lp_lock_fee_vault.to_account_info(),
+                            authority: self.get_msol_from_authority.to_account_info(),
+                        },
+                    ),
+                    lock_msol_cut,
+                )?;
+                self.state.liq_pool_ext.on_lock_fee(lock_msol_cut);
+            }
+        }
+
+        if let Some(lp_price_oracle) = &mut self.lp_price_oracle {
+            lp_price_oracle.update(
+                &self.state,
+                self.liq_pool_sol_leg_pda.lamports(),
+                liq_pool_msol_balance + msol_amount - treasury_msol_cut - lock_msol_cut,
+                self.clock.slot,
+            )?;
+        }
+
         emit!(LiquidUnstakeEvent {
             state: self.state.key(),
             msol_owner: self.get_msol_from.owner,
@@ -174,13 +296,40 @@ impl<'info> LiquidUnstake<'info> {
             user_sol_balance,
             msol_fee,
             treasury_msol_cut,
+            lock_msol_cut,
+            lp_msol_cut,
             sol_amount: working_lamports_value,
             lp_liquidity_target: self.state.liq_pool.lp_liquidity_target,
             lp_max_fee: self.state.liq_pool.lp_max_fee,
             lp_min_fee: self.state.liq_pool.lp_min_fee,
-            treasury_cut: self.state.liq_pool.treasury_cut
+            treasury_cut: self.state.liq_pool.treasury_cut,
+            holding_discount,
         });
 
+        if let Some(holding_record) = &mut self.holding_record {
+            holding_record.on_liquid_unstake(msol_amount);
+        }
+        if let Some(user_stats) = &mut self.user_stats {
+            require_keys_eq!(
+                user_stats.owner,
+                self.get_msol_from.owner,
+                MarinadeError::WrongUserStats
+            );
+            user_stats.on_unstake(msol_amount, working_lamports_value)?;
+        }
+
+        if let Some(integrator_partner) = &mut self.integrator_partner {
+            integrator_partner.on_liquid_unstake(working_lamports_value, msol_fee_rebate);
+            emit!(IntegratorLiquidUnstakeEvent {
+                state: self.state.key(),
+                partner: integrator_partner.partner,
+                lamports: working_lamports_value,
+                msol_fee_rebate,
+                unstaked_lamports: integrator_partner.unstaked_lamports,
+                liquid_unstakes: integrator_partner.liquid_unstakes,
+            });
+        }
+
         Ok(())
     }
 }
@@ -0,0 +1,116 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::system_program;
+use anchor_spl::token::{transfer, Token, TokenAccount, Transfer};
+
+use crate::{
+    checks::check_token_source_account,
+    error::MarinadeError,
+    events::liq_pool::LockLiquidityEvent,
+    state::{lp_lock::LpLock, paused_operations::PausedOperations},
+    State,
+};
+
+#[derive(Accounts)]
+pub struct LockLiquidity<'info> {
+    #[account(
+        mut,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = LpLock::LEN,
+        seeds = [
+            &state.key().to_bytes(),
+            LpLock::SEED,
+            &owner.key().to_bytes()
+        ],
+        bump,
+    )]
+    pub lp_lock: Box<Account<'info, LpLock>>,
+
+    #[account(
+        mut,
+        address = state.liq_pool_ext.lp_lock_vault
+    )]
+    pub lp_lock_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = state.liq_pool.lp_mint
+    )]
+    pub transfer_from: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        owner = system_program::ID
+    )]
+    pub owner: Signer<'info>, // transfer_from owner or delegate_authority, pays the lock rent
+
+    pub clock: Sysvar<'info, Clock>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> LockLiquidity<'info> {
+    // fn lock_liquidity()
+    // adds LP tokens to the user lock, the unlock slot can only be extended
+    pub fn process(&mut self, amount: u64, unlock_slot: u64, bump: u8) -> Result<()> {
+        self.state.check_paused(PausedOperations::LIQUIDITY)?;
+        check_token_source_account(&self.transfer_from, self.owner.key, amount)
+            .map_err(|e| e.with_account_name("transfer_from"))?;
+
+        if self.lp_lock.state == Pubkey::default() {
+            self.lp_lock.set_inner(LpLock {
+                state: self.state.key(),
+                owner: self.owner.key(),
+                bump,
+                amount: 0,
+                unlock_slot: 0,
+                fee_debt: 0,
+                pending_fees: 0,
+            });
+        }
+        require_gt!(
+            unlock_slot,
+            self.clock.slot,
+            MarinadeError::InvalidUnlockSlot
+        );
+        require_gte!(
+            unlock_slot,
+            self.lp_lock.unlock_slot,
+            MarinadeError::InvalidUnlockSlot
+        );
+        self.lp_lock.unlock_slot = unlock_slot;
+
+        let acc_fee_per_share = self.state.liq_pool_ext.lock_acc_fee_per_share;
+        self.lp_lock.settle(acc_fee_per_share)?;
+
+        transfer(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                Transfer {
+                    from: self.transfer_from.to_account_info(),
+                    to: self.lp_lock_vault.to_account_info(),
+                    authority: self.owner.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+        self.lp_lock.on_lock(amount, acc_fee_per_share);
+        self.state.liq_pool_ext.on_lp_lock(amount);
+
+        emit!(LockLiquidityEvent {
+            state: self.state.key(),
+            owner: self.owner.key(),
+            amount,
+            locked_amount: self.lp_lock.amount,
+            unlock_slot,
+            locked_lp_supply: self.state.liq_pool_ext.locked_lp_supply,
+        });
+
+        Ok(())
+    }
+}
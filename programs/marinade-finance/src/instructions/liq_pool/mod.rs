@@ -1,7 +1,29 @@
 pub mod add_liquidity;
+pub mod add_liquidity_msol;
+pub mod add_liquidity_proportional;
+pub mod add_liquidity_wsol;
+pub mod claim_lp_lock_fees;
+pub mod flash_loan;
 pub mod liquid_unstake;
+pub mod lock_liquidity;
 pub mod remove_liquidity;
+pub mod remove_liquidity_msol_only;
+pub mod remove_liquidity_sol_only;
+pub mod remove_liquidity_with_permit;
+pub mod unlock_liquidity;
+pub mod update_lp_price;
 
 pub use add_liquidity::*;
+pub use add_liquidity_msol::*;
+pub use add_liquidity_proportional::*;
+pub use add_liquidity_wsol::*;
+pub use claim_lp_lock_fees::*;
+pub use flash_loan::*;
 pub use liquid_unstake::*;
+pub use lock_liquidity::*;
 pub use remove_liquidity::*;
+pub use remove_liquidity_msol_only::*;
+pub use remove_liquidity_sol_only::*;
+pub use remove_liquidity_with_permit::*;
+pub use unlock_liquidity::*;
+pub use update_lp_price::*;
@@ -1,16 +1,27 @@
 use crate::{
-    calc::proportional, checks::check_token_source_account, error::MarinadeError,
-    events::liq_pool::RemoveLiquidityEvent, state::liq_pool::LiqPool, State,
+    checks::{check_sol_destination, check_token_source_account},
+    debug_msg,
+    error::MarinadeError,
+    events::liq_pool::RemoveLiquidityEvent,
+    state::{
+        liq_pool::LiqPool, lp_deposit_record::LpDepositRecord, lp_price_oracle::LpPriceOracle,
+        paused_operations::PausedOperations, quote::RemoveLiquidityQuote, Fee,
+    },
+    State,
 };
 use anchor_lang::prelude::*;
 use anchor_lang::system_program::{transfer, Transfer};
 use anchor_spl::token::{
-    burn, transfer as transfer_token, Burn, Mint, Token, TokenAccount, Transfer as TransferToken,
+    burn, sync_native, transfer as transfer_token, Burn, Mint, SyncNative, Token, TokenAccount,
+    Transfer as TransferToken,
 };
 
 #[derive(Accounts)]
 pub struct RemoveLiquidity<'info> {
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
     pub state: Box<Account<'info, State>>,
 
     #[account(
@@ -24,10 +35,21 @@ pub struct RemoveLiquidity<'info> {
         token::mint = state.liq_pool.lp_mint
     )]
     pub burn_from: Box<Account<'info, TokenAccount>>,
+    /// CHECK: PDA, deserialized in code if it exists
+    #[account(
+        seeds = [
+            &state.key().to_bytes(),
+            LpDepositRecord::SEED,
+            &burn_from.key().to_bytes()
+        ],
+        bump
+    )]
+    pub lp_deposit_record: UncheckedAccount<'info>,
     pub burn_from_authority: Signer<'info>,
 
+    /// CHECK: system account or wSOL token account, checked in code
     #[account(mut)]
-    pub transfer_sol_to: SystemAccount<'info>,
+    pub transfer_sol_to: UncheckedAccount<'info>,
 
     #[account(
         mut,
@@ -60,18 +82,41 @@ pub struct RemoveLiquidity<'info> {
     )]
     pub liq_pool_msol_leg_authority: UncheckedAccount<'info>,
 
+    #[account(
+        mut,
+        has_one = state
+    )]
+    pub lp_price_oracle: Option<Box<Account<'info, LpPriceOracle>>>,
+
     pub system_program: Program<'info, System>,
     pub token_program: Program<'info, Token>,
 }
 
 impl<'info> RemoveLiquidity<'info> {
-    pub fn process(&mut self, tokens: u64) -> Result<()> {
-        require!(!self.state.paused, MarinadeError::ProgramIsPaused);
+    /// removes a share of the burn_from balance, 10_000 basis points burn the whole balance.
+    /// The amount is read on-chain so it can't race with concurrent transfers
+    pub fn process_share(&mut self, share: Fee, min_sol_out: u64, min_msol_out: u64) -> Result<()> {
+        share.check()?;
+        let tokens = share.apply(self.burn_from.amount);
+        debug_msg!("Remove {} of {} LP", share, self.burn_from.amount);
+        self.process(tokens, min_sol_out, min_msol_out)
+    }
+
+    pub fn process(&mut self, tokens: u64, min_sol_out: u64, min_msol_out: u64) -> Result<()> {
+        self.state.check_paused(PausedOperations::LIQUIDITY)?;
+        self.state.liq_pool.check_no_flash_loan()?;
+        LpDepositRecord::check_holding_period(
+            &self.lp_deposit_record,
+            self.state.liq_pool_ext.lp_min_holding_slots,
+            Clock::get()?.slot,
+        )
+        .map_err(|e| e.with_account_name("burn_from"))?;
 
         check_token_source_account(&self.burn_from, self.burn_from_authority.key, tokens)
             .map_err(|e| e.with_account_name("burn_from"))?;
 
         let user_lp_balance = self.burn_from.amount;
+        let transfer_sol_to_wsol = check_sol_destination(&self.transfer_sol_to, "transfer_sol_to")?;
         let user_sol_balance = self.transfer_sol_to.lamports();
         let user_msol_balance = self.transfer_msol_to.amount;
 
@@ -80,22 +125,16 @@ impl<'info> RemoveLiquidity<'info> {
 
         // Update virtual lp_supply by real one
         let lp_mint_supply = self.lp_mint.supply;
-        if lp_mint_supply > self.state.liq_pool.lp_supply {
-            // impossible to happen unless bug
-            msg!("Someone minted lp tokens without our permission or bug found");
-        } else {
-            // maybe burn
-            self.state.liq_pool.lp_supply = lp_mint_supply;
-        }
-        msg!("mSOL-SOL-LP total supply:{}", lp_mint_supply);
-
-        let sol_out_amount = proportional(
-            tokens,
-            sol_leg_balance - self.state.rent_exempt_for_token_acc,
-            self.state.liq_pool.lp_supply, // Use virtual amount
-        )?;
-        let msol_out_amount = proportional(
+        let synced_lp_supply = self.state.lp_supply_of(lp_mint_supply);
+        self.state.liq_pool.sync_lp_supply(synced_lp_supply)?;
+        debug_msg!("mSOL-SOL-LP total supply:{}", lp_mint_supply);
+
+        let RemoveLiquidityQuote {
+            sol_out: sol_out_amount,
+            msol_out: msol_out_amount,
+        } = self.state.quote_remove_liquidity(
             tokens,
+            sol_leg_balance,
             msol_leg_balance,
             self.state.liq_pool.lp_supply, // Use virtual amount
         )?;
@@ -105,14 +144,21 @@ impl<'info> RemoveLiquidity<'info> {
             self.state.min_withdraw,
             MarinadeError::WithdrawAmountIsTooLow,
         );
-        msg!(
+        debug_msg!(
             "SOL out amount:{}, mSOL out amount:{}",
             sol_out_amount,
             msol_out_amount
         );
+        // protect the user from the pool composition shifting between quote and execution
+        require_gte!(sol_out_amount, min_sol_out, MarinadeError::SlippageExceeded);
+        require_gte!(
+            msol_out_amount,
+            min_msol_out,
+            MarinadeError::SlippageExceeded
+        );
 
         if sol_out_amount > 0 {
-            msg!("transfer SOL");
+            debug_msg!("transfer SOL");
             transfer(
                 CpiContext::new_with_signer(
                     self.system_program.to_account_info(),
@@ -128,10 +174,18 @@ impl<'info> RemoveLiquidity<'info> {
                 ),
                 sol_out_amount,
             )?;
+            if transfer_sol_to_wsol {
+                sync_native(CpiContext::new(
+                    self.token_program.to_account_info(),
+                    SyncNative {
+                        account: self.transfer_sol_to.to_account_info(),
+                    },
+                ))?;
+            }
         }
 
         if msol_out_amount > 0 {
-            msg!("transfer mSOL");
+            debug_msg!("transfer mSOL");
             transfer_token(
                 CpiContext::new_with_signer(
                     self.token_program.to_account_info(),
@@ -163,6 +217,15 @@ impl<'info> RemoveLiquidity<'info> {
         )?;
         self.state.liq_pool.on_lp_burn(tokens);
 
+        if let Some(lp_price_oracle) = &mut self.lp_price_oracle {
+            lp_price_oracle.update(
+                &self.state,
+                self.liq_pool_sol_leg_pda.lamports(),
+                msol_leg_balance - msol_out_amount,
+                Clock::get()?.slot,
+            )?;
+        }
+
         emit!(RemoveLiquidityEvent {
             state: self.state.key(),
             sol_leg_balance,
@@ -0,0 +1,218 @@
+use crate::{
+    calc::{proportional, Rounding},
+    checks::check_token_source_account,
+    debug_msg,
+    error::MarinadeError,
+    events::liq_pool::RemoveLiquidityMsolOnlyEvent,
+    require_lte,
+    state::{
+        liq_pool::LiqPool, lp_deposit_record::LpDepositRecord, lp_price_oracle::LpPriceOracle,
+        paused_operations::PausedOperations,
+    },
+    State,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{
+    burn, transfer as transfer_token, Burn, Mint, Token, TokenAccount, Transfer as TransferToken,
+};
+
+#[derive(Accounts)]
+pub struct RemoveLiquidityMsolOnly<'info> {
+    #[account(
+        mut,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+
+    #[account(
+        mut,
+        address = state.liq_pool.lp_mint
+    )]
+    pub lp_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        token::mint = state.liq_pool.lp_mint
+    )]
+    pub burn_from: Box<Account<'info, TokenAccount>>,
+    /// CHECK: PDA, deserialized in code if it exists
+    #[account(
+        seeds = [
+            &state.key().to_bytes(),
+            LpDepositRecord::SEED,
+            &burn_from.key().to_bytes()
+        ],
+        bump
+    )]
+    pub lp_deposit_record: UncheckedAccount<'info>,
+    pub burn_from_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        token::mint = state.msol_mint
+    )]
+    pub transfer_msol_to: Box<Account<'info, TokenAccount>>,
+
+    // legs
+    // liq_pool_sol_leg_pda to be able to compute the user's SOL share. The SOL share stays in the pool
+    #[account(
+        seeds = [
+            &state.key().to_bytes(),
+            LiqPool::SOL_LEG_SEED
+        ],
+        bump = state.liq_pool.sol_leg_bump_seed
+    )]
+    pub liq_pool_sol_leg_pda: SystemAccount<'info>,
+    #[account(
+        mut,
+        address = state.liq_pool.msol_leg
+    )]
+    pub liq_pool_msol_leg: Box<Account<'info, TokenAccount>>,
+    /// CHECK: PDA
+    #[account(
+        seeds = [
+            &state.key().to_bytes(),
+            LiqPool::MSOL_LEG_AUTHORITY_SEED
+        ],
+        bump = state.liq_pool.msol_leg_authority_bump_seed
+    )]
+    pub liq_pool_msol_leg_authority: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+
+    #[account(
+        mut,
+        has_one = state
+    )]
+    pub lp_price_oracle: Option<Box<Account<'info, LpPriceOracle>>>,
+}
+
+impl<'info> RemoveLiquidityMsolOnly<'info> {
+    // fn remove_liquidity_msol_only()
+    // The SOL part of the LP share is valued at the current mSOL price and paid from the mSOL leg.
+    // It is the same operation deposit does when it sells mSOL from the liq-pool, so no fee is applied
+    pub fn process(&mut self, tokens: u64, min_msol_out: u64) -> Result<()> {
+        self.state.check_paused(PausedOperations::LIQUIDITY)?;
+        self.state.liq_pool.check_no_flash_loan()?;
+        LpDepositRecord::check_holding_period(
+            &self.lp_deposit_record,
+            self.state.liq_pool_ext.lp_min_holding_slots,
+            Clock::get()?.slot,
+        )
+        .map_err(|e| e.with_account_name("burn_from"))?;
+
+        check_token_source_account(&self.burn_from, self.burn_from_authority.key, tokens)
+            .map_err(|e| e.with_account_name("burn_from"))?;
+
+        let user_lp_balance = self.burn_from.amount;
+        let user_msol_balance = self.transfer_msol_to.amount;
+
+        let sol_leg_balance = self.liq_pool_sol_leg_pda.lamports();
+        let msol_leg_balance = self.liq_pool_msol_leg.amount;
+
+        // Update virtual lp_supply by real one
+        let lp_mint_supply = self.lp_mint.supply;
+        let synced_lp_supply = self.state.lp_supply_of(lp_mint_supply);
+        self.state.liq_pool.sync_lp_supply(synced_lp_supply)?;
+        debug_msg!("mSOL-SOL-LP total supply:{}", lp_mint_supply);
+
+        let sol_share_amount = proportional(
+            tokens,
+            sol_leg_balance - self.state.rent_exempt_for_token_acc,
+            self.state.liq_pool.lp_supply, // Use virtual amount
+            Rounding::Down,
+        )?;
+        let msol_share_amount = proportional(
+            tokens,
+            msol_leg_balance,
+            self.state.liq_pool.lp_supply, // Use virtual amount
+            Rounding::Down,
+        )?;
+
+        // value the SOL share at the current mSOL price
+        let msol_swapped_amount = self.state.calc_msol_from_lamports(sol_share_amount)?;
+        let msol_out_amount = msol_share_amount + msol_swapped_amount;
+        // it can't be more than what's in the LiqPool
+        require_lte!(
+            msol_out_amount,
+            msol_leg_balance,
+            MarinadeError::InsufficientLiquidity
+        );
+
+        require_gte!(
+            self.state.msol_to_sol(msol_out_amount)?,
+            self.state.min_withdraw,
+            MarinadeError::WithdrawAmountIsTooLow,
+        );
+        debug_msg!(
+            "SOL share:{}, mSOL share:{}, mSOL out amount:{}",
+            sol_share_amount,
+            msol_share_amount,
+            msol_out_amount
+        );
+        // protect the user from the pool composition shifting between quote and execution
+        require_gte!(
+            msol_out_amount,
+            min_msol_out,
+            MarinadeError::SlippageExceeded
+        );
+
+        if msol_out_amount > 0 {
+            transfer_token(
+                CpiContext::new_with_signer(
+                    self.token_program.to_account_info(),
+                    TransferToken {
+                        from: self.liq_pool_msol_leg.to_account_info(),
+                        to: self.transfer_msol_to.to_account_info(),
+                        authority: self.liq_pool_msol_leg_authority.to_account_info(),
+                    },
+                    &[&[
+                        &self.state.key().to_bytes(),
+                        LiqPool::MSOL_LEG_AUTHORITY_SEED,
+                        &[self.state.liq_pool.msol_leg_authority_bump_seed],
+                    ]],
+                ),
+                msol_out_amount,
+            )?;
+        }
+
+        burn(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                Burn {
+                    mint: self.lp_mint.to_account_info(),
+                    from: self.burn_from.to_account_info(),
+                    authority: self.burn_from_authority.to_account_info(),
+                },
+            ),
+            tokens,
+        )?;
+        self.state.liq_pool.on_lp_burn(tokens);
+
+        if let Some(lp_price_oracle) = &mut self.lp_price_oracle {
+            lp_price_oracle.update(
+                &self.state,
+                self.liq_pool_sol_leg_pda.lamports(),
+                msol_leg_balance - msol_out_amount,
+                Clock::get()?.slot,
+            )?;
+        }
+
+        emit!(RemoveLiquidityMsolOnlyEvent {
+            state: self.state.key(),
+            sol_leg_balance,
+            msol_leg_balance,
+            user_lp_balance,
+            user_msol_balance,
+            lp_mint_supply,
+            lp_burned: tokens,
+            sol_share_amount,
+            msol_share_amount,
+            msol_out_amount,
+            total_virtual_staked_lamports: self.state.total_virtual_staked_lamports(),
+            msol_supply: self.state.msol_supply,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,331 @@
+use crate::{
+    calc::{proportional, Rounding},
+    checks::{check_sol_destination, check_token_source_account},
+    debug_msg,
+    error::MarinadeError,
+    events::liq_pool::RemoveLiquiditySolOnlyEvent,
+    require_lte,
+    state::{
+        fee_curve::FeeCurve, liq_pool::LiqPool, lp_deposit_record::LpDepositRecord,
+        lp_price_oracle::LpPriceOracle, paused_operations::PausedOperations,
+        quote::LiquidUnstakeQuote, Fee,
+    },
+    State,
+};
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::token::{
+    burn, sync_native, transfer as transfer_token, Burn, Mint, SyncNative, Token, TokenAccount,
+    Transfer as TransferToken,
+};
+
+#[derive(Accounts)]
+pub struct RemoveLiquiditySolOnly<'info> {
+    #[account(
+        mut,
+        has_one = treasury_msol_account,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+
+    #[account(
+        mut,
+        address = state.liq_pool.lp_mint
+    )]
+    pub lp_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        token::mint = state.liq_pool.lp_mint
+    )]
+    pub burn_from: Box<Account<'info, TokenAccount>>,
+    /// CHECK: PDA, deserialized in code if it exists
+    #[account(
+        seeds = [
+            &state.key().to_bytes(),
+            LpDepositRecord::SEED,
+            &burn_from.key().to_bytes()
+        ],
+        bump
+    )]
+    pub lp_deposit_record: UncheckedAccount<'info>,
+    pub burn_from_authority: Signer<'info>,
+
+    /// CHECK: system account or wSOL token account, checked in code
+    #[account(mut)]
+    pub transfer_sol_to: UncheckedAccount<'info>,
+
+    // legs
+    #[account(
+        mut,
+        seeds = [
+            &state.key().to_bytes(),
+            LiqPool::SOL_LEG_SEED
+        ],
+        bump = state.liq_pool.sol_leg_bump_seed
+    )]
+    pub liq_pool_sol_leg_pda: SystemAccount<'info>,
+    // liq_pool_msol_leg to be able to compute the user's mSOL share. The mSOL share stays in the pool,
+    // except the treasury and lock cuts of the fee
+    #[account(
+        mut,
+        address = state.liq_pool.msol_leg
+    )]
+    pub liq_pool_msol_leg: Box<Account<'info, TokenAccount>>,
+    /// CHECK: PDA
+    #[account(
+        seeds = [
+            &state.key().to_bytes(),
+            LiqPool::MSOL_LEG_AUTHORITY_SEED
+        ],
+        bump = state.liq_pool.msol_leg_authority_bump_seed
+    )]
+    pub liq_pool_msol_leg_authority: UncheckedAccount<'info>,
+
+    /// CHECK: deserialized in code, must be the one in State (State has_one treasury_msol_account)
+    #[account(mut)]
+    pub treasury_msol_account: UncheckedAccount<'info>,
+
+    #[account(address = state.liq_pool_ext.fee_curve)]
+    pub fee_curve: Option<Box<Account<'info, FeeCurve>>>,
+
+    #[account(
+        mut,
+        address = state.liq_pool_ext.lp_lock_fee_vault
+    )]
+    pub lp_lock_fee_vault: Option<Box<Account<'info, TokenAccount>>>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+
+    #[account(
+        mut,
+        has_one = state
+    )]
+    pub lp_price_oracle: Option<Box<Account<'info, LpPriceOracle>>>,
+}
+
+impl<'info> RemoveLiquiditySolOnly<'info> {
+    // fn remove_liquidity_sol_only()
+    // The mSOL part of the LP share is swapped into SOL inside the pool like a liquid unstake,
+    // the fee is split the same way between the LPs, the treasury and the locked LP
+    pub fn process(&mut self, tokens: u64, min_sol_out: u64) -> Result<()> {
+        self.state.check_paused(PausedOperations::LIQUIDITY)?;
+        self.state.liq_pool.check_no_flash_loan()?;
+        let clock = Clock::get()?;
+        LpDepositRecord::check_holding_period(
+            &self.lp_deposit_record,
+            self.state.liq_pool_ext.lp_min_holding_slots,
+            clock.slot,
+        )
+        .map_err(|e| e.with_account_name("burn_from"))?;
+        self.state
+            .liq_pool_ext
+            .check_fee_curve(self.fee_curve.is_some())
+            .map_err(|e| e.with_account_name("fee_curve"))?;
+        self.state
+            .liq_pool_ext
+            .check_lp_lock_fee_vault(self.lp_lock_fee_vault.is_some())
+            .map_err(|e| e.with_account_name("lp_lock_fee_vault"))?;
+
+        check_token_source_account(&self.burn_from, self.burn_from_authority.key, tokens)
+            .map_err(|e| e.with_account_name("burn_from"))?;
+
+        let user_lp_balance = self.burn_from.amount;
+        let transfer_sol_to_wsol = check_sol_destination(&self.transfer_sol_to, "transfer_sol_to")?;
+        let user_sol_balance = self.transfer_sol_to.lamports();
+        let treasury_msol_balance = self
+            .state
+            .get_treasury_msol_balance(&self.treasury_msol_account);
+
+        let sol_leg_balance = self.liq_pool_sol_leg_pda.lamports();
+        let sol_leg_available_balance = sol_leg_balance - self.state.rent_exempt_for_token_acc;
+        let msol_leg_balance = self.liq_pool_msol_leg.amount;
+
+        // Update virtual lp_supply by real one
+        let lp_mint_supply = self.lp_mint.supply;
+        let synced_lp_supply = self.state.lp_supply_of(lp_mint_supply);
+        self.state.liq_pool.sync_lp_supply(synced_lp_supply)?;
+        debug_msg!("mSOL-SOL-LP total supply:{}", lp_mint_supply);
+
+        let sol_share_amount = proportional(
+            tokens,
+            sol_leg_available_balance,
+            self.state.liq_pool.lp_supply, // Use virtual amount
+            Rounding::Down,
+        )?;
+        let msol_share_amount = proportional(
+            tokens,
+            msol_leg_balance,
+            self.state.liq_pool.lp_supply, // Use virtual amount
+            Rounding::Down,
+        )?;
+
+        // swap the mSOL share like a liquid unstake does,
+        // the fee is computed based on the liquidity left after the SOL share is taken
+        let LiquidUnstakeQuote {
+            msol_fee,
+            sol_out: msol_swapped_sol_amount,
+            ..
+        } = self.state.quote_liquid_unstake(
+            msol_share_amount,
+            sol_leg_balance - sol_share_amount,
+            self.fee_curve.as_ref().map(|fee_curve| &***fee_curve),
+            None,
+            Fee::ZERO,
+        )?;
+
+        let sol_out_amount = sol_share_amount + msol_swapped_sol_amount;
+        // it can't be more than what's in the LiqPool
+        require_lte!(
+            sol_out_amount,
+            sol_leg_available_balance,
+            MarinadeError::InsufficientLiquidity
+        );
+
+        require_gte!(
+            sol_out_amount,
+            self.state.min_withdraw,
+            MarinadeError::WithdrawAmountIsTooLow,
+        );
+        debug_msg!(
+            "SOL share:{}, mSOL share:{}, mSOL fee:{}, SOL out amount:{}",
+            sol_share_amount,
+            msol_share_amount,
+            msol_fee,
+            sol_out_amount
+        );
+        // protect the user from the pool composition shifting between quote and execution
+        require_gte!(sol_out_amount, min_sol_out, MarinadeError::SlippageExceeded);
+        // the swap counts for the liquid unstake circuit breaker
+        self.state
+            .liq_pool_ext
+            .on_liquid_unstake(msol_swapped_sol_amount, &clock)?;
+
+        if sol_out_amount > 0 {
+            transfer(
+                CpiContext::new_with_signer(
+                    self.system_program.to_account_info(),
+                    Transfer {
+                        from: self.liq_pool_sol_leg_pda.to_account_info(),
+                        to: self.transfer_sol_to.to_account_info(),
+                    },
+                    &[&[
+                        &self.state.key().to_bytes(),
+                        LiqPool::SOL_LEG_SEED,
+                        &[self.state.liq_pool.sol_leg_bump_seed],
+                    ]],
+                ),
+                sol_out_amount,
+            )?;
+            if transfer_sol_to_wsol {
+                sync_native(CpiContext::new(
+                    self.token_program.to_account_info(),
+                    SyncNative {
+                        account: self.transfer_sol_to.to_account_info(),
+                    },
+                ))?;
+            }
+        }
+
+        burn(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                Burn {
+                    mint: self.lp_mint.to_account_info(),
+                    from: self.burn_from.to_account_info(),
+                    authority: self.burn_from_authority.to_account_info(),
+                },
+            ),
+            tokens,
+        )?;
+        self.state.liq_pool.on_lp_burn(tokens);
+
+        // cut treasury_cut from the fee for the treasury
+        let treasury_msol_cut = if treasury_msol_balance.is_some() {
+            self.state.liq_pool.treasury_cut.apply(msol_fee)
+        } else {
+            0
+        };
+        // boost for the time-locked LP from the LP part of the fee
+        let lock_msol_cut = if self.lp_lock_fee_vault.is_some() {
+            self.state
+                .liq_pool_ext
+                .lock_fee_cut(msol_fee - treasury_msol_cut)
+        } else {
+            0
+        };
+        debug_msg!(
+            "treasury_msol_cut {}, lock_msol_cut {}",
+            treasury_msol_cut,
+            lock_msol_cut
+        );
+        // the rest of the fee stays in the mSOL leg for the LPs
+        let lp_msol_cut = msol_fee - treasury_msol_cut - lock_msol_cut;
+        self.state.fund_add_liquidity_bonus(lp_msol_cut)?;
+
+        if treasury_msol_cut > 0 {
+            self.transfer_from_msol_leg(
+                self.treasury_msol_account.to_account_info(),
+                treasury_msol_cut,
+            )?;
+        }
+        if lock_msol_cut > 0 {
+            if let Some(lp_lock_fee_vault) = &self.lp_lock_fee_vault {
+                self.transfer_from_msol_leg(lp_lock_fee_vault.to_account_info(), lock_msol_cut)?;
+                self.state.liq_pool_ext.on_lock_fee(lock_msol_cut);
+            }
+        }
+
+        if let Some(lp_price_oracle) = &mut self.lp_price_oracle {
+            lp_price_oracle.update(
+                &self.state,
+                self.liq_pool_sol_leg_pda.lamports(),
+                msol_leg_balance - treasury_msol_cut - lock_msol_cut,
+                clock.slot,
+            )?;
+        }
+
+        emit!(RemoveLiquiditySolOnlyEvent {
+            state: self.state.key(),
+            sol_leg_balance,
+            msol_leg_balance,
+            user_lp_balance,
+            user_sol_balance,
+            lp_mint_supply,
+            lp_burned: tokens,
+            sol_share_amount,
+            msol_share_amount,
+            msol_fee,
+            treasury_msol_cut,
+            lock_msol_cut,
+            lp_msol_cut,
+            msol_swapped_sol_amount,
+            sol_out_amount,
+            lp_liquidity_target: self.state.liq_pool.lp_liquidity_target,
+            lp_max_fee: self.state.liq_pool.lp_max_fee,
+            lp_min_fee: self.state.liq_pool.lp_min_fee,
+        });
+
+        Ok(())
+    }
+
+    fn transfer_from_msol_leg(&self, to: AccountInfo<'info>, amount: u64) -> Result<()> {
+        transfer_token(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                TransferToken {
+                    from: self.liq_pool_msol_leg.to_account_info(),
+                    to,
+                    authority: self.liq_pool_msol_leg_authority.to_account_info(),
+                },
+                &[&[
+                    &self.state.key().to_bytes(),
+                    LiqPool::MSOL_LEG_AUTHORITY_SEED,
+                    &[self.state.liq_pool.msol_leg_authority_bump_seed],
+                ]],
+            ),
+            amount,
+        )
+    }
+}
@@ -0,0 +1,325 @@
+use crate::{
+    calc::{proportional, Rounding},
+    checks::{check_ed25519_signature, check_sol_destination, check_token_source_account},
+    debug_msg,
+    error::MarinadeError,
+    events::liq_pool::RemoveLiquidityEvent,
+    state::{
+        liq_pool::LiqPool, lp_deposit_record::LpDepositRecord, lp_price_oracle::LpPriceOracle,
+        paused_operations::PausedOperations,
+    },
+    State,
+};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::system_program;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::token::{
+    burn, sync_native, transfer as transfer_token, Burn, Mint, SyncNative, Token, TokenAccount,
+    Transfer as TransferToken,
+};
+
+/// Message signed off-chain by the burn_from owner (borsh serialized)
+#[derive(Clone, Debug, AnchorSerialize, AnchorDeserialize)]
+pub struct RemoveLiquidityPermit {
+    pub state: Pubkey,
+    pub burn_from: Pubkey,
+    pub transfer_sol_to: Pubkey,
+    pub transfer_msol_to: Pubkey,
+    pub tokens: u64,
+    pub min_sol_out: u64,
+    pub min_msol_out: u64,
+    pub nonce: u64,
+    pub expiry_slot: u64,
+}
+
+#[derive(Accounts)]
+#[instruction(permit: RemoveLiquidityPermit)]
+pub struct RemoveLiquidityWithPermit<'info> {
+    #[account(
+        mut,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+
+    #[account(
+        mut,
+        address = state.liq_pool.lp_mint
+    )]
+    pub lp_mint: Box<Account<'info, Mint>>,
+
+    // must be pre-delegated to lp_permit_authority by the owner
+    #[account(
+        mut,
+        token::mint = state.liq_pool.lp_mint,
+        address = permit.burn_from
+    )]
+    pub burn_from: Box<Account<'info, TokenAccount>>,
+    /// CHECK: PDA, deserialized in code if it exists
+    #[account(
+        seeds = [
+            &state.key().to_bytes(),
+            LpDepositRecord::SEED,
+            &burn_from.key().to_bytes()
+        ],
+        bump
+    )]
+    pub lp_deposit_record: UncheckedAccount<'info>,
+    /// CHECK: PDA
+    #[account(
+        seeds = [
+            &state.key().to_bytes(),
+            LiqPool::LP_PERMIT_AUTHORITY_SEED
+        ],
+        bump
+    )]
+    pub lp_permit_authority: UncheckedAccount<'info>,
+
+    /// CHECK: no discriminator used
+    /// by initializing this account we mark the permit as used
+    #[account(
+        init, // will ensure it is system account
+        payer = relayer,
+        space = 0,
+        seeds = [
+            &state.key().to_bytes(),
+            LiqPool::LP_PERMIT_NONCE_SEED,
+            &burn_from.owner.to_bytes(),
+            &permit.nonce.to_le_bytes(),
+        ],
+        bump,
+    )]
+    pub permit_nonce: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        owner = system_program::ID
+    )]
+    pub relayer: Signer<'info>,
+
+    /// CHECK: system account or wSOL token account, checked in code
+    #[account(
+        mut,
+        address = permit.transfer_sol_to
+    )]
+    pub transfer_sol_to: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        token::mint = state.msol_mint,
+        address = permit.transfer_msol_to
+    )]
+    pub transfer_msol_to: Box<Account<'info, TokenAccount>>,
+
+    // legs
+    #[account(
+        mut,
+        seeds = [
+            &state.key().to_bytes(),
+            LiqPool::SOL_LEG_SEED
+        ],
+        bump = state.liq_pool.sol_leg_bump_seed
+    )]
+    pub liq_pool_sol_leg_pda: SystemAccount<'info>,
+    #[account(
+        mut,
+        address = state.liq_pool.msol_leg
+    )]
+    pub liq_pool_msol_leg: Box<Account<'info, TokenAccount>>,
+    /// CHECK: PDA
+    #[account(
+        seeds = [
+            &state.key().to_bytes(),
+            LiqPool::MSOL_LEG_AUTHORITY_SEED
+        ],
+        bump = state.liq_pool.msol_leg_authority_bump_seed
+    )]
+    pub liq_pool_msol_leg_authority: UncheckedAccount<'info>,
+
+    /// CHECK: sysvar, used to find the ed25519 signature verification
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+    pub clock: Sysvar<'info, Clock>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+
+    #[account(
+        mut,
+        has_one = state
+    )]
+    pub lp_price_oracle: Option<Box<Account<'info, LpPriceOracle>>>,
+}
+
+impl<'info> RemoveLiquidityWithPermit<'info> {
+    // fn remove_liquidity_with_permit()
+    // Same as remove_liquidity but authorized by the burn_from owner ed25519 signature of the permit,
+    // verified by the ed25519 program instruction right before this one. The transaction is paid by a relayer
+    pub fn process(
+        &mut self,
+        permit: RemoveLiquidityPermit,
+        lp_permit_authority_bump: u8,
+    ) -> Result<()> {
+        self.state.check_paused(PausedOperations::LIQUIDITY)?;
+        self.state.liq_pool.check_no_flash_loan()?;
+        LpDepositRecord::check_holding_period(
+            &self.lp_deposit_record,
+            self.state.liq_pool_ext.lp_min_holding_slots,
+            Clock::get()?.slot,
+        )
+        .map_err(|e| e.with_account_name("burn_from"))?;
+
+        require_keys_eq!(
+            permit.state,
+            self.state.key(),
+            MarinadeError::InvalidSignature
+        );
+        require_gte!(
+            permit.expiry_slot,
+            self.clock.slot,
+            MarinadeError::PermitExpired
+        );
+        check_ed25519_signature(
+            &self.instructions,
+            &self.burn_from.owner,
+            &permit.try_to_vec()?,
+        )?;
+
+        let tokens = permit.tokens;
+        check_token_source_account(&self.burn_from, self.lp_permit_authority.key, tokens)
+            .map_err(|e| e.with_account_name("burn_from"))?;
+
+        let user_lp_balance = self.burn_from.amount;
+        let transfer_sol_to_wsol = check_sol_destination(&self.transfer_sol_to, "transfer_sol_to")?;
+        let user_sol_balance = self.transfer_sol_to.lamports();
+        let user_msol_balance = self.transfer_msol_to.amount;
+
+        let sol_leg_balance = self.liq_pool_sol_leg_pda.lamports();
+        let msol_leg_balance = self.liq_pool_msol_leg.amount;
+
+        // Update virtual lp_supply by real one
+        let lp_mint_supply = self.lp_mint.supply;
+        let synced_lp_supply = self.state.lp_supply_of(lp_mint_supply);
+        self.state.liq_pool.sync_lp_supply(synced_lp_supply)?;
+        debug_msg!("mSOL-SOL-LP total supply:{}", lp_mint_supply);
+
+        let sol_out_amount = proportional(
+            tokens,
+            sol_leg_balance - self.state.rent_exempt_for_token_acc,
+            self.state.liq_pool.lp_supply, // Use virtual amount
+            Rounding::Down,
+        )?;
+        let msol_out_amount = proportional(
+            tokens,
+            msol_leg_balance,
+            self.state.liq_pool.lp_supply, // Use virtual amount
+            Rounding::Down,
+        )?;
+
+        require_gte!(
+            sol_out_amount + self.state.msol_to_sol(msol_out_amount)?,
+            self.state.min_withdraw,
+            MarinadeError::WithdrawAmountIsTooLow,
+        );
+        debug_msg!(
+            "SOL out amount:{}, mSOL out amount:{}",
+            sol_out_amount,
+            msol_out_amount
+        );
+        require_gte!(
+            sol_out_amount,
+            permit.min_sol_out,
+            MarinadeError::SlippageExceeded
+        );
+        require_gte!(
+            msol_out_amount,
+            permit.min_msol_out,
+            MarinadeError::SlippageExceeded
+        );
+
+        if sol_out_amount > 0 {
+            transfer(
+                CpiContext::new_with_signer(
+                    self.system_program.to_account_info(),
+                    Transfer {
+                        from: self.liq_pool_sol_leg_pda.to_account_info(),
+                        to: self.transfer_sol_to.to_account_info(),
+                    },
+                    &[&[
+                        &self.state.key().to_bytes(),
+                        LiqPool::SOL_LEG_SEED,
+                        &[self.state.liq_pool.sol_leg_bump_seed],
+                    ]],
+                ),
+                sol_out_amount,
+            )?;
+            if transfer_sol_to_wsol {
+                sync_native(CpiContext::new(
+                    self.token_program.to_account_info(),
+                    SyncNative {
+                        account: self.transfer_sol_to.to_account_info(),
+                    },
+                ))?;
+            }
+        }
+
+        if msol_out_amount > 0 {
+            transfer_token(
+                CpiContext::new_with_signer(
+                    self.token_program.to_account_info(),
+                    TransferToken {
+                        from: self.liq_pool_msol_leg.to_account_info(),
+                        to: self.transfer_msol_to.to_account_info(),
+                        authority: self.liq_pool_msol_leg_authority.to_account_info(),
+                    },
+                    &[&[
+                        &self.state.key().to_bytes(),
+                        LiqPool::MSOL_LEG_AUTHORITY_SEED,
+                        &[self.state.liq_pool.msol_leg_authority_bump_seed],
+                    ]],
+                ),
+                msol_out_amount,
+            )?;
+        }
+
+        burn(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                Burn {
+                    mint: self.lp_mint.to_account_info(),
+                    from: self.burn_from.to_account_info(),
+                    authority: self.lp_permit_authority.to_account_info(),
+                },
+                &[&[
+                    &self.state.key().to_bytes(),
+                    LiqPool::LP_PERMIT_AUTHORITY_SEED,
+                    &[lp_permit_authority_bump],
+                ]],
+            ),
+            tokens,
+        )?;
+        self.state.liq_pool.on_lp_burn(tokens);
+
+        if let Some(lp_price_oracle) = &mut self.lp_price_oracle {
+            lp_price_oracle.update(
+                &self.state,
+                self.liq_pool_sol_leg_pda.lamports(),
+                msol_leg_balance - msol_out_amount,
+                self.clock.slot,
+            )?;
+        }
+
+        emit!(RemoveLiquidityEvent {
+            state: self.state.key(),
+            sol_leg_balance,
+            msol_leg_balance,
+            user_lp_balance,
+            user_sol_balance,
+            user_msol_balance,
+            lp_mint_supply,
+            lp_burned: tokens,
+            sol_out_amount,
+            msol_out_amount,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,108 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{transfer, Token, TokenAccount, Transfer};
+
+use crate::{
+    error::MarinadeError,
+    events::liq_pool::UnlockLiquidityEvent,
+    require_lte,
+    state::{lp_lock::LpLock, paused_operations::PausedOperations},
+    State,
+};
+
+#[derive(Accounts)]
+pub struct UnlockLiquidity<'info> {
+    #[account(
+        mut,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+
+    #[account(
+        mut,
+        has_one = state,
+        has_one = owner,
+        seeds = [
+            &state.key().to_bytes(),
+            LpLock::SEED,
+            &owner.key().to_bytes()
+        ],
+        bump = lp_lock.bump
+    )]
+    pub lp_lock: Box<Account<'info, LpLock>>,
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        address = state.liq_pool_ext.lp_lock_vault
+    )]
+    pub lp_lock_vault: Box<Account<'info, TokenAccount>>,
+    /// CHECK: PDA
+    #[account(
+        seeds = [
+            &state.key().to_bytes(),
+            LpLock::AUTHORITY_SEED
+        ],
+        bump = state.liq_pool_ext.lp_lock_authority_bump_seed
+    )]
+    pub lp_lock_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        token::mint = state.liq_pool.lp_mint
+    )]
+    pub transfer_to: Box<Account<'info, TokenAccount>>,
+
+    pub clock: Sysvar<'info, Clock>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> UnlockLiquidity<'info> {
+    // fn unlock_liquidity()
+    // returns expired locked LP tokens, the pending fees stay in the lock and can be claimed later
+    pub fn process(&mut self, amount: u64) -> Result<()> {
+        self.state.check_paused(PausedOperations::LIQUIDITY)?;
+        require_gte!(
+            self.clock.slot,
+            self.lp_lock.unlock_slot,
+            MarinadeError::LpIsLocked
+        );
+        require_lte!(
+            amount,
+            self.lp_lock.amount,
+            MarinadeError::NotEnoughLockedLp
+        );
+
+        let acc_fee_per_share = self.state.liq_pool_ext.lock_acc_fee_per_share;
+        self.lp_lock.settle(acc_fee_per_share)?;
+        self.lp_lock.on_unlock(amount, acc_fee_per_share);
+        self.state.liq_pool_ext.on_lp_unlock(amount);
+
+        transfer(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                Transfer {
+                    from: self.lp_lock_vault.to_account_info(),
+                    to: self.transfer_to.to_account_info(),
+                    authority: self.lp_lock_authority.to_account_info(),
+                },
+                &[&[
+                    &self.state.key().to_bytes(),
+                    LpLock::AUTHORITY_SEED,
+                    &[self.state.liq_pool_ext.lp_lock_authority_bump_seed],
+                ]],
+            ),
+            amount,
+        )?;
+
+        emit!(UnlockLiquidityEvent {
+            state: self.state.key(),
+            owner: self.owner.key(),
+            amount,
+            locked_amount: self.lp_lock.amount,
+            locked_lp_supply: self.state.liq_pool_ext.locked_lp_supply,
+        });
+
+        Ok(())
+    }
+}
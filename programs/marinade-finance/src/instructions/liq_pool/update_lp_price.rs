@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::system_program;
+use anchor_spl::token::TokenAccount;
+
+use crate::{
+    error::MarinadeError,
+    events::liq_pool::UpdateLpPriceEvent,
+    state::{liq_pool::LiqPool, lp_price_oracle::LpPriceOracle},
+    State,
+};
+
+#[derive(Accounts)]
+pub struct UpdateLpPrice<'info> {
+    #[account(
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+
+    #[account(
+        init_if_needed,
+        payer = rent_payer,
+        space = LpPriceOracle::LEN,
+        seeds = [
+            &state.key().to_bytes(),
+            LpPriceOracle::SEED
+        ],
+        bump,
+    )]
+    pub lp_price_oracle: Box<Account<'info, LpPriceOracle>>,
+
+    #[account(
+        seeds = [
+            &state.key().to_bytes(),
+            LiqPool::SOL_LEG_SEED
+        ],
+        bump = state.liq_pool.sol_leg_bump_seed
+    )]
+    pub liq_pool_sol_leg_pda: SystemAccount<'info>,
+    #[account(address = state.liq_pool.msol_leg)]
+    pub liq_pool_msol_leg: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        owner = system_program::ID
+    )]
+    pub rent_payer: Signer<'info>,
+
+    pub clock: Sysvar<'info, Clock>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> UpdateLpPrice<'info> {
+    // fn update_lp_price()
+    // permissionless, creates the oracle account on the first call
+    pub fn process(&mut self, bump: u8) -> Result<()> {
+        self.state.liq_pool.check_no_flash_loan()?;
+
+        self.lp_price_oracle.state = self.state.key();
+        self.lp_price_oracle.bump = bump;
+        self.lp_price_oracle.update(
+            &self.state,
+            self.liq_pool_sol_leg_pda.lamports(),
+            self.liq_pool_msol_leg.amount,
+            self.clock.slot,
+        )?;
+
+        emit!(UpdateLpPriceEvent {
+            state: self.state.key(),
+            lp_price: self.lp_price_oracle.lp_price,
+            sol_leg_balance: self.lp_price_oracle.sol_leg_balance,
+            msol_leg_balance: self.lp_price_oracle.msol_leg_balance,
+            lp_supply: self.lp_price_oracle.lp_supply,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,101 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{transfer, Token, TokenAccount, Transfer};
+
+use crate::{
+    error::MarinadeError,
+    events::lp_rewards::ClaimLpRewardsEvent,
+    state::{
+        lp_rewards::{LpRewards, LpStakePosition},
+        paused_operations::PausedOperations,
+    },
+    State,
+};
+
+#[derive(Accounts)]
+pub struct ClaimLpRewards<'info> {
+    #[account(
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+
+    #[account(
+        mut,
+        has_one = state,
+        has_one = reward_vault,
+        seeds = [
+            &state.key().to_bytes(),
+            LpRewards::SEED
+        ],
+        bump = lp_rewards.bump
+    )]
+    pub lp_rewards: Box<Account<'info, LpRewards>>,
+    #[account(mut)]
+    pub reward_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        has_one = lp_rewards,
+        has_one = owner,
+        seeds = [
+            &lp_rewards.key().to_bytes(),
+            LpStakePosition::SEED,
+            &owner.key().to_bytes()
+        ],
+        bump = position.bump
+    )]
+    pub position: Box<Account<'info, LpStakePosition>>,
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        token::mint = lp_rewards.reward_mint
+    )]
+    pub transfer_to: Box<Account<'info, TokenAccount>>,
+
+    pub clock: Sysvar<'info, Clock>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> ClaimLpRewards<'info> {
+    pub fn process(&mut self) -> Result<()> {
+        self.state.check_paused(PausedOperations::LIQUIDITY)?;
+
+        self.lp_rewards.accrue(self.clock.slot)?;
+        let acc_reward_per_share = self.lp_rewards.acc_reward_per_share;
+        self.position.settle(acc_reward_per_share)?;
+
+        let amount = self.position.pending_rewards;
+        if amount > 0 {
+            // fails if the reward_vault is underfunded, the rewards stay pending until it is refilled
+            transfer(
+                CpiContext::new_with_signer(
+                    self.token_program.to_account_info(),
+                    Transfer {
+                        from: self.reward_vault.to_account_info(),
+                        to: self.transfer_to.to_account_info(),
+                        authority: self.lp_rewards.to_account_info(),
+                    },
+                    &[&[
+                        &self.state.key().to_bytes(),
+                        LpRewards::SEED,
+                        &[self.lp_rewards.bump],
+                    ]],
+                ),
+                amount,
+            )?;
+            self.position.pending_rewards = 0;
+        }
+
+        emit!(ClaimLpRewardsEvent {
+            state: self.state.key(),
+            lp_rewards: self.lp_rewards.key(),
+            owner: self.owner.key(),
+            amount,
+            staked_amount: self.position.staked_amount,
+            acc_reward_per_share,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,126 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::system_program;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::{
+    error::MarinadeError,
+    events::{lp_rewards::ConfigureLpRewardsEvent, U64ValueChange},
+    state::{lp_rewards::LpRewards, roles::Role},
+    State,
+};
+
+#[derive(Accounts)]
+pub struct ConfigureLpRewards<'info> {
+    #[account(
+        constraint = state.has_role(Role::Treasurer, treasurer_authority.key)
+            @ MarinadeError::InvalidTreasurerAuthority,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+    pub treasurer_authority: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = rent_payer,
+        space = LpRewards::LEN,
+        seeds = [
+            &state.key().to_bytes(),
+            LpRewards::SEED
+        ],
+        bump,
+    )]
+    pub lp_rewards: Box<Account<'info, LpRewards>>,
+
+    pub reward_mint: Box<Account<'info, Mint>>,
+    #[account(
+        init_if_needed,
+        payer = rent_payer,
+        token::mint = reward_mint,
+        token::authority = lp_rewards,
+        seeds = [
+            &lp_rewards.key().to_bytes(),
+            LpRewards::REWARD_VAULT_SEED
+        ],
+        bump,
+    )]
+    pub reward_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(address = state.liq_pool.lp_mint)]
+    pub lp_mint: Box<Account<'info, Mint>>,
+    #[account(
+        init_if_needed,
+        payer = rent_payer,
+        token::mint = lp_mint,
+        token::authority = lp_rewards,
+        seeds = [
+            &lp_rewards.key().to_bytes(),
+            LpRewards::LP_VAULT_SEED
+        ],
+        bump,
+    )]
+    pub lp_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        owner = system_program::ID
+    )]
+    pub rent_payer: Signer<'info>,
+
+    pub clock: Sysvar<'info, Clock>,
+    pub rent: Sysvar<'info, Rent>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> ConfigureLpRewards<'info> {
+    /// creates the LP rewards program on the first call and updates the reward stream on the next ones.
+    /// The reward tokens are funded by transferring them to the reward_vault
+    pub fn process(&mut self, reward_rate_per_slot: u64, end_slot: u64, bump: u8) -> Result<()> {
+        let slot = self.clock.slot;
+        if self.lp_rewards.state == Pubkey::default() {
+            self.lp_rewards.set_inner(LpRewards {
+                state: self.state.key(),
+                reward_mint: self.reward_mint.key(),
+                reward_vault: self.reward_vault.key(),
+                lp_vault: self.lp_vault.key(),
+                bump,
+                reward_rate_per_slot: 0,
+                end_slot: 0,
+                last_update_slot: slot,
+                acc_reward_per_share: 0,
+                total_staked: 0,
+            });
+        }
+        // the reward mint can't be changed later because the existing reward_vault
+        // is checked against the token::mint constraint
+        // accrue with the old rate before switching to the new one
+        self.lp_rewards.accrue(slot)?;
+
+        let reward_rate_per_slot_change = {
+            let old = self.lp_rewards.reward_rate_per_slot;
+            self.lp_rewards.reward_rate_per_slot = reward_rate_per_slot;
+            U64ValueChange {
+                old,
+                new: reward_rate_per_slot,
+            }
+        };
+        let end_slot_change = {
+            let old = self.lp_rewards.end_slot;
+            self.lp_rewards.end_slot = end_slot;
+            U64ValueChange { old, new: end_slot }
+        };
+
+        emit!(ConfigureLpRewardsEvent {
+            state: self.state.key(),
+            authority: self.treasurer_authority.key(),
+            slot: self.clock.slot,
+            lp_rewards: self.lp_rewards.key(),
+            reward_mint: self.reward_mint.key(),
+            reward_rate_per_slot_change,
+            end_slot_change,
+        });
+
+        Ok(())
+    }
+}
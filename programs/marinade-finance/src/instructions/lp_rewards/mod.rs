@@ -0,0 +1,9 @@
+pub mod claim_lp_rewards;
+pub mod configure_lp_rewards;
+pub mod stake_lp;
+pub mod unstake_lp;
+
+pub use claim_lp_rewards::*;
+pub use configure_lp_rewards::*;
+pub use stake_lp::*;
+pub use unstake_lp::*;
@@ -0,0 +1,114 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::system_program;
+use anchor_spl::token::{transfer, Token, TokenAccount, Transfer};
+
+use crate::{
+    checks::check_token_source_account,
+    error::MarinadeError,
+    events::lp_rewards::StakeLpEvent,
+    state::{
+        lp_rewards::{LpRewards, LpStakePosition},
+        paused_operations::PausedOperations,
+    },
+    State,
+};
+
+#[derive(Accounts)]
+pub struct StakeLp<'info> {
+    #[account(
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+
+    #[account(
+        mut,
+        has_one = state,
+        has_one = lp_vault,
+        seeds = [
+            &state.key().to_bytes(),
+            LpRewards::SEED
+        ],
+        bump = lp_rewards.bump
+    )]
+    pub lp_rewards: Box<Account<'info, LpRewards>>,
+    #[account(mut)]
+    pub lp_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = LpStakePosition::LEN,
+        seeds = [
+            &lp_rewards.key().to_bytes(),
+            LpStakePosition::SEED,
+            &owner.key().to_bytes()
+        ],
+        bump,
+    )]
+    pub position: Box<Account<'info, LpStakePosition>>,
+
+    #[account(
+        mut,
+        token::mint = state.liq_pool.lp_mint
+    )]
+    pub transfer_from: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        owner = system_program::ID
+    )]
+    pub owner: Signer<'info>, // transfer_from owner or delegate_authority, pays the position rent
+
+    pub clock: Sysvar<'info, Clock>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> StakeLp<'info> {
+    pub fn process(&mut self, amount: u64, bump: u8) -> Result<()> {
+        self.state.check_paused(PausedOperations::LIQUIDITY)?;
+        check_token_source_account(&self.transfer_from, self.owner.key, amount)
+            .map_err(|e| e.with_account_name("transfer_from"))?;
+
+        if self.position.lp_rewards == Pubkey::default() {
+            self.position.set_inner(LpStakePosition {
+                lp_rewards: self.lp_rewards.key(),
+                owner: self.owner.key(),
+                bump,
+                staked_amount: 0,
+                reward_debt: 0,
+                pending_rewards: 0,
+            });
+        }
+
+        self.lp_rewards.accrue(self.clock.slot)?;
+        let acc_reward_per_share = self.lp_rewards.acc_reward_per_share;
+        self.position.settle(acc_reward_per_share)?;
+
+        transfer(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                Transfer {
+                    from: self.transfer_from.to_account_info(),
+                    to: self.lp_vault.to_account_info(),
+                    authority: self.owner.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+        self.position.on_stake(amount, acc_reward_per_share);
+        self.lp_rewards.on_stake(amount);
+
+        emit!(StakeLpEvent {
+            state: self.state.key(),
+            lp_rewards: self.lp_rewards.key(),
+            owner: self.owner.key(),
+            amount,
+            staked_amount: self.position.staked_amount,
+            total_staked: self.lp_rewards.total_staked,
+            acc_reward_per_share,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,106 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{transfer, Token, TokenAccount, Transfer};
+
+use crate::{
+    error::MarinadeError,
+    events::lp_rewards::UnstakeLpEvent,
+    require_lte,
+    state::{
+        lp_rewards::{LpRewards, LpStakePosition},
+        paused_operations::PausedOperations,
+    },
+    State,
+};
+
+#[derive(Accounts)]
+pub struct UnstakeLp<'info> {
+    #[account(
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+
+    #[account(
+        mut,
+        has_one = state,
+        has_one = lp_vault,
+        seeds = [
+            &state.key().to_bytes(),
+            LpRewards::SEED
+        ],
+        bump = lp_rewards.bump
+    )]
+    pub lp_rewards: Box<Account<'info, LpRewards>>,
+    #[account(mut)]
+    pub lp_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        has_one = lp_rewards,
+        has_one = owner,
+        seeds = [
+            &lp_rewards.key().to_bytes(),
+            LpStakePosition::SEED,
+            &owner.key().to_bytes()
+        ],
+        bump = position.bump
+    )]
+    pub position: Box<Account<'info, LpStakePosition>>,
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        token::mint = state.liq_pool.lp_mint
+    )]
+    pub transfer_to: Box<Account<'info, TokenAccount>>,
+
+    pub clock: Sysvar<'info, Clock>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> UnstakeLp<'info> {
+    // the pending rewards are kept in the position and can be claimed later
+    pub fn process(&mut self, amount: u64) -> Result<()> {
+        self.state.check_paused(PausedOperations::LIQUIDITY)?;
+        require_lte!(
+            amount,
+            self.position.staked_amount,
+            MarinadeError::NotEnoughStakedLp
+        );
+
+        self.lp_rewards.accrue(self.clock.slot)?;
+        let acc_reward_per_share = self.lp_rewards.acc_reward_per_share;
+        self.position.settle(acc_reward_per_share)?;
+        self.position.on_unstake(amount, acc_reward_per_share);
+        self.lp_rewards.on_unstake(amount);
+
+        transfer(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                Transfer {
+                    from: self.lp_vault.to_account_info(),
+                    to: self.transfer_to.to_account_info(),
+                    authority: self.lp_rewards.to_account_info(),
+                },
+                &[&[
+                    &self.state.key().to_bytes(),
+                    LpRewards::SEED,
+                    &[self.lp_rewards.bump],
+                ]],
+            ),
+            amount,
+        )?;
+
+        emit!(UnstakeLpEvent {
+            state: self.state.key(),
+            lp_rewards: self.lp_rewards.key(),
+            owner: self.owner.key(),
+            amount,
+            staked_amount: self.position.staked_amount,
+            total_staked: self.lp_rewards.total_staked,
+            acc_reward_per_share,
+        });
+
+        Ok(())
+    }
+}
@@ -3,13 +3,23 @@ use anchor_lang::solana_program::system_program;
 
 use crate::events::management::AddValidatorEvent;
 use crate::state::validator_system::{ValidatorList, ValidatorRecord};
-use crate::{error::MarinadeError, State};
+use crate::{
+    error::MarinadeError,
+    state::{paused_operations::PausedOperations, roles::Role},
+    State,
+};
 
 #[derive(Accounts)]
 pub struct AddValidator<'info> {
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
     pub state: Account<'info, State>,
-    #[account(address = state.validator_system.manager_authority)]
+    #[account(
+        constraint = state.has_role(Role::ValidatorManager, manager_authority.key)
+            @ MarinadeError::InvalidValidatorManager
+    )]
     pub manager_authority: Signer<'info>,
     #[account(
         mut,
@@ -48,7 +58,7 @@ pub struct AddValidator<'info> {
 
 impl<'info> AddValidator<'info> {
     pub fn process(&mut self, score: u32) -> Result<()> {
-        require!(!self.state.paused, MarinadeError::ProgramIsPaused);
+        self.state.check_paused(PausedOperations::VALIDATOR_OPS)?;
 
         msg!("Add validator {}", self.validator_vote.key);
 
@@ -0,0 +1,142 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::system_program;
+use anchor_spl::stake::StakeAccount;
+
+use crate::events::management::AddValidatorPermissionlessEvent;
+use crate::state::validator_system::{ValidatorList, ValidatorRecord};
+use crate::{
+    error::MarinadeError,
+    state::{paused_operations::PausedOperations, validator_health::ValidatorHealth},
+    vote_account, State,
+};
+
+#[derive(Accounts)]
+pub struct AddValidatorPermissionless<'info> {
+    #[account(
+        mut,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+    #[account(
+        mut,
+        address = state.validator_system.validator_list.account,
+    )]
+    pub validator_list: Account<'info, ValidatorList>,
+
+    /// CHECK: parsed in code
+    pub validator_vote: UncheckedAccount<'info>,
+
+    /// CHECK: no discriminator used
+    /// by initializing this account we mark the validator as added
+    #[account(
+        init, // will ensure it is system account
+        payer = rent_payer,
+        space = 0,
+        seeds = [
+            &state.key().to_bytes(),
+            ValidatorRecord::DUPLICATE_FLAG_SEED,
+            &validator_vote.key().to_bytes(),
+        ],
+        bump,
+    )]
+    pub duplication_flag: UncheckedAccount<'info>,
+    // holds the blocklist flag
+    #[account(
+        init_if_needed,
+        payer = rent_payer,
+        space = ValidatorHealth::LEN,
+        seeds = [
+            &state.key().to_bytes(),
+            ValidatorHealth::SEED,
+            &validator_vote.key().to_bytes()
+        ],
+        bump,
+    )]
+    pub validator_health: Box<Account<'info, ValidatorHealth>>,
+    #[account(
+        mut,
+        owner = system_program::ID
+    )]
+    pub rent_payer: Signer<'info>,
+
+    pub clock: Sysvar<'info, Clock>,
+    pub rent: Sysvar<'info, Rent>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> AddValidatorPermissionless<'info> {
+    // fn add_validator_permissionless()
+    // adds the validator with score 0 when its commission is not above State::max_commission,
+    // it is not flagged and the activated stake of `stake_accounts` reaches State::min_validator_stake.
+    // The stake accounts are sorted by address so none of them is counted twice
+    pub fn process(&mut self, stake_accounts: &[AccountInfo<'info>], bump: u8) -> Result<()> {
+        self.state.check_paused(PausedOperations::VALIDATOR_OPS)?;
+
+        if self.validator_health.state == Pubkey::default() {
+            self.validator_health.set_inner(ValidatorHealth::new(
+                self.state.key(),
+                self.validator_vote.key(),
+                bump,
+                self.clock.epoch,
+            ));
+        }
+        if self.validator_health.is_flagged() {
+            return err!(MarinadeError::ValidatorIsFlagged)
+                .map_err(|e| e.with_account_name("validator_health"));
+        }
+
+        let commission = vote_account::commission(&self.validator_vote)?;
+        require_lte!(
+            commission,
+            self.state.max_commission,
+            MarinadeError::ValidatorCommissionIsTooHigh
+        );
+
+        let mut activated_stake: u64 = 0;
+        let mut last_stake_account = Pubkey::default();
+        for stake_account_info in stake_accounts {
+            require_gt!(
+                *stake_account_info.key,
+                last_stake_account,
+                MarinadeError::UnsortedStakeAccounts
+            );
+            last_stake_account = *stake_account_info.key;
+            let stake_account = Account::<StakeAccount>::try_from(stake_account_info)?;
+            if let Some(delegation) = stake_account.delegation() {
+                // activated before this epoch and not deactivating
+                if delegation.voter_pubkey == self.validator_vote.key()
+                    && delegation.activation_epoch < self.clock.epoch
+                    && delegation.deactivation_epoch == std::u64::MAX
+                {
+                    activated_stake = activated_stake.saturating_add(delegation.stake);
+                }
+            }
+        }
+        require_gte!(
+            activated_stake,
+            self.state.min_validator_stake,
+            MarinadeError::NotEnoughValidatorStake
+        );
+
+        msg!("Add validator {}", self.validator_vote.key);
+        let state_address = self.state.key();
+        self.state.validator_system.add(
+            &mut self.validator_list.to_account_info().data.borrow_mut(),
+            self.validator_vote.key(),
+            0,
+            &state_address,
+            self.duplication_flag.key,
+        )?;
+
+        emit!(AddValidatorPermissionlessEvent {
+            state: self.state.key(),
+            validator: self.validator_vote.key(),
+            index: self.state.validator_system.validator_count() - 1,
+            commission,
+            activated_stake,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,106 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::system_program;
+
+use crate::{
+    error::MarinadeError,
+    events::management::CheckValidatorCommissionEvent,
+    state::{
+        paused_operations::PausedOperations, validator_health::ValidatorHealth,
+        validator_system::ValidatorList,
+    },
+    vote_account, State,
+};
+
+#[derive(Accounts)]
+pub struct CheckValidatorCommission<'info> {
+    #[account(
+        mut,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+    #[account(
+        mut,
+        address = state.validator_system.validator_list.account,
+    )]
+    pub validator_list: Account<'info, ValidatorList>,
+    /// CHECK: parsed in code
+    pub validator_vote: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = rent_payer,
+        space = ValidatorHealth::LEN,
+        seeds = [
+            &state.key().to_bytes(),
+            ValidatorHealth::SEED,
+            &validator_vote.key().to_bytes()
+        ],
+        bump,
+    )]
+    pub validator_health: Box<Account<'info, ValidatorHealth>>,
+
+    #[account(
+        mut,
+        owner = system_program::ID
+    )]
+    pub rent_payer: Signer<'info>,
+
+    pub clock: Sysvar<'info, Clock>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CheckValidatorCommission<'info> {
+    // fn check_validator_commission()
+    // permissionless, flags the validator and sets its score to 0 when its commission is above
+    // State::max_commission, clears the flag when it is not (the score is restored by the validator manager)
+    pub fn process(&mut self, validator_index: u32, bump: u8) -> Result<()> {
+        self.state.check_paused(PausedOperations::VALIDATOR_OPS)?;
+
+        if self.validator_health.state == Pubkey::default() {
+            self.validator_health.set_inner(ValidatorHealth::new(
+                self.state.key(),
+                self.validator_vote.key(),
+                bump,
+                self.clock.epoch,
+            ));
+        }
+
+        let commission = vote_account::commission(&self.validator_vote)?;
+        let flagged = commission > self.state.max_commission;
+        let validator_list = self.validator_list.to_account_info();
+        let validator_list_data = &mut validator_list.data.borrow_mut();
+        let old_score = if flagged {
+            self.state.validator_system.set_score(
+                validator_list_data,
+                validator_index,
+                self.validator_vote.key,
+                0,
+            )?
+        } else {
+            self.state
+                .validator_system
+                .get_checked(
+                    validator_list_data,
+                    validator_index,
+                    self.validator_vote.key,
+                )?
+                .score
+        };
+
+        self.validator_health
+            .set_flag(ValidatorHealth::COMMISSION_FLAG, flagged);
+        self.validator_health.commission = commission;
+        self.validator_health.last_check_epoch = self.clock.epoch;
+
+        emit!(CheckValidatorCommissionEvent {
+            state: self.state.key(),
+            validator: self.validator_vote.key(),
+            index: validator_index,
+            commission,
+            max_commission: self.state.max_commission,
+            flagged,
+            old_score,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,113 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::system_program;
+
+use crate::{
+    error::MarinadeError,
+    events::management::CheckValidatorDelinquencyEvent,
+    state::{
+        paused_operations::PausedOperations, validator_health::ValidatorHealth,
+        validator_system::ValidatorList,
+    },
+    vote_account, State,
+};
+
+#[derive(Accounts)]
+pub struct CheckValidatorDelinquency<'info> {
+    #[account(
+        mut,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+    #[account(
+        mut,
+        address = state.validator_system.validator_list.account,
+    )]
+    pub validator_list: Account<'info, ValidatorList>,
+    /// CHECK: parsed in code
+    pub validator_vote: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = rent_payer,
+        space = ValidatorHealth::LEN,
+        seeds = [
+            &state.key().to_bytes(),
+            ValidatorHealth::SEED,
+            &validator_vote.key().to_bytes()
+        ],
+        bump,
+    )]
+    pub validator_health: Box<Account<'info, ValidatorHealth>>,
+
+    #[account(
+        mut,
+        owner = system_program::ID
+    )]
+    pub rent_payer: Signer<'info>,
+
+    pub clock: Sysvar<'info, Clock>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CheckValidatorDelinquency<'info> {
+    // fn check_validator_delinquency()
+    // permissionless, flags the validator and sets its score to 0 when its vote credits have not grown
+    // for State::delinquent_epochs, clears the flag when they grow again
+    pub fn process(&mut self, validator_index: u32, bump: u8) -> Result<()> {
+        self.state.check_paused(PausedOperations::VALIDATOR_OPS)?;
+
+        if self.validator_health.state == Pubkey::default() {
+            self.validator_health.set_inner(ValidatorHealth::new(
+                self.state.key(),
+                self.validator_vote.key(),
+                bump,
+                self.clock.epoch,
+            ));
+        }
+
+        let credits = vote_account::credits(&self.validator_vote)?;
+        if credits > self.validator_health.credits {
+            self.validator_health.credits = credits;
+            self.validator_health.credits_epoch = self.clock.epoch;
+        }
+        let stalled_epochs = self.clock.epoch - self.validator_health.credits_epoch;
+        let flagged =
+            self.state.delinquent_epochs > 0 && stalled_epochs >= self.state.delinquent_epochs;
+
+        let validator_list = self.validator_list.to_account_info();
+        let validator_list_data = &mut validator_list.data.borrow_mut();
+        let old_score = if flagged {
+            self.state.validator_system.set_score(
+                validator_list_data,
+                validator_index,
+                self.validator_vote.key,
+                0,
+            )?
+        } else {
+            self.state
+                .validator_system
+                .get_checked(
+                    validator_list_data,
+                    validator_index,
+                    self.validator_vote.key,
+                )?
+                .score
+        };
+
+        self.validator_health
+            .set_flag(ValidatorHealth::DELINQUENT_FLAG, flagged);
+        self.validator_health.last_check_epoch = self.clock.epoch;
+
+        emit!(CheckValidatorDelinquencyEvent {
+            state: self.state.key(),
+            validator: self.validator_vote.key(),
+            index: validator_index,
+            credits,
+            stalled_epochs,
+            delinquent_epochs: self.state.delinquent_epochs,
+            flagged,
+            old_score,
+        });
+
+        Ok(())
+    }
+}
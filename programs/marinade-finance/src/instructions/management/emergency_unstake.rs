@@ -2,7 +2,10 @@ use crate::{
     checks::check_stake_amount_and_validator,
     error::MarinadeError,
     state::{
+        paused_operations::PausedOperations,
+        roles::Role,
         stake_system::{StakeList, StakeSystem},
+        validator_health::ValidatorHealth,
         validator_system::ValidatorList,
     },
     State,
@@ -13,12 +16,12 @@ use anchor_spl::stake::{deactivate_stake, DeactivateStake, Stake, StakeAccount};
 
 #[derive(Accounts)]
 pub struct EmergencyUnstake<'info> {
-    #[account(mut)]
-    pub state: Account<'info, State>,
     #[account(
-        address = state.validator_system.manager_authority
-            @ MarinadeError::InvalidValidatorManager
+        mut,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
     )]
+    pub state: Account<'info, State>,
+    // any signer when the validator is flagged in validator_health (quarantine excluded)
     pub validator_manager_authority: Signer<'info>,
     #[account(
         mut,
@@ -45,11 +48,14 @@ pub struct EmergencyUnstake<'info> {
     pub clock: Sysvar<'info, Clock>,
 
     pub stake_program: Program<'info, Stake>,
+
+    #[account(has_one = state)]
+    pub validator_health: Option<Box<Account<'info, ValidatorHealth>>>,
 }
 
 impl<'info> EmergencyUnstake<'info> {
     pub fn process(&mut self, stake_index: u32, validator_index: u32) -> Result<()> {
-        require!(!self.state.paused, MarinadeError::ProgramIsPaused);
+        self.state.check_paused(PausedOperations::VALIDATOR_OPS)?;
 
         let mut stake = self.state.stake_system.get_checked(
             &self.stake_list.to_account_info().data.as_ref().borrow(),
@@ -62,6 +68,24 @@ impl<'info> EmergencyUnstake<'info> {
             validator_index,
         )?;
 
+        let flagged = if let Some(validator_health) = &self.validator_health {
+            require_keys_eq!(
+                validator_health.validator,
+                validator.validator_account,
+                MarinadeError::WrongValidatorHealth
+            );
+            validator_health.is_emergency_unstakable()
+        } else {
+            false
+        };
+        require!(
+            flagged
+                || self
+                    .state
+                    .has_role(Role::ValidatorManager, self.validator_manager_authority.key),
+            MarinadeError::InvalidValidatorManager
+        );
+
         // One more level of protection: need to run setScore(0) before this. I don't know is it really a good idea
         require_eq!(
             validator.score,
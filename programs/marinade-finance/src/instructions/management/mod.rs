@@ -1,11 +1,31 @@
 pub mod add_validator;
+pub mod add_validator_permissionless;
+pub mod check_validator_commission;
+pub mod check_validator_delinquency;
 pub mod emergency_unstake;
 pub mod partial_unstake;
+pub mod quarantine_validator;
+pub mod redelegate_stake;
 pub mod remove_validator;
+pub mod set_validator_blocked;
+pub mod set_validator_metadata;
+pub mod set_validator_quarantined;
 pub mod set_validator_score;
+pub mod split_stake_for_withdraw;
+pub mod update_validator_scores;
 
 pub use add_validator::*;
+pub use add_validator_permissionless::*;
+pub use check_validator_commission::*;
+pub use check_validator_delinquency::*;
 pub use emergency_unstake::*;
 pub use partial_unstake::*;
+pub use quarantine_validator::*;
+pub use redelegate_stake::*;
 pub use remove_validator::*;
+pub use set_validator_blocked::*;
+pub use set_validator_metadata::*;
+pub use set_validator_quarantined::*;
 pub use set_validator_score::*;
+pub use split_stake_for_withdraw::*;
+pub use update_validator_scores::*;
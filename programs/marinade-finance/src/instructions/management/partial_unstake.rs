@@ -2,6 +2,8 @@ use crate::{
     checks::check_stake_amount_and_validator,
     error::MarinadeError,
     state::{
+        paused_operations::PausedOperations,
+        roles::Role,
         stake_system::{StakeList, StakeSystem},
         validator_system::ValidatorList,
     },
@@ -19,10 +21,13 @@ use anchor_spl::stake::{
 
 #[derive(Accounts)]
 pub struct PartialUnstake<'info> {
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
     pub state: Box<Account<'info, State>>,
     #[account(
-        address = state.validator_system.manager_authority
+        constraint = state.has_role(Role::ValidatorManager, validator_manager_authority.key)
             @ MarinadeError::InvalidValidatorManager
     )]
     pub validator_manager_authority: Signer<'info>,
@@ -86,7 +91,7 @@ impl<'info> PartialUnstake<'info> {
         validator_index: u32,
         desired_unstake_amount: u64,
     ) -> Result<()> {
-        require!(!self.state.paused, MarinadeError::ProgramIsPaused);
+        self.state.check_paused(PausedOperations::VALIDATOR_OPS)?;
 
         assert!(
             desired_unstake_amount >= self.state.stake_system.min_stake,
@@ -106,7 +111,8 @@ impl<'info> PartialUnstake<'info> {
 
         // check the account is not already in emergency_unstake
         if stake.is_emergency_unstaking != 0 {
-            return err!(MarinadeError::StakeAccountIsEmergencyUnstaking);
+            return err!(MarinadeError::StakeAccountIsEmergencyUnstaking)
+                .map_err(|e| e.with_account_name("stake_account"));
         }
 
         // check amount currently_staked in this account
@@ -118,7 +124,9 @@ impl<'info> PartialUnstake<'info> {
         )?;
 
         // compute total required stake delta (i128, must be negative)
-        let total_stake_delta_i128 = self.state.stake_delta(self.reserve_pda.lamports());
+        let total_stake_delta_i128 = self
+            .state
+            .stake_delta(self.reserve_pda.lamports(), self.clock.epoch);
         // compute total target stake (current total active stake +/- delta)
         let total_stake_target_i128 =
             self.state.validator_system.total_active_balance as i128 + total_stake_delta_i128;
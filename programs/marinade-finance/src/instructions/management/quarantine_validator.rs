@@ -0,0 +1,104 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::system_program;
+
+use crate::{
+    error::MarinadeError,
+    events::management::QuarantineValidatorEvent,
+    state::{
+        paused_operations::PausedOperations, validator_health::ValidatorHealth,
+        validator_metadata::ValidatorMetadata, validator_system::ValidatorList,
+    },
+    vote_account, State,
+};
+
+#[derive(Accounts)]
+pub struct QuarantineValidator<'info> {
+    #[account(
+        mut,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+    #[account(
+        mut,
+        address = state.validator_system.validator_list.account,
+    )]
+    pub validator_list: Account<'info, ValidatorList>,
+    /// CHECK: parsed in code
+    pub validator_vote: UncheckedAccount<'info>,
+    #[account(
+        has_one = state,
+        seeds = [
+            &state.key().to_bytes(),
+            ValidatorMetadata::SEED,
+            &validator_vote.key().to_bytes()
+        ],
+        bump = validator_metadata.bump,
+    )]
+    pub validator_metadata: Box<Account<'info, ValidatorMetadata>>,
+    #[account(
+        init_if_needed,
+        payer = rent_payer,
+        space = ValidatorHealth::LEN,
+        seeds = [
+            &state.key().to_bytes(),
+            ValidatorHealth::SEED,
+            &validator_vote.key().to_bytes()
+        ],
+        bump,
+    )]
+    pub validator_health: Box<Account<'info, ValidatorHealth>>,
+
+    #[account(
+        mut,
+        owner = system_program::ID
+    )]
+    pub rent_payer: Signer<'info>,
+
+    pub clock: Sysvar<'info, Clock>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> QuarantineValidator<'info> {
+    // fn quarantine_validator()
+    // permissionless, quarantines a validator whose vote account node identity is no longer
+    // the one that set its ValidatorMetadata, a sign of a compromised validator.
+    // Only the validator manager lifts the quarantine
+    pub fn process(&mut self, validator_index: u32, bump: u8) -> Result<()> {
+        self.state.check_paused(PausedOperations::VALIDATOR_OPS)?;
+
+        let identity = vote_account::node_pubkey(&self.validator_vote)?;
+        require_keys_neq!(
+            identity,
+            self.validator_metadata.identity,
+            MarinadeError::ValidatorIdentityNotChanged
+        );
+
+        if self.validator_health.state == Pubkey::default() {
+            self.validator_health.set_inner(ValidatorHealth::new(
+                self.state.key(),
+                self.validator_vote.key(),
+                bump,
+                self.clock.epoch,
+            ));
+        }
+        let old_score = self.state.validator_system.set_score(
+            &mut self.validator_list.to_account_info().data.borrow_mut(),
+            validator_index,
+            self.validator_vote.key,
+            0,
+        )?;
+        self.validator_health
+            .set_flag(ValidatorHealth::QUARANTINED_FLAG, true);
+
+        emit!(QuarantineValidatorEvent {
+            state: self.state.key(),
+            validator: self.validator_vote.key(),
+            index: validator_index,
+            identity,
+            metadata_identity: self.validator_metadata.identity,
+            old_score,
+        });
+
+        Ok(())
+    }
+}
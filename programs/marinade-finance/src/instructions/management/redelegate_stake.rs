@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::MarinadeError, instructions::crank::redelegate::ReDelegate, state::roles::Role,
+};
+
+#[derive(Accounts)]
+pub struct RedelegateStake<'info> {
+    pub redelegate: ReDelegate<'info>,
+    #[account(
+        constraint = redelegate.state.has_role(Role::ValidatorManager, validator_manager_authority.key)
+            @ MarinadeError::InvalidValidatorManager
+    )]
+    pub validator_manager_authority: Signer<'info>,
+}
+
+impl<'info> RedelegateStake<'info> {
+    // fn redelegate_stake()
+    // set rotation: moves the stake of a validator with score 0 to the dest validator at any time
+    // in the epoch, with the native stake redelegate the stake is not left undelegated for an epoch
+    pub fn process(
+        &mut self,
+        stake_index: u32,
+        source_validator_index: u32,
+        dest_validator_index: u32,
+    ) -> Result<()> {
+        let source_validator = self.redelegate.state.validator_system.get(
+            &self
+                .redelegate
+                .validator_list
+                .to_account_info()
+                .data
+                .as_ref()
+                .borrow(),
+            source_validator_index,
+        )?;
+        require_eq!(
+            source_validator.score,
+            0,
+            MarinadeError::RedelegatingFromNonZeroScoredValidator
+        );
+
+        self.redelegate
+            .redelegate(stake_index, source_validator_index, dest_validator_index)
+    }
+}
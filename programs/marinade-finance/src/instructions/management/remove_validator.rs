@@ -3,7 +3,11 @@ use anchor_lang::{prelude::*, system_program};
 use crate::{
     error::MarinadeError,
     events::management::RemoveValidatorEvent,
-    state::validator_system::{ValidatorList, ValidatorRecord},
+    state::{
+        paused_operations::PausedOperations,
+        roles::Role,
+        validator_system::{ValidatorList, ValidatorRecord},
+    },
     State, ID,
 };
 
@@ -12,11 +16,12 @@ use crate::{
 pub struct RemoveValidator<'info> {
     #[account(
         mut,
-        has_one = operational_sol_account
+        has_one = operational_sol_account,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
     )]
     pub state: Account<'info, State>,
     #[account(
-        address = state.validator_system.manager_authority
+        constraint = state.has_role(Role::ValidatorManager, manager_authority.key)
             @ MarinadeError::InvalidValidatorManager
     )]
     pub manager_authority: Signer<'info>,
@@ -45,7 +50,7 @@ pub struct RemoveValidator<'info> {
 
 impl<'info> RemoveValidator<'info> {
     pub fn process(&mut self, index: u32, validator_vote: Pubkey) -> Result<()> {
-        require!(!self.state.paused, MarinadeError::ProgramIsPaused);
+        self.state.check_paused(PausedOperations::VALIDATOR_OPS)?;
 
         let validator = self.state.validator_system.get_checked(
             &self.validator_list.to_account_info().data.borrow(),
@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::system_program;
+
+use crate::{
+    error::MarinadeError,
+    events::management::SetValidatorBlockedEvent,
+    state::{paused_operations::PausedOperations, roles::Role, validator_health::ValidatorHealth},
+    State,
+};
+
+#[derive(Accounts)]
+pub struct SetValidatorBlocked<'info> {
+    #[account(
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+    #[account(
+        constraint = state.has_role(Role::ValidatorManager, manager_authority.key)
+            @ MarinadeError::InvalidValidatorManager
+    )]
+    pub manager_authority: Signer<'info>,
+
+    /// CHECK: only the address is used
+    pub validator_vote: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = rent_payer,
+        space = ValidatorHealth::LEN,
+        seeds = [
+            &state.key().to_bytes(),
+            ValidatorHealth::SEED,
+            &validator_vote.key().to_bytes()
+        ],
+        bump,
+    )]
+    pub validator_health: Box<Account<'info, ValidatorHealth>>,
+
+    #[account(
+        mut,
+        owner = system_program::ID
+    )]
+    pub rent_payer: Signer<'info>,
+
+    pub clock: Sysvar<'info, Clock>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SetValidatorBlocked<'info> {
+    // fn set_validator_blocked()
+    // a blocked validator can't be added by add_validator_permissionless
+    pub fn process(&mut self, blocked: bool, bump: u8) -> Result<()> {
+        self.state.check_paused(PausedOperations::VALIDATOR_OPS)?;
+
+        if self.validator_health.state == Pubkey::default() {
+            self.validator_health.set_inner(ValidatorHealth::new(
+                self.state.key(),
+                self.validator_vote.key(),
+                bump,
+                self.clock.epoch,
+            ));
+        }
+        self.validator_health
+            .set_flag(ValidatorHealth::BLOCKED_FLAG, blocked);
+
+        emit!(SetValidatorBlockedEvent {
+            state: self.state.key(),
+            authority: self.manager_authority.key(),
+            slot: self.clock.slot,
+            validator: self.validator_vote.key(),
+            blocked,
+        });
+
+        Ok(())
+    }
+}
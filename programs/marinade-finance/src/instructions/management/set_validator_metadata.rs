@@ -0,0 +1,86 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::system_program;
+
+use crate::{
+    error::MarinadeError,
+    events::management::SetValidatorMetadataEvent,
+    state::{paused_operations::PausedOperations, validator_metadata::ValidatorMetadata},
+    vote_account, State,
+};
+
+#[derive(Accounts)]
+pub struct SetValidatorMetadata<'info> {
+    #[account(
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+
+    /// CHECK: parsed in code
+    pub validator_vote: UncheckedAccount<'info>,
+    // node_pubkey of validator_vote
+    pub identity: Signer<'info>,
+    #[account(
+        init_if_needed,
+        payer = rent_payer,
+        space = ValidatorMetadata::LEN,
+        seeds = [
+            &state.key().to_bytes(),
+            ValidatorMetadata::SEED,
+            &validator_vote.key().to_bytes()
+        ],
+        bump,
+    )]
+    pub validator_metadata: Box<Account<'info, ValidatorMetadata>>,
+
+    #[account(
+        mut,
+        owner = system_program::ID
+    )]
+    pub rent_payer: Signer<'info>,
+
+    pub clock: Sysvar<'info, Clock>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SetValidatorMetadata<'info> {
+    // fn set_validator_metadata()
+    pub fn process(
+        &mut self,
+        name_hash: [u8; 32],
+        website_hash: [u8; 32],
+        datacenter_hash: [u8; 32],
+        asn: u32,
+        bump: u8,
+    ) -> Result<()> {
+        self.state.check_paused(PausedOperations::VALIDATOR_OPS)?;
+        require_keys_eq!(
+            vote_account::node_pubkey(&self.validator_vote)?,
+            self.identity.key(),
+            MarinadeError::InvalidValidatorIdentity
+        );
+
+        self.validator_metadata.set_inner(ValidatorMetadata {
+            state: self.state.key(),
+            validator: self.validator_vote.key(),
+            bump,
+            identity: self.identity.key(),
+            name_hash,
+            website_hash,
+            datacenter_hash,
+            asn,
+            updated_epoch: self.clock.epoch,
+        });
+
+        emit!(SetValidatorMetadataEvent {
+            state: self.state.key(),
+            validator: self.validator_vote.key(),
+            identity: self.identity.key(),
+            name_hash,
+            website_hash,
+            datacenter_hash,
+            asn,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,103 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::system_program;
+
+use crate::{
+    error::MarinadeError,
+    events::management::SetValidatorQuarantinedEvent,
+    state::{
+        paused_operations::PausedOperations, roles::Role, validator_health::ValidatorHealth,
+        validator_system::ValidatorList,
+    },
+    State,
+};
+
+#[derive(Accounts)]
+pub struct SetValidatorQuarantined<'info> {
+    #[account(
+        mut,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+    #[account(
+        constraint = state.has_role(Role::ValidatorManager, manager_authority.key)
+            @ MarinadeError::InvalidValidatorManager
+    )]
+    pub manager_authority: Signer<'info>,
+    #[account(
+        mut,
+        address = state.validator_system.validator_list.account,
+    )]
+    pub validator_list: Account<'info, ValidatorList>,
+
+    /// CHECK: only the address is used
+    pub validator_vote: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = rent_payer,
+        space = ValidatorHealth::LEN,
+        seeds = [
+            &state.key().to_bytes(),
+            ValidatorHealth::SEED,
+            &validator_vote.key().to_bytes()
+        ],
+        bump,
+    )]
+    pub validator_health: Box<Account<'info, ValidatorHealth>>,
+
+    #[account(
+        mut,
+        owner = system_program::ID
+    )]
+    pub rent_payer: Signer<'info>,
+
+    pub clock: Sysvar<'info, Clock>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SetValidatorQuarantined<'info> {
+    // fn set_validator_quarantined()
+    // the quarantine sets the score to 0, lifting it does not restore the score
+    pub fn process(&mut self, validator_index: u32, quarantined: bool, bump: u8) -> Result<()> {
+        self.state.check_paused(PausedOperations::VALIDATOR_OPS)?;
+
+        if self.validator_health.state == Pubkey::default() {
+            self.validator_health.set_inner(ValidatorHealth::new(
+                self.state.key(),
+                self.validator_vote.key(),
+                bump,
+                self.clock.epoch,
+            ));
+        }
+        let validator_list = self.validator_list.to_account_info();
+        let validator_list_data = &mut validator_list.data.borrow_mut();
+        let old_score = if quarantined {
+            self.state.validator_system.set_score(
+                validator_list_data,
+                validator_index,
+                self.validator_vote.key,
+                0,
+            )?
+        } else {
+            self.state
+                .validator_system
+                .get_checked(
+                    validator_list_data,
+                    validator_index,
+                    self.validator_vote.key,
+                )?
+                .score
+        };
+        self.validator_health
+            .set_flag(ValidatorHealth::QUARANTINED_FLAG, quarantined);
+
+        emit!(SetValidatorQuarantinedEvent {
+            state: self.state.key(),
+            validator: self.validator_vote.key(),
+            index: validator_index,
+            quarantined,
+            old_score,
+        });
+
+        Ok(())
+    }
+}
@@ -3,16 +3,19 @@ use anchor_lang::prelude::*;
 use crate::{
     error::MarinadeError,
     events::{management::SetValidatorScoreEvent, U32ValueChange},
-    state::validator_system::ValidatorList,
+    state::{paused_operations::PausedOperations, roles::Role, validator_system::ValidatorList},
     State,
 };
 
 #[derive(Accounts)]
 pub struct SetValidatorScore<'info> {
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
     pub state: Account<'info, State>,
     #[account(
-        address = state.validator_system.manager_authority
+        constraint = state.has_role(Role::ValidatorManager, manager_authority.key)
             @ MarinadeError::InvalidValidatorManager
     )]
     pub manager_authority: Signer<'info>,
@@ -25,26 +28,15 @@ pub struct SetValidatorScore<'info> {
 
 impl<'info> SetValidatorScore<'info> {
     pub fn process(&mut self, index: u32, validator_vote: Pubkey, score: u32) -> Result<()> {
-        require!(!self.state.paused, MarinadeError::ProgramIsPaused);
-
-        let mut validator = self.state.validator_system.get_checked(
-            &self.validator_list.to_account_info().data.borrow(),
-            index,
-            &validator_vote,
-        )?;
+        self.state.check_paused(PausedOperations::VALIDATOR_OPS)?;
 
-        self.state.validator_system.total_validator_score -= validator.score;
-        let score_change = {
-            let old = validator.score;
-            validator.score = score;
-            U32ValueChange { old, new: score }
-        };
-        self.state.validator_system.total_validator_score += score;
-        self.state.validator_system.set(
+        let old = self.state.validator_system.set_score(
             &mut self.validator_list.to_account_info().data.borrow_mut(),
             index,
-            validator,
+            &validator_vote,
+            score,
         )?;
+        let score_change = U32ValueChange { old, new: score };
 
         emit!(SetValidatorScoreEvent {
             state: self.state.key(),
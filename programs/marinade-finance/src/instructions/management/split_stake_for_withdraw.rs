@@ -0,0 +1,228 @@
+use crate::{
+    checks::check_stake_amount_and_validator,
+    error::MarinadeError,
+    events::management::SplitStakeForWithdrawEvent,
+    state::{
+        paused_operations::PausedOperations,
+        roles::Role,
+        stake_system::{StakeList, StakeSystem},
+        validator_system::ValidatorList,
+    },
+    State,
+};
+use std::convert::TryFrom;
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    program::invoke_signed, stake, stake::state::StakeState, system_program,
+};
+use anchor_spl::stake::{deactivate_stake, DeactivateStake, Stake, StakeAccount};
+
+#[derive(Accounts)]
+pub struct SplitStakeForWithdraw<'info> {
+    #[account(
+        mut,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+    #[account(
+        constraint = state.has_role(Role::ValidatorManager, validator_manager_authority.key)
+            @ MarinadeError::InvalidValidatorManager
+    )]
+    pub validator_manager_authority: Signer<'info>,
+    #[account(
+        mut,
+        address = state.validator_system.validator_list.account,
+    )]
+    pub validator_list: Account<'info, ValidatorList>,
+    #[account(
+        mut,
+        address = state.stake_system.stake_list.account,
+    )]
+    pub stake_list: Account<'info, StakeList>,
+    #[account(mut)]
+    pub stake_account: Box<Account<'info, StakeAccount>>,
+    /// CHECK: PDA
+    #[account(
+        seeds = [
+            &state.key().to_bytes(),
+            StakeSystem::STAKE_DEPOSIT_SEED
+        ],
+        bump = state.stake_system.stake_deposit_bump_seed
+    )]
+    pub stake_deposit_authority: UncheckedAccount<'info>,
+    // Readonly. For stake delta calculation
+    #[account(
+        seeds = [
+            &state.key().to_bytes(),
+            State::RESERVE_SEED
+        ],
+        bump = state.reserve_bump_seed
+    )]
+    pub reserve_pda: SystemAccount<'info>,
+
+    #[account(
+        init,
+        payer = split_stake_rent_payer,
+        space = std::mem::size_of::<StakeState>(),
+        owner = stake::program::ID,
+    )]
+    pub split_stake_account: Account<'info, StakeAccount>,
+    #[account(
+        mut,
+        owner = system_program::ID
+    )]
+    pub split_stake_rent_payer: Signer<'info>,
+
+    pub clock: Sysvar<'info, Clock>,
+
+    pub system_program: Program<'info, System>,
+    pub stake_program: Program<'info, Stake>,
+}
+
+impl<'info> SplitStakeForWithdraw<'info> {
+    // fn split_stake_for_withdraw()
+    // splits `amount` from an active stake account and deactivates it for the delayed unstake
+    // tickets. Unlike deactivate_stake it can run at any time in the epoch and the amount is
+    // chosen by the bot to match the ticket demand, at most the negative stake delta.
+    // The split account is cooling down as the ones from deactivate_stake
+    pub fn process(&mut self, stake_index: u32, validator_index: u32, amount: u64) -> Result<()> {
+        self.state.check_paused(PausedOperations::VALIDATOR_OPS)?;
+
+        require_gte!(
+            amount,
+            self.state.stake_system.min_stake,
+            MarinadeError::SplitAmountIsTooLow
+        );
+
+        let mut validator = self.state.validator_system.get(
+            &self.validator_list.to_account_info().data.as_ref().borrow(),
+            validator_index,
+        )?;
+        let mut stake = self.state.stake_system.get_checked(
+            &self.stake_list.to_account_info().data.as_ref().borrow(),
+            stake_index,
+            self.stake_account.to_account_info().key,
+        )?;
+        let last_update_stake_delegation = stake.last_update_delegated_lamports;
+        require_eq!(
+            stake.is_emergency_unstaking,
+            0,
+            MarinadeError::StakeAccountIsEmergencyUnstaking
+        );
+        check_stake_amount_and_validator(
+            &self.stake_account,
+            stake.last_update_delegated_lamports,
+            &validator.validator_account,
+        )?;
+
+        // unstake demand of the tickets not covered by the reserve and cooling down stake
+        let total_stake_delta_i128 = self
+            .state
+            .stake_delta(self.reserve_pda.lamports(), self.clock.epoch);
+        require_lt!(
+            total_stake_delta_i128,
+            0,
+            MarinadeError::UnstakingOnPositiveDelta
+        );
+        let total_unstake_delta =
+            u64::try_from(-total_stake_delta_i128).expect("Unstake delta overflow");
+        require_lte!(
+            amount,
+            total_unstake_delta,
+            MarinadeError::SplitAmountAboveUnstakeDelta
+        );
+        // the whole account is deactivated by deactivate_stake
+        require_gte!(
+            stake.last_update_delegated_lamports.saturating_sub(amount),
+            self.state.stake_system.min_stake,
+            MarinadeError::StakeAccountRemainderTooLow
+        );
+
+        self.state.stake_system.add(
+            &mut self.stake_list.to_account_info().data.as_ref().borrow_mut(),
+            &self.split_stake_account.key(),
+            amount,
+            &self.clock,
+            0, // is_emergency_unstaking? no
+        )?;
+
+        let split_instruction = stake::instruction::split(
+            self.stake_account.to_account_info().key,
+            self.stake_deposit_authority.key,
+            amount,
+            &self.split_stake_account.key(),
+        )
+        .last()
+        .unwrap()
+        .clone();
+        invoke_signed(
+            &split_instruction,
+            &[
+                self.stake_program.to_account_info(),
+                self.stake_account.to_account_info(),
+                self.split_stake_account.to_account_info(),
+                self.stake_deposit_authority.to_account_info(),
+            ],
+            &[&[
+                &self.state.key().to_bytes(),
+                StakeSystem::STAKE_DEPOSIT_SEED,
+                &[self.state.stake_system.stake_deposit_bump_seed],
+            ]],
+        )?;
+
+        deactivate_stake(CpiContext::new_with_signer(
+            self.stake_program.to_account_info(),
+            DeactivateStake {
+                stake: self.split_stake_account.to_account_info(),
+                staker: self.stake_deposit_authority.to_account_info(),
+                clock: self.clock.to_account_info(),
+            },
+            &[&[
+                &self.state.key().to_bytes(),
+                StakeSystem::STAKE_DEPOSIT_SEED,
+                &[self.state.stake_system.stake_deposit_bump_seed],
+            ]],
+        ))?;
+
+        stake.last_update_delegated_lamports -= amount;
+        validator.active_balance -= amount;
+        // tickets ordered after this wait for the next epoch, as after deactivate_stake
+        self.state.stake_system.last_stake_delta_epoch = self.clock.epoch;
+        // move from total_active_balance -> total_cooling_down
+        self.state.validator_system.total_active_balance -= amount;
+        self.state.stake_system.delayed_unstake_cooling_down += amount;
+
+        self.state.stake_system.set(
+            &mut self.stake_list.to_account_info().data.as_ref().borrow_mut(),
+            stake_index,
+            stake,
+        )?;
+        self.state.validator_system.set(
+            &mut self
+                .validator_list
+                .to_account_info()
+                .data
+                .as_ref()
+                .borrow_mut(),
+            validator_index,
+            validator,
+        )?;
+
+        emit!(SplitStakeForWithdrawEvent {
+            state: self.state.key(),
+            epoch: self.clock.epoch,
+            stake_index,
+            stake_account: self.stake_account.key(),
+            last_update_stake_delegation,
+            split_stake_account: self.split_stake_account.key(),
+            split_stake_index: self.state.stake_system.stake_count() - 1,
+            validator_index,
+            validator_vote: validator.validator_account,
+            total_unstake_delta,
+            amount,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::MarinadeError,
+    events::{management::SetValidatorScoreEvent, U32ValueChange},
+    state::{paused_operations::PausedOperations, roles::Role, validator_system::ValidatorList},
+    State,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub struct ValidatorScore {
+    pub index: u32,
+    pub validator_vote: Pubkey,
+    pub score: u32,
+}
+
+#[derive(Accounts)]
+pub struct UpdateValidatorScores<'info> {
+    #[account(
+        mut,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Account<'info, State>,
+    // the validator manager, or the scoring bot it is delegated to
+    #[account(
+        constraint = state.has_role(Role::ValidatorManager, manager_authority.key)
+            @ MarinadeError::InvalidValidatorManager
+    )]
+    pub manager_authority: Signer<'info>,
+    #[account(
+        mut,
+        address = state.validator_system.validator_list.account,
+    )]
+    pub validator_list: Account<'info, ValidatorList>,
+}
+
+impl<'info> UpdateValidatorScores<'info> {
+    // fn update_validator_scores()
+    // set_validator_score for many validators at once.
+    // stake_reserve and deactivate_stake move the stake towards the new score-weighted targets
+    // during the next stake delta windows, bounded by max_stake_moved_per_epoch for redelegations
+    pub fn process(&mut self, scores: Vec<ValidatorScore>) -> Result<()> {
+        self.state.check_paused(PausedOperations::VALIDATOR_OPS)?;
+        require_gt!(scores.len(), 0, MarinadeError::EmptyValidatorScores);
+
+        for ValidatorScore {
+            index,
+            validator_vote,
+            score,
+        } in scores
+        {
+            let old = self.state.validator_system.set_score(
+                &mut self.validator_list.to_account_info().data.borrow_mut(),
+                index,
+                &validator_vote,
+                score,
+            )?;
+
+            emit!(SetValidatorScoreEvent {
+                state: self.state.key(),
+                validator: validator_vote,
+                index,
+                score_change: U32ValueChange { old, new: score },
+            });
+        }
+
+        Ok(())
+    }
+}
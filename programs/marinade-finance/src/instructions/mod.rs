@@ -1,13 +1,31 @@
 pub mod admin;
+pub mod affiliated_validator;
 pub mod crank;
 pub mod delayed_unstake;
+pub mod directed_stake;
+pub mod guardians;
 pub mod liq_pool;
+pub mod lp_rewards;
 pub mod management;
+pub mod msol_lock;
+pub mod quote;
+pub mod referral;
+pub mod share_vault;
 pub mod user;
+pub mod validator_bond;
 
 pub use admin::*;
+pub use affiliated_validator::*;
 pub use crank::*;
 pub use delayed_unstake::*;
+pub use directed_stake::*;
+pub use guardians::*;
 pub use liq_pool::*;
+pub use lp_rewards::*;
 pub use management::*;
+pub use msol_lock::*;
+pub use quote::*;
+pub use referral::*;
+pub use share_vault::*;
 pub use user::*;
+pub use validator_bond::*;
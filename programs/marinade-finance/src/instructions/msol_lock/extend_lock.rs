@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::MarinadeError,
+    events::msol_lock::ExtendLockEvent,
+    state::{msol_lock::MsolLock, paused_operations::PausedOperations},
+    State,
+};
+
+#[derive(Accounts)]
+pub struct ExtendLock<'info> {
+    #[account(
+        mut,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+
+    #[account(
+        mut,
+        has_one = state,
+        has_one = owner,
+        seeds = [
+            &state.key().to_bytes(),
+            MsolLock::SEED,
+            &owner.key().to_bytes()
+        ],
+        bump = msol_lock.bump
+    )]
+    pub msol_lock: Box<Account<'info, MsolLock>>,
+    pub owner: Signer<'info>,
+
+    pub clock: Sysvar<'info, Clock>,
+}
+
+impl<'info> ExtendLock<'info> {
+    // fn extend_lock()
+    // restarts the lock term of `tier` from the current epoch, the fees accrued so far are kept
+    pub fn process(&mut self, tier: u8) -> Result<()> {
+        self.state.check_paused(PausedOperations::DEPOSITS)?;
+
+        let acc_fee_per_weight = self.state.msol_lock.acc_fee_per_weight;
+        self.msol_lock.settle(acc_fee_per_weight)?;
+        let old_weight = self
+            .msol_lock
+            .lock(0, tier, self.clock.epoch, acc_fee_per_weight)?;
+        self.state
+            .msol_lock
+            .on_lock(0, old_weight, self.msol_lock.weight);
+
+        emit!(ExtendLockEvent {
+            state: self.state.key(),
+            owner: self.owner.key(),
+            locked_amount: self.msol_lock.amount,
+            tier,
+            unlock_epoch: self.msol_lock.unlock_epoch,
+            weight: self.msol_lock.weight,
+            total_weight: self.state.msol_lock.total_weight,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,115 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::system_program;
+use anchor_spl::token::{transfer, Token, TokenAccount, Transfer};
+
+use crate::{
+    checks::check_token_source_account,
+    error::MarinadeError,
+    events::msol_lock::LockMsolEvent,
+    state::{msol_lock::MsolLock, paused_operations::PausedOperations},
+    State,
+};
+
+#[derive(Accounts)]
+pub struct LockMsol<'info> {
+    #[account(
+        mut,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = MsolLock::LEN,
+        seeds = [
+            &state.key().to_bytes(),
+            MsolLock::SEED,
+            &owner.key().to_bytes()
+        ],
+        bump,
+    )]
+    pub msol_lock: Box<Account<'info, MsolLock>>,
+
+    #[account(
+        mut,
+        address = state.msol_lock.vault
+    )]
+    pub msol_lock_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = state.msol_mint
+    )]
+    pub transfer_from: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        owner = system_program::ID
+    )]
+    pub owner: Signer<'info>, // transfer_from owner or delegate_authority, pays the lock rent
+
+    pub clock: Sysvar<'info, Clock>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> LockMsol<'info> {
+    // fn lock_msol()
+    // adds mSOL to the user lock for the term of `tier`, counted from the current epoch.
+    // The tier of an existing lock can't be lowered and its unlock epoch is never moved back
+    pub fn process(&mut self, amount: u64, tier: u8, bump: u8) -> Result<()> {
+        self.state.check_paused(PausedOperations::DEPOSITS)?;
+        check_token_source_account(&self.transfer_from, self.owner.key, amount)
+            .map_err(|e| e.with_account_name("transfer_from"))?;
+
+        if self.msol_lock.state == Pubkey::default() {
+            self.msol_lock.set_inner(MsolLock {
+                state: self.state.key(),
+                owner: self.owner.key(),
+                bump,
+                amount: 0,
+                tier: 0,
+                unlock_epoch: 0,
+                weight: 0,
+                fee_debt: 0,
+                pending_fees: 0,
+            });
+        }
+
+        let acc_fee_per_weight = self.state.msol_lock.acc_fee_per_weight;
+        self.msol_lock.settle(acc_fee_per_weight)?;
+
+        transfer(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                Transfer {
+                    from: self.transfer_from.to_account_info(),
+                    to: self.msol_lock_vault.to_account_info(),
+                    authority: self.owner.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+        let old_weight = self
+            .msol_lock
+            .lock(amount, tier, self.clock.epoch, acc_fee_per_weight)?;
+        self.state
+            .msol_lock
+            .on_lock(amount, old_weight, self.msol_lock.weight);
+
+        emit!(LockMsolEvent {
+            state: self.state.key(),
+            owner: self.owner.key(),
+            amount,
+            locked_amount: self.msol_lock.amount,
+            tier,
+            unlock_epoch: self.msol_lock.unlock_epoch,
+            weight: self.msol_lock.weight,
+            total_locked: self.state.msol_lock.total_locked,
+            total_weight: self.state.msol_lock.total_weight,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,7 @@
+pub mod extend_lock;
+pub mod lock_msol;
+pub mod unlock_msol;
+
+pub use extend_lock::*;
+pub use lock_msol::*;
+pub use unlock_msol::*;
@@ -0,0 +1,122 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{transfer, Token, TokenAccount, Transfer};
+
+use crate::{
+    error::MarinadeError,
+    events::msol_lock::UnlockMsolEvent,
+    state::{msol_lock::MsolLock, paused_operations::PausedOperations},
+    State,
+};
+
+#[derive(Accounts)]
+pub struct UnlockMsol<'info> {
+    #[account(
+        mut,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+
+    #[account(
+        mut,
+        close = owner,
+        has_one = state,
+        has_one = owner,
+        seeds = [
+            &state.key().to_bytes(),
+            MsolLock::SEED,
+            &owner.key().to_bytes()
+        ],
+        bump = msol_lock.bump
+    )]
+    pub msol_lock: Box<Account<'info, MsolLock>>,
+    #[account(mut)]
+    pub owner: Signer<'info>, // receives the lock rent
+
+    #[account(
+        mut,
+        address = state.msol_lock.vault
+    )]
+    pub msol_lock_vault: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        address = state.msol_lock.fee_vault
+    )]
+    pub msol_lock_fee_vault: Box<Account<'info, TokenAccount>>,
+    /// CHECK: PDA
+    #[account(
+        seeds = [
+            &state.key().to_bytes(),
+            MsolLock::AUTHORITY_SEED
+        ],
+        bump = state.msol_lock.authority_bump_seed
+    )]
+    pub msol_lock_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        token::mint = state.msol_mint
+    )]
+    pub transfer_msol_to: Box<Account<'info, TokenAccount>>,
+
+    pub clock: Sysvar<'info, Clock>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> UnlockMsol<'info> {
+    fn transfer_from_vault(&self, vault: AccountInfo<'info>, amount: u64) -> Result<()> {
+        transfer(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                Transfer {
+                    from: vault,
+                    to: self.transfer_msol_to.to_account_info(),
+                    authority: self.msol_lock_authority.to_account_info(),
+                },
+                &[&[
+                    &self.state.key().to_bytes(),
+                    MsolLock::AUTHORITY_SEED,
+                    &[self.state.msol_lock.authority_bump_seed],
+                ]],
+            ),
+            amount,
+        )
+    }
+
+    // fn unlock_msol()
+    // returns the expired locked mSOL together with the fees the lock earned and closes the lock
+    pub fn process(&mut self) -> Result<()> {
+        self.state.check_paused(PausedOperations::DEPOSITS)?;
+        require_gte!(
+            self.clock.epoch,
+            self.msol_lock.unlock_epoch,
+            MarinadeError::MsolIsLocked
+        );
+
+        self.msol_lock
+            .settle(self.state.msol_lock.acc_fee_per_weight)?;
+        let amount = self.msol_lock.amount;
+        let msol_fees = self.msol_lock.pending_fees;
+        self.state
+            .msol_lock
+            .on_unlock(amount, self.msol_lock.weight);
+
+        if amount > 0 {
+            self.transfer_from_vault(self.msol_lock_vault.to_account_info(), amount)?;
+        }
+        if msol_fees > 0 {
+            self.transfer_from_vault(self.msol_lock_fee_vault.to_account_info(), msol_fees)?;
+        }
+
+        emit!(UnlockMsolEvent {
+            state: self.state.key(),
+            owner: self.owner.key(),
+            amount,
+            msol_fees,
+            total_locked: self.state.msol_lock.total_locked,
+            total_weight: self.state.msol_lock.total_weight,
+        });
+
+        Ok(())
+    }
+}
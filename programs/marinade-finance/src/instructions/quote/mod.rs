@@ -0,0 +1,9 @@
+pub mod quote_add_liquidity;
+pub mod quote_deposit;
+pub mod quote_liquid_unstake;
+pub mod quote_remove_liquidity;
+
+pub use quote_add_liquidity::*;
+pub use quote_deposit::*;
+pub use quote_liquid_unstake::*;
+pub use quote_remove_liquidity::*;
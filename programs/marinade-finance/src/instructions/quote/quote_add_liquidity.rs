@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, TokenAccount};
+
+use crate::{
+    error::MarinadeError,
+    require_lte,
+    state::{fee_curve::FeeCurve, liq_pool::LiqPool},
+    State,
+};
+
+#[derive(Accounts)]
+pub struct QuoteAddLiquidity<'info> {
+    #[account(
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+
+    #[account(address = state.liq_pool.lp_mint)]
+    pub lp_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        seeds = [
+            &state.key().to_bytes(),
+            LiqPool::SOL_LEG_SEED
+        ],
+        bump = state.liq_pool.sol_leg_bump_seed
+    )]
+    pub liq_pool_sol_leg_pda: SystemAccount<'info>,
+    #[account(address = state.liq_pool.msol_leg)]
+    pub liq_pool_msol_leg: Box<Account<'info, TokenAccount>>,
+
+    #[account(address = state.liq_pool_ext.fee_curve)]
+    pub fee_curve: Option<Box<Account<'info, FeeCurve>>>,
+}
+
+impl<'info> QuoteAddLiquidity<'info> {
+    // fn quote_add_liquidity()
+    // read-only, returns the LP tokens add_liquidity would mint for `lamports`
+    pub fn process(&self, lamports: u64) -> Result<u64> {
+        self.state.liq_pool.check_no_flash_loan()?;
+        let lp_supply = self.state.lp_supply_of(self.lp_mint.supply);
+        require_lte!(
+            lp_supply,
+            self.state.liq_pool.lp_supply,
+            MarinadeError::UnregisteredLPMinted
+        );
+        let bonus_lamports = if !self.state.add_liquidity_bonus_share.is_zero() {
+            self.state
+                .liq_pool_ext
+                .check_fee_curve(self.fee_curve.is_some())
+                .map_err(|e| e.with_account_name("fee_curve"))?;
+            self.state.add_liquidity_bonus(
+                lamports,
+                self.liq_pool_sol_leg_pda.lamports(),
+                self.fee_curve.as_ref().map(|fee_curve| &***fee_curve),
+            )
+        } else {
+            0
+        };
+        let shares = self.state.quote_add_liquidity(
+            lamports + bonus_lamports,
+            self.liq_pool_sol_leg_pda.lamports(),
+            self.liq_pool_msol_leg.amount,
+            lp_supply,
+        )?;
+        self.state.quote_lp_shares_to_mint(shares)
+    }
+}
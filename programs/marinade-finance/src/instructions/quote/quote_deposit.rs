@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+use crate::{
+    error::MarinadeError,
+    state::{liq_pool::LiqPool, quote::DepositQuote},
+    State,
+};
+
+#[derive(Accounts)]
+pub struct QuoteDeposit<'info> {
+    #[account(
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+
+    #[account(address = state.liq_pool.msol_leg)]
+    pub liq_pool_msol_leg: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [
+            &state.key().to_bytes(),
+            LiqPool::SOL_LEG_SEED
+        ],
+        bump = state.liq_pool.sol_leg_bump_seed
+    )]
+    pub liq_pool_sol_leg_pda: SystemAccount<'info>,
+}
+
+impl<'info> QuoteDeposit<'info> {
+    // fn quote_deposit()
+    // read-only, the result is returned in the transaction return data
+    pub fn process(&self, lamports: u64) -> Result<DepositQuote> {
+        self.state.liq_pool.check_no_flash_loan()?;
+        self.state.quote_deposit(
+            lamports,
+            self.liq_pool_msol_leg.amount,
+            self.liq_pool_sol_leg_pda.lamports(),
+        )
+    }
+}
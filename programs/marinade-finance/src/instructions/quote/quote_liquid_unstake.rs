@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::MarinadeError,
+    state::{
+        fee_curve::FeeCurve, holding_record::HoldingRecord, integrator::IntegratorPartner,
+        liq_pool::LiqPool, quote::LiquidUnstakeQuote,
+    },
+    Fee, State,
+};
+
+#[derive(Accounts)]
+pub struct QuoteLiquidUnstake<'info> {
+    #[account(
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+
+    #[account(
+        seeds = [
+            &state.key().to_bytes(),
+            LiqPool::SOL_LEG_SEED
+        ],
+        bump = state.liq_pool.sol_leg_bump_seed
+    )]
+    pub liq_pool_sol_leg_pda: SystemAccount<'info>,
+
+    #[account(address = state.liq_pool_ext.fee_curve)]
+    pub fee_curve: Option<Box<Account<'info, FeeCurve>>>,
+
+    // quotes the rebated fee of the integrator
+    #[account(has_one = state)]
+    pub integrator_partner: Option<Box<Account<'info, IntegratorPartner>>>,
+
+    // quotes the holding discount of the wallet
+    #[account(has_one = state)]
+    pub holding_record: Option<Box<Account<'info, HoldingRecord>>>,
+}
+
+impl<'info> QuoteLiquidUnstake<'info> {
+    // fn quote_liquid_unstake()
+    // read-only, returns the fee and the SOL liquid_unstake would pay for `msol_amount`
+    pub fn process(&self, msol_amount: u64) -> Result<LiquidUnstakeQuote> {
+        self.state.liq_pool.check_no_flash_loan()?;
+        self.state
+            .liq_pool_ext
+            .check_fee_curve(self.fee_curve.is_some())
+            .map_err(|e| e.with_account_name("fee_curve"))?;
+        let holding_discount = match &self.holding_record {
+            Some(holding_record) => holding_record.discount(
+                &self.state.holding_discount_tiers,
+                msol_amount,
+                Clock::get()?.unix_timestamp,
+            ),
+            None => Fee::ZERO,
+        };
+        self.state.quote_liquid_unstake(
+            msol_amount,
+            self.liq_pool_sol_leg_pda.lamports(),
+            self.fee_curve.as_ref().map(|fee_curve| &***fee_curve),
+            self.integrator_partner.as_deref().map(|p| &**p),
+            holding_discount,
+        )
+    }
+}
@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, TokenAccount};
+
+use crate::{
+    error::MarinadeError,
+    require_lte,
+    state::{liq_pool::LiqPool, quote::RemoveLiquidityQuote},
+    State,
+};
+
+#[derive(Accounts)]
+pub struct QuoteRemoveLiquidity<'info> {
+    #[account(
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+
+    #[account(address = state.liq_pool.lp_mint)]
+    pub lp_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        seeds = [
+            &state.key().to_bytes(),
+            LiqPool::SOL_LEG_SEED
+        ],
+        bump = state.liq_pool.sol_leg_bump_seed
+    )]
+    pub liq_pool_sol_leg_pda: SystemAccount<'info>,
+    #[account(address = state.liq_pool.msol_leg)]
+    pub liq_pool_msol_leg: Box<Account<'info, TokenAccount>>,
+}
+
+impl<'info> QuoteRemoveLiquidity<'info> {
+    // fn quote_remove_liquidity()
+    // read-only, returns the SOL and mSOL remove_liquidity would pay for `tokens`
+    pub fn process(&self, tokens: u64) -> Result<RemoveLiquidityQuote> {
+        self.state.liq_pool.check_no_flash_loan()?;
+        let lp_supply = self.state.lp_supply_of(self.lp_mint.supply);
+        require_lte!(
+            lp_supply,
+            self.state.liq_pool.lp_supply,
+            MarinadeError::UnexpectedLpSupply
+        );
+        self.state.quote_remove_liquidity(
+            tokens,
+            self.liq_pool_sol_leg_pda.lamports(),
+            self.liq_pool_msol_leg.amount,
+            lp_supply,
+        )
+    }
+}
@@ -0,0 +1,95 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{transfer, Token, TokenAccount, Transfer};
+
+use crate::{
+    error::MarinadeError,
+    events::referral::ClaimReferralRewardsEvent,
+    state::{paused_operations::PausedOperations, referral::ReferralPartner},
+    State,
+};
+
+#[derive(Accounts)]
+pub struct ClaimReferralRewards<'info> {
+    #[account(
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+
+    #[account(
+        mut,
+        has_one = state,
+        has_one = partner,
+        seeds = [
+            &state.key().to_bytes(),
+            ReferralPartner::SEED,
+            &partner.key().to_bytes()
+        ],
+        bump = referral_partner.bump,
+    )]
+    pub referral_partner: Box<Account<'info, ReferralPartner>>,
+    pub partner: Signer<'info>,
+
+    #[account(
+        mut,
+        address = state.referral_vault
+    )]
+    pub referral_vault: Box<Account<'info, TokenAccount>>,
+    /// CHECK: PDA
+    #[account(
+        seeds = [
+            &state.key().to_bytes(),
+            ReferralPartner::VAULT_AUTHORITY_SEED
+        ],
+        bump = state.referral_vault_authority_bump_seed
+    )]
+    pub referral_vault_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        token::mint = state.msol_mint
+    )]
+    pub transfer_msol_to: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> ClaimReferralRewards<'info> {
+    // fn claim_referral_rewards()
+    // pays what the vault can afford, the rest stays pending until the vault is funded again
+    pub fn process(&mut self) -> Result<()> {
+        self.state.check_paused(PausedOperations::DEPOSITS)?;
+
+        let msol_amount = self
+            .referral_partner
+            .pending_msol
+            .min(self.referral_vault.amount);
+        if msol_amount > 0 {
+            transfer(
+                CpiContext::new_with_signer(
+                    self.token_program.to_account_info(),
+                    Transfer {
+                        from: self.referral_vault.to_account_info(),
+                        to: self.transfer_msol_to.to_account_info(),
+                        authority: self.referral_vault_authority.to_account_info(),
+                    },
+                    &[&[
+                        &self.state.key().to_bytes(),
+                        ReferralPartner::VAULT_AUTHORITY_SEED,
+                        &[self.state.referral_vault_authority_bump_seed],
+                    ]],
+                ),
+                msol_amount,
+            )?;
+            self.referral_partner.on_claim(msol_amount);
+        }
+
+        emit!(ClaimReferralRewardsEvent {
+            state: self.state.key(),
+            partner: self.partner.key(),
+            msol_amount,
+            pending_msol: self.referral_partner.pending_msol,
+        });
+
+        Ok(())
+    }
+}
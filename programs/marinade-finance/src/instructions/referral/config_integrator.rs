@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::MarinadeError,
+    events::{referral::ConfigIntegratorEvent, FeeValueChange},
+    state::{integrator::IntegratorPartner, roles::Role, Fee},
+    State,
+};
+
+#[derive(Accounts)]
+pub struct ConfigIntegrator<'info> {
+    #[account(
+        constraint = state.has_role(Role::Treasurer, treasurer_authority.key)
+            @ MarinadeError::InvalidTreasurerAuthority,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+    pub treasurer_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = state
+    )]
+    pub integrator_partner: Box<Account<'info, IntegratorPartner>>,
+}
+
+impl<'info> ConfigIntegrator<'info> {
+    // fn config_integrator()
+    // a zero rebate takes the integrator out of the whitelist, the volume is kept
+    pub fn process(&mut self, fee_rebate: Fee) -> Result<()> {
+        self.state.check_config_not_timelocked()?;
+        fee_rebate.check()?;
+        let fee_rebate_change = {
+            let old = self.integrator_partner.fee_rebate;
+            self.integrator_partner.fee_rebate = fee_rebate;
+            FeeValueChange {
+                old,
+                new: fee_rebate,
+            }
+        };
+
+        emit!(ConfigIntegratorEvent {
+            state: self.state.key(),
+            partner: self.integrator_partner.partner,
+            fee_rebate_change,
+        });
+
+        Ok(())
+    }
+}
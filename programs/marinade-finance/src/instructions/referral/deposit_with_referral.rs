@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    events::referral::DepositWithReferralEvent,
+    instructions::user::deposit::Deposit,
+    state::{quote::DepositQuote, referral::ReferralPartner},
+};
+
+#[derive(Accounts)]
+pub struct DepositWithReferral<'info> {
+    pub deposit: Deposit<'info>,
+
+    #[account(
+        mut,
+        constraint = referral_partner.state == deposit.state.key(),
+        seeds = [
+            &deposit.state.key().to_bytes(),
+            ReferralPartner::SEED,
+            &referral_partner.partner.to_bytes()
+        ],
+        bump = referral_partner.bump,
+    )]
+    pub referral_partner: Box<Account<'info, ReferralPartner>>,
+}
+
+impl<'info> DepositWithReferral<'info> {
+    // fn deposit_with_referral()
+    // a regular deposit attributed to the partner
+    pub fn process(&mut self, lamports: u64, min_msol_out: u64) -> Result<()> {
+        let DepositQuote { deposit_fee, .. } = self.deposit.process(lamports, min_msol_out)?;
+
+        // the partner earns a share of the fee the deposit paid, not of the principal
+        let msol_reward = self
+            .deposit
+            .state
+            .referral_share
+            .apply(self.deposit.state.calc_msol_from_lamports(deposit_fee)?);
+
+        self.referral_partner.on_deposit(lamports, msol_reward);
+
+        emit!(DepositWithReferralEvent {
+            state: self.deposit.state.key(),
+            partner: self.referral_partner.partner,
+            sol_owner: self.deposit.transfer_from.key(),
+            lamports,
+            deposit_fee,
+            msol_reward,
+            referred_lamports: self.referral_partner.referred_lamports,
+            pending_msol: self.referral_partner.pending_msol,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,11 @@
+pub mod claim_referral_rewards;
+pub mod config_integrator;
+pub mod deposit_with_referral;
+pub mod register_integrator;
+pub mod register_partner;
+
+pub use claim_referral_rewards::*;
+pub use config_integrator::*;
+pub use deposit_with_referral::*;
+pub use register_integrator::*;
+pub use register_partner::*;
@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::system_program;
+
+use crate::{
+    error::MarinadeError,
+    events::referral::RegisterIntegratorEvent,
+    state::{integrator::IntegratorPartner, roles::Role, Fee},
+    State,
+};
+
+#[derive(Accounts)]
+pub struct RegisterIntegrator<'info> {
+    #[account(
+        constraint = state.has_role(Role::Treasurer, treasurer_authority.key)
+            @ MarinadeError::InvalidTreasurerAuthority,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+    pub treasurer_authority: Signer<'info>,
+
+    /// CHECK: any account, signs the liquid unstakes of the integrator
+    pub partner: UncheckedAccount<'info>,
+    #[account(
+        init,
+        payer = rent_payer,
+        space = IntegratorPartner::LEN,
+        seeds = [
+            &state.key().to_bytes(),
+            IntegratorPartner::SEED,
+            &partner.key().to_bytes()
+        ],
+        bump,
+    )]
+    pub integrator_partner: Box<Account<'info, IntegratorPartner>>,
+
+    #[account(
+        mut,
+        owner = system_program::ID
+    )]
+    pub rent_payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RegisterIntegrator<'info> {
+    // fn register_integrator()
+    pub fn process(&mut self, fee_rebate: Fee, bump: u8) -> Result<()> {
+        self.state.check_config_not_timelocked()?;
+        fee_rebate.check()?;
+        self.integrator_partner.set_inner(IntegratorPartner {
+            state: self.state.key(),
+            partner: self.partner.key(),
+            bump,
+            fee_rebate,
+            unstaked_lamports: 0,
+            liquid_unstakes: 0,
+            rebated_msol: 0,
+        });
+
+        emit!(RegisterIntegratorEvent {
+            state: self.state.key(),
+            partner: self.partner.key(),
+            integrator_partner: self.integrator_partner.key(),
+            fee_rebate,
+        });
+
+        Ok(())
+    }
+}
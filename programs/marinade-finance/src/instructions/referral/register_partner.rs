@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::system_program;
+
+use crate::{
+    error::MarinadeError,
+    events::referral::RegisterPartnerEvent,
+    state::{referral::ReferralPartner, roles::Role},
+    State,
+};
+
+#[derive(Accounts)]
+pub struct RegisterPartner<'info> {
+    #[account(
+        constraint = state.has_role(Role::Treasurer, treasurer_authority.key)
+            @ MarinadeError::InvalidTreasurerAuthority,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+    pub treasurer_authority: Signer<'info>,
+
+    /// CHECK: any account, claims the rewards
+    pub partner: UncheckedAccount<'info>,
+    #[account(
+        init,
+        payer = rent_payer,
+        space = ReferralPartner::LEN,
+        seeds = [
+            &state.key().to_bytes(),
+            ReferralPartner::SEED,
+            &partner.key().to_bytes()
+        ],
+        bump,
+    )]
+    pub referral_partner: Box<Account<'info, ReferralPartner>>,
+
+    #[account(
+        mut,
+        owner = system_program::ID
+    )]
+    pub rent_payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RegisterPartner<'info> {
+    // fn register_partner()
+    pub fn process(&mut self, bump: u8) -> Result<()> {
+        self.referral_partner.set_inner(ReferralPartner {
+            state: self.state.key(),
+            partner: self.partner.key(),
+            bump,
+            referred_lamports: 0,
+            referred_deposits: 0,
+            pending_msol: 0,
+            claimed_msol: 0,
+        });
+
+        emit!(RegisterPartnerEvent {
+            state: self.state.key(),
+            partner: self.partner.key(),
+            referral_partner: self.referral_partner.key(),
+        });
+
+        Ok(())
+    }
+}
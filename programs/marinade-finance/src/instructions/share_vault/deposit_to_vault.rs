@@ -0,0 +1,118 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{mint_to, transfer, Mint, MintTo, Token, TokenAccount, Transfer};
+
+use crate::{
+    checks::check_token_source_account,
+    error::MarinadeError,
+    events::share_vault::VaultDepositEvent,
+    state::{paused_operations::PausedOperations, share_vault::ShareVault},
+    State,
+};
+
+#[derive(Accounts)]
+pub struct DepositToVault<'info> {
+    #[account(
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+
+    #[account(
+        mut,
+        has_one = state,
+        has_one = asset_vault,
+        has_one = share_mint,
+        seeds = [
+            &state.key().to_bytes(),
+            ShareVault::SEED
+        ],
+        bump = share_vault.bump,
+    )]
+    pub share_vault: Box<Account<'info, ShareVault>>,
+    /// CHECK: PDA
+    #[account(
+        seeds = [
+            &state.key().to_bytes(),
+            ShareVault::AUTHORITY_SEED
+        ],
+        bump = share_vault.authority_bump,
+    )]
+    pub share_vault_authority: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub asset_vault: Box<Account<'info, TokenAccount>>,
+    #[account(mut)]
+    pub share_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        token::mint = state.msol_mint
+    )]
+    pub transfer_from: Box<Account<'info, TokenAccount>>,
+    pub transfer_from_authority: Signer<'info>, // transfer_from owner or delegate_authority
+    #[account(
+        mut,
+        token::mint = share_mint
+    )]
+    pub mint_to: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> DepositToVault<'info> {
+    // fn deposit_to_vault()
+    // deposits `assets` mSOL for the shares they are worth at the current share price
+    pub fn process(&mut self, assets: u64, min_shares_out: u64) -> Result<()> {
+        self.state.check_paused(PausedOperations::DEPOSITS)?;
+        check_token_source_account(
+            &self.transfer_from,
+            self.transfer_from_authority.key,
+            assets,
+        )
+        .map_err(|e| e.with_account_name("transfer_from"))?;
+
+        let shares = self.share_vault.convert_to_shares(assets)?;
+        require_gt!(shares, 0, MarinadeError::VaultAmountTooLow);
+        require_gte!(shares, min_shares_out, MarinadeError::SlippageExceeded);
+
+        transfer(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                Transfer {
+                    from: self.transfer_from.to_account_info(),
+                    to: self.asset_vault.to_account_info(),
+                    authority: self.transfer_from_authority.to_account_info(),
+                },
+            ),
+            assets,
+        )?;
+        mint_to(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                MintTo {
+                    mint: self.share_mint.to_account_info(),
+                    to: self.mint_to.to_account_info(),
+                    authority: self.share_vault_authority.to_account_info(),
+                },
+                &[&[
+                    &self.state.key().to_bytes(),
+                    ShareVault::AUTHORITY_SEED,
+                    &[self.share_vault.authority_bump],
+                ]],
+            ),
+            shares,
+        )?;
+        self.share_vault.on_deposit(assets, shares);
+
+        emit!(VaultDepositEvent {
+            state: self.state.key(),
+            vault: self.share_vault.key(),
+            sender: self.transfer_from_authority.key(),
+            owner: self.mint_to.owner,
+            assets,
+            shares,
+            total_assets: self.share_vault.total_assets,
+            total_shares: self.share_vault.total_shares,
+        });
+
+        Ok(())
+    }
+}
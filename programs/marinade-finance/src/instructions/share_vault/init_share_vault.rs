@@ -0,0 +1,101 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::system_program;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::{
+    error::MarinadeError, events::share_vault::InitShareVaultEvent, state::share_vault::ShareVault,
+    State,
+};
+
+#[derive(Accounts)]
+pub struct InitShareVault<'info> {
+    #[account(
+        has_one = admin_authority @ MarinadeError::InvalidAdminAuthority,
+        has_one = msol_mint,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+    pub admin_authority: Signer<'info>,
+
+    pub msol_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = rent_payer,
+        space = ShareVault::LEN,
+        seeds = [
+            &state.key().to_bytes(),
+            ShareVault::SEED
+        ],
+        bump,
+    )]
+    pub share_vault: Box<Account<'info, ShareVault>>,
+    /// CHECK: PDA
+    #[account(
+        seeds = [
+            &state.key().to_bytes(),
+            ShareVault::AUTHORITY_SEED
+        ],
+        bump,
+    )]
+    pub share_vault_authority: UncheckedAccount<'info>,
+    #[account(
+        init,
+        payer = rent_payer,
+        token::mint = msol_mint,
+        token::authority = share_vault_authority,
+        seeds = [
+            &state.key().to_bytes(),
+            ShareVault::ASSET_VAULT_SEED
+        ],
+        bump,
+    )]
+    pub asset_vault: Box<Account<'info, TokenAccount>>,
+    #[account(
+        init,
+        payer = rent_payer,
+        mint::decimals = msol_mint.decimals,
+        mint::authority = share_vault_authority,
+        seeds = [
+            &state.key().to_bytes(),
+            ShareVault::SHARE_MINT_SEED
+        ],
+        bump,
+    )]
+    pub share_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        owner = system_program::ID
+    )]
+    pub rent_payer: Signer<'info>,
+
+    pub rent: Sysvar<'info, Rent>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> InitShareVault<'info> {
+    // fn init_share_vault()
+    pub fn process(&mut self, bump: u8, authority_bump: u8) -> Result<()> {
+        self.share_vault.set_inner(ShareVault {
+            state: self.state.key(),
+            bump,
+            authority_bump,
+            asset_vault: self.asset_vault.key(),
+            share_mint: self.share_mint.key(),
+            total_assets: 0,
+            total_shares: 0,
+        });
+
+        emit!(InitShareVaultEvent {
+            state: self.state.key(),
+            vault: self.share_vault.key(),
+            asset_vault: self.asset_vault.key(),
+            share_mint: self.share_mint.key(),
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,7 @@
+pub mod deposit_to_vault;
+pub mod init_share_vault;
+pub mod redeem_from_vault;
+
+pub use deposit_to_vault::*;
+pub use init_share_vault::*;
+pub use redeem_from_vault::*;
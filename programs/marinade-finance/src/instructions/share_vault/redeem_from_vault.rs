@@ -0,0 +1,115 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{burn, transfer, Burn, Mint, Token, TokenAccount, Transfer};
+
+use crate::{
+    checks::check_token_source_account,
+    error::MarinadeError,
+    events::share_vault::VaultWithdrawEvent,
+    state::{paused_operations::PausedOperations, share_vault::ShareVault},
+    State,
+};
+
+#[derive(Accounts)]
+pub struct RedeemFromVault<'info> {
+    #[account(
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+
+    #[account(
+        mut,
+        has_one = state,
+        has_one = asset_vault,
+        has_one = share_mint,
+        seeds = [
+            &state.key().to_bytes(),
+            ShareVault::SEED
+        ],
+        bump = share_vault.bump,
+    )]
+    pub share_vault: Box<Account<'info, ShareVault>>,
+    /// CHECK: PDA
+    #[account(
+        seeds = [
+            &state.key().to_bytes(),
+            ShareVault::AUTHORITY_SEED
+        ],
+        bump = share_vault.authority_bump,
+    )]
+    pub share_vault_authority: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub asset_vault: Box<Account<'info, TokenAccount>>,
+    #[account(mut)]
+    pub share_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        token::mint = share_mint
+    )]
+    pub burn_from: Box<Account<'info, TokenAccount>>,
+    pub burn_from_authority: Signer<'info>, // burn_from owner or delegate_authority
+    #[account(
+        mut,
+        token::mint = state.msol_mint
+    )]
+    pub transfer_to: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> RedeemFromVault<'info> {
+    // fn redeem_from_vault()
+    // burns `shares` for their part of the vault mSOL
+    pub fn process(&mut self, shares: u64, min_assets_out: u64) -> Result<()> {
+        self.state.check_paused(PausedOperations::DEPOSITS)?;
+        check_token_source_account(&self.burn_from, self.burn_from_authority.key, shares)
+            .map_err(|e| e.with_account_name("burn_from"))?;
+
+        let assets = self.share_vault.convert_to_assets(shares)?;
+        require_gt!(assets, 0, MarinadeError::VaultAmountTooLow);
+        require_gte!(assets, min_assets_out, MarinadeError::SlippageExceeded);
+
+        burn(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                Burn {
+                    mint: self.share_mint.to_account_info(),
+                    from: self.burn_from.to_account_info(),
+                    authority: self.burn_from_authority.to_account_info(),
+                },
+            ),
+            shares,
+        )?;
+        transfer(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                Transfer {
+                    from: self.asset_vault.to_account_info(),
+                    to: self.transfer_to.to_account_info(),
+                    authority: self.share_vault_authority.to_account_info(),
+                },
+                &[&[
+                    &self.state.key().to_bytes(),
+                    ShareVault::AUTHORITY_SEED,
+                    &[self.share_vault.authority_bump],
+                ]],
+            ),
+            assets,
+        )?;
+        self.share_vault.on_redeem(assets, shares);
+
+        emit!(VaultWithdrawEvent {
+            state: self.state.key(),
+            vault: self.share_vault.key(),
+            sender: self.burn_from_authority.key(),
+            receiver: self.transfer_to.owner,
+            owner: self.burn_from.owner,
+            assets,
+            shares,
+            total_assets: self.share_vault.total_assets,
+            total_shares: self.share_vault.total_shares,
+        });
+
+        Ok(())
+    }
+}
@@ -6,16 +6,21 @@ use anchor_spl::token::{
     Transfer as TransferTokens,
 };
 
+use crate::associated_token::{self, init_token_account_if_needed, CreateAssociatedTokenAccounts};
 use crate::error::MarinadeError;
 use crate::events::user::DepositEvent;
-use crate::state::liq_pool::LiqPool;
-use crate::{require_lte, State};
+use crate::state::{
+    holding_record::HoldingRecord, liq_pool::LiqPool, paused_operations::PausedOperations,
+    quote::DepositQuote, user_stats::UserStats,
+};
+use crate::{debug_msg, require_lte, State};
 
 #[derive(Accounts)]
 pub struct Deposit<'info> {
     #[account(
         mut,
-        has_one = msol_mint
+        has_one = msol_mint,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
     )]
     pub state: Box<Account<'info, State>>,
 
@@ -63,12 +68,9 @@ pub struct Deposit<'info> {
     )]
     pub transfer_from: Signer<'info>,
 
-    /// user mSOL Token account to send the mSOL
-    #[account(
-        mut,
-        token::mint = state.msol_mint
-    )]
-    pub mint_to: Box<Account<'info, TokenAccount>>,
+    /// CHECK: user mSOL Token account to send the mSOL, created in code if needed
+    #[account(mut)]
+    pub mint_to: UncheckedAccount<'info>,
 
     /// CHECK: PDA
     #[account(
@@ -82,12 +84,37 @@ pub struct Deposit<'info> {
 
     pub system_program: Program<'info, System>,
     pub token_program: Program<'info, Token>,
+
+    // required only to create mint_to as the mint_to_owner associated token account
+    /// CHECK: any owner for the new mSOL token account
+    pub mint_to_owner: Option<UncheckedAccount<'info>>,
+    #[account(mut)]
+    pub ata_rent_payer: Option<Signer<'info>>,
+    /// CHECK: program id
+    #[account(address = associated_token::program::ID)]
+    pub associated_token_program: Option<UncheckedAccount<'info>>,
+
+    // records the deposit time of the mint_to owner for the liquid unstake holding discount
+    #[account(
+        mut,
+        has_one = state
+    )]
+    pub holding_record: Option<Box<Account<'info, HoldingRecord>>>,
+
+    // staking statistics of the mint_to owner
+    #[account(
+        mut,
+        has_one = state
+    )]
+    pub user_stats: Option<Box<Account<'info, UserStats>>>,
 }
 
 impl<'info> Deposit<'info> {
     // fn deposit_sol()
-    pub fn process(&mut self, lamports: u64) -> Result<()> {
-        require!(!self.state.paused, MarinadeError::ProgramIsPaused);
+    // returns the executed quote for the instructions wrapping the deposit
+    pub fn process(&mut self, lamports: u64, min_msol_out: u64) -> Result<DepositQuote> {
+        self.state.check_paused(PausedOperations::DEPOSITS)?;
+        self.state.liq_pool.check_no_flash_loan()?;
 
         require_gte!(
             lamports,
@@ -101,8 +128,21 @@ impl<'info> Deposit<'info> {
             MarinadeError::NotEnoughUserFunds
         );
 
+        let mint_to_account = init_token_account_if_needed(
+            &self.mint_to.to_account_info(),
+            &self.msol_mint.to_account_info(),
+            CreateAssociatedTokenAccounts::from_optional_accounts(
+                &self.mint_to_owner,
+                &self.ata_rent_payer,
+                &self.associated_token_program,
+                &self.system_program,
+                &self.token_program,
+            ),
+            "mint_to",
+        )?;
+
         // store for event log
-        let user_msol_balance = self.mint_to.amount;
+        let user_msol_balance = mint_to_account.amount;
         let reserve_balance = self.reserve_pda.lamports();
         let sol_leg_balance = self.liq_pool_sol_leg_pda.lamports();
 
@@ -116,31 +156,45 @@ impl<'info> Deposit<'info> {
         let total_virtual_staked_lamports = self.state.total_virtual_staked_lamports();
         let msol_supply = self.state.msol_supply;
 
-        //compute how many mSOL to sell/mint for the user, base on how many lamports being deposited
-        let user_msol_buy_order = self.state.calc_msol_from_lamports(lamports)?;
-        msg!("--- user_m_sol_buy_order {}", user_msol_buy_order);
-
-        //First we try to "sell" mSOL to the user from the LiqPool.
         //The LiqPool needs to get rid of their mSOL because it works better if fully "unbalanced", i.e. with all SOL no mSOL
         //so, if we can, the LiqPool "sells" mSOL to the user (no fee)
-        //
-        // At max, we can sell all the mSOL in the LiqPool.mSOL_leg
         let msol_leg_balance = self.liq_pool_msol_leg.amount;
-        let msol_swapped: u64 = user_msol_buy_order.min(msol_leg_balance);
-        msg!("--- swap_m_sol_max {}", msol_swapped);
+        let quote = self
+            .state
+            .quote_deposit(lamports, msol_leg_balance, sol_leg_balance)?;
+        let DepositQuote {
+            msol_out: user_msol_buy_order,
+            msol_swapped,
+            sol_swapped,
+            sol_deposited,
+            msol_minted,
+            deposit_fee,
+        } = quote;
+        debug_msg!("--- user_m_sol_buy_order {}", user_msol_buy_order);
+        // protect the user from the mSOL price moving between quote and execution
+        require_gte!(
+            user_msol_buy_order,
+            min_msol_out,
+            MarinadeError::SlippageExceeded
+        );
+        debug_msg!("--- swap_m_sol_max {}", msol_swapped);
 
-        //if we can sell from the LiqPool
-        let sol_swapped = if msol_swapped > 0 {
-            // how much lamports go into the LiqPool?
-            let sol_swapped = if user_msol_buy_order == msol_swapped {
-                //we are fulfilling 100% the user order
-                lamports //100% of the user deposit
-            } else {
-                // partially filled
-                // then it's the lamport value of the tokens we're selling
-                self.state.msol_to_sol(msol_swapped)?
-            };
+        // the imbalance fee refills the SOL leg
+        if deposit_fee > 0 {
+            transfer(
+                CpiContext::new(
+                    self.system_program.to_account_info(),
+                    Transfer {
+                        from: self.transfer_from.to_account_info(),
+                        to: self.liq_pool_sol_leg_pda.to_account_info(),
+                    },
+                ),
+                deposit_fee,
+            )?;
+        }
 
+        //if we can sell from the LiqPool
+        if msol_swapped > 0 {
             // transfer mSOL to the user
 
             transfer_tokens(
@@ -171,15 +225,10 @@ impl<'info> Deposit<'info> {
                 ),
                 sol_swapped,
             )?;
-
-            sol_swapped
             //end of sale from the LiqPool
-        } else {
-            0
-        };
+        }
 
         // check if we have more lamports from the user besides the amount we swapped
-        let sol_deposited = lamports - sol_swapped;
         if sol_deposited > 0 {
             self.state.check_staking_cap(sol_deposited)?;
 
@@ -197,10 +246,9 @@ impl<'info> Deposit<'info> {
             self.state.on_transfer_to_reserve(sol_deposited);
         }
 
-        // compute how much mSOL we own the user besides the amount we already swapped
-        let msol_minted = user_msol_buy_order - msol_swapped;
+        // mint the mSOL we own the user besides the amount we already swapped
         if msol_minted > 0 {
-            msg!("--- msol_to_mint {}", msol_minted);
+            debug_msg!("--- msol_to_mint {}", msol_minted);
             mint_to(
                 CpiContext::new_with_signer(
                     self.token_program.to_account_info(),
@@ -220,6 +268,23 @@ impl<'info> Deposit<'info> {
             self.state.on_msol_mint(msol_minted);
         }
 
+        if let Some(holding_record) = &mut self.holding_record {
+            require_keys_eq!(
+                holding_record.owner,
+                mint_to_account.owner,
+                MarinadeError::WrongHoldingRecord
+            );
+            holding_record.on_deposit(user_msol_buy_order, Clock::get()?.unix_timestamp);
+        }
+        if let Some(user_stats) = &mut self.user_stats {
+            require_keys_eq!(
+                user_stats.owner,
+                mint_to_account.owner,
+                MarinadeError::WrongUserStats
+            );
+            user_stats.on_deposit(lamports, user_msol_buy_order, Clock::get()?.epoch);
+        }
+
         emit!(DepositEvent {
             state: self.state.key(),
             sol_owner: self.transfer_from.key(),
@@ -232,10 +297,11 @@ impl<'info> Deposit<'info> {
             msol_swapped,
             sol_deposited,
             msol_minted,
+            deposit_fee,
             total_virtual_staked_lamports,
             msol_supply
         });
 
-        Ok(())
+        Ok(quote)
     }
 }
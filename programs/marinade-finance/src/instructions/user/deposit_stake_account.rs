@@ -9,13 +9,19 @@ use anchor_spl::token::{mint_to, Mint, MintTo, Token, TokenAccount};
 use crate::events::user::DepositStakeAccountEvent;
 use crate::state::stake_system::StakeList;
 use crate::state::validator_system::ValidatorList;
-use crate::{error::MarinadeError, require_lte, state::stake_system::StakeSystem, State, ID};
+use crate::{
+    error::MarinadeError,
+    require_lte,
+    state::{paused_operations::PausedOperations, stake_system::StakeSystem},
+    State, ID,
+};
 
 #[derive(Accounts)]
 pub struct DepositStakeAccount<'info> {
     #[account(
         mut,
-        has_one = msol_mint
+        has_one = msol_mint,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
     )]
     pub state: Box<Account<'info, State>>,
 
@@ -67,188 +73,254 @@ pub struct DepositStakeAccount<'info> {
     pub system_program: Program<'info, System>,
     pub token_program: Program<'info, Token>,
     pub stake_program: Program<'info, Stake>,
-}
-
-impl<'info> DepositStakeAccount<'info> {
-    pub const WAIT_EPOCHS: u64 = 0; // Accepting fresh/redelegated accounts also because those are mergeable anyways
-    pub fn process(&mut self, validator_index: u32) -> Result<()> {
-        require!(!self.state.paused, MarinadeError::ProgramIsPaused);
-
-        // impossible to happen check outside bug (msol mint auth is a PDA)
-        require_lte!(
-            self.msol_mint.supply,
-            self.state.msol_supply,
-            MarinadeError::UnregisteredMsolMinted
-        );
 
-        // record values for event log
-        let user_msol_balance = self.mint_to.amount;
-        let total_virtual_staked_lamports = self.state.total_virtual_staked_lamports();
-        let msol_supply = self.state.msol_supply;
+    // lockup custodian of stake_account, required only if the lockup is in force
+    pub custodian: Option<Signer<'info>>,
+}
 
-        let delegation = self.stake_account.delegation().ok_or_else(|| {
-            error!(MarinadeError::RequiredDelegatedStake).with_account_name("stake_account")
-        })?;
+/// deposited stake account info for the event log
+pub(crate) struct AbsorbedStake {
+    pub delegated: u64,
+    pub withdrawer: Pubkey,
+    pub validator: Pubkey,
+    pub validator_active_balance: u64,
+    /// the deactivating stake goes to the reserve instead of the validator active balance
+    pub deactivating: bool,
+}
 
-        // require stake is active (deactivation_epoch == u64::MAX)
-        require_eq!(
-            delegation.deactivation_epoch,
-            std::u64::MAX,
-            MarinadeError::RequiredActiveStake
-        );
+/// Validates a user stake account and moves it under the Marinade stake authorities,
+/// registering it in the stake list and in the validator active balance.
+/// A stake account with a lockup in force is accepted only if `custodian` is its lockup custodian.
+/// A deactivating stake account is valued at its stake minus State::deactivating_stake_discount
+/// and cools down as an emergency unstake, update_deactivated moves it to the reserve.
+/// The caller is responsible for the staking cap, total_active_balance and the mSOL minting
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn absorb_stake_account<'info>(
+    state: &mut State,
+    state_address: &Pubkey,
+    validator_list: &AccountInfo<'info>,
+    stake_list: &AccountInfo<'info>,
+    stake_account: &Account<'info, StakeAccount>,
+    stake_authority: &AccountInfo<'info>,
+    custodian: Option<&AccountInfo<'info>>,
+    clock: &Sysvar<'info, Clock>,
+    stake_program: &AccountInfo<'info>,
+    validator_index: u32,
+) -> Result<AbsorbedStake> {
+    let delegation = stake_account.delegation().ok_or_else(|| {
+        error!(MarinadeError::RequiredDelegatedStake).with_account_name("stake_account")
+    })?;
 
-        // require the stake to have been created for at least WAIT_EPOCHS = 0 (activation_epoch field contains creation epoch)
-        require_gte!(
-            self.clock.epoch,
-            delegation.activation_epoch + Self::WAIT_EPOCHS,
-            MarinadeError::DepositingNotActivatedStake
-        );
+    let deactivating = delegation.deactivation_epoch != std::u64::MAX;
 
-        // require the stake amount is at least min_stake
-        require_gte!(
-            delegation.stake,
-            self.state.stake_system.min_stake,
-            MarinadeError::TooLowDelegationInDepositingStake
-        );
+    // require the stake to have been created for at least WAIT_EPOCHS = 0 (activation_epoch field contains creation epoch)
+    require_gte!(
+        clock.epoch,
+        delegation.activation_epoch + DepositStakeAccount::WAIT_EPOCHS,
+        MarinadeError::DepositingNotActivatedStake
+    );
 
-        // Check that stake account has the right amount of lamports.
-        // if there's extra the user should withdraw the extra and try again
-        // (some times users send lamports to active stake accounts believing that will top up the account)
-        require_eq!(
-            self.stake_account.to_account_info().lamports(),
-            delegation.stake + self.stake_account.meta().unwrap().rent_exempt_reserve,
-            MarinadeError::WrongStakeBalance,
-        );
+    // require the stake amount is at least min_stake
+    require_gte!(
+        delegation.stake,
+        state.stake_system.min_stake,
+        MarinadeError::TooLowDelegationInDepositingStake
+    );
 
-        self.state.check_staking_cap(delegation.stake)?;
+    // Check that stake account has the right amount of lamports.
+    // if there's extra the user should withdraw the extra and try again
+    // (some times users send lamports to active stake accounts believing that will top up the account)
+    require_eq!(
+        stake_account.to_account_info().lamports(),
+        delegation.stake + stake_account.meta().unwrap().rent_exempt_reserve,
+        MarinadeError::WrongStakeBalance,
+    );
 
-        let lockup = self.stake_account.lockup().unwrap();
-        // Check Lockup
-        if lockup.is_in_force(&self.clock, None) {
-            msg!("Can not deposit stake account with lockup");
-            return err!(MarinadeError::StakeAccountWithLockup)
-                .map_err(|e| e.with_account_name("stake_account"));
-        }
+    let lockup = stake_account.lockup().unwrap();
+    // Check Lockup, the custodian can waive it
+    let lockup_in_force = lockup.is_in_force(clock, None);
+    if lockup.is_in_force(clock, custodian.map(|custodian| custodian.key)) {
+        msg!("Can not deposit stake account with lockup");
+        return err!(MarinadeError::StakeAccountWithLockup)
+            .map_err(|e| e.with_account_name("stake_account"));
+    }
 
-        let mut validator = self.state.validator_system.get_checked(
-            &self.validator_list.to_account_info().data.as_ref().borrow(),
-            validator_index,
-            &delegation.voter_pubkey,
-        )?;
-        // record balance for event log
-        let validator_active_balance = validator.active_balance;
+    let mut validator = state.validator_system.get_checked(
+        &validator_list.data.as_ref().borrow(),
+        validator_index,
+        &delegation.voter_pubkey,
+    )?;
+    // record balance for event log
+    let validator_active_balance = validator.active_balance;
+    let delegated = if deactivating {
+        // the discount stays in the pool as rewards when update_deactivated withdraws the stake
+        let delegated = state
+            .deactivating_stake_discount
+            .apply_remainder(delegation.stake);
+        state.emergency_cooling_down += delegated;
+        delegated
+    } else {
         // update validator.active_balance
         validator.active_balance += delegation.stake;
-        self.state.validator_system.set(
-            &mut self
-                .validator_list
-                .to_account_info()
-                .data
-                .as_ref()
-                .borrow_mut(),
+        state.validator_system.set(
+            &mut validator_list.data.as_ref().borrow_mut(),
             validator_index,
             validator,
         )?;
+        delegation.stake
+    };
 
-        {
-            let new_staker = Pubkey::create_program_address(
-                &[
-                    &self.state.key().to_bytes(),
-                    StakeSystem::STAKE_DEPOSIT_SEED,
-                    &[self.state.stake_system.stake_deposit_bump_seed],
-                ],
-                &ID,
-            )
-            .unwrap();
-            let old_staker = self.stake_account.meta().unwrap().authorized.staker;
-            // Can not deposit stake already under marinade stake auth. old staker must be different than ours
-            require_keys_neq!(
-                old_staker,
-                new_staker,
-                MarinadeError::RedepositingMarinadeStake
-            );
-
-            // Clean old lockup
-            if lockup.custodian != Pubkey::default() {
-                invoke(
-                    &stake::instruction::set_lockup(
-                        &self.stake_account.key(),
-                        &LockupArgs {
-                            unix_timestamp: Some(0),
-                            epoch: Some(0),
-                            custodian: Some(Pubkey::default()),
-                        },
-                        self.stake_authority.key,
-                    ),
-                    &[
-                        self.stake_program.to_account_info(),
-                        self.stake_account.to_account_info(),
-                        self.stake_authority.to_account_info(),
-                    ],
-                )?;
-            }
+    {
+        let new_staker = Pubkey::create_program_address(
+            &[
+                &state_address.to_bytes(),
+                StakeSystem::STAKE_DEPOSIT_SEED,
+                &[state.stake_system.stake_deposit_bump_seed],
+            ],
+            &ID,
+        )
+        .unwrap();
+        let old_staker = stake_account.meta().unwrap().authorized.staker;
+        // Can not deposit stake already under marinade stake auth. old staker must be different than ours
+        require_keys_neq!(
+            old_staker,
+            new_staker,
+            MarinadeError::RedepositingMarinadeStake
+        );
 
+        // Clean old lockup
+        if lockup.custodian != Pubkey::default() {
+            // while the lockup is in force only the custodian can change it, otherwise the withdrawer
+            let lockup_authority = if lockup_in_force {
+                custodian.unwrap() // checked above, the lockup is waived only by the custodian
+            } else {
+                stake_authority
+            };
             invoke(
-                &stake::instruction::authorize(
-                    self.stake_account.to_account_info().key,
-                    self.stake_authority.key,
-                    &new_staker,
-                    StakeAuthorize::Staker,
-                    None,
+                &stake::instruction::set_lockup(
+                    &stake_account.key(),
+                    &LockupArgs {
+                        unix_timestamp: Some(0),
+                        epoch: Some(0),
+                        custodian: Some(Pubkey::default()),
+                    },
+                    lockup_authority.key,
                 ),
                 &[
-                    self.stake_program.to_account_info(),
-                    self.stake_account.to_account_info(),
-                    self.clock.to_account_info(),
-                    self.stake_authority.to_account_info(),
+                    stake_program.clone(),
+                    stake_account.to_account_info(),
+                    lockup_authority.clone(),
                 ],
             )?;
         }
 
-        let old_withdrawer = self.stake_account.meta().unwrap().authorized.withdrawer;
-        {
-            let new_withdrawer = Pubkey::create_program_address(
-                &[
-                    &self.state.key().to_bytes(),
-                    StakeSystem::STAKE_WITHDRAW_SEED,
-                    &[self.state.stake_system.stake_withdraw_bump_seed],
-                ],
-                &ID,
-            )
-            .unwrap();
-            // Can not deposit stake already under marinade stake auth. old_withdrawer must be different than ours
-            require_keys_neq!(
-                old_withdrawer,
-                new_withdrawer,
-                MarinadeError::RedepositingMarinadeStake
-            );
+        invoke(
+            &stake::instruction::authorize(
+                stake_account.to_account_info().key,
+                stake_authority.key,
+                &new_staker,
+                StakeAuthorize::Staker,
+                None,
+            ),
+            &[
+                stake_program.clone(),
+                stake_account.to_account_info(),
+                clock.to_account_info(),
+                stake_authority.clone(),
+            ],
+        )?;
+    }
 
-            invoke(
-                &stake::instruction::authorize(
-                    self.stake_account.to_account_info().key,
-                    self.stake_authority.key,
-                    &new_withdrawer,
-                    StakeAuthorize::Withdrawer,
-                    None,
-                ),
-                &[
-                    self.stake_program.to_account_info(),
-                    self.stake_account.to_account_info(),
-                    self.clock.to_account_info(),
-                    self.stake_authority.to_account_info(),
-                ],
-            )?;
-        }
+    let old_withdrawer = stake_account.meta().unwrap().authorized.withdrawer;
+    {
+        let new_withdrawer = Pubkey::create_program_address(
+            &[
+                &state_address.to_bytes(),
+                StakeSystem::STAKE_WITHDRAW_SEED,
+                &[state.stake_system.stake_withdraw_bump_seed],
+            ],
+            &ID,
+        )
+        .unwrap();
+        // Can not deposit stake already under marinade stake auth. old_withdrawer must be different than ours
+        require_keys_neq!(
+            old_withdrawer,
+            new_withdrawer,
+            MarinadeError::RedepositingMarinadeStake
+        );
 
-        self.state.stake_system.add(
-            &mut self.stake_list.to_account_info().data.as_ref().borrow_mut(),
-            self.stake_account.to_account_info().key,
-            delegation.stake,
+        invoke(
+            &stake::instruction::authorize(
+                stake_account.to_account_info().key,
+                stake_authority.key,
+                &new_withdrawer,
+                StakeAuthorize::Withdrawer,
+                None,
+            ),
+            &[
+                stake_program.clone(),
+                stake_account.to_account_info(),
+                clock.to_account_info(),
+                stake_authority.clone(),
+            ],
+        )?;
+    }
+
+    state.stake_system.add(
+        &mut stake_list.data.as_ref().borrow_mut(),
+        stake_account.to_account_info().key,
+        delegated,
+        clock,
+        if deactivating { 1 } else { 0 }, // is_emergency_unstaking
+    )?;
+
+    Ok(AbsorbedStake {
+        delegated,
+        withdrawer: old_withdrawer,
+        validator: delegation.voter_pubkey,
+        validator_active_balance,
+        deactivating,
+    })
+}
+
+impl<'info> DepositStakeAccount<'info> {
+    pub const WAIT_EPOCHS: u64 = 0; // Accepting fresh/redelegated accounts also because those are mergeable anyways
+    pub fn process(&mut self, validator_index: u32) -> Result<()> {
+        self.state.check_paused(PausedOperations::DEPOSITS)?;
+
+        // impossible to happen check outside bug (msol mint auth is a PDA)
+        require_lte!(
+            self.msol_mint.supply,
+            self.state.msol_supply,
+            MarinadeError::UnregisteredMsolMinted
+        );
+
+        // record values for event log
+        let user_msol_balance = self.mint_to.amount;
+        let total_virtual_staked_lamports = self.state.total_virtual_staked_lamports();
+        let msol_supply = self.state.msol_supply;
+
+        let state_address = self.state.key();
+        let custodian = self
+            .custodian
+            .as_ref()
+            .map(|custodian| custodian.to_account_info());
+        let deposited = absorb_stake_account(
+            &mut self.state,
+            &state_address,
+            &self.validator_list.to_account_info(),
+            &self.stake_list.to_account_info(),
+            &self.stake_account,
+            &self.stake_authority.to_account_info(),
+            custodian.as_ref(),
             &self.clock,
-            0, // is_emergency_unstaking? no
+            &self.stake_program.to_account_info(),
+            validator_index,
         )?;
 
-        let msol_to_mint = self.state.calc_msol_from_lamports(delegation.stake)?;
+        self.state.check_staking_cap(deposited.delegated)?;
+
+        let msol_to_mint = self.state.calc_msol_from_lamports(deposited.delegated)?;
 
         mint_to(
             CpiContext::new_with_signer(
@@ -270,23 +342,26 @@ impl<'info> DepositStakeAccount<'info> {
 
         // record current total_active_balance for the event log
         let total_active_balance = self.state.validator_system.total_active_balance;
-        // update total_active_balance
-        self.state.validator_system.total_active_balance += delegation.stake;
+        if !deposited.deactivating {
+            // update total_active_balance
+            self.state.validator_system.total_active_balance += deposited.delegated;
+        }
 
         emit!(DepositStakeAccountEvent {
             state: self.state.key(),
             stake: self.stake_account.key(),
-            delegated: delegation.stake,
-            withdrawer: old_withdrawer,
+            delegated: deposited.delegated,
+            withdrawer: deposited.withdrawer,
             stake_index: self.state.stake_system.stake_count() - 1,
-            validator: delegation.voter_pubkey,
+            validator: deposited.validator,
             validator_index,
-            validator_active_balance,
+            validator_active_balance: deposited.validator_active_balance,
             total_active_balance,
             user_msol_balance,
             msol_minted: msol_to_mint,
             total_virtual_staked_lamports,
-            msol_supply
+            msol_supply,
+            deactivating: deposited.deactivating,
         });
         Ok(())
     }
@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{program::invoke, stake, stake::state::StakeState};
+use anchor_spl::stake::StakeAccount;
+
+use crate::{
+    error::MarinadeError, instructions::user::deposit_stake_account::DepositStakeAccount,
+    state::paused_operations::PausedOperations,
+};
+
+#[derive(Accounts)]
+pub struct DepositStakeAccountPartial<'info> {
+    pub deposit: DepositStakeAccount<'info>,
+
+    // receives the part of stake_account that is not deposited, under the same user authorities
+    #[account(
+        init,
+        payer = deposit.rent_payer,
+        space = std::mem::size_of::<StakeState>(),
+        owner = stake::program::ID,
+    )]
+    pub remainder_stake_account: Account<'info, StakeAccount>,
+}
+
+impl<'info> DepositStakeAccountPartial<'info> {
+    // fn deposit_stake_account_partial()
+    // Deposits only `lamports` of the delegated stake of stake_account.
+    // The rest is split into remainder_stake_account before the authorities are moved to Marinade,
+    // so the remainder stays owned by the user
+    pub fn process(&mut self, validator_index: u32, lamports: u64) -> Result<()> {
+        self.deposit
+            .state
+            .check_paused(PausedOperations::DEPOSITS)?;
+
+        let stake_account_lamports = self.deposit.stake_account.to_account_info().lamports();
+        let rent_exempt_reserve = self
+            .deposit
+            .stake_account
+            .meta()
+            .ok_or_else(|| {
+                error!(MarinadeError::RequiredDelegatedStake).with_account_name("stake_account")
+            })?
+            .rent_exempt_reserve;
+        // the deposited account keeps its rent exempt reserve, everything else is split out
+        let lamports_to_keep = lamports + rent_exempt_reserve;
+        require_gt!(
+            stake_account_lamports,
+            lamports_to_keep,
+            MarinadeError::SelectedStakeAccountHasNotEnoughFunds
+        );
+        let remainder_lamports = stake_account_lamports - lamports_to_keep;
+
+        msg!(
+            "Split {} lamports from stake {} into {}",
+            remainder_lamports,
+            self.deposit.stake_account.key(),
+            self.remainder_stake_account.key(),
+        );
+        let split_instruction = stake::instruction::split(
+            self.deposit.stake_account.to_account_info().key,
+            self.deposit.stake_authority.key,
+            remainder_lamports,
+            &self.remainder_stake_account.key(),
+        )
+        .last()
+        .unwrap()
+        .clone();
+        invoke(
+            &split_instruction,
+            &[
+                self.deposit.stake_program.to_account_info(),
+                self.deposit.stake_account.to_account_info(),
+                self.remainder_stake_account.to_account_info(),
+                self.deposit.stake_authority.to_account_info(),
+            ],
+        )?;
+        // the delegation changed in the CPI
+        self.deposit.stake_account.reload()?;
+
+        self.deposit.process(validator_index)
+    }
+}
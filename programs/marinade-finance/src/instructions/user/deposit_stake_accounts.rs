@@ -0,0 +1,183 @@
+use anchor_lang::prelude::*;
+use anchor_spl::stake::{Stake, StakeAccount};
+use anchor_spl::token::{mint_to, Mint, MintTo, Token, TokenAccount};
+
+use crate::events::user::{DepositStakeAccountEvent, DepositStakeAccountsEvent};
+use crate::instructions::user::deposit_stake_account::absorb_stake_account;
+use crate::state::stake_system::StakeList;
+use crate::state::validator_system::ValidatorList;
+use crate::{error::MarinadeError, require_lte, state::paused_operations::PausedOperations, State};
+
+#[derive(Accounts)]
+pub struct DepositStakeAccounts<'info> {
+    #[account(
+        mut,
+        has_one = msol_mint,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+
+    #[account(
+        mut,
+        address = state.validator_system.validator_list.account,
+    )]
+    pub validator_list: Account<'info, ValidatorList>,
+    #[account(
+        mut,
+        address = state.stake_system.stake_list.account,
+    )]
+    pub stake_list: Account<'info, StakeList>,
+
+    // staker and withdrawer of all the deposited stake accounts
+    pub stake_authority: Signer<'info>,
+
+    #[account(mut)]
+    pub msol_mint: Account<'info, Mint>,
+    /// user mSOL Token account to send the mSOL
+    #[account(
+        mut,
+        token::mint = state.msol_mint
+    )]
+    pub mint_to: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: PDA
+    #[account(
+        seeds = [
+            &state.key().to_bytes(),
+            State::MSOL_MINT_AUTHORITY_SEED
+        ],
+        bump = state.msol_mint_authority_bump_seed
+    )]
+    pub msol_mint_authority: UncheckedAccount<'info>,
+
+    pub clock: Sysvar<'info, Clock>,
+
+    pub token_program: Program<'info, Token>,
+    pub stake_program: Program<'info, Stake>,
+
+    // lockup custodian of the stake accounts with a lockup in force, if any
+    pub custodian: Option<Signer<'info>>,
+}
+
+impl<'info> DepositStakeAccounts<'info> {
+    // fn deposit_stake_accounts()
+    // Same as deposit_stake_account for every stake account in `stake_accounts`,
+    // `validator_indexes[i]` is the index of the validator `stake_accounts[i]` is delegated to.
+    // The mSOL for all of them is minted once, at the price from before the deposit
+    pub fn process(
+        &mut self,
+        stake_accounts: &[AccountInfo<'info>],
+        validator_indexes: Vec<u32>,
+    ) -> Result<()> {
+        self.state.check_paused(PausedOperations::DEPOSITS)?;
+        require_gt!(
+            stake_accounts.len(),
+            0,
+            MarinadeError::StakeAccountsMismatch
+        );
+        require_eq!(
+            stake_accounts.len(),
+            validator_indexes.len(),
+            MarinadeError::StakeAccountsMismatch
+        );
+
+        // impossible to happen check outside bug (msol mint auth is a PDA)
+        require_lte!(
+            self.msol_mint.supply,
+            self.state.msol_supply,
+            MarinadeError::UnregisteredMsolMinted
+        );
+
+        // record values for event log
+        let user_msol_balance = self.mint_to.amount;
+        let total_virtual_staked_lamports = self.state.total_virtual_staked_lamports();
+        let msol_supply = self.state.msol_supply;
+        let total_active_balance = self.state.validator_system.total_active_balance;
+
+        let state_address = self.state.key();
+        let custodian = self
+            .custodian
+            .as_ref()
+            .map(|custodian| custodian.to_account_info());
+        let mut total_delegated: u64 = 0;
+        let mut total_active_delegated: u64 = 0;
+        for (stake_account_info, validator_index) in stake_accounts.iter().zip(validator_indexes) {
+            if !stake_account_info.is_writable {
+                return err!(MarinadeError::RequiredDelegatedStake)
+                    .map_err(|e| e.with_account_name("stake_accounts"));
+            }
+            let stake_account = Account::<StakeAccount>::try_from(stake_account_info)?;
+            let deposited = absorb_stake_account(
+                &mut self.state,
+                &state_address,
+                &self.validator_list.to_account_info(),
+                &self.stake_list.to_account_info(),
+                &stake_account,
+                &self.stake_authority.to_account_info(),
+                custodian.as_ref(),
+                &self.clock,
+                &self.stake_program.to_account_info(),
+                validator_index,
+            )?;
+            total_delegated += deposited.delegated;
+            if !deposited.deactivating {
+                total_active_delegated += deposited.delegated;
+            }
+
+            // the mSOL is minted for all the accounts at the end
+            emit!(DepositStakeAccountEvent {
+                state: state_address,
+                stake: stake_account.key(),
+                delegated: deposited.delegated,
+                withdrawer: deposited.withdrawer,
+                stake_index: self.state.stake_system.stake_count() - 1,
+                validator: deposited.validator,
+                validator_index,
+                validator_active_balance: deposited.validator_active_balance,
+                total_active_balance,
+                user_msol_balance,
+                msol_minted: 0,
+                total_virtual_staked_lamports,
+                msol_supply,
+                deactivating: deposited.deactivating,
+            });
+        }
+
+        self.state.check_staking_cap(total_delegated)?;
+
+        let msol_to_mint = self.state.calc_msol_from_lamports(total_delegated)?;
+
+        mint_to(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                MintTo {
+                    mint: self.msol_mint.to_account_info(),
+                    to: self.mint_to.to_account_info(),
+                    authority: self.msol_mint_authority.to_account_info(),
+                },
+                &[&[
+                    &state_address.to_bytes(),
+                    State::MSOL_MINT_AUTHORITY_SEED,
+                    &[self.state.msol_mint_authority_bump_seed],
+                ]],
+            ),
+            msol_to_mint,
+        )?;
+        self.state.on_msol_mint(msol_to_mint);
+
+        // update total_active_balance
+        self.state.validator_system.total_active_balance += total_active_delegated;
+
+        emit!(DepositStakeAccountsEvent {
+            state: state_address,
+            stake_accounts: stake_accounts.iter().map(|account| account.key()).collect(),
+            delegated: total_delegated,
+            total_active_balance,
+            user_msol_balance,
+            msol_minted: msol_to_mint,
+            total_virtual_staked_lamports,
+            msol_supply
+        });
+        Ok(())
+    }
+}
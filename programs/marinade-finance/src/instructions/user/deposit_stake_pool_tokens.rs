@@ -0,0 +1,103 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::invoke;
+use anchor_spl::token::{Mint, TokenAccount};
+
+use crate::{
+    error::MarinadeError, events::user::DepositStakePoolTokensEvent,
+    instructions::user::deposit_stake_account::DepositStakeAccount, spl_stake_pool,
+    state::paused_operations::PausedOperations,
+};
+
+#[derive(Accounts)]
+pub struct DepositStakePoolTokens<'info> {
+    // deposit.stake_account is created just before as an uninitialized stake account,
+    // deposit.stake_authority owns the pool tokens
+    pub deposit: DepositStakeAccount<'info>,
+
+    /// CHECK: CPI
+    #[account(mut)]
+    pub stake_pool: UncheckedAccount<'info>,
+    /// CHECK: CPI
+    #[account(mut)]
+    pub validator_stake_list: UncheckedAccount<'info>,
+    /// CHECK: CPI
+    pub stake_pool_withdraw_authority: UncheckedAccount<'info>,
+    /// CHECK: CPI, validator or reserve stake account of the pool split into deposit.stake_account
+    #[account(mut)]
+    pub pool_stake_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        token::mint = pool_mint,
+        token::authority = deposit.stake_authority,
+    )]
+    pub pool_token_account: Box<Account<'info, TokenAccount>>,
+    /// CHECK: CPI
+    #[account(mut)]
+    pub pool_fee_account: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub pool_mint: Box<Account<'info, Mint>>,
+
+    /// CHECK: CPI
+    #[account(address = spl_stake_pool::ID)]
+    pub stake_pool_program: UncheckedAccount<'info>,
+}
+
+impl<'info> DepositStakePoolTokens<'info> {
+    // fn deposit_stake_pool_tokens()
+    // Migration from a SPL stake pool in one instruction: `pool_tokens` are withdrawn
+    // as a stake account delegated to the validator of pool_stake_account,
+    // then deposited as in deposit_stake_account
+    pub fn process(&mut self, validator_index: u32, pool_tokens: u64) -> Result<()> {
+        self.deposit
+            .state
+            .check_paused(PausedOperations::DEPOSITS)?;
+        require_gt!(pool_tokens, 0, MarinadeError::StakePoolWithdrawFailed);
+
+        invoke(
+            &spl_stake_pool::withdraw_stake(
+                self.stake_pool.key,
+                self.validator_stake_list.key,
+                self.stake_pool_withdraw_authority.key,
+                self.pool_stake_account.key,
+                &self.deposit.stake_account.key(),
+                self.deposit.stake_authority.key,
+                &self.pool_token_account.key(),
+                self.pool_fee_account.key,
+                &self.pool_mint.key(),
+                pool_tokens,
+            ),
+            &[
+                self.stake_pool_program.to_account_info(),
+                self.stake_pool.to_account_info(),
+                self.validator_stake_list.to_account_info(),
+                self.stake_pool_withdraw_authority.to_account_info(),
+                self.pool_stake_account.to_account_info(),
+                self.deposit.stake_account.to_account_info(),
+                self.deposit.stake_authority.to_account_info(),
+                self.pool_token_account.to_account_info(),
+                self.pool_fee_account.to_account_info(),
+                self.pool_mint.to_account_info(),
+                self.deposit.clock.to_account_info(),
+                self.deposit.token_program.to_account_info(),
+                self.deposit.stake_program.to_account_info(),
+            ],
+        )?;
+        // the stake account was initialized in the CPI
+        self.deposit.stake_account.reload()?;
+        if !self.deposit.stake_account.delegation().is_some() {
+            return err!(MarinadeError::StakePoolWithdrawFailed)
+                .map_err(|e| e.with_account_name("stake_account"));
+        }
+
+        emit!(DepositStakePoolTokensEvent {
+            state: self.deposit.state.key(),
+            stake_pool: self.stake_pool.key(),
+            pool_mint: self.pool_mint.key(),
+            pool_tokens,
+            stake_account: self.deposit.stake_account.key(),
+            stake_account_lamports: self.deposit.stake_account.to_account_info().lamports(),
+        });
+
+        self.deposit.process(validator_index)
+    }
+}
@@ -0,0 +1,321 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::system_program;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::token::{
+    close_account, mint_to, spl_token, transfer as transfer_tokens, CloseAccount, Mint, MintTo,
+    Token, TokenAccount, Transfer as TransferTokens,
+};
+
+use crate::checks::check_token_source_account;
+use crate::error::MarinadeError;
+use crate::events::user::DepositEvent;
+use crate::state::{
+    holding_record::HoldingRecord, liq_pool::LiqPool, paused_operations::PausedOperations,
+    quote::DepositQuote, user_stats::UserStats,
+};
+use crate::{require_lte, State};
+
+#[derive(Accounts)]
+pub struct DepositWsol<'info> {
+    #[account(
+        mut,
+        has_one = msol_mint,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+
+    #[account(mut)]
+    pub msol_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [
+            &state.key().to_bytes(),
+            LiqPool::SOL_LEG_SEED
+        ],
+        bump = state.liq_pool.sol_leg_bump_seed
+    )]
+    pub liq_pool_sol_leg_pda: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        address = state.liq_pool.msol_leg
+    )]
+    pub liq_pool_msol_leg: Box<Account<'info, TokenAccount>>,
+    /// CHECK: PDA
+    #[account(
+        seeds = [
+            &state.key().to_bytes(),
+            LiqPool::MSOL_LEG_AUTHORITY_SEED
+        ],
+        bump = state.liq_pool.msol_leg_authority_bump_seed
+    )]
+    pub liq_pool_msol_leg_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            &state.key().to_bytes(),
+            State::RESERVE_SEED
+        ],
+        bump = state.reserve_bump_seed
+    )]
+    pub reserve_pda: SystemAccount<'info>,
+
+    // user wSOL token account to take the SOL from
+    #[account(
+        mut,
+        token::mint = native_mint
+    )]
+    pub transfer_from: Box<Account<'info, TokenAccount>>,
+    pub transfer_from_authority: Signer<'info>, // transfer_from owner or delegate_authority
+
+    #[account(address = spl_token::native_mint::ID)]
+    pub native_mint: Box<Account<'info, Mint>>,
+    // temporary wSOL account closed into the reserve in the same instruction
+    #[account(
+        init,
+        payer = rent_payer,
+        token::mint = native_mint,
+        token::authority = reserve_pda,
+        seeds = [
+            &state.key().to_bytes(),
+            LiqPool::WSOL_UNWRAP_SEED
+        ],
+        bump,
+    )]
+    pub wsol_unwrap: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        owner = system_program::ID
+    )]
+    pub rent_payer: Signer<'info>, // gets the wsol_unwrap rent back
+
+    /// user mSOL Token account to send the mSOL
+    #[account(
+        mut,
+        token::mint = state.msol_mint
+    )]
+    pub mint_to: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: PDA
+    #[account(
+        seeds = [
+            &state.key().to_bytes(),
+            State::MSOL_MINT_AUTHORITY_SEED
+        ],
+        bump = state.msol_mint_authority_bump_seed
+    )]
+    pub msol_mint_authority: UncheckedAccount<'info>,
+
+    pub rent: Sysvar<'info, Rent>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+
+    // records the deposit time of the mint_to owner for the liquid unstake holding discount
+    #[account(
+        mut,
+        has_one = state
+    )]
+    pub holding_record: Option<Box<Account<'info, HoldingRecord>>>,
+
+    // staking statistics of the mint_to owner
+    #[account(
+        mut,
+        has_one = state
+    )]
+    pub user_stats: Option<Box<Account<'info, UserStats>>>,
+}
+
+impl<'info> DepositWsol<'info> {
+    fn transfer_from_reserve(&self, to: AccountInfo<'info>, lamports: u64) -> Result<()> {
+        transfer(
+            CpiContext::new_with_signer(
+                self.system_program.to_account_info(),
+                Transfer {
+                    from: self.reserve_pda.to_account_info(),
+                    to,
+                },
+                &[&[
+                    &self.state.key().to_bytes(),
+                    State::RESERVE_SEED,
+                    &[self.state.reserve_bump_seed],
+                ]],
+            ),
+            lamports,
+        )
+    }
+
+    // fn deposit_wsol()
+    // Same as deposit but the SOL is taken from a wrapped SOL token account.
+    // The wSOL is unwrapped into the reserve, which pays the liq-pool part of the deposit
+    pub fn process(&mut self, lamports: u64, min_msol_out: u64) -> Result<()> {
+        self.state.check_paused(PausedOperations::DEPOSITS)?;
+        self.state.liq_pool.check_no_flash_loan()?;
+
+        require_gte!(
+            lamports,
+            self.state.min_deposit,
+            MarinadeError::DepositAmountIsTooLow
+        );
+        check_token_source_account(
+            &self.transfer_from,
+            self.transfer_from_authority.key,
+            lamports,
+        )
+        .map_err(|e| e.with_account_name("transfer_from"))?;
+
+        // store for event log
+        let user_sol_balance = self.transfer_from.amount;
+        let user_msol_balance = self.mint_to.amount;
+        let reserve_balance = self.reserve_pda.lamports();
+        let sol_leg_balance = self.liq_pool_sol_leg_pda.lamports();
+
+        // impossible to happen check outside bug (msol mint auth is a PDA)
+        require_lte!(
+            self.msol_mint.supply,
+            self.state.msol_supply,
+            MarinadeError::UnregisteredMsolMinted
+        );
+
+        let total_virtual_staked_lamports = self.state.total_virtual_staked_lamports();
+        let msol_supply = self.state.msol_supply;
+
+        let msol_leg_balance = self.liq_pool_msol_leg.amount;
+        let DepositQuote {
+            msol_out,
+            msol_swapped,
+            sol_swapped,
+            sol_deposited,
+            msol_minted,
+            deposit_fee,
+        } = self
+            .state
+            .quote_deposit(lamports, msol_leg_balance, sol_leg_balance)?;
+        // protect the user from the mSOL price moving between quote and execution
+        require_gte!(msol_out, min_msol_out, MarinadeError::SlippageExceeded);
+        if sol_deposited > 0 {
+            self.state.check_staking_cap(sol_deposited)?;
+        }
+
+        // unwrap: move the wSOL to the temporary account and close it into the reserve
+        transfer_tokens(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                TransferTokens {
+                    from: self.transfer_from.to_account_info(),
+                    to: self.wsol_unwrap.to_account_info(),
+                    authority: self.transfer_from_authority.to_account_info(),
+                },
+            ),
+            lamports,
+        )?;
+        let wsol_unwrap_rent = self.wsol_unwrap.to_account_info().lamports() - lamports;
+        close_account(CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            CloseAccount {
+                account: self.wsol_unwrap.to_account_info(),
+                destination: self.reserve_pda.to_account_info(),
+                authority: self.reserve_pda.to_account_info(),
+            },
+            &[&[
+                &self.state.key().to_bytes(),
+                State::RESERVE_SEED,
+                &[self.state.reserve_bump_seed],
+            ]],
+        ))?;
+        // return the temporary account rent
+        self.transfer_from_reserve(self.rent_payer.to_account_info(), wsol_unwrap_rent)?;
+
+        //if we can sell from the LiqPool
+        if msol_swapped > 0 {
+            // transfer mSOL to the user
+            transfer_tokens(
+                CpiContext::new_with_signer(
+                    self.token_program.to_account_info(),
+                    TransferTokens {
+                        from: self.liq_pool_msol_leg.to_account_info(),
+                        to: self.mint_to.to_account_info(),
+                        authority: self.liq_pool_msol_leg_authority.to_account_info(),
+                    },
+                    &[&[
+                        &self.state.key().to_bytes(),
+                        LiqPool::MSOL_LEG_AUTHORITY_SEED,
+                        &[self.state.liq_pool.msol_leg_authority_bump_seed],
+                    ]],
+                ),
+                msol_swapped,
+            )?;
+            // the unwrapped lamports of the swap go to the LiqPool
+            self.transfer_from_reserve(self.liq_pool_sol_leg_pda.to_account_info(), sol_swapped)?;
+        }
+
+        // the imbalance fee refills the SOL leg
+        if deposit_fee > 0 {
+            self.transfer_from_reserve(self.liq_pool_sol_leg_pda.to_account_info(), deposit_fee)?;
+        }
+
+        // the rest of the unwrapped lamports stay in the reserve
+        if sol_deposited > 0 {
+            self.state.on_transfer_to_reserve(sol_deposited);
+        }
+
+        // mint the mSOL we own the user besides the amount we already swapped
+        if msol_minted > 0 {
+            mint_to(
+                CpiContext::new_with_signer(
+                    self.token_program.to_account_info(),
+                    MintTo {
+                        mint: self.msol_mint.to_account_info(),
+                        to: self.mint_to.to_account_info(),
+                        authority: self.msol_mint_authority.to_account_info(),
+                    },
+                    &[&[
+                        &self.state.key().to_bytes(),
+                        State::MSOL_MINT_AUTHORITY_SEED,
+                        &[self.state.msol_mint_authority_bump_seed],
+                    ]],
+                ),
+                msol_minted,
+            )?;
+            self.state.on_msol_mint(msol_minted);
+        }
+
+        if let Some(holding_record) = &mut self.holding_record {
+            require_keys_eq!(
+                holding_record.owner,
+                self.mint_to.owner,
+                MarinadeError::WrongHoldingRecord
+            );
+            holding_record.on_deposit(msol_out, Clock::get()?.unix_timestamp);
+        }
+        if let Some(user_stats) = &mut self.user_stats {
+            require_keys_eq!(
+                user_stats.owner,
+                self.mint_to.owner,
+                MarinadeError::WrongUserStats
+            );
+            user_stats.on_deposit(lamports, msol_out, Clock::get()?.epoch);
+        }
+
+        emit!(DepositEvent {
+            state: self.state.key(),
+            sol_owner: self.transfer_from.owner,
+            user_sol_balance,
+            user_msol_balance,
+            sol_leg_balance,
+            msol_leg_balance,
+            reserve_balance,
+            sol_swapped,
+            msol_swapped,
+            sol_deposited,
+            msol_minted,
+            deposit_fee,
+            total_virtual_staked_lamports,
+            msol_supply
+        });
+
+        Ok(())
+    }
+}
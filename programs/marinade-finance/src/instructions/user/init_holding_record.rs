@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::system_program;
+
+use crate::{error::MarinadeError, state::holding_record::HoldingRecord, State};
+
+#[derive(Accounts)]
+pub struct InitHoldingRecord<'info> {
+    #[account(
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = HoldingRecord::LEN,
+        seeds = [
+            &state.key().to_bytes(),
+            HoldingRecord::SEED,
+            &owner.key().to_bytes()
+        ],
+        bump,
+    )]
+    pub holding_record: Box<Account<'info, HoldingRecord>>,
+    #[account(
+        mut,
+        owner = system_program::ID
+    )]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitHoldingRecord<'info> {
+    // fn init_holding_record()
+    // the deposits made before the record exists are not counted
+    pub fn process(&mut self, bump: u8) -> Result<()> {
+        self.holding_record.set_inner(HoldingRecord {
+            state: self.state.key(),
+            owner: self.owner.key(),
+            bump,
+            msol_amount: 0,
+            deposited_at: 0,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::system_program;
+
+use crate::{error::MarinadeError, state::user_stats::UserStats, State};
+
+#[derive(Accounts)]
+pub struct InitUserStats<'info> {
+    #[account(
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = UserStats::LEN,
+        seeds = [
+            &state.key().to_bytes(),
+            UserStats::SEED,
+            &owner.key().to_bytes()
+        ],
+        bump,
+    )]
+    pub user_stats: Box<Account<'info, UserStats>>,
+    #[account(
+        mut,
+        owner = system_program::ID
+    )]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitUserStats<'info> {
+    // fn init_user_stats()
+    // opt-in, the deposits and unstakes made before the stats exist are not counted
+    pub fn process(&mut self, bump: u8) -> Result<()> {
+        self.user_stats.set_inner(UserStats {
+            state: self.state.key(),
+            owner: self.owner.key(),
+            bump,
+            total_deposited: 0,
+            total_unstaked: 0,
+            msol_amount: 0,
+            cost_basis: 0,
+            realized_yield: 0,
+            first_deposit_epoch: 0,
+        });
+        Ok(())
+    }
+}
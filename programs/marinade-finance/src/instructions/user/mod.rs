@@ -1,7 +1,19 @@
 pub mod deposit;
 pub mod deposit_stake_account;
+pub mod deposit_stake_account_partial;
+pub mod deposit_stake_accounts;
+pub mod deposit_stake_pool_tokens;
+pub mod deposit_wsol;
+pub mod init_holding_record;
+pub mod init_user_stats;
 pub mod withdraw_stake_account;
 
 pub use deposit::*;
 pub use deposit_stake_account::*;
+pub use deposit_stake_account_partial::*;
+pub use deposit_stake_accounts::*;
+pub use deposit_stake_pool_tokens::*;
+pub use deposit_wsol::*;
+pub use init_holding_record::*;
+pub use init_user_stats::*;
 pub use withdraw_stake_account::*;
@@ -3,6 +3,7 @@ use crate::{
     error::MarinadeError,
     events::user::WithdrawStakeAccountEvent,
     state::{
+        paused_operations::PausedOperations,
         stake_system::{StakeList, StakeSystem},
         validator_system::ValidatorList,
     },
@@ -18,7 +19,7 @@ use anchor_lang::{
     },
 };
 use anchor_spl::{
-    stake::{Stake, StakeAccount},
+    stake::{deactivate_stake, DeactivateStake, Stake, StakeAccount},
     token::{burn, transfer, Burn, Mint, Token, TokenAccount, Transfer},
 };
 
@@ -30,6 +31,7 @@ pub struct WithdrawStakeAccount<'info> {
         mut,
         has_one = msol_mint,
         has_one = treasury_msol_account,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
     )]
     pub state: Box<Account<'info, State>>,
 
@@ -101,14 +103,17 @@ pub struct WithdrawStakeAccount<'info> {
 }
 
 impl<'info> WithdrawStakeAccount<'info> {
+    /// `deactivate` starts the deactivation of the split stake account before it is handed over,
+    /// so the user can withdraw the SOL from it in the next epoch without any extra step
     pub fn process(
         &mut self,
         stake_index: u32,
         validator_index: u32,
         msol_amount: u64,
         beneficiary: Pubkey,
+        deactivate: bool,
     ) -> Result<()> {
-        require!(!self.state.paused, MarinadeError::ProgramIsPaused);
+        self.state.check_paused(PausedOperations::LIQUID_UNSTAKE)?;
         require!(
             self.state.withdraw_stake_account_enabled,
             MarinadeError::WithdrawStakeAccountIsNotEnabled
@@ -174,7 +179,7 @@ impl<'info> WithdrawStakeAccount<'info> {
             // apply withdraw_stake_account_fee to avoid economical attacks
             // withdraw_stake_account_fee must be >= one epoch staking rewards
             let withdraw_stake_account_fee_lamports =
-                self.state.withdraw_stake_account_fee.apply(sol_value);
+                self.state.withdraw_stake_account_fee.apply_ceil(sol_value);
             // The mSOL fee value is sending to the treasury but
             // the corresponding SOL value is not delivering inside the stake to the user
             // because it is a fee user is paying for running this instruction
@@ -276,6 +281,23 @@ impl<'info> WithdrawStakeAccount<'info> {
             ]],
         )?;
 
+        if deactivate {
+            // the split stake account inherits the deposit authority as staker
+            deactivate_stake(CpiContext::new_with_signer(
+                self.stake_program.to_account_info(),
+                DeactivateStake {
+                    stake: self.split_stake_account.to_account_info(),
+                    staker: self.stake_deposit_authority.to_account_info(),
+                    clock: self.clock.to_account_info(),
+                },
+                &[&[
+                    &self.state.key().to_bytes(),
+                    StakeSystem::STAKE_DEPOSIT_SEED,
+                    &[self.state.stake_system.stake_deposit_bump_seed],
+                ]],
+            ))?;
+        }
+
         stake.last_update_delegated_lamports -= split_lamports;
 
         // we now consider amount no longer "active" for this specific validator
@@ -357,6 +379,7 @@ impl<'info> WithdrawStakeAccount<'info> {
             msol_fees,
             split_stake: self.split_stake_account.key(),
             split_lamports,
+            deactivated: deactivate,
             fee_bp_cents: self.state.withdraw_stake_account_fee.bp_cents,
             total_virtual_staked_lamports,
             msol_supply,
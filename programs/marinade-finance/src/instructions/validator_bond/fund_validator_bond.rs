@@ -0,0 +1,119 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::system_program;
+use anchor_spl::token::{transfer, Mint, Token, TokenAccount, Transfer};
+
+use crate::{
+    checks::check_token_source_account,
+    error::MarinadeError,
+    events::validator_bond::FundValidatorBondEvent,
+    state::{paused_operations::PausedOperations, validator_bond::ValidatorBond},
+    State,
+};
+
+#[derive(Accounts)]
+pub struct FundValidatorBond<'info> {
+    #[account(
+        has_one = msol_mint,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+    pub msol_mint: Box<Account<'info, Mint>>,
+
+    /// CHECK: only the address is used
+    pub validator_vote: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = funder,
+        space = ValidatorBond::LEN,
+        seeds = [
+            &state.key().to_bytes(),
+            ValidatorBond::SEED,
+            &validator_vote.key().to_bytes()
+        ],
+        bump,
+    )]
+    pub validator_bond: Box<Account<'info, ValidatorBond>>,
+    /// CHECK: PDA
+    #[account(
+        seeds = [
+            &state.key().to_bytes(),
+            ValidatorBond::AUTHORITY_SEED
+        ],
+        bump,
+    )]
+    pub validator_bond_authority: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = funder,
+        token::mint = msol_mint,
+        token::authority = validator_bond_authority,
+        seeds = [
+            &state.key().to_bytes(),
+            ValidatorBond::VAULT_SEED,
+            &validator_vote.key().to_bytes()
+        ],
+        bump,
+    )]
+    pub validator_bond_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = state.msol_mint
+    )]
+    pub transfer_from: Box<Account<'info, TokenAccount>>,
+    // transfer_from owner or delegate_authority, becomes the bond authority when the bond is created
+    #[account(
+        mut,
+        owner = system_program::ID
+    )]
+    pub funder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+impl<'info> FundValidatorBond<'info> {
+    // fn fund_validator_bond()
+    // anybody can add mSOL to the bond, only the bond authority can withdraw it
+    pub fn process(&mut self, amount: u64, bump: u8, vault_bump: u8) -> Result<()> {
+        self.state.check_paused(PausedOperations::VALIDATOR_OPS)?;
+        check_token_source_account(&self.transfer_from, self.funder.key, amount)
+            .map_err(|e| e.with_account_name("transfer_from"))?;
+
+        if self.validator_bond.state == Pubkey::default() {
+            self.validator_bond.set_inner(ValidatorBond {
+                state: self.state.key(),
+                validator: self.validator_vote.key(),
+                authority: self.funder.key(),
+                bump,
+                vault_bump,
+                amount: 0,
+                last_slash_epoch: 0,
+            });
+        }
+
+        transfer(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                Transfer {
+                    from: self.transfer_from.to_account_info(),
+                    to: self.validator_bond_vault.to_account_info(),
+                    authority: self.funder.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+        self.validator_bond.amount += amount;
+
+        emit!(FundValidatorBondEvent {
+            state: self.state.key(),
+            validator: self.validator_vote.key(),
+            funder: self.funder.key(),
+            amount,
+            bond_amount: self.validator_bond.amount,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,7 @@
+pub mod fund_validator_bond;
+pub mod slash_validator_bond;
+pub mod withdraw_validator_bond;
+
+pub use fund_validator_bond::*;
+pub use slash_validator_bond::*;
+pub use withdraw_validator_bond::*;
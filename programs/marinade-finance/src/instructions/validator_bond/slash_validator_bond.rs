@@ -0,0 +1,122 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{transfer, Token, TokenAccount, Transfer};
+
+use crate::{
+    error::MarinadeError,
+    events::validator_bond::SlashValidatorBondEvent,
+    state::{
+        paused_operations::PausedOperations, validator_bond::ValidatorBond,
+        validator_health::ValidatorHealth,
+    },
+    State,
+};
+
+#[derive(Accounts)]
+pub struct SlashValidatorBond<'info> {
+    #[account(
+        has_one = treasury_msol_account,
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+
+    /// CHECK: only the address is used
+    pub validator_vote: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        has_one = state,
+        seeds = [
+            &state.key().to_bytes(),
+            ValidatorBond::SEED,
+            &validator_vote.key().to_bytes()
+        ],
+        bump = validator_bond.bump,
+    )]
+    pub validator_bond: Box<Account<'info, ValidatorBond>>,
+    /// CHECK: PDA
+    #[account(
+        seeds = [
+            &state.key().to_bytes(),
+            ValidatorBond::AUTHORITY_SEED
+        ],
+        bump,
+    )]
+    pub validator_bond_authority: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            &state.key().to_bytes(),
+            ValidatorBond::VAULT_SEED,
+            &validator_vote.key().to_bytes()
+        ],
+        bump = validator_bond.vault_bump,
+    )]
+    pub validator_bond_vault: Box<Account<'info, TokenAccount>>,
+    #[account(
+        has_one = state,
+        seeds = [
+            &state.key().to_bytes(),
+            ValidatorHealth::SEED,
+            &validator_vote.key().to_bytes()
+        ],
+        bump = validator_health.bump,
+    )]
+    pub validator_health: Box<Account<'info, ValidatorHealth>>,
+
+    #[account(mut)]
+    pub treasury_msol_account: Box<Account<'info, TokenAccount>>,
+
+    pub clock: Sysvar<'info, Clock>,
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> SlashValidatorBond<'info> {
+    // fn slash_validator_bond()
+    // permissionless, sends State::validator_bond_slash of the bond of a flagged validator
+    // to the treasury, at most once per epoch
+    pub fn process(&mut self, validator_bond_authority_bump: u8) -> Result<()> {
+        self.state.check_paused(PausedOperations::VALIDATOR_OPS)?;
+
+        let flags = self.validator_health.flags & ValidatorBond::SLASHABLE_FLAGS;
+        require_neq!(flags, 0, MarinadeError::ValidatorIsNotFlagged);
+        require_gt!(
+            self.clock.epoch,
+            self.validator_bond.last_slash_epoch,
+            MarinadeError::ValidatorBondAlreadySlashed
+        );
+
+        let amount = self
+            .state
+            .validator_bond_slash
+            .apply(self.validator_bond.amount);
+        if amount > 0 {
+            transfer(
+                CpiContext::new_with_signer(
+                    self.token_program.to_account_info(),
+                    Transfer {
+                        from: self.validator_bond_vault.to_account_info(),
+                        to: self.treasury_msol_account.to_account_info(),
+                        authority: self.validator_bond_authority.to_account_info(),
+                    },
+                    &[&[
+                        &self.state.key().to_bytes(),
+                        ValidatorBond::AUTHORITY_SEED,
+                        &[validator_bond_authority_bump],
+                    ]],
+                ),
+                amount,
+            )?;
+        }
+        self.validator_bond.amount -= amount;
+        self.validator_bond.last_slash_epoch = self.clock.epoch;
+
+        emit!(SlashValidatorBondEvent {
+            state: self.state.key(),
+            validator: self.validator_vote.key(),
+            flags,
+            amount,
+            bond_amount: self.validator_bond.amount,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,162 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::system_program;
+use anchor_spl::token::{transfer, Token, TokenAccount, Transfer};
+
+use crate::{
+    error::MarinadeError,
+    events::validator_bond::WithdrawValidatorBondEvent,
+    state::{
+        paused_operations::PausedOperations, validator_bond::ValidatorBond,
+        validator_health::ValidatorHealth, validator_system::ValidatorRecord,
+    },
+    State, ID,
+};
+
+#[derive(Accounts)]
+pub struct WithdrawValidatorBond<'info> {
+    #[account(
+        constraint = state.check_version() @ MarinadeError::StaleStateVersion
+    )]
+    pub state: Box<Account<'info, State>>,
+
+    /// CHECK: only the address is used
+    pub validator_vote: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        has_one = state,
+        has_one = authority,
+        seeds = [
+            &state.key().to_bytes(),
+            ValidatorBond::SEED,
+            &validator_vote.key().to_bytes()
+        ],
+        bump = validator_bond.bump,
+    )]
+    pub validator_bond: Box<Account<'info, ValidatorBond>>,
+    #[account(
+        mut,
+        owner = system_program::ID
+    )]
+    pub authority: Signer<'info>,
+    /// CHECK: PDA
+    #[account(
+        seeds = [
+            &state.key().to_bytes(),
+            ValidatorBond::AUTHORITY_SEED
+        ],
+        bump,
+    )]
+    pub validator_bond_authority: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            &state.key().to_bytes(),
+            ValidatorBond::VAULT_SEED,
+            &validator_vote.key().to_bytes()
+        ],
+        bump = validator_bond.vault_bump,
+    )]
+    pub validator_bond_vault: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: owned by the program while the validator is in the list
+    #[account(
+        seeds = [
+            &state.key().to_bytes(),
+            ValidatorRecord::DUPLICATE_FLAG_SEED,
+            &validator_vote.key().to_bytes(),
+        ],
+        bump,
+    )]
+    pub duplication_flag: UncheckedAccount<'info>,
+    // a validator flagged for misbehavior can't escape the slashing
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = ValidatorHealth::LEN,
+        seeds = [
+            &state.key().to_bytes(),
+            ValidatorHealth::SEED,
+            &validator_vote.key().to_bytes()
+        ],
+        bump,
+    )]
+    pub validator_health: Box<Account<'info, ValidatorHealth>>,
+
+    #[account(
+        mut,
+        token::mint = state.msol_mint
+    )]
+    pub transfer_msol_to: Box<Account<'info, TokenAccount>>,
+
+    pub clock: Sysvar<'info, Clock>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> WithdrawValidatorBond<'info> {
+    // fn withdraw_validator_bond()
+    // while the validator is in the list at least State::min_validator_bond must stay in the bond
+    pub fn process(
+        &mut self,
+        amount: u64,
+        validator_bond_authority_bump: u8,
+        validator_health_bump: u8,
+    ) -> Result<()> {
+        self.state.check_paused(PausedOperations::VALIDATOR_OPS)?;
+
+        if self.validator_health.state == Pubkey::default() {
+            self.validator_health.set_inner(ValidatorHealth::new(
+                self.state.key(),
+                self.validator_vote.key(),
+                validator_health_bump,
+                self.clock.epoch,
+            ));
+        }
+        if !self.validator_health.flags & ValidatorBond::SLASHABLE_FLAGS == 0 {
+            return err!(MarinadeError::ValidatorIsFlagged)
+                .map_err(|e| e.with_account_name("validator_health"));
+        }
+
+        require_lte!(
+            amount,
+            self.validator_bond.amount,
+            MarinadeError::NotEnoughUserFunds
+        );
+        let bond_amount = self.validator_bond.amount - amount;
+        if *self.duplication_flag.owner == ID {
+            require_gte!(
+                bond_amount,
+                self.state.min_validator_bond,
+                MarinadeError::ValidatorBondIsTooLow
+            );
+        }
+
+        transfer(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                Transfer {
+                    from: self.validator_bond_vault.to_account_info(),
+                    to: self.transfer_msol_to.to_account_info(),
+                    authority: self.validator_bond_authority.to_account_info(),
+                },
+                &[&[
+                    &self.state.key().to_bytes(),
+                    ValidatorBond::AUTHORITY_SEED,
+                    &[validator_bond_authority_bump],
+                ]],
+            ),
+            amount,
+        )?;
+        self.validator_bond.amount = bond_amount;
+
+        emit!(WithdrawValidatorBondEvent {
+            state: self.state.key(),
+            validator: self.validator_vote.key(),
+            authority: self.authority.key(),
+            amount,
+            bond_amount,
+        });
+
+        Ok(())
+    }
+}
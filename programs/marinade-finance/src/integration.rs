@@ -0,0 +1,280 @@
+//! Helpers for the programs integrating through CPI, enabled with the `cpi` feature.
+//! The CPI calls are the anchor generated `crate::cpi` functions, this module derives the
+//! program PDAs, fills the account lists of the main user instructions and quotes their outputs
+//! the same way the instructions compute them
+
+use anchor_lang::{prelude::*, solana_program::sysvar, system_program};
+use anchor_spl::token::{spl_token, TokenAccount};
+
+use crate::{
+    accounts,
+    state::{
+        fee_curve::FeeCurve,
+        quote::{DepositQuote, LiquidUnstakeQuote, RemoveLiquidityQuote},
+        Fee,
+    },
+    State,
+};
+
+/// Program PDAs of a marinade `state`
+pub mod pda {
+    use crate::{
+        state::{
+            holding_record::HoldingRecord, liq_pool::LiqPool, lp_deposit_record::LpDepositRecord,
+        },
+        State, ID,
+    };
+    use anchor_lang::prelude::*;
+
+    fn find(seeds: &[&[u8]]) -> Pubkey {
+        Pubkey::find_program_address(seeds, &ID).0
+    }
+
+    pub fn reserve(state: &Pubkey) -> Pubkey {
+        find(&[&state.to_bytes(), State::RESERVE_SEED])
+    }
+
+    pub fn msol_mint_authority(state: &Pubkey) -> Pubkey {
+        find(&[&state.to_bytes(), State::MSOL_MINT_AUTHORITY_SEED])
+    }
+
+    pub fn liq_pool_sol_leg(state: &Pubkey) -> Pubkey {
+        find(&[&state.to_bytes(), LiqPool::SOL_LEG_SEED])
+    }
+
+    pub fn liq_pool_msol_leg_authority(state: &Pubkey) -> Pubkey {
+        find(&[&state.to_bytes(), LiqPool::MSOL_LEG_AUTHORITY_SEED])
+    }
+
+    pub fn lp_mint_authority(state: &Pubkey) -> Pubkey {
+        find(&[&state.to_bytes(), LiqPool::LP_MINT_AUTHORITY_SEED])
+    }
+
+    /// add_liquidity record of the LP token account
+    pub fn lp_deposit_record(state: &Pubkey, lp_token_account: &Pubkey) -> Pubkey {
+        find(&[
+            &state.to_bytes(),
+            LpDepositRecord::SEED,
+            &lp_token_account.to_bytes(),
+        ])
+    }
+
+    pub fn holding_record(state: &Pubkey, owner: &Pubkey) -> Pubkey {
+        find(&[&state.to_bytes(), HoldingRecord::SEED, &owner.to_bytes()])
+    }
+}
+
+// optional accounts stored in State, Pubkey::default() when not in use
+fn configured(address: Pubkey) -> Option<Pubkey> {
+    if address == Pubkey::default() {
+        None
+    } else {
+        Some(address)
+    }
+}
+
+/// deposit of the SOL of `transfer_from` into the existing mSOL token account `mint_to`
+pub fn deposit_accounts(
+    state: &Pubkey,
+    state_data: &State,
+    transfer_from: &Pubkey,
+    mint_to: &Pubkey,
+) -> accounts::Deposit {
+    accounts::Deposit {
+        state: *state,
+        msol_mint: state_data.msol_mint,
+        liq_pool_sol_leg_pda: pda::liq_pool_sol_leg(state),
+        liq_pool_msol_leg: state_data.liq_pool.msol_leg,
+        liq_pool_msol_leg_authority: pda::liq_pool_msol_leg_authority(state),
+        reserve_pda: pda::reserve(state),
+        transfer_from: *transfer_from,
+        mint_to: *mint_to,
+        msol_mint_authority: pda::msol_mint_authority(state),
+        system_program: system_program::ID,
+        token_program: spl_token::ID,
+        mint_to_owner: None,
+        ata_rent_payer: None,
+        associated_token_program: None,
+        holding_record: None,
+        user_stats: None,
+    }
+}
+
+/// liquid_unstake of the mSOL of `get_msol_from`, the SOL goes to `transfer_sol_to`
+pub fn liquid_unstake_accounts(
+    state: &Pubkey,
+    state_data: &State,
+    get_msol_from: &Pubkey,
+    get_msol_from_authority: &Pubkey,
+    transfer_sol_to: &Pubkey,
+) -> accounts::LiquidUnstake {
+    let lp_lock_fee_vault = if state_data.liq_pool_ext.locked_lp_supply > 0
+        && !state_data.liq_pool_ext.lock_fee_boost.is_zero()
+    {
+        Some(state_data.liq_pool_ext.lp_lock_fee_vault)
+    } else {
+        None
+    };
+    accounts::LiquidUnstake {
+        state: *state,
+        msol_mint: state_data.msol_mint,
+        liq_pool_sol_leg_pda: pda::liq_pool_sol_leg(state),
+        liq_pool_msol_leg: state_data.liq_pool.msol_leg,
+        treasury_msol_account: state_data.treasury_msol_account,
+        get_msol_from: *get_msol_from,
+        get_msol_from_authority: *get_msol_from_authority,
+        transfer_sol_to: *transfer_sol_to,
+        fee_curve: configured(state_data.liq_pool_ext.fee_curve),
+        lp_lock_fee_vault,
+        clock: sysvar::clock::ID,
+        lp_price_oracle: None,
+        system_program: system_program::ID,
+        token_program: spl_token::ID,
+        integrator_partner: None,
+        integrator_authority: None,
+        holding_record: None,
+        user_stats: None,
+    }
+}
+
+/// add_liquidity of the SOL of `transfer_from` into the existing LP token account `mint_to`
+pub fn add_liquidity_accounts(
+    state: &Pubkey,
+    state_data: &State,
+    transfer_from: &Pubkey,
+    mint_to: &Pubkey,
+) -> accounts::AddLiquidity {
+    accounts::AddLiquidity {
+        state: *state,
+        lp_mint: state_data.liq_pool.lp_mint,
+        lp_mint_authority: pda::lp_mint_authority(state),
+        liq_pool_msol_leg: state_data.liq_pool.msol_leg,
+        liq_pool_sol_leg_pda: pda::liq_pool_sol_leg(state),
+        transfer_from: *transfer_from,
+        mint_to: *mint_to,
+        lp_deposit_record: pda::lp_deposit_record(state, mint_to),
+        lp_price_oracle: None,
+        system_program: system_program::ID,
+        token_program: spl_token::ID,
+        mint_to_owner: None,
+        ata_rent_payer: None,
+        associated_token_program: None,
+        fee_curve: configured(state_data.liq_pool_ext.fee_curve),
+    }
+}
+
+/// remove_liquidity of the LP tokens of `burn_from`
+pub fn remove_liquidity_accounts(
+    state: &Pubkey,
+    state_data: &State,
+    burn_from: &Pubkey,
+    burn_from_authority: &Pubkey,
+    transfer_sol_to: &Pubkey,
+    transfer_msol_to: &Pubkey,
+) -> accounts::RemoveLiquidity {
+    accounts::RemoveLiquidity {
+        state: *state,
+        lp_mint: state_data.liq_pool.lp_mint,
+        burn_from: *burn_from,
+        lp_deposit_record: pda::lp_deposit_record(state, burn_from),
+        burn_from_authority: *burn_from_authority,
+        transfer_sol_to: *transfer_sol_to,
+        transfer_msol_to: *transfer_msol_to,
+        liq_pool_sol_leg_pda: pda::liq_pool_sol_leg(state),
+        liq_pool_msol_leg: state_data.liq_pool.msol_leg,
+        liq_pool_msol_leg_authority: pda::liq_pool_msol_leg_authority(state),
+        lp_price_oracle: None,
+        system_program: system_program::ID,
+        token_program: spl_token::ID,
+    }
+}
+
+/// order_unstake of the mSOL of `burn_msol_from`,
+/// `new_ticket_account` is created just before with the program as owner
+pub fn order_unstake_accounts(
+    state: &Pubkey,
+    state_data: &State,
+    burn_msol_from: &Pubkey,
+    burn_msol_authority: &Pubkey,
+    new_ticket_account: &Pubkey,
+) -> accounts::OrderUnstake {
+    accounts::OrderUnstake {
+        state: *state,
+        msol_mint: state_data.msol_mint,
+        burn_msol_from: *burn_msol_from,
+        burn_msol_authority: *burn_msol_authority,
+        new_ticket_account: *new_ticket_account,
+        clock: sysvar::clock::ID,
+        rent: sysvar::rent::ID,
+        token_program: spl_token::ID,
+        user_stats: None,
+    }
+}
+
+/// mSOL `deposit` mints or sells for `lamports`
+pub fn expected_deposit(
+    state: &State,
+    lamports: u64,
+    liq_pool_sol_leg_pda: &AccountInfo,
+    liq_pool_msol_leg: &TokenAccount,
+) -> Result<DepositQuote> {
+    state.quote_deposit(
+        lamports,
+        liq_pool_msol_leg.amount,
+        liq_pool_sol_leg_pda.lamports(),
+    )
+}
+
+/// SOL `liquid_unstake` pays for `msol_amount`, before the integrator and holding discounts.
+/// `fee_curve` is the account set in LiqPoolExt::fee_curve
+pub fn expected_liquid_unstake(
+    state: &State,
+    msol_amount: u64,
+    liq_pool_sol_leg_pda: &AccountInfo,
+    fee_curve: Option<&FeeCurve>,
+) -> Result<LiquidUnstakeQuote> {
+    state.quote_liquid_unstake(
+        msol_amount,
+        liq_pool_sol_leg_pda.lamports(),
+        fee_curve,
+        None,
+        Fee::default(),
+    )
+}
+
+/// LP tokens `add_liquidity` mints for `lamports`, without the add liquidity bonus
+pub fn expected_add_liquidity(
+    state: &State,
+    lamports: u64,
+    liq_pool_sol_leg_pda: &AccountInfo,
+    liq_pool_msol_leg: &TokenAccount,
+) -> Result<u64> {
+    let shares = state.quote_add_liquidity(
+        lamports,
+        liq_pool_sol_leg_pda.lamports(),
+        liq_pool_msol_leg.amount,
+        state.liq_pool.lp_supply,
+    )?;
+    state.quote_lp_shares_to_mint(shares)
+}
+
+/// SOL and mSOL `remove_liquidity` pays for `tokens`
+pub fn expected_remove_liquidity(
+    state: &State,
+    tokens: u64,
+    liq_pool_sol_leg_pda: &AccountInfo,
+    liq_pool_msol_leg: &TokenAccount,
+) -> Result<RemoveLiquidityQuote> {
+    state.quote_remove_liquidity(
+        tokens,
+        liq_pool_sol_leg_pda.lamports(),
+        liq_pool_msol_leg.amount,
+        state.liq_pool.lp_supply,
+    )
+}
+
+/// lamports of the ticket `order_unstake` creates for `msol_amount`
+pub fn expected_order_unstake(state: &State, msol_amount: u64) -> Result<u64> {
+    let lamports = state.msol_to_sol(msol_amount)?;
+    Ok(lamports - state.delayed_unstake_fee.apply_ceil(lamports))
+}
@@ -4,18 +4,34 @@ use anchor_lang::prelude::*;
 
 use error::MarinadeError;
 
+pub mod address_lookup_table;
+pub mod associated_token;
 pub mod calc;
 pub mod checks;
 pub mod error;
 pub mod events;
 pub mod instructions;
+#[cfg(feature = "cpi")]
+pub mod integration;
+pub mod spl_governance;
+pub mod spl_stake_pool;
 pub mod state;
+pub mod tip_distribution;
+pub mod token_metadata;
+pub mod vote_account;
 
 use instructions::*;
 
 #[cfg(not(feature = "no-entrypoint"))]
 use solana_security_txt::security_txt;
-pub use state::State;
+pub use state::{
+    fee_curve::{FeeCurvePoint, FeeSizeTier},
+    holding_record::HoldingDiscountTier,
+    quote::{DepositQuote, LiquidUnstakeQuote, RemoveLiquidityQuote},
+    roles::Role,
+    treasury_splitter::TreasuryRecipient,
+    Fee, State,
+};
 
 declare_id!("MarBmsSgKXdrN1egZf5sqe1TMai9K1rChYNDJgjq7aD");
 
@@ -31,10 +47,15 @@ security_txt! {
     auditors: "https://docs.marinade.finance/marinade-protocol/security/audits"
 }
 
-fn check_context<T>(ctx: &Context<T>) -> Result<()> {
+fn check_program_id<T>(ctx: &Context<T>) -> Result<()> {
     if !check_id(ctx.program_id) {
         return err!(MarinadeError::InvalidProgramId);
     }
+    Ok(())
+}
+
+fn check_context<T>(ctx: &Context<T>) -> Result<()> {
+    check_program_id(ctx)?;
     // make sure there are no extra accounts
     if !ctx.remaining_accounts.is_empty() {
         return err!(MarinadeError::UnexpectedAccount);
@@ -65,6 +86,12 @@ pub mod marinade_finance {
         Ok(())
     }
 
+    // upgrades the state account to the current State::VERSION layout
+    pub fn migrate_state(ctx: Context<MigrateState>) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts.process()
+    }
+
     pub fn change_authority(
         ctx: Context<ChangeAuthority>,
         data: ChangeAuthorityData,
@@ -73,11 +100,99 @@ pub mod marinade_finance {
         ctx.accounts.process(data)
     }
 
+    // hands the admin or the validator manager authority to a SPL Governance (Realms) DAO
+    pub fn set_governance_authority(
+        ctx: Context<SetGovernanceAuthority>,
+        authority: GovernedAuthority,
+    ) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts.process(authority)
+    }
+
+    // permissionless, fails unless the authority is held by the governance
+    pub fn check_governance_authority(
+        ctx: Context<CheckGovernanceAuthority>,
+        authority: GovernedAuthority,
+    ) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts.process(authority)
+    }
+
+    // a role not granted is held by the admin
+    pub fn grant_role(ctx: Context<ManageRoles>, role: Role, authority: Pubkey) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts.grant_role(role, authority)
+    }
+
+    pub fn revoke_role(ctx: Context<ManageRoles>, role: Role) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts.revoke_role(role)
+    }
+
     pub fn add_validator(ctx: Context<AddValidator>, score: u32) -> Result<()> {
         check_context(&ctx)?;
         ctx.accounts.process(score)
     }
 
+    // permissionless, the validator stake accounts proving State::min_validator_stake
+    // are passed in remaining_accounts
+    pub fn add_validator_permissionless<'info>(
+        ctx: Context<'_, '_, '_, 'info, AddValidatorPermissionless<'info>>,
+    ) -> Result<()> {
+        check_program_id(&ctx)?;
+        ctx.accounts.process(
+            ctx.remaining_accounts,
+            *ctx.bumps.get("validator_health").unwrap(),
+        )
+    }
+
+    // signed by the validator identity
+    pub fn set_validator_metadata(
+        ctx: Context<SetValidatorMetadata>,
+        name_hash: [u8; 32],
+        website_hash: [u8; 32],
+        datacenter_hash: [u8; 32],
+        asn: u32,
+    ) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts.process(
+            name_hash,
+            website_hash,
+            datacenter_hash,
+            asn,
+            *ctx.bumps.get("validator_metadata").unwrap(),
+        )
+    }
+
+    pub fn set_validator_blocked(ctx: Context<SetValidatorBlocked>, blocked: bool) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts
+            .process(blocked, *ctx.bumps.get("validator_health").unwrap())
+    }
+
+    pub fn set_validator_quarantined(
+        ctx: Context<SetValidatorQuarantined>,
+        validator_index: u32,
+        quarantined: bool,
+    ) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts.process(
+            validator_index,
+            quarantined,
+            *ctx.bumps.get("validator_health").unwrap(),
+        )
+    }
+
+    // permissionless, quarantines the validators whose identity changed since set_validator_metadata
+    pub fn quarantine_validator(
+        ctx: Context<QuarantineValidator>,
+        validator_index: u32,
+    ) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts
+            .process(validator_index, *ctx.bumps.get("validator_health").unwrap())
+    }
+
     pub fn remove_validator(
         ctx: Context<RemoveValidator>,
         index: u32,
@@ -97,6 +212,34 @@ pub mod marinade_finance {
         ctx.accounts.process(index, validator_vote, score)
     }
 
+    // permissionless, flags the validators with a commission above State::max_commission
+    pub fn check_validator_commission(
+        ctx: Context<CheckValidatorCommission>,
+        validator_index: u32,
+    ) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts
+            .process(validator_index, *ctx.bumps.get("validator_health").unwrap())
+    }
+
+    // permissionless, flags the validators without new vote credits for State::delinquent_epochs
+    pub fn check_validator_delinquency(
+        ctx: Context<CheckValidatorDelinquency>,
+        validator_index: u32,
+    ) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts
+            .process(validator_index, *ctx.bumps.get("validator_health").unwrap())
+    }
+
+    pub fn update_validator_scores(
+        ctx: Context<UpdateValidatorScores>,
+        scores: Vec<ValidatorScore>,
+    ) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts.process(scores)
+    }
+
     pub fn config_validator_system(
         ctx: Context<ConfigValidatorSystem>,
         extra_runs: u32,
@@ -106,9 +249,26 @@ pub mod marinade_finance {
     }
 
     // deposit AKA stake, AKA deposit_sol
-    pub fn deposit(ctx: Context<Deposit>, lamports: u64) -> Result<()> {
+    pub fn deposit(ctx: Context<Deposit>, lamports: u64, min_msol_out: u64) -> Result<()> {
         check_context(&ctx)?;
-        ctx.accounts.process(lamports)
+        ctx.accounts.process(lamports, min_msol_out)?;
+        Ok(())
+    }
+
+    pub fn deposit_wsol(ctx: Context<DepositWsol>, lamports: u64, min_msol_out: u64) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts.process(lamports, min_msol_out)
+    }
+
+    pub fn init_holding_record(ctx: Context<InitHoldingRecord>) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts
+            .process(*ctx.bumps.get("holding_record").unwrap())
+    }
+
+    pub fn init_user_stats(ctx: Context<InitUserStats>) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts.process(*ctx.bumps.get("user_stats").unwrap())
     }
 
     // SPL stake pool like
@@ -120,6 +280,38 @@ pub mod marinade_finance {
         ctx.accounts.process(validator_index)
     }
 
+    // same as deposit_stake_account, but only `lamports` of the stake are deposited,
+    // the rest is returned to the user in remainder_stake_account
+    pub fn deposit_stake_account_partial(
+        ctx: Context<DepositStakeAccountPartial>,
+        validator_index: u32,
+        lamports: u64,
+    ) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts.process(validator_index, lamports)
+    }
+
+    // migration from a SPL stake pool: the pool tokens are withdrawn as a stake account
+    // created just before as uninitialized and deposited as in deposit_stake_account
+    pub fn deposit_stake_pool_tokens(
+        ctx: Context<DepositStakePoolTokens>,
+        validator_index: u32,
+        pool_tokens: u64,
+    ) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts.process(validator_index, pool_tokens)
+    }
+
+    // the stake accounts are passed in remaining_accounts
+    pub fn deposit_stake_accounts<'info>(
+        ctx: Context<'_, '_, '_, 'info, DepositStakeAccounts<'info>>,
+        validator_indexes: Vec<u32>,
+    ) -> Result<()> {
+        check_program_id(&ctx)?;
+        ctx.accounts
+            .process(ctx.remaining_accounts, validator_indexes)
+    }
+
     pub fn liquid_unstake(ctx: Context<LiquidUnstake>, msol_amount: u64) -> Result<()> {
         check_context(&ctx)?;
         ctx.accounts.process(msol_amount)
@@ -130,9 +322,96 @@ pub mod marinade_finance {
         ctx.accounts.process(lamports)
     }
 
-    pub fn remove_liquidity(ctx: Context<RemoveLiquidity>, tokens: u64) -> Result<()> {
+    pub fn add_liquidity_msol(ctx: Context<AddLiquidityMsol>, msol_amount: u64) -> Result<()> {
         check_context(&ctx)?;
-        ctx.accounts.process(tokens)
+        ctx.accounts.process(msol_amount)
+    }
+
+    pub fn add_liquidity_wsol(ctx: Context<AddLiquidityWsol>, lamports: u64) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts.process(lamports)
+    }
+
+    pub fn add_liquidity_proportional(
+        ctx: Context<AddLiquidityProportional>,
+        lamports: u64,
+        max_msol_amount: u64,
+    ) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts.process(lamports, max_msol_amount)
+    }
+
+    pub fn remove_liquidity(
+        ctx: Context<RemoveLiquidity>,
+        tokens: u64,
+        min_sol_out: u64,
+        min_msol_out: u64,
+    ) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts.process(tokens, min_sol_out, min_msol_out)
+    }
+
+    pub fn remove_liquidity_percent(
+        ctx: Context<RemoveLiquidity>,
+        share: Fee,
+        min_sol_out: u64,
+        min_msol_out: u64,
+    ) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts.process_share(share, min_sol_out, min_msol_out)
+    }
+
+    pub fn remove_liquidity_all(
+        ctx: Context<RemoveLiquidity>,
+        min_sol_out: u64,
+        min_msol_out: u64,
+    ) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts
+            .process_share(Fee::FULL, min_sol_out, min_msol_out)
+    }
+
+    pub fn remove_liquidity_with_permit(
+        ctx: Context<RemoveLiquidityWithPermit>,
+        permit: RemoveLiquidityPermit,
+    ) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts
+            .process(permit, *ctx.bumps.get("lp_permit_authority").unwrap())
+    }
+
+    pub fn remove_liquidity_sol_only(
+        ctx: Context<RemoveLiquiditySolOnly>,
+        tokens: u64,
+        min_sol_out: u64,
+    ) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts.process(tokens, min_sol_out)
+    }
+
+    pub fn remove_liquidity_msol_only(
+        ctx: Context<RemoveLiquidityMsolOnly>,
+        tokens: u64,
+        min_msol_out: u64,
+    ) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts.process(tokens, min_msol_out)
+    }
+
+    pub fn flash_loan_begin(ctx: Context<FlashLoanBegin>, lamports: u64) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts.process(lamports)
+    }
+
+    pub fn flash_loan_end(ctx: Context<FlashLoanEnd>) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts.process()
+    }
+
+    pub fn update_lp_price(ctx: Context<UpdateLpPrice>) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts
+            .process(*ctx.bumps.get("lp_price_oracle").unwrap())
     }
 
     pub fn config_lp(ctx: Context<ConfigLp>, params: ConfigLpParams) -> Result<()> {
@@ -148,6 +427,26 @@ pub mod marinade_finance {
         ctx.accounts.process(params)
     }
 
+    // config_marinade behind the State::config_change_delay_slots timelock
+    pub fn queue_config_change(
+        ctx: Context<QueueConfigChange>,
+        params: ConfigMarinadeParams,
+    ) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts
+            .process(params, *ctx.bumps.get("queued_config_change").unwrap())
+    }
+
+    pub fn execute_config_change(ctx: Context<ExecuteConfigChange>) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts.process()
+    }
+
+    pub fn cancel_config_change(ctx: Context<CancelConfigChange>) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts.process()
+    }
+
     //-------------------------------------------------------------------------------------
     // Advanced instructions: deposit-stake-account, Delayed-Unstake
     // backend/bot "crank" related functions:
@@ -158,7 +457,17 @@ pub mod marinade_finance {
 
     pub fn order_unstake(ctx: Context<OrderUnstake>, msol_amount: u64) -> Result<()> {
         check_context(&ctx)?;
-        ctx.accounts.process(msol_amount)
+        ctx.accounts.process(msol_amount, None)
+    }
+
+    // the ticket is due on created_epoch + 1, up to UnstakeSchedule::MAX_EPOCHS epochs ahead
+    pub fn order_unstake_for_epoch(
+        ctx: Context<OrderUnstake>,
+        msol_amount: u64,
+        created_epoch: u64,
+    ) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts.process(msol_amount, Some(created_epoch))
     }
 
     pub fn claim(ctx: Context<Claim>) -> Result<()> {
@@ -166,11 +475,80 @@ pub mod marinade_finance {
         ctx.accounts.process()
     }
 
+    pub fn claim_wsol(ctx: Context<ClaimWsol>) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts.process()
+    }
+
+    // the tickets are passed in remaining_accounts
+    pub fn claim_many<'info>(ctx: Context<'_, '_, '_, 'info, ClaimMany<'info>>) -> Result<()> {
+        check_program_id(&ctx)?;
+        ctx.accounts.process(ctx.remaining_accounts)
+    }
+
+    pub fn wrap_ticket(ctx: Context<WrapTicket>) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts.process()
+    }
+
+    pub fn split_ticket(ctx: Context<SplitTicket>, amount: u64) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts.process(amount)
+    }
+
+    pub fn merge_tickets(ctx: Context<MergeTickets>) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts.process()
+    }
+
+    pub fn sweep_stale_ticket(ctx: Context<SweepStaleTicket>) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts
+            .process(*ctx.bumps.get("unclaimed_tickets").unwrap())
+    }
+
+    pub fn enable_auto_claim(ctx: Context<EnableAutoClaim>, tip: Fee) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts
+            .process(tip, *ctx.bumps.get("auto_claim").unwrap())
+    }
+
+    pub fn change_ticket_beneficiary(
+        ctx: Context<ChangeTicketBeneficiary>,
+        new_beneficiary: Pubkey,
+    ) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts.process(new_beneficiary)
+    }
+
     pub fn stake_reserve(ctx: Context<StakeReserve>, validator_index: u32) -> Result<()> {
         check_context(&ctx)?;
         ctx.accounts.process(validator_index)
     }
 
+    // the accounts of every validator are passed in remaining_accounts,
+    // see StakeReserveBatch::ACCOUNTS_PER_VALIDATOR
+    pub fn stake_reserve_batch<'info>(
+        ctx: Context<'_, '_, '_, 'info, StakeReserveBatch<'info>>,
+        validator_indexes: Vec<u32>,
+    ) -> Result<()> {
+        check_program_id(&ctx)?;
+        ctx.accounts
+            .process(ctx.remaining_accounts, validator_indexes)
+    }
+
+    pub fn claim_mev_tips(
+        ctx: Context<ClaimMevTips>,
+        stake_index: u32,
+        claim_status_bump: u8,
+        amount: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts
+            .process(stake_index, claim_status_bump, amount, proof)
+    }
+
     pub fn update_active(
         ctx: Context<UpdateActive>,
         stake_index: u32,
@@ -179,11 +557,56 @@ pub mod marinade_finance {
         check_context(&ctx)?;
         ctx.accounts.process(stake_index, validator_index)
     }
+    // the other stake accounts are passed in remaining_accounts
+    pub fn update_active_batch<'info>(
+        ctx: Context<'_, '_, '_, 'info, UpdateActive<'info>>,
+        stake_indexes: Vec<u32>,
+        validator_indexes: Vec<u32>,
+    ) -> Result<()> {
+        check_program_id(&ctx)?;
+        ctx.accounts
+            .process_batch(ctx.remaining_accounts, stake_indexes, validator_indexes)
+    }
     pub fn update_deactivated(ctx: Context<UpdateDeactivated>, stake_index: u32) -> Result<()> {
         check_context(&ctx)?;
         ctx.accounts.process(stake_index)
     }
 
+    // permissionless, the recipient token accounts are passed in remaining_accounts
+    pub fn distribute_treasury<'info>(
+        ctx: Context<'_, '_, '_, 'info, DistributeTreasury<'info>>,
+    ) -> Result<()> {
+        check_program_id(&ctx)?;
+        ctx.accounts.process(ctx.remaining_accounts)
+    }
+
+    // permissionless, the update instructions also refresh the oracle passed to them
+    pub fn update_msol_price_oracle(ctx: Context<UpdateMsolPriceOracle>) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts
+            .process(*ctx.bumps.get("msol_price_oracle").unwrap())
+    }
+
+    // permissionless, the update instructions also record the price in the history passed to them
+    pub fn update_msol_price_history(ctx: Context<UpdateMsolPriceHistory>) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts
+            .process(*ctx.bumps.get("msol_price_history").unwrap())
+    }
+
+    // permissionless, only reports the donations, the update instructions sync the reserve balance
+    pub fn sweep_reserve_donations(ctx: Context<SweepReserveDonations>) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts.process()
+    }
+
+    // permissionless, after the last update of the epoch
+    pub fn report_epoch(ctx: Context<ReportEpoch>) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts
+            .process(*ctx.bumps.get("epoch_reports").unwrap())
+    }
+
     pub fn deactivate_stake(
         ctx: Context<DeactivateStake>,
         stake_index: u32,
@@ -213,6 +636,28 @@ pub mod marinade_finance {
             .process(stake_index, validator_index, desired_unstake_amount)
     }
 
+    pub fn split_stake_for_withdraw(
+        ctx: Context<SplitStakeForWithdraw>,
+        stake_index: u32,
+        validator_index: u32,
+        amount: u64,
+    ) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts.process(stake_index, validator_index, amount)
+    }
+
+    // validator set rotation, see redelegate for the permissionless rebalancing
+    pub fn redelegate_stake(
+        ctx: Context<RedelegateStake>,
+        stake_index: u32,
+        source_validator_index: u32,
+        dest_validator_index: u32,
+    ) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts
+            .process(stake_index, source_validator_index, dest_validator_index)
+    }
+
     pub fn merge_stakes(
         ctx: Context<MergeStakes>,
         destination_stake_index: u32,
@@ -224,6 +669,22 @@ pub mod marinade_finance {
             .process(destination_stake_index, source_stake_index, validator_index)
     }
 
+    // the source stake accounts are passed in remaining_accounts
+    pub fn consolidate_stakes<'info>(
+        ctx: Context<'_, '_, '_, 'info, ConsolidateStakes<'info>>,
+        destination_stake_index: u32,
+        source_stake_indexes: Vec<u32>,
+        validator_index: u32,
+    ) -> Result<()> {
+        check_program_id(&ctx)?;
+        ctx.accounts.process(
+            ctx.remaining_accounts,
+            destination_stake_index,
+            source_stake_indexes,
+            validator_index,
+        )
+    }
+
     pub fn redelegate(
         ctx: Context<ReDelegate>,
         stake_index: u32,
@@ -235,6 +696,16 @@ pub mod marinade_finance {
             .process(stake_index, source_validator_index, dest_validator_index)
     }
 
+    pub fn crank_claim(ctx: Context<CrankClaim>) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts.process()
+    }
+
+    pub fn rebalance_liq_pool(ctx: Context<RebalanceLiqPool>) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts.process()
+    }
+
     // emergency pauses the contract
     pub fn pause(ctx: Context<EmergencyPause>) -> Result<()> {
         check_context(&ctx)?;
@@ -247,6 +718,72 @@ pub mod marinade_finance {
         ctx.accounts.resume()
     }
 
+    // pauses some groups of instructions, see PausedOperations
+    pub fn pause_operations(ctx: Context<EmergencyPause>, operations: u8) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts.set_paused_operations(operations, true)
+    }
+
+    // resumes some groups of instructions, see PausedOperations
+    pub fn resume_operations(ctx: Context<EmergencyPause>, operations: u8) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts.set_paused_operations(operations, false)
+    }
+
+    // permissionless, emits the config invariants that do not hold
+    pub fn verify_config(ctx: Context<VerifyConfig>) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts.process()
+    }
+
+    // permissionless, fails when the recorded balances are off by more than `tolerance` lamports
+    pub fn verify_invariants(ctx: Context<VerifyInvariants>, tolerance: u64) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts.process(tolerance)
+    }
+
+    // sends tokens other than mSOL and LP held by a program PDA to the treasury
+    pub fn rescue_tokens(ctx: Context<RescueTokens>, authority: RescueAuthority) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts.process(authority)
+    }
+
+    // metadata of the mSOL and LP mints, signed by their mint authority PDA
+    pub fn create_token_metadata(
+        ctx: Context<CreateTokenMetadata>,
+        metadata_mint: MetadataMint,
+        name: String,
+        symbol: String,
+        uri: String,
+    ) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts.process(metadata_mint, name, symbol, uri)
+    }
+
+    pub fn update_token_metadata(
+        ctx: Context<UpdateTokenMetadata>,
+        metadata_mint: MetadataMint,
+        name: String,
+        symbol: String,
+        uri: String,
+    ) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts.process(metadata_mint, name, symbol, uri)
+    }
+
+    // sends the SOL sent by mistake to the mSOL leg authority to the treasury,
+    // the reserve and SOL leg lamports belong to the protocol
+    pub fn rescue_lamports(ctx: Context<RescueLamports>, authority: RescueAuthority) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts.process(authority)
+    }
+
+    // resumes the contract paused by the mSOL price circuit breaker
+    pub fn ack_circuit_breaker(ctx: Context<AckCircuitBreaker>) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts.process()
+    }
+
     // immediate withdraw of an active stake account - feature can be enabled or disable by the DAO
     pub fn withdraw_stake_account(
         ctx: Context<WithdrawStakeAccount>,
@@ -254,10 +791,28 @@ pub mod marinade_finance {
         validator_index: u32,
         msol_amount: u64,
         beneficiary: Pubkey,
+    ) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts.process(
+            stake_index,
+            validator_index,
+            msol_amount,
+            beneficiary,
+            false,
+        )
+    }
+
+    // same as withdraw_stake_account, but the stake account is delivered already deactivating
+    pub fn withdraw_stake_account_deactivated(
+        ctx: Context<WithdrawStakeAccount>,
+        stake_index: u32,
+        validator_index: u32,
+        msol_amount: u64,
+        beneficiary: Pubkey,
     ) -> Result<()> {
         check_context(&ctx)?;
         ctx.accounts
-            .process(stake_index, validator_index, msol_amount, beneficiary)
+            .process(stake_index, validator_index, msol_amount, beneficiary, true)
     }
 
     pub fn realloc_validator_list(ctx: Context<ReallocValidatorList>, capacity: u32) -> Result<()> {
@@ -269,4 +824,415 @@ pub mod marinade_finance {
         check_context(&ctx)?;
         ctx.accounts.process(capacity)
     }
+
+    // the pool is funded with plain transfers, State::keeper_reward sets the reward
+    pub fn init_keeper_pool(ctx: Context<InitKeeperPool>) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts.process(*ctx.bumps.get("keeper_pool").unwrap())
+    }
+
+    // the strategy is selected with the config_marinade delegation_strategy param
+    pub fn create_delegation_strategy(
+        ctx: Context<CreateDelegationStrategy>,
+        score_share: Fee,
+        min_score: u32,
+        max_validator_stake_share: Fee,
+        max_directed_stake_share: Fee,
+    ) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts.process(
+            score_share,
+            min_score,
+            max_validator_stake_share,
+            max_directed_stake_share,
+        )
+    }
+
+    pub fn reconcile_lp_supply(ctx: Context<ReconcileLpSupply>) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts.process()
+    }
+
+    // the table address is derived from `recent_slot`, see State::lookup_table
+    pub fn create_lookup_table(ctx: Context<CreateLookupTable>, recent_slot: u64) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts.process(
+            recent_slot,
+            *ctx.bumps.get("lookup_table_authority").unwrap(),
+        )
+    }
+
+    pub fn config_lp_lock(ctx: Context<ConfigLpLock>, lock_fee_boost: Fee) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts
+            .process(lock_fee_boost, *ctx.bumps.get("lp_lock_authority").unwrap())
+    }
+
+    pub fn lock_liquidity(
+        ctx: Context<LockLiquidity>,
+        amount: u64,
+        unlock_slot: u64,
+    ) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts
+            .process(amount, unlock_slot, *ctx.bumps.get("lp_lock").unwrap())
+    }
+
+    pub fn unlock_liquidity(ctx: Context<UnlockLiquidity>, amount: u64) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts.process(amount)
+    }
+
+    pub fn claim_lp_lock_fees(ctx: Context<ClaimLpLockFees>) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts.process()
+    }
+
+    pub fn config_fee_curve(
+        ctx: Context<ConfigFeeCurve>,
+        points: Vec<FeeCurvePoint>,
+        size_tiers: Vec<FeeSizeTier>,
+    ) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts
+            .process(points, size_tiers, *ctx.bumps.get("fee_curve").unwrap())
+    }
+
+    pub fn config_holding_discount(
+        ctx: Context<ConfigHoldingDiscount>,
+        tiers: Vec<HoldingDiscountTier>,
+    ) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts.process(tiers)
+    }
+
+    pub fn config_treasury_splitter(
+        ctx: Context<ConfigTreasurySplitter>,
+        recipients: Vec<TreasuryRecipient>,
+    ) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts.process(
+            recipients,
+            *ctx.bumps.get("treasury_splitter").unwrap(),
+            *ctx.bumps.get("treasury_authority").unwrap(),
+        )
+    }
+
+    //----------------------------------------------------------------------------
+    // LP rewards
+    //----------------------------------------------------------------------------
+    pub fn configure_lp_rewards(
+        ctx: Context<ConfigureLpRewards>,
+        reward_rate_per_slot: u64,
+        end_slot: u64,
+    ) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts.process(
+            reward_rate_per_slot,
+            end_slot,
+            *ctx.bumps.get("lp_rewards").unwrap(),
+        )
+    }
+
+    pub fn stake_lp(ctx: Context<StakeLp>, amount: u64) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts
+            .process(amount, *ctx.bumps.get("position").unwrap())
+    }
+
+    pub fn unstake_lp(ctx: Context<UnstakeLp>, amount: u64) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts.process(amount)
+    }
+
+    pub fn claim_lp_rewards(ctx: Context<ClaimLpRewards>) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts.process()
+    }
+
+    //----------------------------------------------------------------------------
+    // Insurance fund
+    //----------------------------------------------------------------------------
+    pub fn config_insurance_fund(ctx: Context<ConfigInsuranceFund>, fee_share: Fee) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts.process(
+            fee_share,
+            *ctx.bumps.get("insurance_fund_authority").unwrap(),
+        )
+    }
+
+    pub fn cover_loss(ctx: Context<CoverLoss>, lamports: u64) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts.process(lamports)
+    }
+
+    //----------------------------------------------------------------------------
+    // buyback-and-burn
+    //----------------------------------------------------------------------------
+    pub fn config_buyback(ctx: Context<ConfigBuyback>, epoch_budget: u64) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts.process(
+            epoch_budget,
+            *ctx.bumps.get("buyback_config").unwrap(),
+            *ctx.bumps.get("buyback_authority").unwrap(),
+        )
+    }
+
+    // the AMM swap accounts are passed in remaining_accounts
+    pub fn buyback_and_burn<'info>(
+        ctx: Context<'_, '_, '_, 'info, BuybackAndBurn<'info>>,
+        msol_amount: u64,
+        min_burn_amount: u64,
+        swap_data: Vec<u8>,
+    ) -> Result<()> {
+        check_program_id(&ctx)?;
+        ctx.accounts.process(
+            ctx.remaining_accounts,
+            msol_amount,
+            min_burn_amount,
+            swap_data,
+        )
+    }
+
+    //----------------------------------------------------------------------------
+    // mSOL locks
+    //----------------------------------------------------------------------------
+    pub fn config_msol_lock(ctx: Context<ConfigMsolLock>, fee_share: Fee) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts
+            .process(fee_share, *ctx.bumps.get("msol_lock_authority").unwrap())
+    }
+
+    pub fn lock_msol(ctx: Context<LockMsol>, amount: u64, tier: u8) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts
+            .process(amount, tier, *ctx.bumps.get("msol_lock").unwrap())
+    }
+
+    pub fn extend_lock(ctx: Context<ExtendLock>, tier: u8) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts.process(tier)
+    }
+
+    pub fn unlock_msol(ctx: Context<UnlockMsol>) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts.process()
+    }
+
+    //----------------------------------------------------------------------------
+    // mSOL share vault (ERC-4626 like)
+    //----------------------------------------------------------------------------
+    pub fn init_share_vault(ctx: Context<InitShareVault>) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts.process(
+            *ctx.bumps.get("share_vault").unwrap(),
+            *ctx.bumps.get("share_vault_authority").unwrap(),
+        )
+    }
+
+    pub fn deposit_to_vault(
+        ctx: Context<DepositToVault>,
+        assets: u64,
+        min_shares_out: u64,
+    ) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts.process(assets, min_shares_out)
+    }
+
+    pub fn redeem_from_vault(
+        ctx: Context<RedeemFromVault>,
+        shares: u64,
+        min_assets_out: u64,
+    ) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts.process(shares, min_assets_out)
+    }
+
+    //----------------------------------------------------------------------------
+    // Guardians
+    // the guardian signatures are passed in remaining_accounts
+    //----------------------------------------------------------------------------
+    pub fn init_guardians(
+        ctx: Context<InitGuardians>,
+        guardians: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts.process(
+            guardians,
+            threshold,
+            *ctx.bumps.get("guardian_set").unwrap(),
+        )
+    }
+
+    pub fn config_guardians<'info>(
+        ctx: Context<'_, '_, '_, 'info, ConfigGuardians<'info>>,
+        guardians: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        check_program_id(&ctx)?;
+        ctx.accounts
+            .process(ctx.remaining_accounts, guardians, threshold)
+    }
+
+    pub fn guardian_veto<'info>(
+        ctx: Context<'_, '_, '_, 'info, GuardianVeto<'info>>,
+    ) -> Result<()> {
+        check_program_id(&ctx)?;
+        ctx.accounts.process(ctx.remaining_accounts)
+    }
+
+    pub fn guardian_pause<'info>(
+        ctx: Context<'_, '_, '_, 'info, GuardianPause<'info>>,
+    ) -> Result<()> {
+        check_program_id(&ctx)?;
+        ctx.accounts.process(ctx.remaining_accounts)
+    }
+
+    //----------------------------------------------------------------------------
+    // Referral program
+    //----------------------------------------------------------------------------
+    pub fn config_referral(ctx: Context<ConfigReferral>, referral_share: Fee) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts.process(
+            referral_share,
+            *ctx.bumps.get("referral_vault_authority").unwrap(),
+        )
+    }
+
+    pub fn register_partner(ctx: Context<RegisterPartner>) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts
+            .process(*ctx.bumps.get("referral_partner").unwrap())
+    }
+
+    pub fn deposit_with_referral(
+        ctx: Context<DepositWithReferral>,
+        lamports: u64,
+        min_msol_out: u64,
+    ) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts.process(lamports, min_msol_out)
+    }
+
+    pub fn claim_referral_rewards(ctx: Context<ClaimReferralRewards>) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts.process()
+    }
+
+    pub fn register_integrator(ctx: Context<RegisterIntegrator>, fee_rebate: Fee) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts
+            .process(fee_rebate, *ctx.bumps.get("integrator_partner").unwrap())
+    }
+
+    pub fn config_integrator(ctx: Context<ConfigIntegrator>, fee_rebate: Fee) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts.process(fee_rebate)
+    }
+
+    //----------------------------------------------------------------------------
+    // Directed stake
+    // the preferences are honored by stake_reserve and deactivate_stake
+    // up to State::max_directed_stake_share
+    //----------------------------------------------------------------------------
+    pub fn deposit_directed(
+        ctx: Context<DepositDirected>,
+        lamports: u64,
+        min_msol_out: u64,
+    ) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts.process(
+            lamports,
+            min_msol_out,
+            *ctx.bumps.get("directed_stake").unwrap(),
+            *ctx.bumps.get("validator_directed_stake").unwrap(),
+        )
+    }
+
+    pub fn redirect_stake(ctx: Context<RedirectStake>) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts
+            .process(*ctx.bumps.get("new_validator_directed_stake").unwrap())
+    }
+
+    //----------------------------------------------------------------------------
+    // Validator bonds
+    //----------------------------------------------------------------------------
+    pub fn fund_validator_bond(ctx: Context<FundValidatorBond>, amount: u64) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts.process(
+            amount,
+            *ctx.bumps.get("validator_bond").unwrap(),
+            *ctx.bumps.get("validator_bond_vault").unwrap(),
+        )
+    }
+
+    pub fn withdraw_validator_bond(ctx: Context<WithdrawValidatorBond>, amount: u64) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts.process(
+            amount,
+            *ctx.bumps.get("validator_bond_authority").unwrap(),
+            *ctx.bumps.get("validator_health").unwrap(),
+        )
+    }
+
+    // permissionless, slashes the bond of a flagged validator
+    pub fn slash_validator_bond(ctx: Context<SlashValidatorBond>) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts
+            .process(*ctx.bumps.get("validator_bond_authority").unwrap())
+    }
+
+    //----------------------------------------------------------------------------
+    // Protocol operated validators
+    //----------------------------------------------------------------------------
+    pub fn set_affiliated_validator(
+        ctx: Context<SetAffiliatedValidator>,
+        operator: Pubkey,
+        reserve_share: Fee,
+    ) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts.process(
+            operator,
+            reserve_share,
+            *ctx.bumps.get("affiliated_validator").unwrap(),
+        )
+    }
+
+    // permissionless, sweeps the commission of a protocol operated validator
+    pub fn sweep_validator_commission(ctx: Context<SweepValidatorCommission>) -> Result<()> {
+        check_context(&ctx)?;
+        ctx.accounts
+            .process(*ctx.bumps.get("vote_withdraw_authority").unwrap())
+    }
+
+    //----------------------------------------------------------------------------
+    // Quotes: read-only, the amounts are returned in the transaction return data
+    //----------------------------------------------------------------------------
+    pub fn quote_deposit(ctx: Context<QuoteDeposit>, lamports: u64) -> Result<DepositQuote> {
+        check_context(&ctx)?;
+        ctx.accounts.process(lamports)
+    }
+
+    pub fn quote_add_liquidity(ctx: Context<QuoteAddLiquidity>, lamports: u64) -> Result<u64> {
+        check_context(&ctx)?;
+        ctx.accounts.process(lamports)
+    }
+
+    pub fn quote_remove_liquidity(
+        ctx: Context<QuoteRemoveLiquidity>,
+        tokens: u64,
+    ) -> Result<RemoveLiquidityQuote> {
+        check_context(&ctx)?;
+        ctx.accounts.process(tokens)
+    }
+
+    pub fn quote_liquid_unstake(
+        ctx: Context<QuoteLiquidUnstake>,
+        msol_amount: u64,
+    ) -> Result<LiquidUnstakeQuote> {
+        check_context(&ctx)?;
+        ctx.accounts.process(msol_amount)
+    }
 }
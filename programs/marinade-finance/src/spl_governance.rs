@@ -0,0 +1,67 @@
+//! Reads of the SPL Governance (Realms) accounts.
+//! A DAO reaches the program through proposals executed by the governance program,
+//! which signs with the governance PDA and its native treasury PDA only. The admin instructions
+//! need no other signer: their accounts are PDAs and the rent payer can be the native treasury
+
+use anchor_lang::prelude::*;
+
+declare_id!("GovER5Lthms3bLBqWub97yVrMmEogzX7xNjdXpPPCVZw");
+
+const NATIVE_TREASURY_SEED: &[u8] = b"native-treasury";
+
+// GovernanceAccountType enum tags
+const REALM_V1: u8 = 1;
+const REALM_V2: u8 = 16;
+const GOVERNANCE_TYPES: [u8; 8] = [
+    3,  // GovernanceV1
+    4,  // ProgramGovernanceV1
+    9,  // MintGovernanceV1
+    10, // TokenGovernanceV1
+    18, // GovernanceV2
+    19, // ProgramGovernanceV2
+    20, // MintGovernanceV2
+    21, // TokenGovernanceV2
+];
+
+// every governance account type starts with account_type, realm, governed_account
+const GOVERNANCE_REALM_OFFSET: usize = 1;
+
+/// system account of the governance holding its SOL, signs the proposal instructions
+pub fn native_treasury_address(governance: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[NATIVE_TREASURY_SEED, governance.as_ref()], &ID).0
+}
+
+fn account_type(account: &AccountInfo) -> Result<u8> {
+    require_keys_eq!(*account.owner, ID, ErrorCode::AccountOwnedByWrongProgram);
+    let data = account.try_borrow_data()?;
+    data.first()
+        .copied()
+        .ok_or_else(|| error!(ErrorCode::AccountDidNotDeserialize))
+}
+
+/// checks `governance` is a governance of `realm`
+pub fn check_governance(realm: &AccountInfo, governance: &AccountInfo) -> Result<()> {
+    let realm_type = account_type(realm)?;
+    require!(
+        realm_type == REALM_V1 || realm_type == REALM_V2,
+        ErrorCode::AccountDiscriminatorMismatch
+    );
+    let governance_type = account_type(governance)?;
+    require!(
+        GOVERNANCE_TYPES.contains(&governance_type),
+        ErrorCode::AccountDiscriminatorMismatch
+    );
+    let data = governance.try_borrow_data()?;
+    let governance_realm = data
+        .get(GOVERNANCE_REALM_OFFSET..GOVERNANCE_REALM_OFFSET + 32)
+        .map(Pubkey::try_from)
+        .and_then(|key| key.ok())
+        .ok_or_else(|| error!(ErrorCode::AccountDidNotDeserialize))?;
+    require_keys_eq!(governance_realm, realm.key(), ErrorCode::ConstraintHasOne);
+    Ok(())
+}
+
+/// the keys a proposal of `governance` signs with
+pub fn is_governance_signer(governance: &Pubkey, authority: &Pubkey) -> bool {
+    authority == governance || *authority == native_treasury_address(governance)
+}
@@ -0,0 +1,55 @@
+//! CPI to the SPL stake pool program.
+//! Pool tokens are withdrawn as a stake account that deposit_stake_account can absorb
+
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        stake, sysvar,
+    },
+};
+use anchor_spl::token::spl_token;
+
+declare_id!("SPoo1Ku8WFXoNDMHPsrGSTSG1Y47rzgn41SLUNakuHy");
+
+// StakePoolInstruction::WithdrawStake
+const WITHDRAW_STAKE: u8 = 10;
+
+/// `pool_tokens` of `pool_token_account` are burned for a split of `pool_stake_account`
+/// into the uninitialized `stake_account`, `stake_authority` becomes its staker and withdrawer
+#[allow(clippy::too_many_arguments)]
+pub fn withdraw_stake(
+    stake_pool: &Pubkey,
+    validator_stake_list: &Pubkey,
+    stake_pool_withdraw_authority: &Pubkey,
+    pool_stake_account: &Pubkey,
+    stake_account: &Pubkey,
+    stake_authority: &Pubkey,
+    pool_token_account: &Pubkey,
+    pool_fee_account: &Pubkey,
+    pool_mint: &Pubkey,
+    pool_tokens: u64,
+) -> Instruction {
+    let mut data = vec![WITHDRAW_STAKE];
+    data.extend_from_slice(&pool_tokens.to_le_bytes());
+    Instruction {
+        program_id: ID,
+        accounts: vec![
+            AccountMeta::new(*stake_pool, false),
+            AccountMeta::new(*validator_stake_list, false),
+            AccountMeta::new_readonly(*stake_pool_withdraw_authority, false),
+            AccountMeta::new(*pool_stake_account, false),
+            AccountMeta::new(*stake_account, false),
+            AccountMeta::new_readonly(*stake_authority, false),
+            // the same user signs the pool token transfer
+            AccountMeta::new_readonly(*stake_authority, true),
+            AccountMeta::new(*pool_token_account, false),
+            AccountMeta::new(*pool_fee_account, false),
+            AccountMeta::new(*pool_mint, false),
+            AccountMeta::new_readonly(sysvar::clock::ID, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new_readonly(stake::program::ID, false),
+        ],
+        data,
+    }
+}
@@ -0,0 +1,25 @@
+use crate::state::Fee;
+use anchor_lang::prelude::*;
+
+/// A validator operated by the protocol. Its vote account withdraw authority is
+/// the AffiliatedValidator::WITHDRAW_AUTHORITY_SEED PDA, sweep_validator_commission
+/// splits the commission between the reserve and the operator
+#[account]
+#[derive(Debug)]
+pub struct AffiliatedValidator {
+    pub state: Pubkey,
+    /// validator vote account
+    pub validator: Pubkey,
+    pub bump: u8,
+    /// receives the commission not kept by the protocol
+    pub operator: Pubkey,
+    /// share of the commission sent to the reserve
+    pub reserve_share: Fee,
+    pub swept_lamports: u64,
+}
+
+impl AffiliatedValidator {
+    pub const SEED: &'static [u8] = b"affiliated_validator";
+    pub const WITHDRAW_AUTHORITY_SEED: &'static [u8] = b"vote_withdraw_authority";
+    pub const LEN: usize = 8 + 32 * 2 + 1 + 32 + 4 + 8;
+}
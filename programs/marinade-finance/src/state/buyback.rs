@@ -0,0 +1,59 @@
+use crate::error::MarinadeError;
+use anchor_lang::prelude::*;
+
+/// Buyback-and-burn of the governance token with the protocol mSOL fees.
+/// The mSOL vault is funded like any treasury account (e.g. as a TreasurySplitter recipient),
+/// buyback_and_burn swaps it through the whitelisted AMM and burns the tokens received
+#[account]
+#[derive(Debug)]
+pub struct BuybackConfig {
+    pub state: Pubkey,
+    pub bump: u8,
+    pub authority_bump: u8,
+    pub governance_mint: Pubkey,
+    /// the only program buyback_and_burn swaps through
+    pub amm_program: Pubkey,
+    /// mSOL token account of the buyback authority
+    pub msol_vault: Pubkey,
+    /// governance token account of the buyback authority receiving the swap output
+    pub governance_vault: Pubkey,
+    /// max mSOL spent per epoch
+    pub epoch_budget: u64,
+    pub budget_epoch: u64,
+    /// mSOL spent in budget_epoch
+    pub spent_in_epoch: u64,
+    pub total_msol_spent: u64,
+    pub total_burned: u64,
+}
+
+impl BuybackConfig {
+    pub const SEED: &'static [u8] = b"buyback";
+    pub const AUTHORITY_SEED: &'static [u8] = b"buyback_authority";
+    pub const MSOL_VAULT_SEED: &'static [u8] = b"buyback_msol_vault";
+    pub const GOVERNANCE_VAULT_SEED: &'static [u8] = b"buyback_governance_vault";
+    pub const LEN: usize = 8 + 32 + 1 + 1 + 32 * 4 + 8 * 5;
+
+    pub fn remaining_budget(&self, epoch: u64) -> u64 {
+        if epoch == self.budget_epoch {
+            self.epoch_budget.saturating_sub(self.spent_in_epoch)
+        } else {
+            self.epoch_budget
+        }
+    }
+
+    pub fn on_buyback(&mut self, epoch: u64, msol_spent: u64, burned: u64) -> Result<()> {
+        require_gte!(
+            self.remaining_budget(epoch),
+            msol_spent,
+            MarinadeError::BuybackBudgetExceeded
+        );
+        if epoch != self.budget_epoch {
+            self.budget_epoch = epoch;
+            self.spent_in_epoch = 0;
+        }
+        self.spent_in_epoch += msol_spent;
+        self.total_msol_spent += msol_spent;
+        self.total_burned += burned;
+        Ok(())
+    }
+}
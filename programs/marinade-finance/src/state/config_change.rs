@@ -0,0 +1,21 @@
+use crate::instructions::ConfigMarinadeParams;
+use anchor_lang::prelude::*;
+
+/// config_marinade params waiting State::config_change_delay_slots before they can be applied.
+/// One change can be queued at a time, the account is closed on execute or cancel
+#[account]
+#[derive(Debug)]
+pub struct QueuedConfigChange {
+    pub state: Pubkey,
+    pub rent_payer: Pubkey, // gets the rent back on close
+    pub bump: u8,
+    pub params: ConfigMarinadeParams,
+    pub queued_slot: u64,
+    /// earliest slot to execute the change
+    pub execute_slot: u64,
+}
+
+impl QueuedConfigChange {
+    pub const SEED: &'static [u8] = b"queued_config_change";
+    pub const LEN: usize = 8 + 32 * 2 + 1 + ConfigMarinadeParams::MAX_LEN + 8 * 2;
+}
@@ -1,10 +1,73 @@
+use crate::state::Fee;
 use anchor_lang::prelude::*;
 
 #[account]
 #[derive(Debug)]
 pub struct TicketAccountData {
     pub state_address: Pubkey, // instance of marinade state this ticket belongs to
-    pub beneficiary: Pubkey,   // main account where to send SOL when claimed
-    pub lamports_amount: u64,  // amount this ticked is worth
+    pub beneficiary: Pubkey, // main account where to send SOL when claimed, or the ticket NFT mint if wrapped
+    pub lamports_amount: u64, // amount this ticked is worth
     pub created_epoch: u64, // epoch when this acc was created (epoch when delayed-unstake was requested)
 }
+
+impl TicketAccountData {
+    pub const NFT_MINT_SEED: &'static [u8] = b"ticket_nft";
+    /// created_epoch of the tickets moved to UnclaimedTickets
+    pub const SWEPT_EPOCH: u64 = u64::MAX;
+
+    pub fn is_swept(&self) -> bool {
+        self.created_epoch == Self::SWEPT_EPOCH
+    }
+
+    pub fn find_nft_mint_address(&self, ticket: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(
+            &[
+                &self.state_address.to_bytes(),
+                Self::NFT_MINT_SEED,
+                &ticket.to_bytes(),
+            ],
+            &crate::ID,
+        )
+        .0
+    }
+
+    /// a wrapped ticket can be claimed by the holder of its NFT only
+    pub fn is_wrapped(&self, ticket: &Pubkey) -> bool {
+        self.beneficiary == self.find_nft_mint_address(ticket)
+    }
+}
+
+/// Opt-in of a ticket beneficiary to let anyone claim the ticket on their behalf
+/// for a tip taken from the claimed amount
+#[account]
+#[derive(Debug)]
+pub struct TicketAutoClaim {
+    pub ticket: Pubkey,
+    /// the opt-in is void once the ticket changes hands
+    pub beneficiary: Pubkey,
+    pub tip: Fee,
+    pub bump: u8,
+}
+
+impl TicketAutoClaim {
+    pub const SEED: &'static [u8] = b"auto_claim";
+    pub const LEN: usize = 8 + 32 * 2 + 4 + 1;
+    pub const MAX_TIP: Fee = Fee::from_basis_points(100); // 1%
+}
+
+/// Holds the lamports of the tickets left unclaimed for too long, out of the reserve.
+/// The swept tickets are still claimed with claim
+#[account]
+#[derive(Debug)]
+pub struct UnclaimedTickets {
+    pub state: Pubkey,
+    pub bump: u8,
+    /// total lamports of the swept and not claimed yet tickets
+    pub lamports_amount: u64,
+    pub ticket_count: u64,
+}
+
+impl UnclaimedTickets {
+    pub const SEED: &'static [u8] = b"unclaimed_tickets";
+    pub const LEN: usize = 8 + 32 + 1 + 8 * 2;
+}
@@ -0,0 +1,56 @@
+use crate::{
+    calc::{proportional, Rounding},
+    state::{
+        validator_system::{ValidatorRecord, ValidatorSystem},
+        Fee,
+    },
+};
+use anchor_lang::prelude::*;
+
+/// Delegation policy used by stake_reserve instead of the State parameters while it is
+/// State::delegation_strategy. Strategies are not modified, governance creates a new one
+/// with create_delegation_strategy and switches to it with config_marinade
+#[account]
+#[derive(Debug)]
+pub struct DelegationStrategy {
+    pub state: Pubkey,
+    /// share of the total stake target spread by score, the rest is spread equally
+    /// between all the validators of the list
+    pub score_share: Fee,
+    /// validators with a lower score get no stake, as the validators with score 0
+    pub min_score: u32,
+    /// replaces State::max_validator_stake_share
+    pub max_validator_stake_share: Fee,
+    /// replaces State::max_directed_stake_share
+    pub max_directed_stake_share: Fee,
+}
+
+impl DelegationStrategy {
+    pub const LEN: usize = 8 + 32 + 4 * 4;
+
+    pub fn check(&self) -> Result<()> {
+        self.score_share.check()?;
+        self.max_validator_stake_share.check()?;
+        self.max_directed_stake_share.check()
+    }
+
+    pub fn validator_stake_target(
+        &self,
+        validator_system: &ValidatorSystem,
+        validator: &ValidatorRecord,
+        total_stake_target: u64,
+    ) -> Result<u64> {
+        if validator.score < self.min_score || validator.score == 0 {
+            return Ok(0);
+        }
+        let by_score = self.score_share.apply(total_stake_target);
+        let score_target = validator_system.validator_stake_target(validator, by_score)?;
+        let uniform_target = proportional(
+            total_stake_target - by_score,
+            1,
+            validator_system.validator_count() as u64,
+            Rounding::Down,
+        )?;
+        Ok(score_target + uniform_target)
+    }
+}
@@ -0,0 +1,44 @@
+use crate::state::Fee;
+use anchor_lang::prelude::*;
+
+/// Validator preference of one depositor.
+/// `lamports` are the lamports deposited with the preference, they stay attributed
+/// to `validator` until the owner redirects them with redirect_stake
+#[account]
+#[derive(Debug)]
+pub struct DirectedStake {
+    pub state: Pubkey,
+    pub owner: Pubkey,
+    pub bump: u8,
+    /// vote account of the chosen validator
+    pub validator: Pubkey,
+    pub lamports: u64,
+}
+
+impl DirectedStake {
+    pub const SEED: &'static [u8] = b"directed_stake";
+    pub const LEN: usize = 8 + 32 * 2 + 1 + 32 + 8;
+}
+
+/// Sum of the DirectedStake preferences for one validator,
+/// honored by the delegation planner up to State::max_directed_stake_share of the total stake target
+#[account]
+#[derive(Debug)]
+pub struct ValidatorDirectedStake {
+    pub state: Pubkey,
+    /// validator vote account
+    pub validator: Pubkey,
+    pub bump: u8,
+    pub directed_lamports: u64,
+}
+
+impl ValidatorDirectedStake {
+    pub const SEED: &'static [u8] = b"validator_directed_stake";
+    pub const LEN: usize = 8 + 32 * 2 + 1 + 8;
+
+    /// stake the validator gets from the directed preferences, capped at `max_share` of `total_stake_target`
+    pub fn stake_target(&self, max_share: Fee, total_stake_target: u64) -> u64 {
+        self.directed_lamports
+            .min(max_share.apply(total_stake_target))
+    }
+}
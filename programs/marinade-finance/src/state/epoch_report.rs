@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+
+/// Rewards found by the update instructions of one epoch, written to the epoch report by report_epoch
+#[derive(Clone, Copy, Debug, Default, AnchorSerialize, AnchorDeserialize)]
+pub struct EpochStats {
+    pub epoch: u64,
+    pub rewards: u64,
+    pub msol_fees: u64,
+}
+
+impl EpochStats {
+    pub fn on_rewards(&mut self, epoch: u64, rewards: u64, msol_fees: u64) {
+        if self.epoch != epoch {
+            // first rewards of the epoch
+            *self = Self {
+                epoch,
+                ..Self::default()
+            };
+        }
+        self.rewards += rewards;
+        self.msol_fees += msol_fees;
+    }
+
+    pub fn of_epoch(&self, epoch: u64) -> Self {
+        if self.epoch == epoch {
+            *self
+        } else {
+            Self {
+                epoch,
+                ..Self::default()
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, AnchorSerialize, AnchorDeserialize)]
+pub struct EpochReport {
+    pub epoch: u64,
+    /// mSOL price before the first update of the epoch
+    pub start_msol_price: u64,
+    pub end_msol_price: u64,
+    pub rewards: u64,
+    /// protocol fees minted, in mSOL
+    pub msol_fees: u64,
+    /// stake delta left for stake_reserve and deactivate_stake
+    pub stake_delta: i128,
+    pub validator_count: u32,
+    pub total_active_balance: u64,
+    pub msol_supply: u64,
+}
+
+/// Ring buffer of the last EpochReports::SIZE epoch reports
+#[account]
+#[derive(Debug)]
+pub struct EpochReports {
+    pub state: Pubkey,
+    pub bump: u8,
+    /// reports written so far, the next one goes to reports[count % SIZE]
+    pub count: u64,
+    pub reports: [EpochReport; Self::SIZE],
+}
+
+impl EpochReports {
+    pub const SEED: &'static [u8] = b"epoch_reports";
+    pub const SIZE: usize = 32;
+    pub const REPORT_LEN: usize = 8 * 5 + 16 + 4 + 8 * 2;
+    pub const LEN: usize = 8 + 32 + 1 + 8 + Self::REPORT_LEN * Self::SIZE;
+
+    pub fn last(&self) -> Option<&EpochReport> {
+        if self.count == 0 {
+            None
+        } else {
+            Some(&self.reports[((self.count - 1) % Self::SIZE as u64) as usize])
+        }
+    }
+
+    pub fn push(&mut self, report: EpochReport) {
+        self.reports[(self.count % Self::SIZE as u64) as usize] = report;
+        self.count += 1;
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{error::MarinadeError, require_lte};
+use crate::{error::MarinadeError, require_lt, require_lte};
 use anchor_lang::prelude::*;
 
 use std::fmt::Display;
@@ -26,11 +26,20 @@ impl Display for Fee {
 
 impl Fee {
     pub const MAX_BASIS_POINTS: u32 = 10_000;
+    pub const ZERO: Fee = Fee::from_basis_points(0);
+    pub const FULL: Fee = Fee::from_basis_points(Self::MAX_BASIS_POINTS); // 100%
 
     pub const fn from_basis_points(basis_points: u32) -> Self {
         Self { basis_points }
     }
 
+    /// from_basis_points for the values that do not come from a constant
+    pub fn try_from_basis_points(basis_points: u32) -> Result<Self> {
+        let fee = Self::from_basis_points(basis_points);
+        fee.check()?;
+        Ok(fee)
+    }
+
     pub fn check(&self) -> Result<()> {
         require_lte!(
             self.basis_points,
@@ -40,10 +49,78 @@ impl Fee {
         Ok(())
     }
 
+    /// check and the hard-limit of the field, `error` when above `max`
+    pub fn check_max(&self, max: Fee, error: MarinadeError) -> Result<()> {
+        self.check()?;
+        require_lte!(*self, max, error);
+        Ok(())
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.basis_points == 0
+    }
+
+    pub fn saturating_add(&self, other: Fee) -> Fee {
+        Self::from_basis_points(self.basis_points.saturating_add(other.basis_points))
+    }
+
+    pub fn saturating_sub(&self, other: Fee) -> Fee {
+        Self::from_basis_points(self.basis_points.saturating_sub(other.basis_points))
+    }
+
+    pub fn abs_diff(&self, other: Fee) -> Fee {
+        Self::from_basis_points(self.basis_points.abs_diff(other.basis_points))
+    }
+
+    /// `fee` reduced by the `self` share of it (rebates, discounts)
+    pub fn discount(&self, fee: Fee) -> Fee {
+        fee.saturating_sub(Self::from_basis_points(
+            self.apply(fee.basis_points as u64) as u32
+        ))
+    }
+
+    /// linear from `from` at 0 to `to` at `den`, `num` must be < `den`
+    pub fn interpolate(from: Fee, to: Fee, num: u64, den: u64) -> Fee {
+        let delta = from.basis_points.saturating_sub(to.basis_points);
+        Self::from_basis_points(
+            from.basis_points - (delta as u128 * num as u128 / den as u128) as u32,
+        )
+    }
+
     pub fn apply(&self, lamports: u64) -> u64 {
         // LMT no error possible
         (lamports as u128 * self.basis_points as u128 / Self::MAX_BASIS_POINTS as u128) as u64
     }
+
+    /// for the fees users pay, the rounding goes to the pool
+    pub fn apply_ceil(&self, lamports: u64) -> u64 {
+        let max = Self::MAX_BASIS_POINTS as u128;
+        ((lamports as u128 * self.basis_points as u128 + max - 1) / max) as u64
+    }
+
+    /// `lamports` left after the fee
+    pub fn apply_remainder(&self, lamports: u64) -> u64 {
+        lamports - self.apply(lamports)
+    }
+
+    /// amount that leaves `net` after the fee, rounded up. Fails for a 100% fee
+    pub fn apply_inverse(&self, net: u64) -> Result<u64> {
+        require_lt!(
+            self.basis_points,
+            Self::MAX_BASIS_POINTS,
+            MarinadeError::BasisPointsOverflow
+        );
+        let max = Self::MAX_BASIS_POINTS as u128;
+        let remainder = max - self.basis_points as u128;
+        u64::try_from((net as u128 * max + remainder - 1) / remainder)
+            .map_err(|_| error!(MarinadeError::CalculationFailure))
+    }
+
+    /// yearly (or any `period`) rate applied for `elapsed` of the period
+    pub fn apply_prorated(&self, lamports: u64, elapsed: u64, period: u64) -> u64 {
+        (lamports as u128 * self.basis_points as u128 * elapsed as u128
+            / (Self::MAX_BASIS_POINTS as u128 * period as u128)) as u64
+    }
 }
 
 #[cfg(feature = "no-entrypoint")]
@@ -110,6 +187,12 @@ impl FeeCents {
         // LMT no error possible
         (lamports as u128 * self.bp_cents as u128 / Self::MAX_BP_CENTS.bp_cents as u128) as u64
     }
+
+    /// for the fees users pay, the rounding goes to the pool
+    pub fn apply_ceil(&self, lamports: u64) -> u64 {
+        let max = Self::MAX_BP_CENTS.bp_cents as u128;
+        ((lamports as u128 * self.bp_cents as u128 + max - 1) / max) as u64
+    }
 }
 
 #[cfg(feature = "no-entrypoint")]
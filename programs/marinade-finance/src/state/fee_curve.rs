@@ -0,0 +1,167 @@
+use crate::{error::MarinadeError, require_lte, state::Fee};
+use anchor_lang::prelude::*;
+
+use super::liq_pool::LiqPool;
+
+#[derive(Clone, Copy, Debug, Default, AnchorSerialize, AnchorDeserialize, PartialEq, Eq)]
+pub struct FeeCurvePoint {
+    /// SOL leg liquidity after the unstake
+    pub liquidity: u64,
+    pub fee: Fee,
+}
+
+#[derive(Clone, Copy, Debug, Default, AnchorSerialize, AnchorDeserialize, PartialEq, Eq)]
+pub struct FeeSizeTier {
+    /// unstake size as a share of the SOL leg liquidity before the unstake
+    pub min_size: Fee,
+    /// added to the curve fee for unstakes of at least min_size
+    pub extra_fee: Fee,
+}
+
+/// Piecewise linear liquid unstake fee curve.
+/// Replaces the lp_min_fee/lp_max_fee/lp_liquidity_target linear fee when set in LiqPoolExt::fee_curve
+#[account]
+#[derive(Debug)]
+pub struct FeeCurve {
+    pub state: Pubkey,
+    pub bump: u8,
+    pub point_count: u8,
+    /// sorted by increasing liquidity and non-increasing fee, only the first point_count are used
+    pub points: [FeeCurvePoint; FeeCurve::MAX_POINTS],
+    pub size_tier_count: u8,
+    /// sorted by increasing min_size and extra_fee, only the first size_tier_count are used
+    pub size_tiers: [FeeSizeTier; FeeCurve::MAX_SIZE_TIERS],
+}
+
+impl FeeCurve {
+    pub const SEED: &'static [u8] = b"fee_curve";
+    pub const MAX_POINTS: usize = 8;
+    pub const MAX_SIZE_TIERS: usize = 4;
+    pub const LEN: usize =
+        8 + 32 + 1 + 1 + (8 + 4) * Self::MAX_POINTS + 1 + (4 + 4) * Self::MAX_SIZE_TIERS;
+
+    pub fn points(&self) -> &[FeeCurvePoint] {
+        &self.points[..self.point_count as usize]
+    }
+
+    pub fn set_points(&mut self, points: &[FeeCurvePoint]) -> Result<()> {
+        Self::validate(points)?;
+        self.points = [FeeCurvePoint::default(); Self::MAX_POINTS];
+        self.points[..points.len()].copy_from_slice(points);
+        self.point_count = points.len() as u8;
+        Ok(())
+    }
+
+    pub fn size_tiers(&self) -> &[FeeSizeTier] {
+        &self.size_tiers[..self.size_tier_count as usize]
+    }
+
+    pub fn set_size_tiers(&mut self, size_tiers: &[FeeSizeTier]) -> Result<()> {
+        Self::validate_size_tiers(size_tiers)?;
+        self.size_tiers = [FeeSizeTier::default(); Self::MAX_SIZE_TIERS];
+        self.size_tiers[..size_tiers.len()].copy_from_slice(size_tiers);
+        self.size_tier_count = size_tiers.len() as u8;
+        Ok(())
+    }
+
+    pub fn validate(points: &[FeeCurvePoint]) -> Result<()> {
+        require_gte!(points.len(), 2, MarinadeError::InvalidFeeCurve);
+        require_lte!(
+            points.len(),
+            Self::MAX_POINTS,
+            MarinadeError::InvalidFeeCurve
+        );
+        for point in points {
+            point.fee.check().map_err(|e| e.with_source(source!()))?;
+        }
+        // the highest fee is at the lowest liquidity, same hard-limit as the linear fee
+        require_lte!(
+            points[0].fee,
+            LiqPool::MAX_FEE,
+            MarinadeError::LpMaxFeeIsTooHigh
+        );
+        for pair in points.windows(2) {
+            require_gt!(
+                pair[1].liquidity,
+                pair[0].liquidity,
+                MarinadeError::InvalidFeeCurve
+            );
+            require_gte!(
+                pair[0].fee,
+                pair[1].fee,
+                MarinadeError::LpFeesAreWrongWayRound
+            );
+        }
+        Ok(())
+    }
+
+    pub fn validate_size_tiers(size_tiers: &[FeeSizeTier]) -> Result<()> {
+        require_lte!(
+            size_tiers.len(),
+            Self::MAX_SIZE_TIERS,
+            MarinadeError::InvalidFeeCurve
+        );
+        for tier in size_tiers {
+            tier.min_size
+                .check()
+                .map_err(|e| e.with_source(source!()))?;
+            tier.extra_fee
+                .check()
+                .map_err(|e| e.with_source(source!()))?;
+            require_gt!(
+                tier.min_size.basis_points,
+                0,
+                MarinadeError::InvalidFeeCurve
+            );
+            require_lte!(
+                tier.extra_fee,
+                LiqPool::MAX_FEE,
+                MarinadeError::LpMaxFeeIsTooHigh
+            );
+        }
+        for pair in size_tiers.windows(2) {
+            require_gt!(
+                pair[1].min_size,
+                pair[0].min_size,
+                MarinadeError::InvalidFeeCurve
+            );
+            require_gte!(
+                pair[1].extra_fee,
+                pair[0].extra_fee,
+                MarinadeError::InvalidFeeCurve
+            );
+        }
+        Ok(())
+    }
+
+    /// extra fee of the largest size tier reached by taking `lamports` out of `liquidity`
+    pub fn size_tier_fee(&self, lamports: u64, liquidity: u64) -> Fee {
+        self.size_tiers()
+            .iter()
+            .rev()
+            .find(|tier| lamports >= tier.min_size.apply(liquidity))
+            .map(|tier| tier.extra_fee)
+            .unwrap_or(Fee::ZERO)
+    }
+
+    /// fee interpolated for the liquidity left in the SOL leg
+    pub fn fee(&self, liquidity: u64) -> Fee {
+        let points = self.points();
+        let first = points[0];
+        if liquidity <= first.liquidity {
+            return first.fee;
+        }
+        for pair in points.windows(2) {
+            let (low, high) = (pair[0], pair[1]);
+            if liquidity < high.liquidity {
+                return Fee::interpolate(
+                    low.fee,
+                    high.fee,
+                    liquidity - low.liquidity,
+                    high.liquidity - low.liquidity,
+                );
+            }
+        }
+        points[points.len() - 1].fee
+    }
+}
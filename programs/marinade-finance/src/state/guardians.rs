@@ -0,0 +1,58 @@
+use crate::error::MarinadeError;
+use anchor_lang::prelude::*;
+
+/// Guardians of the protocol, a backstop against a compromised admin key.
+/// `threshold` guardians signing together can veto a queued config change before it is executable,
+/// pause the program, or change the guardian set itself (the admin can only create it)
+#[account]
+#[derive(Debug)]
+pub struct GuardianSet {
+    pub state: Pubkey,
+    pub bump: u8,
+    pub threshold: u8,
+    pub guardians: Vec<Pubkey>,
+}
+
+impl GuardianSet {
+    pub const SEED: &'static [u8] = b"guardian_set";
+    pub const MAX_GUARDIANS: usize = 10;
+    pub const LEN: usize = 8 + 32 + 1 + 1 + 4 + 32 * Self::MAX_GUARDIANS;
+
+    pub fn check_guardians(guardians: &[Pubkey], threshold: u8) -> Result<()> {
+        require_gte!(
+            Self::MAX_GUARDIANS,
+            guardians.len(),
+            MarinadeError::InvalidGuardianSet
+        );
+        for (i, guardian) in guardians.iter().enumerate() {
+            require!(
+                !guardians[..i].contains(guardian),
+                MarinadeError::InvalidGuardianSet
+            );
+        }
+        require!(
+            threshold > 0 && threshold as usize <= guardians.len(),
+            MarinadeError::InvalidGuardianSet
+        );
+        Ok(())
+    }
+
+    /// returns the guardians signing among `signers`, at least `threshold` of them
+    pub fn check_approvals(&self, signers: &[AccountInfo]) -> Result<Vec<Pubkey>> {
+        let mut approvals: Vec<Pubkey> = Vec::new();
+        for signer in signers {
+            if signer.is_signer
+                && self.guardians.contains(signer.key)
+                && !approvals.contains(signer.key)
+            {
+                approvals.push(*signer.key);
+            }
+        }
+        require_gte!(
+            approvals.len(),
+            self.threshold as usize,
+            MarinadeError::GuardianThresholdNotReached
+        );
+        Ok(approvals)
+    }
+}
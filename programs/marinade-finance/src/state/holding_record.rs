@@ -0,0 +1,97 @@
+use crate::{error::MarinadeError, state::Fee};
+use anchor_lang::prelude::*;
+
+/// Share of the liquid unstake fee waived for mSOL held at least min_holding_seconds
+#[derive(Clone, Copy, Debug, Default, AnchorSerialize, AnchorDeserialize, PartialEq, Eq)]
+pub struct HoldingDiscountTier {
+    pub min_holding_seconds: u64,
+    pub discount: Fee,
+}
+
+impl HoldingDiscountTier {
+    pub const MAX_TIERS: usize = 4;
+
+    /// tiers sorted by increasing min_holding_seconds and discount,
+    /// the unused tiers at the end are zeroed
+    pub fn validate(tiers: &[HoldingDiscountTier]) -> Result<()> {
+        require_gte!(
+            Self::MAX_TIERS,
+            tiers.len(),
+            MarinadeError::InvalidHoldingDiscountTiers
+        );
+        for tier in tiers {
+            tier.discount.check()?;
+            require_gt!(
+                tier.min_holding_seconds,
+                0,
+                MarinadeError::InvalidHoldingDiscountTiers
+            );
+        }
+        for pair in tiers.windows(2) {
+            require_gt!(
+                pair[1].min_holding_seconds,
+                pair[0].min_holding_seconds,
+                MarinadeError::InvalidHoldingDiscountTiers
+            );
+            require_gte!(
+                pair[1].discount,
+                pair[0].discount,
+                MarinadeError::InvalidHoldingDiscountTiers
+            );
+        }
+        Ok(())
+    }
+
+    /// discount of the longest tier reached by `held_seconds`
+    pub fn discount(tiers: &[HoldingDiscountTier], held_seconds: u64) -> Fee {
+        tiers
+            .iter()
+            .rev()
+            .find(|tier| tier.min_holding_seconds > 0 && held_seconds >= tier.min_holding_seconds)
+            .map(|tier| tier.discount)
+            .unwrap_or(Fee::ZERO)
+    }
+}
+
+/// mSOL deposits of one wallet, created by the user with init_holding_record.
+/// deposit sets `deposited_at` to the amount-weighted average time of the deposits,
+/// liquid_unstake from the wallet gets the State::holding_discount_tiers discount
+/// for the mSOL it deposited
+#[account]
+#[derive(Debug)]
+pub struct HoldingRecord {
+    pub state: Pubkey,
+    pub owner: Pubkey,
+    pub bump: u8,
+    /// mSOL deposited and not liquid unstaked yet
+    pub msol_amount: u64,
+    pub deposited_at: i64,
+}
+
+impl HoldingRecord {
+    pub const SEED: &'static [u8] = b"holding_record";
+    pub const LEN: usize = 8 + 32 * 2 + 1 + 8 * 2;
+
+    pub fn on_deposit(&mut self, msol_amount: u64, now: i64) {
+        let total = self.msol_amount + msol_amount;
+        if total > 0 {
+            self.deposited_at = ((self.deposited_at as i128 * self.msol_amount as i128
+                + now as i128 * msol_amount as i128)
+                / total as i128) as i64;
+        }
+        self.msol_amount = total;
+    }
+
+    /// discount for unstaking `msol_amount`, only the deposited mSOL gets it
+    pub fn discount(&self, tiers: &[HoldingDiscountTier], msol_amount: u64, now: i64) -> Fee {
+        if msol_amount > self.msol_amount {
+            return Fee::ZERO;
+        }
+        let held_seconds = now.saturating_sub(self.deposited_at).max(0) as u64;
+        HoldingDiscountTier::discount(tiers, held_seconds)
+    }
+
+    pub fn on_liquid_unstake(&mut self, msol_amount: u64) {
+        self.msol_amount = self.msol_amount.saturating_sub(msol_amount);
+    }
+}
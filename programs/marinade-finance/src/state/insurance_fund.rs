@@ -0,0 +1,56 @@
+use crate::{error::MarinadeError, state::Fee};
+use anchor_lang::prelude::*;
+
+/// Insurance fund, part of the State.
+/// The fund gets `fee_share` of the protocol reward fee minted as mSOL into the vault on update.
+/// The slashing seen by update is recorded in `recorded_losses`, cover_loss burns vault mSOL
+/// worth up to the recorded losses: the SOL backing the burned mSOL stays in the pool
+/// and brings the mSOL price back
+#[derive(Clone, AnchorSerialize, AnchorDeserialize, Debug, Default)]
+pub struct InsuranceFund {
+    pub authority_bump_seed: u8,
+    pub vault: Pubkey,  // mSOL of the fund, Pubkey::default() when not set
+    pub fee_share: Fee, // share of the protocol reward fee going to the fund
+    /// lamports slashed and not covered yet
+    pub recorded_losses: u64,
+    /// mSOL minted into the vault
+    pub total_contributed: u64,
+    /// lamports of losses covered
+    pub total_covered: u64,
+}
+
+impl InsuranceFund {
+    pub const AUTHORITY_SEED: &'static [u8] = b"insurance_fund_authority";
+    pub const VAULT_SEED: &'static [u8] = b"insurance_fund_vault";
+    pub const MAX_FEE_SHARE: Fee = Fee::from_basis_points(5_000); // 50% of the protocol fee
+
+    /// mSOL fee amount diverted from the protocol fee to the fund
+    pub fn fee_cut(&self, msol_fee: u64) -> u64 {
+        if self.vault == Pubkey::default() {
+            0
+        } else {
+            self.fee_share.apply(msol_fee)
+        }
+    }
+
+    /// the vault is an optional account of the update instructions, make sure it can't be skipped
+    pub fn check_vault(&self, vault_provided: bool) -> Result<()> {
+        if self.vault != Pubkey::default() && !self.fee_share.is_zero() {
+            require!(vault_provided, MarinadeError::MissingInsuranceFundVault);
+        }
+        Ok(())
+    }
+
+    pub fn on_contribution(&mut self, msol_amount: u64) {
+        self.total_contributed += msol_amount;
+    }
+
+    pub fn on_loss(&mut self, lamports: u64) {
+        self.recorded_losses += lamports;
+    }
+
+    pub fn on_cover(&mut self, lamports: u64) {
+        self.recorded_losses -= lamports;
+        self.total_covered += lamports;
+    }
+}
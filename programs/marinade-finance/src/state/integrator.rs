@@ -0,0 +1,36 @@
+use crate::state::Fee;
+use anchor_lang::prelude::*;
+
+/// Integrator whitelisted by the treasurer, registered by its partner key.
+/// liquid_unstake signed by the partner gets fee_rebate off the liquid unstake fee
+#[account]
+#[derive(Debug)]
+pub struct IntegratorPartner {
+    pub state: Pubkey,
+    /// signer of the liquid unstakes routed by the integrator, a PDA for integrator programs
+    pub partner: Pubkey,
+    pub bump: u8,
+    /// share of the liquid unstake fee waived
+    pub fee_rebate: Fee,
+    /// lamports paid out by the liquid unstakes of this integrator
+    pub unstaked_lamports: u64,
+    pub liquid_unstakes: u64,
+    /// mSOL fee the integrator users did not pay
+    pub rebated_msol: u64,
+}
+
+impl IntegratorPartner {
+    pub const SEED: &'static [u8] = b"integrator_partner";
+    pub const LEN: usize = 8 + 32 * 2 + 1 + 4 + 8 * 3;
+
+    /// liquid unstake fee after the rebate
+    pub fn rebated_fee(&self, fee: Fee) -> Fee {
+        self.fee_rebate.discount(fee)
+    }
+
+    pub fn on_liquid_unstake(&mut self, lamports: u64, rebated_msol: u64) {
+        self.unstaked_lamports += lamports;
+        self.liquid_unstakes += 1;
+        self.rebated_msol += rebated_msol;
+    }
+}
@@ -0,0 +1,16 @@
+use anchor_lang::prelude::*;
+
+/// Lamports paid to the callers of the permissionless cranks, State::keeper_reward per crank.
+/// Funded by plain transfers to the account, the program moves the lamports directly
+#[account]
+#[derive(Debug)]
+pub struct KeeperPool {
+    pub state: Pubkey,
+    pub bump: u8,
+    pub paid_lamports: u64,
+}
+
+impl KeeperPool {
+    pub const SEED: &'static [u8] = b"keeper_pool";
+    pub const LEN: usize = 8 + 32 + 1 + 8;
+}
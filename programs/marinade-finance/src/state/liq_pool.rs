@@ -1,4 +1,9 @@
-use crate::{calc::proportional, error::MarinadeError, require_lte, state::Fee, ID};
+use crate::{
+    error::MarinadeError,
+    require_lte,
+    state::{fee_curve::FeeCurve, lp_lock::LpLock, Fee},
+    ID,
+};
 use anchor_lang::{prelude::*, solana_program::native_token::LAMPORTS_PER_SOL};
 use anchor_spl::token::spl_token;
 
@@ -22,18 +27,58 @@ pub struct LiqPool {
     pub treasury_cut: Fee, //2500 => 25% how much of the Liquid unstake fee goes to treasury_msol_account
 
     pub lp_supply: u64, // virtual lp token supply. May be > real supply because of burning tokens. Use UpdateLiqPool to align it with real value
-    pub lent_from_sol_leg: u64,
+    pub lent_from_sol_leg: u64, // flash loan in progress
     pub liquidity_sol_cap: u64,
 }
 
+/// LiqPool fields added after the State layout was deployed, stored at the end of State
+/// (State::liq_pool_ext) so the fields after LiqPool keep their offsets
+#[derive(Clone, AnchorSerialize, AnchorDeserialize, Debug)]
+pub struct LiqPoolExt {
+    /// max share of the pool value in mSOL, the excess can be swapped against the reserve by rebalance_liq_pool
+    pub msol_leg_target: Fee, // 10_000 => 100%, rebalance disabled
+
+    // circuit breaker for liquid unstakes
+    pub last_liquid_unstake_epoch: u64,
+    pub liquid_unstaked_in_epoch: u64, // total SOL paid by liquid_unstake during the epoch #last_liquid_unstake_epoch
+    pub max_liquid_unstake_per_epoch: u64,
+
+    /// FeeCurve account replacing the linear fee, Pubkey::default() when not set
+    pub fee_curve: Pubkey,
+
+    // time-locked LP positions, see LpLock
+    pub lp_lock_authority_bump_seed: u8,
+    pub lp_lock_vault: Pubkey, // LP tokens locked, Pubkey::default() when not set
+    pub lp_lock_fee_vault: Pubkey, // mSOL fees for the locked LP
+    pub lock_fee_boost: Fee, // share of the LP part of the liquid unstake fee going to the locked LP
+    pub locked_lp_supply: u64,
+    pub lock_acc_fee_per_share: u128,
+
+    // guarded launch limits of the LP minted
+    pub lp_wallet_cap: u64, // max LP balance of the receiving token account
+    pub lp_global_cap: u64, // max lp_supply
+
+    pub flash_loan_enabled: bool,
+    pub flash_loan_fee: Fee,
+
+    /// slots LP tokens must be held after add_liquidity before remove_liquidity, 0 disables it
+    pub lp_min_holding_slots: u64,
+}
+
 impl LiqPool {
     pub const LP_MINT_AUTHORITY_SEED: &'static [u8] = b"liq_mint";
     pub const SOL_LEG_SEED: &'static [u8] = b"liq_sol";
     pub const MSOL_LEG_AUTHORITY_SEED: &'static [u8] = b"liq_st_sol_authority";
     pub const MSOL_LEG_SEED: &'static str = "liq_st_sol";
+    pub const WSOL_UNWRAP_SEED: &'static [u8] = b"liq_wsol_unwrap";
+    pub const LP_PERMIT_AUTHORITY_SEED: &'static [u8] = b"liq_permit";
+    pub const LP_PERMIT_NONCE_SEED: &'static [u8] = b"liq_permit_nonce";
     pub const MAX_FEE: Fee = Fee::from_basis_points(1000); // 10%
     pub const MIN_LIQUIDITY_TARGET: u64 = 50 * LAMPORTS_PER_SOL; // 50 SOL
     pub const MAX_TREASURY_CUT: Fee = Fee::from_basis_points(7500); // 75%
+    pub const MAX_LOCK_FEE_BOOST: Fee = Fee::from_basis_points(5000); // 50%
+    pub const MAX_FLASH_LOAN_FEE: Fee = Fee::from_basis_points(100); // 1%
+    pub const MINIMUM_LIQUIDITY: u64 = 1_000_000; // LP locked by the first add_liquidity
 
     pub fn find_lp_mint_authority(state: &Pubkey) -> (Pubkey, u8) {
         Pubkey::find_program_address(
@@ -57,25 +102,64 @@ impl LiqPool {
         Pubkey::create_with_seed(state, Self::MSOL_LEG_SEED, &spl_token::ID).unwrap()
     }
 
-    pub fn delta(&self) -> u32 {
-        self.lp_max_fee
-            .basis_points
-            .saturating_sub(self.lp_min_fee.basis_points)
-    }
-
     ///compute a linear fee based on liquidity amount, it goes from fee(0)=max -> fee(x>=target)=min
     pub fn linear_fee(&self, lamports: u64) -> Fee {
         if lamports >= self.lp_liquidity_target {
             self.lp_min_fee
         } else {
-            Fee {
-                basis_points: self.lp_max_fee.basis_points
-                    - proportional(self.delta() as u64, lamports, self.lp_liquidity_target).unwrap()
-                        as u32,
-            }
+            Fee::interpolate(
+                self.lp_max_fee,
+                self.lp_min_fee,
+                lamports,
+                self.lp_liquidity_target,
+            )
+        }
+    }
+
+    /// fee for taking `lamports` out of the SOL leg, it is computed based on the liquidity *after* the removal.
+    /// Size tiers of the fee curve are keyed on `lamports` relative to `sol_leg_available_balance`.
+    /// fee_curve must be the account set in LiqPoolExt::fee_curve (see check_fee_curve)
+    pub fn unstake_fee(
+        &self,
+        lamports: u64,
+        sol_leg_available_balance: u64,
+        fee_curve: Option<&FeeCurve>,
+    ) -> Fee {
+        let liquidity = sol_leg_available_balance.saturating_sub(lamports);
+        match fee_curve {
+            // large unstakes pay the size tier fee on top of the curve
+            Some(fee_curve) => fee_curve
+                .fee(liquidity)
+                .saturating_add(fee_curve.size_tier_fee(lamports, sol_leg_available_balance))
+                .min(Self::MAX_FEE),
+            // removing all liquidity
+            None if liquidity == 0 => self.lp_max_fee,
+            None => self.linear_fee(liquidity),
         }
     }
 
+    /// Update virtual lp_supply by real one (real supply may be lower because of burning tokens)
+    pub fn sync_lp_supply(&mut self, lp_mint_supply: u64) -> Result<()> {
+        // impossible to happen unless bug. Use reconcile_lp_supply after investigation
+        require_lte!(
+            lp_mint_supply,
+            self.lp_supply,
+            MarinadeError::UnexpectedLpSupply
+        );
+        self.lp_supply = lp_mint_supply;
+        Ok(())
+    }
+
+    /// the SOL leg balance is not reliable for pricing while a flash loan is in progress
+    pub fn check_no_flash_loan(&self) -> Result<()> {
+        require_eq!(
+            self.lent_from_sol_leg,
+            0,
+            MarinadeError::FlashLoanInProgress
+        );
+        Ok(())
+    }
+
     pub fn on_lp_mint(&mut self, amount: u64) {
         self.lp_supply += amount
     }
@@ -133,3 +217,135 @@ impl LiqPool {
         Ok(())
     }
 }
+
+// rebalance, liquid unstake cap and LP caps off, no fee curve, LP lock nor flash loans
+impl Default for LiqPoolExt {
+    fn default() -> Self {
+        Self {
+            msol_leg_target: Fee::FULL,
+            last_liquid_unstake_epoch: 0,
+            liquid_unstaked_in_epoch: 0,
+            max_liquid_unstake_per_epoch: std::u64::MAX,
+            fee_curve: Pubkey::default(),
+            lp_lock_authority_bump_seed: 0,
+            lp_lock_vault: Pubkey::default(),
+            lp_lock_fee_vault: Pubkey::default(),
+            lock_fee_boost: Fee::ZERO,
+            locked_lp_supply: 0,
+            lock_acc_fee_per_share: 0,
+            lp_wallet_cap: std::u64::MAX,
+            lp_global_cap: std::u64::MAX,
+            flash_loan_enabled: false,
+            flash_loan_fee: Fee::ZERO,
+            lp_min_holding_slots: 0,
+        }
+    }
+}
+
+impl LiqPoolExt {
+    /// the fee curve is an optional account, make sure it can't be skipped once it is set
+    pub fn check_fee_curve(&self, fee_curve_provided: bool) -> Result<()> {
+        if self.fee_curve != Pubkey::default() {
+            require!(fee_curve_provided, MarinadeError::MissingFeeCurve);
+        }
+        Ok(())
+    }
+
+    /// mSOL fee amount diverted from the LP fee part to the locked LP
+    pub fn lock_fee_cut(&self, lp_msol_fee: u64) -> u64 {
+        if self.locked_lp_supply == 0 {
+            0
+        } else {
+            self.lock_fee_boost.apply(lp_msol_fee)
+        }
+    }
+
+    /// the lock fee vault is an optional account, make sure it can't be skipped while the locked LP is boosted
+    pub fn check_lp_lock_fee_vault(&self, lp_lock_fee_vault_provided: bool) -> Result<()> {
+        if self.locked_lp_supply > 0 && !self.lock_fee_boost.is_zero() {
+            require!(
+                lp_lock_fee_vault_provided,
+                MarinadeError::MissingLpLockFeeVault
+            );
+        }
+        Ok(())
+    }
+
+    pub fn on_lock_fee(&mut self, msol_amount: u64) {
+        self.lock_acc_fee_per_share +=
+            msol_amount as u128 * LpLock::PRECISION / self.locked_lp_supply as u128;
+    }
+
+    pub fn on_lp_lock(&mut self, amount: u64) {
+        self.locked_lp_supply += amount
+    }
+
+    pub fn on_lp_unlock(&mut self, amount: u64) {
+        self.locked_lp_supply -= amount
+    }
+
+    pub fn on_liquid_unstake(&mut self, lamports: u64, clock: &Clock) -> Result<()> {
+        if clock.epoch != self.last_liquid_unstake_epoch {
+            self.last_liquid_unstake_epoch = clock.epoch;
+            self.liquid_unstaked_in_epoch = 0;
+        }
+        self.liquid_unstaked_in_epoch += lamports;
+        require_lte!(
+            self.liquid_unstaked_in_epoch,
+            self.max_liquid_unstake_per_epoch,
+            MarinadeError::LiquidUnstakeIsCapped
+        );
+        Ok(())
+    }
+
+    /// value in lamports of the mSOL leg above msol_leg_target
+    pub fn msol_leg_excess_value(
+        &self,
+        sol_leg_available_balance: u64,
+        msol_leg_value: u64,
+    ) -> u64 {
+        let target_value = self
+            .msol_leg_target
+            .apply(sol_leg_available_balance + msol_leg_value);
+        msol_leg_value.saturating_sub(target_value)
+    }
+
+    /// the wallet cap is checked on the LP token account receiving the minted LP,
+    /// `lp_supply` is LiqPool::lp_supply before the mint
+    pub fn check_lp_caps(
+        &self,
+        lp_supply: u64,
+        lp_minted: u64,
+        user_lp_balance: u64,
+    ) -> Result<()> {
+        require_lte!(
+            lp_supply + lp_minted,
+            self.lp_global_cap,
+            MarinadeError::LpGlobalCapReached
+        );
+        require_lte!(
+            user_lp_balance + lp_minted,
+            self.lp_wallet_cap,
+            MarinadeError::LpWalletCapReached
+        );
+        Ok(())
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        self.msol_leg_target
+            .check()
+            .map_err(|e| e.with_source(source!()))?;
+        require_lte!(
+            self.lock_fee_boost,
+            LiqPool::MAX_LOCK_FEE_BOOST,
+            MarinadeError::LockFeeBoostIsTooHigh
+        );
+        require_lte!(
+            self.flash_loan_fee,
+            LiqPool::MAX_FLASH_LOAN_FEE,
+            MarinadeError::FlashLoanFeeIsTooHigh
+        );
+
+        Ok(())
+    }
+}
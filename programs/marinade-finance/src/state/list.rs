@@ -6,6 +6,9 @@ use std::convert::TryFrom;
 
 use crate::{error::MarinadeError, require_lt};
 
+/// Header of a list stored in its own account: 8 bytes discriminator followed by `count` items of `item_size`.
+/// Items are read and written in place by index, the account is never deserialized as a whole,
+/// the capacity is given by the account length and grows with the realloc_*_list instructions
 #[derive(Default, Clone, AnchorSerialize, AnchorDeserialize, BorshSchema, Debug)]
 pub struct List {
     pub account: Pubkey,
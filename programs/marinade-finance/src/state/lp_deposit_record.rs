@@ -0,0 +1,42 @@
+use crate::{error::MarinadeError, ID};
+use anchor_lang::prelude::*;
+
+/// Last slot LP tokens were minted to an LP token account.
+/// remove_liquidity rejects burning from the account before LiqPoolExt::lp_min_holding_slots elapsed,
+/// so liquidity can't be added and removed around a large swap (just-in-time liquidity)
+#[account]
+#[derive(Debug)]
+pub struct LpDepositRecord {
+    pub state: Pubkey,
+    pub token_account: Pubkey,
+    pub last_add_slot: u64,
+}
+
+impl LpDepositRecord {
+    pub const SEED: &'static [u8] = b"lp_deposit";
+    pub const LEN: usize = 8 + 32 * 2 + 8;
+
+    pub fn on_add_liquidity(&mut self, state: Pubkey, token_account: Pubkey, slot: u64) {
+        self.state = state;
+        self.token_account = token_account;
+        self.last_add_slot = slot;
+    }
+
+    /// `record` is the PDA of the token account LP is burned from, it does not exist if nothing was ever added to it
+    pub fn check_holding_period(
+        record: &AccountInfo,
+        min_holding_slots: u64,
+        slot: u64,
+    ) -> Result<()> {
+        if min_holding_slots == 0 || *record.owner != ID {
+            return Ok(());
+        }
+        let record = Self::try_deserialize(&mut &record.data.borrow()[..])?;
+        require_gte!(
+            slot,
+            record.last_add_slot.saturating_add(min_holding_slots),
+            MarinadeError::LpHoldingPeriodNotElapsed
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,58 @@
+use crate::error::MarinadeError;
+use anchor_lang::prelude::*;
+use std::convert::TryFrom;
+
+/// LP tokens locked by one user until unlock_slot.
+/// Locked LP tokens are kept in the LiqPoolExt::lp_lock_vault, so they can't be burned in remove_liquidity
+/// before the lock expires. In exchange the lock earns a boosted share of the liquid unstake fees,
+/// using the per-share accumulator LiqPoolExt::lock_acc_fee_per_share
+#[account]
+#[derive(Debug)]
+pub struct LpLock {
+    pub state: Pubkey,
+    pub owner: Pubkey,
+    pub bump: u8,
+    pub amount: u64,
+    pub unlock_slot: u64,
+    /// amount * lock_acc_fee_per_share at the last settlement
+    pub fee_debt: u128,
+    /// mSOL fees settled but not claimed yet
+    pub pending_fees: u64,
+}
+
+impl LpLock {
+    pub const SEED: &'static [u8] = b"lp_lock";
+    pub const AUTHORITY_SEED: &'static [u8] = b"lp_lock_authority";
+    pub const VAULT_SEED: &'static [u8] = b"lp_lock_vault";
+    pub const FEE_VAULT_SEED: &'static [u8] = b"lp_lock_fee_vault";
+    pub const PRECISION: u128 = 1_000_000_000_000;
+    pub const LEN: usize = 8 + 32 * 2 + 1 + 8 * 2 + 16 + 8;
+
+    fn accumulated(&self, acc_fee_per_share: u128) -> u128 {
+        self.amount as u128 * acc_fee_per_share / Self::PRECISION
+    }
+
+    /// move the fees accrued since the last settlement to pending_fees.
+    /// Must be called before changing amount
+    pub fn settle(&mut self, acc_fee_per_share: u128) -> Result<()> {
+        let accrued = self.accumulated(acc_fee_per_share) - self.fee_debt;
+        self.pending_fees = self
+            .pending_fees
+            .checked_add(
+                u64::try_from(accrued).map_err(|_| error!(MarinadeError::CalculationFailure))?,
+            )
+            .ok_or_else(|| error!(MarinadeError::CalculationFailure))?;
+        self.fee_debt = self.accumulated(acc_fee_per_share);
+        Ok(())
+    }
+
+    pub fn on_lock(&mut self, amount: u64, acc_fee_per_share: u128) {
+        self.amount += amount;
+        self.fee_debt = self.accumulated(acc_fee_per_share);
+    }
+
+    pub fn on_unlock(&mut self, amount: u64, acc_fee_per_share: u128) {
+        self.amount -= amount;
+        self.fee_debt = self.accumulated(acc_fee_per_share);
+    }
+}
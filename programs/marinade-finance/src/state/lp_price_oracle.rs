@@ -0,0 +1,47 @@
+use crate::{
+    calc::{value_from_shares, Rounding},
+    State,
+};
+use anchor_lang::prelude::*;
+
+/// LP token value in SOL for integrators (lending protocols accepting the LP as collateral).
+/// Refreshed by update_lp_price and by the liq pool instructions it is passed to
+#[account]
+#[derive(Debug)]
+pub struct LpPriceOracle {
+    pub state: Pubkey,
+    pub bump: u8,
+    /// lamports per LP token * State::PRICE_DENOMINATOR
+    pub lp_price: u64,
+    pub sol_leg_balance: u64,
+    pub msol_leg_balance: u64,
+    pub lp_supply: u64,
+    pub last_update_slot: u64,
+}
+
+impl LpPriceOracle {
+    pub const SEED: &'static [u8] = b"lp_price";
+    pub const LEN: usize = 8 + 32 + 1 + 8 * 5;
+
+    pub fn update(
+        &mut self,
+        state: &State,
+        sol_leg_balance: u64,
+        msol_leg_balance: u64,
+        slot: u64,
+    ) -> Result<()> {
+        let total_value = sol_leg_balance.saturating_sub(state.rent_exempt_for_token_acc)
+            + state.msol_to_sol(msol_leg_balance)?;
+        self.lp_price = value_from_shares(
+            State::PRICE_DENOMINATOR,
+            total_value,
+            state.liq_pool.lp_supply,
+            Rounding::Down,
+        )?;
+        self.sol_leg_balance = sol_leg_balance;
+        self.msol_leg_balance = msol_leg_balance;
+        self.lp_supply = state.liq_pool.lp_supply;
+        self.last_update_slot = slot;
+        Ok(())
+    }
+}
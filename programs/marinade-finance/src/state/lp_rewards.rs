@@ -0,0 +1,168 @@
+use crate::error::MarinadeError;
+use anchor_lang::prelude::*;
+use std::convert::TryFrom;
+
+/// Liquidity mining program for the mSOL-SOL-LP token.
+/// Streams `reward_rate_per_slot` reward tokens to the LP stakers pro-rata to their staked LP
+/// using the per-share accumulator accounting:
+///     pending = staked_amount * acc_reward_per_share - reward_debt
+#[account]
+#[derive(Debug)]
+pub struct LpRewards {
+    pub state: Pubkey,
+    pub reward_mint: Pubkey,
+    /// token account with the reward tokens to distribute, owned by this PDA
+    pub reward_vault: Pubkey,
+    /// token account with the staked LP tokens, owned by this PDA
+    pub lp_vault: Pubkey,
+    pub bump: u8,
+    /// reward tokens distributed for all the stakers on every slot
+    pub reward_rate_per_slot: u64,
+    /// no more rewards are accrued after this slot
+    pub end_slot: u64,
+    pub last_update_slot: u64,
+    /// accumulated reward tokens per staked LP token, multiplied by PRECISION
+    pub acc_reward_per_share: u128,
+    pub total_staked: u64,
+}
+
+impl LpRewards {
+    pub const SEED: &'static [u8] = b"lp_rewards";
+    pub const REWARD_VAULT_SEED: &'static [u8] = b"lp_rewards_vault";
+    pub const LP_VAULT_SEED: &'static [u8] = b"lp_rewards_lp_vault";
+    pub const PRECISION: u128 = 1_000_000_000_000;
+    pub const LEN: usize = 8 + 32 * 4 + 1 + 8 * 3 + 16 + 8;
+
+    /// accrue rewards for the stakers up to `slot`
+    pub fn accrue(&mut self, slot: u64) -> Result<()> {
+        let accrue_until = slot.min(self.end_slot);
+        if accrue_until > self.last_update_slot && self.total_staked > 0 {
+            let rewards =
+                (accrue_until - self.last_update_slot) as u128 * self.reward_rate_per_slot as u128;
+            self.acc_reward_per_share = self
+                .acc_reward_per_share
+                .checked_add(rewards * Self::PRECISION / self.total_staked as u128)
+                .ok_or_else(|| error!(MarinadeError::CalculationFailure))?;
+        }
+        // nothing is accrued between end_slot and slot, even if the program is extended later
+        self.last_update_slot = self.last_update_slot.max(slot);
+        Ok(())
+    }
+
+    pub fn on_stake(&mut self, amount: u64) {
+        self.total_staked += amount
+    }
+
+    pub fn on_unstake(&mut self, amount: u64) {
+        self.total_staked -= amount
+    }
+}
+
+/// LP tokens staked by one user
+#[account]
+#[derive(Debug)]
+pub struct LpStakePosition {
+    pub lp_rewards: Pubkey,
+    pub owner: Pubkey,
+    pub bump: u8,
+    pub staked_amount: u64,
+    /// staked_amount * acc_reward_per_share at the last settlement
+    pub reward_debt: u128,
+    /// rewards settled but not claimed yet
+    pub pending_rewards: u64,
+}
+
+impl LpStakePosition {
+    pub const SEED: &'static [u8] = b"lp_stake";
+    pub const LEN: usize = 8 + 32 * 2 + 1 + 8 + 16 + 8;
+
+    fn accumulated(&self, acc_reward_per_share: u128) -> u128 {
+        self.staked_amount as u128 * acc_reward_per_share / LpRewards::PRECISION
+    }
+
+    /// move the rewards accrued since the last settlement to pending_rewards.
+    /// Must be called with an up to date acc_reward_per_share before changing staked_amount
+    pub fn settle(&mut self, acc_reward_per_share: u128) -> Result<()> {
+        let accrued = self.accumulated(acc_reward_per_share) - self.reward_debt;
+        self.pending_rewards = self
+            .pending_rewards
+            .checked_add(
+                u64::try_from(accrued).map_err(|_| error!(MarinadeError::CalculationFailure))?,
+            )
+            .ok_or_else(|| error!(MarinadeError::CalculationFailure))?;
+        self.reward_debt = self.accumulated(acc_reward_per_share);
+        Ok(())
+    }
+
+    pub fn on_stake(&mut self, amount: u64, acc_reward_per_share: u128) {
+        self.staked_amount += amount;
+        self.reward_debt = self.accumulated(acc_reward_per_share);
+    }
+
+    pub fn on_unstake(&mut self, amount: u64, acc_reward_per_share: u128) {
+        self.staked_amount -= amount;
+        self.reward_debt = self.accumulated(acc_reward_per_share);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LpRewards, LpStakePosition};
+    use anchor_lang::prelude::*;
+
+    fn new_rewards(rate: u64, end_slot: u64) -> LpRewards {
+        LpRewards {
+            state: Pubkey::default(),
+            reward_mint: Pubkey::default(),
+            reward_vault: Pubkey::default(),
+            lp_vault: Pubkey::default(),
+            bump: 0,
+            reward_rate_per_slot: rate,
+            end_slot,
+            last_update_slot: 0,
+            acc_reward_per_share: 0,
+            total_staked: 0,
+        }
+    }
+
+    fn new_position() -> LpStakePosition {
+        LpStakePosition {
+            lp_rewards: Pubkey::default(),
+            owner: Pubkey::default(),
+            bump: 0,
+            staked_amount: 0,
+            reward_debt: 0,
+            pending_rewards: 0,
+        }
+    }
+
+    #[test]
+    fn test_pro_rata_accrual() -> Result<()> {
+        let mut rewards = new_rewards(100, 1_000);
+        let mut alice = new_position();
+        let mut bob = new_position();
+
+        // alice stakes alone for 10 slots
+        rewards.accrue(0)?;
+        alice.on_stake(1_000, rewards.acc_reward_per_share);
+        rewards.on_stake(1_000);
+
+        // bob joins, both of them get half of the rewards for the next 10 slots
+        rewards.accrue(10)?;
+        bob.on_stake(1_000, rewards.acc_reward_per_share);
+        rewards.on_stake(1_000);
+
+        rewards.accrue(20)?;
+        alice.settle(rewards.acc_reward_per_share)?;
+        bob.settle(rewards.acc_reward_per_share)?;
+        assert_eq!(alice.pending_rewards, 1_000 + 500);
+        assert_eq!(bob.pending_rewards, 500);
+
+        // nothing is accrued after end_slot
+        rewards.accrue(2_000)?;
+        alice.settle(rewards.acc_reward_per_share)?;
+        bob.settle(rewards.acc_reward_per_share)?;
+        assert_eq!(alice.pending_rewards + bob.pending_rewards, 100 * 1_000);
+        Ok(())
+    }
+}
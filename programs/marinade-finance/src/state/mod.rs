@@ -1,5 +1,5 @@
 use crate::{
-    calc::{shares_from_value, value_from_shares},
+    calc::{shares_from_value, value_from_shares, Rounding},
     error::MarinadeError,
     require_lte, ID,
 };
@@ -9,13 +9,56 @@ use anchor_lang::{
 use anchor_spl::token::spl_token;
 use std::mem::MaybeUninit;
 
-use self::{liq_pool::LiqPool, stake_system::StakeSystem, validator_system::ValidatorSystem};
+use self::{
+    epoch_report::EpochStats,
+    holding_record::HoldingDiscountTier,
+    insurance_fund::InsuranceFund,
+    liq_pool::{LiqPool, LiqPoolExt},
+    msol_lock::MsolLockSystem,
+    paused_operations::PausedOperations,
+    price_feed::FeedPrice,
+    roles::Role,
+    stake_system::{StakeSystem, UpdateCursor},
+    unstake_schedule::UnstakeSchedule,
+    validator_system::ValidatorSystem,
+};
 
+pub mod affiliated_validator;
+pub mod buyback;
+pub mod config_change;
 pub mod delayed_unstake_ticket;
+pub mod delegation_strategy;
+pub mod directed_stake;
+pub mod epoch_report;
 pub mod fee;
+pub mod fee_curve;
+pub mod guardians;
+pub mod holding_record;
+pub mod insurance_fund;
+pub mod integrator;
+pub mod keeper_pool;
 pub mod liq_pool;
 pub mod list;
+pub mod lp_deposit_record;
+pub mod lp_lock;
+pub mod lp_price_oracle;
+pub mod lp_rewards;
+pub mod msol_lock;
+pub mod msol_price_history;
+pub mod msol_price_oracle;
+pub mod paused_operations;
+pub mod price_feed;
+pub mod quote;
+pub mod referral;
+pub mod roles;
+pub mod share_vault;
 pub mod stake_system;
+pub mod treasury_splitter;
+pub mod unstake_schedule;
+pub mod user_stats;
+pub mod validator_bond;
+pub mod validator_health;
+pub mod validator_metadata;
 pub mod validator_system;
 
 pub use fee::Fee;
@@ -55,6 +98,7 @@ pub struct State {
     pub available_reserve_balance: u64, // reserve_pda.lamports() - self.rent_exempt_for_token_acc. Virtual value (real may be > because of transfers into reserve). Use Update* to align
     pub msol_supply: u64, // Virtual value (may be < because of token burn). Use Update* to align
     // For FE. Don't use it for token amount calculation
+    // 32.32 truncation of msol_price_x64, kept for the existing readers
     pub msol_price: u64,
 
     ///count tickets for delayed-unstake
@@ -70,7 +114,7 @@ pub struct State {
 
     /// emergency pause
     pub pause_authority: Pubkey,
-    pub paused: bool,
+    pub paused: u8, // PausedOperations bits
 
     // delayed unstake account fee
     // to avoid economic attacks this value should not be zero
@@ -91,19 +135,150 @@ pub struct State {
     pub last_stake_move_epoch: u64, // epoch of the last stake move action
     pub stake_moved: u64,           // total amount of moved SOL during the epoch #stake_move_epoch
     pub max_stake_moved_per_epoch: Fee, // % of total_lamports_under_control
+
+    // tickets ordered for a future epoch, see order_unstake_for_epoch
+    pub unstake_schedule: UnstakeSchedule,
+    // epochs after the due epoch a ticket can stay in the reserve before it is swept
+    pub stale_ticket_epochs: u64,
+
+    // referral program, see config_referral
+    pub referral_share: Fee,
+    pub referral_vault: Pubkey,
+    pub referral_vault_authority_bump_seed: u8,
+
+    // time-locked mSOL positions, see MsolLock
+    pub msol_lock: MsolLockSystem,
+
+    // slots a config change must wait in the queue, 0 allows config_marinade without a queue
+    pub config_change_delay_slots: u64,
+
+    // roles added after admin, pause and validator manager, Pubkey::default() when not granted
+    pub operator_authority: Pubkey,
+    pub treasurer_authority: Pubkey,
+
+    // circuit breaker: pauses everything when the mSOL price moves more than
+    // msol_price_change_limit from its value at the start of the epoch (0 disables it)
+    pub msol_price_change_limit: Fee,
+    pub msol_price_reference: u64,
+    pub msol_price_reference_epoch: u64,
+    // set when the breaker pauses the program, cleared by the admin with ack_circuit_breaker
+    pub circuit_breaker_tripped: bool,
+
+    // layout version, see migrate_state. New fields go after it
+    pub version: u8,
+
+    // cap of the stake a validator gets from the directed stake preferences,
+    // as a share of the total stake target (0 ignores the preferences)
+    pub max_directed_stake_share: Fee,
+
+    // validators with a higher commission (in percents) get flagged by check_validator_commission
+    pub max_commission: u8,
+    // epochs without new vote credits before check_validator_delinquency flags a validator (0 disables it)
+    pub delinquent_epochs: u64,
+
+    // max share of total_lamports_under_control a validator can get from stake_reserve and redelegate
+    pub max_validator_stake_share: Fee,
+
+    // activated stake a validator needs for add_validator_permissionless, u64::MAX disables it
+    pub min_validator_stake: u64,
+
+    // mSOL bond stake_reserve requires from a validator (0 disables it), see ValidatorBond
+    pub min_validator_bond: u64,
+    // share of the bond slash_validator_bond sends to the treasury
+    pub validator_bond_slash: Fee,
+
+    // lamports the KeeperPool pays for each crank (0 disables it)
+    pub keeper_reward: u64,
+
+    // rewards of the current update cycle, see report_epoch
+    pub epoch_stats: EpochStats,
+
+    // DelegationStrategy used by stake_reserve, Pubkey::default() uses the State parameters
+    pub delegation_strategy: Pubkey,
+
+    // share of the stake withheld from a deactivating stake account deposit,
+    // the stake earns no rewards until it reaches the reserve
+    pub deactivating_stake_discount: Fee,
+
+    // epoch of the last reward_fee change, see MAX_REWARD_FEE_CHANGE
+    pub reward_fee_change_epoch: u64,
+
+    // yearly fee on total_lamports_under_control minted to the treasury by the first update
+    // of every epoch, 0 disables it
+    pub management_fee: Fee,
+
+    // unix timestamp and epoch the management fee was last accrued
+    pub management_fee_accrued_at: i64,
+    pub management_fee_epoch: u64,
+
+    // insurance fund against slashing, set by config_insurance_fund
+    pub insurance_fund: InsuranceFund,
+
+    // deposit fee paid to the liq pool SOL leg while the SOL leg is below
+    // deposit_imbalance_threshold of lp_liquidity_target, 0 disables it
+    pub deposit_imbalance_fee: Fee,
+    pub deposit_imbalance_threshold: Fee,
+
+    // share of the liquid unstake fee at the current liquidity added as bonus by add_liquidity
+    // while the SOL leg is below lp_liquidity_target, and share of the LP fee funding the bonus
+    pub add_liquidity_bonus_share: Fee,
+    // lamports of LP fees left for the bonus
+    pub add_liquidity_bonus_budget: u64,
+
+    // liquid unstake fee discount by holding time of the HoldingRecord, see config_holding_discount
+    pub holding_discount_tiers: [HoldingDiscountTier; HoldingDiscountTier::MAX_TIERS],
+
+    // cross-check of the mSOL price updates with the Pyth mSOL/USD and SOL/USD feeds,
+    // updates off the feed price by more than price_feed_tolerance fail, 0 disables it
+    pub msol_usd_feed: Pubkey,
+    pub sol_usd_feed: Pubkey,
+    pub price_feed_tolerance: Fee,
+
+    // updates raising the mSOL price more than max_epoch_yield over the price at the start
+    // of the epoch trip the circuit breaker, 0 disables it. Price drops are left to
+    // msol_price_change_limit
+    pub max_epoch_yield: Fee,
+
+    // address lookup table of the protocol accounts made by create_lookup_table
+    pub lookup_table: Pubkey,
+
+    // stake system data appended here to keep the layout append-only
+    pub update_cursor: UpdateCursor,
+
+    // mSOL price as a 64.64 fixed point (lamports per mSOL * PRICE_X64_DENOMINATOR),
+    // set with msol_price by the update instructions
+    pub msol_price_x64: u128,
+
+    // LP shares locked by the first add_liquidity, counted in liq_pool.lp_supply
+    // but never minted, 0 for the pools that had LP holders before
+    pub lp_minimum_liquidity: u64,
+
+    // LiqPool fields added after the deployed layout, see LiqPoolExt
+    pub liq_pool_ext: LiqPoolExt,
+
+    // paused bits when the circuit breaker tripped, restored by ack_circuit_breaker
+    pub paused_before_circuit_breaker: u8,
 }
 
 impl State {
     pub const PRICE_DENOMINATOR: u64 = 0x1_0000_0000;
+    pub const PRICE_X64_DENOMINATOR: u128 = 1 << 64;
     /// Suffix for reserve account seed
     pub const RESERVE_SEED: &'static [u8] = b"reserve";
     pub const MSOL_MINT_AUTHORITY_SEED: &'static [u8] = b"st_mint";
+    pub const LOOKUP_TABLE_AUTHORITY_SEED: &'static [u8] = b"lookup_table_authority";
 
     // Account seeds for simplification of creation (optional)
     pub const STAKE_LIST_SEED: &'static str = "stake_list";
     pub const VALIDATOR_LIST_SEED: &'static str = "validator_list";
 
     pub const MAX_REWARD_FEE: Fee = Fee::from_basis_points(1_000); // 10% max reward fee
+    /// the reward fee ramps, at most one change of this size per epoch
+    pub const MAX_REWARD_FEE_CHANGE: Fee = Fee::from_basis_points(100); // 1%
+    pub const MAX_MANAGEMENT_FEE: Fee = Fee::from_basis_points(200); // 2% per year
+    pub const SECONDS_PER_YEAR: u64 = 365 * 24 * 60 * 60;
+    pub const MAX_DEPOSIT_IMBALANCE_FEE: Fee = Fee::from_basis_points(100); // 1%
+    pub const MAX_ADD_LIQUIDITY_BONUS_SHARE: Fee = Fee::from_basis_points(5_000); // 50%
     pub const MAX_WITHDRAW_ATOM: u64 = LAMPORTS_PER_SOL / 10;
 
     // Note as of July 2023, observable staking reward per epoch is 0.045%
@@ -115,6 +290,239 @@ impl State {
     // min_stake minimum value is MIN_STAKE_MULTIPLIER * rent_exempt_for_token_acc
     pub const MIN_STAKE_LOWER_LIMIT: u64 = LAMPORTS_PER_SOL / 100;
 
+    pub const MAX_COMMISSION: u8 = 100; // percents
+
+    pub const MAX_CONFIG_CHANGE_DELAY_SLOTS: u64 = 432_000 * 2; // ~2 epochs
+
+    /// Current layout version. Bump it for every layout change and add its step to migrate_from
+    pub const VERSION: u8 = 24;
+
+    pub fn role_authority(&self, role: Role) -> Pubkey {
+        match role {
+            Role::Admin => self.admin_authority,
+            Role::Operator => self.operator_authority,
+            Role::Treasurer => self.treasurer_authority,
+            Role::Pauser => self.pause_authority,
+            Role::ValidatorManager => self.validator_system.manager_authority,
+        }
+    }
+
+    pub fn set_role_authority(&mut self, role: Role, authority: Pubkey) {
+        match role {
+            Role::Admin => self.admin_authority = authority,
+            Role::Operator => self.operator_authority = authority,
+            Role::Treasurer => self.treasurer_authority = authority,
+            Role::Pauser => self.pause_authority = authority,
+            Role::ValidatorManager => self.validator_system.manager_authority = authority,
+        }
+    }
+
+    // a role not granted (or revoked) is held by the admin
+    pub fn has_role(&self, role: Role, authority: &Pubkey) -> bool {
+        let role_authority = self.role_authority(role);
+        if role_authority == Pubkey::default() {
+            *authority == self.admin_authority
+        } else {
+            *authority == role_authority
+        }
+    }
+
+    /// Stores the new mSOL price and trips the circuit breaker if it moved too much during the epoch.
+    /// Returns true when the breaker trips
+    pub fn on_msol_price_update(&mut self, msol_price: u64, epoch: u64) -> bool {
+        if self.msol_price_reference_epoch != epoch {
+            // first update in the epoch
+            self.msol_price_reference = self.msol_price;
+            self.msol_price_reference_epoch = epoch;
+        }
+        self.msol_price = msol_price;
+
+        if self.msol_price_change_limit.basis_points == 0 || self.circuit_breaker_tripped {
+            return false;
+        }
+        let change = msol_price.abs_diff(self.msol_price_reference);
+        if change
+            > self
+                .msol_price_change_limit
+                .apply(self.msol_price_reference)
+        {
+            self.trip_circuit_breaker();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// mSOL price at the start of `epoch`, before its first update
+    pub fn epoch_start_msol_price(&self, epoch: u64) -> u64 {
+        if self.msol_price_reference_epoch == epoch {
+            self.msol_price_reference
+        } else {
+            self.msol_price
+        }
+    }
+
+    /// Trips the circuit breaker when `msol_price` grew more than max_epoch_yield over the price
+    /// at the start of `epoch`, so a donation can't block the update. Returns true when the breaker trips.
+    /// Call before on_msol_price_update
+    pub fn on_epoch_yield(&mut self, msol_price: u64, epoch: u64) -> bool {
+        if self.max_epoch_yield.basis_points == 0 || self.circuit_breaker_tripped {
+            return false;
+        }
+        let epoch_start_price = self.epoch_start_msol_price(epoch);
+        if msol_price.saturating_sub(epoch_start_price)
+            > self.max_epoch_yield.apply(epoch_start_price)
+        {
+            self.trip_circuit_breaker();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn trip_circuit_breaker(&mut self) {
+        self.circuit_breaker_tripped = true;
+        self.paused_before_circuit_breaker = self.paused;
+        self.paused = PausedOperations::ALL;
+    }
+
+    /// Fails when `msol_price` is off the price implied by the feeds by more than price_feed_tolerance
+    pub fn check_price_feeds(
+        &self,
+        msol_price: u64,
+        msol_usd: &FeedPrice,
+        sol_usd: &FeedPrice,
+    ) -> Result<()> {
+        let feed_msol_price = FeedPrice::implied_msol_price(msol_usd, sol_usd)?;
+        require_lte!(
+            msol_price.abs_diff(feed_msol_price),
+            self.price_feed_tolerance.apply(feed_msol_price),
+            MarinadeError::MsolPriceFeedDeviation
+        );
+        Ok(())
+    }
+
+    /// Migration step from `version` to `version + 1`, the fields added by the step are zeroed
+    pub fn migrate_from(&mut self, version: u8) {
+        match version {
+            // the accounts created before versioning: give the fields
+            // with a meaningful zero the values initialize uses
+            0 => {
+                // paused was a bool, pausing all the operations
+                if self.paused != 0 {
+                    self.paused = PausedOperations::ALL;
+                }
+                if self.stale_ticket_epochs == 0 {
+                    self.stale_ticket_epochs = std::u64::MAX; // never
+                }
+                if self.msol_price_reference == 0 {
+                    self.msol_price_reference = self.msol_price;
+                }
+            }
+            // max_directed_stake_share added, zero keeps directed stake off
+            1 => {}
+            // max_commission added
+            2 => {
+                self.max_commission = Self::MAX_COMMISSION;
+            }
+            // delinquent_epochs added, zero keeps the check off
+            3 => {}
+            // max_validator_stake_share added
+            4 => {
+                self.max_validator_stake_share = Fee::FULL;
+            }
+            // min_validator_stake added
+            5 => {
+                self.min_validator_stake = std::u64::MAX;
+            }
+            // min_validator_bond and validator_bond_slash added, zero keeps bonds off
+            6 => {}
+            // keeper_reward added, zero keeps the keeper rewards off
+            7 => {}
+            // epoch_stats added, reset by the first rewards of the epoch
+            8 => {}
+            // delegation_strategy added, zero keeps the State parameters
+            9 => {}
+            // deactivating_stake_discount added, zero values deactivating deposits at their stake
+            10 => {}
+            // reward_fee_change_epoch added, zero allows the next reward_fee change
+            11 => {}
+            // management fee added, zero keeps it off
+            12 => {}
+            // insurance fund starts disabled with no recorded losses
+            13 => {}
+            // deposit imbalance fee starts disabled
+            14 => {}
+            // add liquidity bonus starts disabled with no budget
+            15 => {}
+            // holding discount starts with no tiers
+            16 => {}
+            // msol_usd_feed, sol_usd_feed, price_feed_tolerance and max_epoch_yield added, zero keeps the checks off
+            17 => {}
+            // lookup_table added, created by create_lookup_table
+            18 => {}
+            // update_cursor added, reset by the first update of the epoch
+            19 => {}
+            // msol_price_x64 added, from msol_price until the next update
+            20 => {
+                self.msol_price_x64 = (self.msol_price as u128) << 32;
+            }
+            // lp_minimum_liquidity added, the existing LP supply is not locked
+            21 => {}
+            // the LiqPool fields added after the deployed layout moved to liq_pool_ext
+            22 => {
+                self.liq_pool_ext = LiqPoolExt::default();
+            }
+            // paused_before_circuit_breaker added, a tripped breaker resumes everything on ack
+            23 => {}
+            _ => unreachable!("no migration from version {}", version),
+        }
+    }
+
+    /// Runs the migrations of the versions the state missed, returns the old version.
+    /// The layout is append-only: an old account grown with zeroes deserializes with the current layout
+    pub fn migrate(&mut self) -> Result<u8> {
+        let old_version = self.version;
+        require_gt!(
+            Self::VERSION,
+            old_version,
+            MarinadeError::StateAlreadyMigrated
+        );
+        while self.version < Self::VERSION {
+            self.migrate_from(self.version);
+            self.version += 1;
+        }
+        Ok(old_version)
+    }
+
+    /// With a timelock the config changes must go through queue_config_change. The setters
+    /// outside of config_marinade are rejected, a queued change of config_change_delay_slots
+    /// to 0 announces their changes
+    pub fn check_config_not_timelocked(&self) -> Result<()> {
+        require_eq!(
+            self.config_change_delay_slots,
+            0,
+            MarinadeError::ConfigChangeMustBeQueued
+        );
+        Ok(())
+    }
+
+    pub fn check_version(&self) -> bool {
+        self.version == Self::VERSION
+    }
+
+    pub fn check_paused(&self, operations: u8) -> Result<()> {
+        require!(
+            self.paused & operations == 0,
+            MarinadeError::ProgramIsPaused
+        );
+        Ok(())
+    }
+
+    pub fn is_fully_paused(&self) -> bool {
+        self.paused == PausedOperations::ALL
+    }
+
     pub fn serialized_len() -> usize {
         unsafe { MaybeUninit::<Self>::zeroed().assume_init() }
             .try_to_vec()
@@ -193,6 +601,22 @@ impl State {
             + self.available_reserve_balance // reserve_pda.lamports() - self.rent_exempt_for_token_acc
     }
 
+    /// the delegation strategy is an optional account of stake_reserve
+    pub fn check_delegation_strategy(&self, provided: bool) -> Result<()> {
+        require_eq!(
+            provided,
+            self.delegation_strategy != Pubkey::default(),
+            MarinadeError::WrongDelegationStrategy
+        );
+        Ok(())
+    }
+
+    /// cap of the active balance of one validator, see max_validator_stake_share
+    pub fn max_validator_stake(&self) -> u64 {
+        self.max_validator_stake_share
+            .apply(self.total_lamports_under_control())
+    }
+
     pub fn check_staking_cap(&self, transfering_lamports: u64) -> Result<()> {
         let result_amount = self.total_lamports_under_control() + transfering_lamports;
         require_lte!(
@@ -209,26 +633,56 @@ impl State {
             .saturating_sub(self.circulating_ticket_balance) //tickets created -> cooling down lamports or lamports already in reserve and not claimed yet
     }
 
-    /// calculate the amount of msol tokens corresponding to certain lamport amount
+    /// calculate the amount of msol tokens corresponding to certain lamport amount.
+    /// The conversions divide the totals, exact up to the rounding, msol_price_x64 serves the price readers
     pub fn calc_msol_from_lamports(&self, stake_lamports: u64) -> Result<u64> {
         shares_from_value(
             stake_lamports,
             self.total_virtual_staked_lamports(),
             self.msol_supply,
+            Rounding::Down,
         )
     }
     /// calculate lamports value from some msol_amount
     /// result_lamports = msol_amount * msol_price
     pub fn msol_to_sol(&self, msol_amount: u64) -> Result<u64> {
+        self.msol_to_sol_rounded(msol_amount, Rounding::Down)
+    }
+    /// lamports per mSOL * PRICE_X64_DENOMINATOR
+    pub fn calc_msol_price_x64(&self) -> u128 {
+        if self.msol_supply == 0 {
+            return Self::PRICE_X64_DENOMINATOR;
+        }
+        // u64 * 2^64 can't overflow u128
+        ((self.total_virtual_staked_lamports() as u128) << 64) / self.msol_supply as u128
+    }
+
+    /// the 32.32 msol_price of a 64.64 price
+    pub fn msol_price_from_x64(msol_price_x64: u128) -> Result<u64> {
+        u64::try_from(msol_price_x64 >> 32).map_err(|_| error!(MarinadeError::CalculationFailure))
+    }
+
+    /// lamports of `msol_amount` at a 64.64 price, the reverse of calc_msol_price_x64
+    pub fn sol_from_msol_price_x64(msol_amount: u64, msol_price_x64: u128) -> Result<u64> {
+        // (a * (p >> 64) + ((a * (p & low)) >> 64)) splits the product to stay in u128
+        let high = msol_price_x64 >> 64;
+        let low = msol_price_x64 & (u64::MAX as u128);
+        let lamports = msol_amount as u128 * high + ((msol_amount as u128 * low) >> 64);
+        u64::try_from(lamports).map_err(|_| error!(MarinadeError::CalculationFailure))
+    }
+
+    /// msol_to_sol rounded Up for the lamports users pay and the value of the liq pool mSOL leg
+    pub fn msol_to_sol_rounded(&self, msol_amount: u64, rounding: Rounding) -> Result<u64> {
         value_from_shares(
             msol_amount,
             self.total_virtual_staked_lamports(),
             self.msol_supply,
+            rounding,
         )
     }
 
     // **i128**: when do staking/unstaking use real reserve balance instead of virtual field
-    pub fn stake_delta(&self, reserve_balance: u64) -> i128 {
+    pub fn stake_delta(&self, reserve_balance: u64, epoch: u64) -> i128 {
         // Never try to stake lamports from emergency_cooling_down
         // (we must wait for update-deactivated first to keep SOLs for claiming on reserve)
         // But if we need to unstake without counting emergency_cooling_down and we have emergency cooling down
@@ -238,7 +692,9 @@ impl State {
         // OK. Lets get stake_delta without emergency first
         let raw = reserve_balance.saturating_sub(self.rent_exempt_for_token_acc) as i128
             + self.stake_system.delayed_unstake_cooling_down as i128
-            - self.circulating_ticket_balance as i128;
+            - self.circulating_ticket_balance as i128
+            // the tickets scheduled for later epochs are unstaked when their epoch comes
+            + self.unstake_schedule.pending(epoch) as i128;
         if raw >= 0 {
             // When it >= 0 it is right value to use
             raw
@@ -262,6 +718,41 @@ impl State {
         self.msol_supply += amount
     }
 
+    /// liq_pool.lp_supply matching `lp_mint_supply`, the minimum liquidity is not in the mint
+    pub fn lp_supply_of(&self, lp_mint_supply: u64) -> u64 {
+        lp_mint_supply + self.lp_minimum_liquidity
+    }
+
+    /// LP minted to the user for `shares` of the liq pool. The first mint locks
+    /// LiqPool::MINIMUM_LIQUIDITY of the shares, so lamports donated to the legs can't inflate
+    /// the LP price of a tiny supply against the next LPs. Deposits worth no LP are rejected
+    pub fn quote_lp_shares_to_mint(&self, shares: u64) -> Result<u64> {
+        let locked = if self.liq_pool.lp_supply == 0 {
+            LiqPool::MINIMUM_LIQUIDITY
+        } else {
+            0
+        };
+        require_gt!(shares, locked, MarinadeError::NoLpSharesMinted);
+        Ok(shares - locked)
+    }
+
+    /// quote_lp_shares_to_mint, recording the minimum liquidity locked by the first mint
+    pub fn lp_shares_to_mint(&mut self, shares: u64) -> Result<u64> {
+        let user_shares = self.quote_lp_shares_to_mint(shares)?;
+        if self.liq_pool.lp_supply == 0 {
+            self.lp_minimum_liquidity = LiqPool::MINIMUM_LIQUIDITY;
+            self.liq_pool.on_lp_mint(LiqPool::MINIMUM_LIQUIDITY);
+        }
+        Ok(user_shares)
+    }
+
+    /// add_liquidity_bonus_share of the LP part of a liquid unstake fee funds the add liquidity bonus
+    pub fn fund_add_liquidity_bonus(&mut self, lp_msol_fee: u64) -> Result<()> {
+        let lp_fee_lamports = self.msol_to_sol(lp_msol_fee)?;
+        self.add_liquidity_bonus_budget += self.add_liquidity_bonus_share.apply(lp_fee_lamports);
+        Ok(())
+    }
+
     pub fn on_msol_burn(&mut self, amount: u64) {
         self.msol_supply -= amount
     }
@@ -0,0 +1,203 @@
+use crate::{error::MarinadeError, state::Fee};
+use anchor_lang::prelude::*;
+use std::convert::TryFrom;
+
+/// Fixed term of an mSOL lock and the boost its weight gets
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LockTier {
+    pub lock_epochs: u64,
+    /// weight = amount * boost / 10_000
+    pub boost_basis_points: u64,
+}
+
+/// mSOL locks totals, part of the State.
+/// The locks get `fee_share` of the protocol reward fee minted on update, distributed by lock weight
+/// using the per-weight accumulator `acc_fee_per_weight`
+#[derive(Clone, AnchorSerialize, AnchorDeserialize, Debug, Default)]
+pub struct MsolLockSystem {
+    pub authority_bump_seed: u8,
+    pub vault: Pubkey,     // locked mSOL, Pubkey::default() when not set
+    pub fee_vault: Pubkey, // mSOL fees for the locks
+    pub fee_share: Fee,    // share of the protocol reward fee going to the locks
+    pub total_locked: u64,
+    pub total_weight: u64,
+    pub acc_fee_per_weight: u128,
+}
+
+impl MsolLockSystem {
+    pub const MAX_FEE_SHARE: Fee = Fee::from_basis_points(5_000); // 50% of the protocol fee
+
+    /// mSOL fee amount diverted from the protocol fee to the locks
+    pub fn fee_cut(&self, msol_fee: u64) -> u64 {
+        if self.total_weight == 0 {
+            0
+        } else {
+            self.fee_share.apply(msol_fee)
+        }
+    }
+
+    /// the fee vault is an optional account, make sure it can't be skipped while there are locks
+    pub fn check_fee_vault(&self, fee_vault_provided: bool) -> Result<()> {
+        if self.total_weight > 0 && !self.fee_share.is_zero() {
+            require!(fee_vault_provided, MarinadeError::MissingMsolLockFeeVault);
+        }
+        Ok(())
+    }
+
+    pub fn on_fee(&mut self, msol_amount: u64) {
+        self.acc_fee_per_weight +=
+            msol_amount as u128 * MsolLock::PRECISION / self.total_weight as u128;
+    }
+
+    pub fn on_lock(&mut self, amount: u64, old_weight: u64, new_weight: u64) {
+        self.total_locked += amount;
+        self.total_weight = self.total_weight - old_weight + new_weight;
+    }
+
+    pub fn on_unlock(&mut self, amount: u64, weight: u64) {
+        self.total_locked -= amount;
+        self.total_weight -= weight;
+    }
+}
+
+/// mSOL locked by one user until unlock_epoch, kept in the MsolLockSystem::vault.
+/// The fees accrued by the lock are paid together with the mSOL on unlock
+#[account]
+#[derive(Debug)]
+pub struct MsolLock {
+    pub state: Pubkey,
+    pub owner: Pubkey,
+    pub bump: u8,
+    pub amount: u64,
+    /// index in MsolLock::TIERS
+    pub tier: u8,
+    pub unlock_epoch: u64,
+    pub weight: u64,
+    /// weight * acc_fee_per_weight at the last settlement
+    pub fee_debt: u128,
+    /// mSOL fees settled but not paid yet
+    pub pending_fees: u64,
+}
+
+impl MsolLock {
+    pub const SEED: &'static [u8] = b"msol_lock";
+    pub const AUTHORITY_SEED: &'static [u8] = b"msol_lock_authority";
+    pub const VAULT_SEED: &'static [u8] = b"msol_lock_vault";
+    pub const FEE_VAULT_SEED: &'static [u8] = b"msol_lock_fee_vault";
+    pub const PRECISION: u128 = 1_000_000_000_000;
+    pub const LEN: usize = 8 + 32 * 2 + 1 + 8 + 1 + 8 * 2 + 16 + 8;
+
+    pub const TIERS: [LockTier; 3] = [
+        LockTier {
+            lock_epochs: 30, // ~2 months
+            boost_basis_points: 10_000,
+        },
+        LockTier {
+            lock_epochs: 90, // ~6 months
+            boost_basis_points: 15_000,
+        },
+        LockTier {
+            lock_epochs: 180, // ~1 year
+            boost_basis_points: 25_000,
+        },
+    ];
+
+    pub fn get_tier(tier: u8) -> Result<LockTier> {
+        Self::TIERS
+            .get(tier as usize)
+            .copied()
+            .ok_or_else(|| error!(MarinadeError::InvalidLockTier))
+    }
+
+    fn accumulated(&self, acc_fee_per_weight: u128) -> u128 {
+        self.weight as u128 * acc_fee_per_weight / Self::PRECISION
+    }
+
+    /// move the fees accrued since the last settlement to pending_fees.
+    /// Must be called before changing the weight
+    pub fn settle(&mut self, acc_fee_per_weight: u128) -> Result<()> {
+        let accrued = self.accumulated(acc_fee_per_weight) - self.fee_debt;
+        self.pending_fees = self
+            .pending_fees
+            .checked_add(
+                u64::try_from(accrued).map_err(|_| error!(MarinadeError::CalculationFailure))?,
+            )
+            .ok_or_else(|| error!(MarinadeError::CalculationFailure))?;
+        self.fee_debt = self.accumulated(acc_fee_per_weight);
+        Ok(())
+    }
+
+    /// adds `amount` and moves the lock to `tier` starting from `epoch`.
+    /// The tier can't be lowered and the unlock epoch can't be moved back.
+    /// Returns the weight before the change
+    pub fn lock(
+        &mut self,
+        amount: u64,
+        tier: u8,
+        epoch: u64,
+        acc_fee_per_weight: u128,
+    ) -> Result<u64> {
+        let lock_tier = Self::get_tier(tier)?;
+        require_gte!(tier, self.tier, MarinadeError::InvalidLockTier);
+        let old_weight = self.weight;
+        self.amount += amount;
+        self.tier = tier;
+        self.unlock_epoch = self.unlock_epoch.max(epoch + lock_tier.lock_epochs);
+        self.weight =
+            u64::try_from(self.amount as u128 * lock_tier.boost_basis_points as u128 / 10_000)
+                .map_err(|_| error!(MarinadeError::CalculationFailure))?;
+        self.fee_debt = self.accumulated(acc_fee_per_weight);
+        Ok(old_weight)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MsolLock, MsolLockSystem};
+    use crate::state::Fee;
+    use anchor_lang::prelude::*;
+
+    fn new_lock() -> MsolLock {
+        MsolLock {
+            state: Pubkey::default(),
+            owner: Pubkey::default(),
+            bump: 0,
+            amount: 0,
+            tier: 0,
+            unlock_epoch: 0,
+            weight: 0,
+            fee_debt: 0,
+            pending_fees: 0,
+        }
+    }
+
+    #[test]
+    fn test_boosted_fee_share() -> Result<()> {
+        let mut system = MsolLockSystem {
+            fee_share: Fee::from_basis_points(5_000),
+            ..Default::default()
+        };
+        let mut short = new_lock();
+        let mut long = new_lock();
+
+        let old_weight = short.lock(1_000, 0, 10, system.acc_fee_per_weight)?;
+        system.on_lock(1_000, old_weight, short.weight);
+        let old_weight = long.lock(1_000, 2, 10, system.acc_fee_per_weight)?;
+        system.on_lock(1_000, old_weight, long.weight);
+        assert_eq!(short.unlock_epoch, 40);
+        assert_eq!(long.unlock_epoch, 190);
+
+        // half of the protocol fee goes to the locks, 1x and 2.5x boosted
+        let cut = system.fee_cut(7_000);
+        assert_eq!(cut, 3_500);
+        system.on_fee(cut);
+        short.settle(system.acc_fee_per_weight)?;
+        long.settle(system.acc_fee_per_weight)?;
+        assert_eq!(short.pending_fees, 1_000);
+        assert_eq!(long.pending_fees, 2_500);
+
+        // the tier can't be lowered
+        assert!(long.lock(0, 1, 20, system.acc_fee_per_weight).is_err());
+        Ok(())
+    }
+}
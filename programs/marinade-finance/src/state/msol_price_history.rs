@@ -0,0 +1,91 @@
+use crate::State;
+use anchor_lang::prelude::*;
+
+#[derive(Clone, Copy, Debug, Default, AnchorSerialize, AnchorDeserialize)]
+pub struct MsolPricePoint {
+    pub epoch: u64,
+    /// lamports per mSOL * State::PRICE_DENOMINATOR, last price seen in the epoch
+    pub msol_price: u64,
+    pub unix_timestamp: i64,
+    /// lamports per mSOL * State::PRICE_X64_DENOMINATOR, msol_price before the 32.32 truncation
+    pub msol_price_x64: u128,
+}
+
+/// Ring buffer of the mSOL price of the last MsolPriceHistory::SIZE epochs, for the consumers
+/// computing the realized APY or moving averages on chain. Refreshed by update_msol_price_history
+/// and by the update instructions it is passed to, the point of the current epoch moves with the
+/// updates until the epoch ends
+#[account]
+#[derive(Debug)]
+pub struct MsolPriceHistory {
+    pub state: Pubkey,
+    pub bump: u8,
+    /// epochs recorded so far, the next one goes to points[count % SIZE]
+    pub count: u64,
+    pub points: [MsolPricePoint; Self::SIZE],
+}
+
+impl MsolPriceHistory {
+    pub const SEED: &'static [u8] = b"msol_price_history";
+    pub const SIZE: usize = 64;
+    pub const POINT_LEN: usize = 8 * 3 + 16;
+    pub const LEN: usize = 8 + 32 + 1 + 8 + Self::POINT_LEN * Self::SIZE;
+
+    pub fn last(&self) -> Option<&MsolPricePoint> {
+        if self.count == 0 {
+            None
+        } else {
+            Some(&self.points[((self.count - 1) % Self::SIZE as u64) as usize])
+        }
+    }
+
+    /// records the price once an update refreshed it in the current epoch,
+    /// before that msol_price is still the price of the previous epoch. Returns true when recorded
+    pub fn update(&mut self, state: &State, clock: &Clock) -> bool {
+        if state.msol_price_reference_epoch != clock.epoch {
+            return false;
+        }
+        let point = MsolPricePoint {
+            epoch: clock.epoch,
+            msol_price: state.msol_price,
+            unix_timestamp: clock.unix_timestamp,
+            msol_price_x64: state.msol_price_x64,
+        };
+        match self.last() {
+            Some(last) if last.epoch == clock.epoch => {
+                self.points[((self.count - 1) % Self::SIZE as u64) as usize] = point;
+            }
+            _ => {
+                self.points[(self.count % Self::SIZE as u64) as usize] = point;
+                self.count += 1;
+            }
+        }
+        true
+    }
+
+    /// the `epochs` most recent points, newest first
+    pub fn recent(&self, epochs: usize) -> impl Iterator<Item = &MsolPricePoint> {
+        let len = (self.count.min(Self::SIZE as u64) as usize).min(epochs);
+        (0..len).map(move |back| {
+            &self.points[((self.count - 1 - back as u64) % Self::SIZE as u64) as usize]
+        })
+    }
+
+    pub fn get(&self, epoch: u64) -> Option<&MsolPricePoint> {
+        self.recent(Self::SIZE).find(|point| point.epoch == epoch)
+    }
+
+    /// average 64.64 price of the `epochs` most recent points, None without points
+    pub fn average_price_x64(&self, epochs: usize) -> Option<u128> {
+        let (sum, len) = self
+            .recent(epochs)
+            .fold((0u128, 0u128), |(sum, len), point| {
+                (sum + point.msol_price_x64, len + 1)
+            });
+        if len == 0 {
+            None
+        } else {
+            Some(sum / len)
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use crate::State;
+use anchor_lang::prelude::*;
+
+/// mSOL price for integrators (lending protocols), a single small account to read instead of
+/// deserializing State. Refreshed by update_msol_price_oracle and by the update instructions
+/// it is passed to
+#[account]
+#[derive(Debug)]
+pub struct MsolPriceOracle {
+    pub state: Pubkey,
+    pub bump: u8,
+    /// lamports per mSOL * State::PRICE_DENOMINATOR
+    pub msol_price: u64,
+    /// price change since the start of msol_price_epoch, the price is still moving
+    /// while the stake accounts of the epoch are being updated
+    pub confidence: u64,
+    /// epoch of the price, the updates of a new epoch are pending when it is behind
+    pub msol_price_epoch: u64,
+    /// price movement over the limit, consumers should not trust the price
+    pub circuit_breaker_tripped: bool,
+    pub last_update_epoch: u64,
+    pub last_update_slot: u64,
+    /// lamports per mSOL * State::PRICE_X64_DENOMINATOR, msol_price before the 32.32 truncation
+    pub msol_price_x64: u128,
+}
+
+impl MsolPriceOracle {
+    pub const SEED: &'static [u8] = b"msol_price";
+    pub const LEN: usize = 8 + 32 + 1 + 8 * 3 + 1 + 8 * 2 + 16;
+
+    pub fn update(&mut self, state: &State, clock: &Clock) {
+        self.msol_price = state.msol_price;
+        self.msol_price_x64 = state.msol_price_x64;
+        self.msol_price_epoch = state.msol_price_reference_epoch;
+        self.confidence = if state.msol_price_reference == 0 {
+            0
+        } else {
+            state.msol_price.abs_diff(state.msol_price_reference)
+        };
+        self.circuit_breaker_tripped = state.circuit_breaker_tripped;
+        self.last_update_epoch = clock.epoch;
+        self.last_update_slot = clock.slot;
+    }
+
+    /// lamports of `msol_amount` at the oracle price
+    pub fn msol_to_sol(&self, msol_amount: u64) -> Result<u64> {
+        State::sol_from_msol_price_x64(msol_amount, self.msol_price_x64)
+    }
+}
@@ -0,0 +1,23 @@
+/// Groups of instructions that can be paused independently, bits of State::paused.
+/// Pausing one group lets the users keep using the others, e.g. exit while deposits are halted
+pub struct PausedOperations;
+
+impl PausedOperations {
+    /// deposit*, mSOL locks, the share vault and referral rewards.
+    /// First bit so a program paused before the split still rejects deposits
+    pub const DEPOSITS: u8 = 1 << 0;
+    /// liquid_unstake and withdraw_stake_account
+    pub const LIQUID_UNSTAKE: u8 = 1 << 1;
+    /// liquidity pool, flash loans, LP locks and LP rewards
+    pub const LIQUIDITY: u8 = 1 << 2;
+    /// tickets: order_unstake, claim and ticket management
+    pub const DELAYED_UNSTAKE: u8 = 1 << 3;
+    /// validator management and the stake cranks
+    pub const VALIDATOR_OPS: u8 = 1 << 4;
+
+    pub const ALL: u8 = Self::DEPOSITS
+        | Self::LIQUID_UNSTAKE
+        | Self::LIQUIDITY
+        | Self::DELAYED_UNSTAKE
+        | Self::VALIDATOR_OPS;
+}
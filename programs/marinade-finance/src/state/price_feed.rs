@@ -0,0 +1,82 @@
+use crate::{error::MarinadeError, State};
+use anchor_lang::prelude::*;
+
+/// Aggregate price of a Pyth v2 price account.
+/// Read by offsets, the program has no Pyth SDK dependency
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FeedPrice {
+    pub price: i64,
+    pub conf: u64,
+    pub expo: i32,
+    pub pub_slot: u64,
+}
+
+impl FeedPrice {
+    const MAGIC: u32 = 0xa1b2c3d4;
+    const VERSION: u32 = 2;
+    const PRICE_ACCOUNT_TYPE: u32 = 3;
+    const STATUS_TRADING: u32 = 1;
+
+    const EXPO_OFFSET: usize = 20;
+    const AGG_PRICE_OFFSET: usize = 208;
+    const AGG_CONF_OFFSET: usize = 216;
+    const AGG_STATUS_OFFSET: usize = 224;
+    const AGG_PUB_SLOT_OFFSET: usize = 232;
+    const MIN_LEN: usize = 240;
+
+    /// a price published longer ago is not used (~1 minute)
+    pub const MAX_AGE_SLOTS: u64 = 150;
+
+    pub fn load(feed: &AccountInfo, slot: u64) -> Result<Self> {
+        let data = feed.try_borrow_data()?;
+        require_gte!(data.len(), Self::MIN_LEN, MarinadeError::InvalidPriceFeed);
+        require!(
+            read_u32(&data, 0) == Self::MAGIC
+                && read_u32(&data, 4) == Self::VERSION
+                && read_u32(&data, 8) == Self::PRICE_ACCOUNT_TYPE,
+            MarinadeError::InvalidPriceFeed
+        );
+        require_eq!(
+            read_u32(&data, Self::AGG_STATUS_OFFSET),
+            Self::STATUS_TRADING,
+            MarinadeError::InvalidPriceFeed
+        );
+        let feed_price = Self {
+            price: read_u64(&data, Self::AGG_PRICE_OFFSET) as i64,
+            conf: read_u64(&data, Self::AGG_CONF_OFFSET),
+            expo: read_u32(&data, Self::EXPO_OFFSET) as i32,
+            pub_slot: read_u64(&data, Self::AGG_PUB_SLOT_OFFSET),
+        };
+        require_gt!(feed_price.price, 0, MarinadeError::InvalidPriceFeed);
+        require_gte!(
+            feed_price.pub_slot + Self::MAX_AGE_SLOTS,
+            slot,
+            MarinadeError::InvalidPriceFeed
+        );
+        Ok(feed_price)
+    }
+
+    /// mSOL price in SOL * State::PRICE_DENOMINATOR from the mSOL/USD and SOL/USD prices
+    pub fn implied_msol_price(msol_usd: &Self, sol_usd: &Self) -> Result<u64> {
+        let expo_diff = msol_usd.expo - sol_usd.expo;
+        let scale = 10u128
+            .checked_pow(expo_diff.unsigned_abs())
+            .ok_or_else(|| error!(MarinadeError::InvalidPriceFeed))?;
+        let (numerator, denominator) = if expo_diff >= 0 {
+            (msol_usd.price as u128 * scale, sol_usd.price as u128)
+        } else {
+            (msol_usd.price as u128, sol_usd.price as u128 * scale)
+        };
+        (numerator * State::PRICE_DENOMINATOR as u128 / denominator)
+            .try_into()
+            .map_err(|_| error!(MarinadeError::InvalidPriceFeed))
+    }
+}
+
+fn read_u32(data: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap())
+}
+
+fn read_u64(data: &[u8], offset: usize) -> u64 {
+    u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap())
+}
@@ -0,0 +1,181 @@
+//! Amounts computed by the user instructions, shared with the read-only quote instructions
+//! so the quotes can't drift from the real execution
+
+use crate::{
+    calc::{proportional, shares_from_value, Rounding},
+    debug_msg,
+    state::{fee_curve::FeeCurve, integrator::IntegratorPartner, Fee},
+    State,
+};
+use anchor_lang::prelude::*;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub struct DepositQuote {
+    pub msol_out: u64,
+    /// mSOL bought from the liq pool
+    pub msol_swapped: u64,
+    /// SOL paid to the liq pool
+    pub sol_swapped: u64,
+    /// SOL going to the reserve
+    pub sol_deposited: u64,
+    pub msol_minted: u64,
+    /// SOL paid to the liq pool as State::deposit_imbalance_fee
+    pub deposit_fee: u64,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub struct RemoveLiquidityQuote {
+    pub sol_out: u64,
+    pub msol_out: u64,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub struct LiquidUnstakeQuote {
+    pub msol_fee: u64,
+    pub sol_out: u64,
+    /// waived from the fee by the integrator rebate and the holding discount
+    pub msol_fee_rebate: u64,
+}
+
+impl State {
+    /// State::deposit_imbalance_fee of `lamports` while the SOL leg is below
+    /// deposit_imbalance_threshold of the liquidity target
+    pub fn deposit_imbalance_fee(&self, lamports: u64, sol_leg_balance: u64) -> u64 {
+        let sol_leg_available_balance =
+            sol_leg_balance.saturating_sub(self.rent_exempt_for_token_acc);
+        if sol_leg_available_balance
+            < self
+                .deposit_imbalance_threshold
+                .apply(self.liq_pool.lp_liquidity_target)
+        {
+            self.deposit_imbalance_fee.apply_ceil(lamports)
+        } else {
+            0
+        }
+    }
+
+    pub fn quote_deposit(
+        &self,
+        lamports: u64,
+        msol_leg_balance: u64,
+        sol_leg_balance: u64,
+    ) -> Result<DepositQuote> {
+        // the fee goes to the SOL leg, only the rest is deposited
+        let deposit_fee = self.deposit_imbalance_fee(lamports, sol_leg_balance);
+        let lamports = lamports - deposit_fee;
+        //compute how many mSOL to sell/mint for the user, base on how many lamports being deposited
+        let msol_out = self.calc_msol_from_lamports(lamports)?;
+        //First we try to "sell" mSOL to the user from the LiqPool.
+        // At max, we can sell all the mSOL in the LiqPool.mSOL_leg
+        let msol_swapped = msol_out.min(msol_leg_balance);
+        let sol_swapped = if msol_swapped == 0 {
+            0
+        } else if msol_out == msol_swapped {
+            //we are fulfilling 100% the user order
+            lamports
+        } else {
+            // partially filled, it's the lamport value of the tokens we're selling
+            self.msol_to_sol_rounded(msol_swapped, Rounding::Up)?
+        };
+        Ok(DepositQuote {
+            msol_out,
+            msol_swapped,
+            sol_swapped,
+            sol_deposited: lamports - sol_swapped,
+            msol_minted: msol_out - msol_swapped,
+            deposit_fee,
+        })
+    }
+
+    /// bonus lamports credited to an add_liquidity of `lamports` while the SOL leg is below the target:
+    /// add_liquidity_bonus_share of the liquid unstake fee at the current liquidity, up to the budget.
+    /// fee_curve must be the account set in LiqPoolExt::fee_curve
+    pub fn add_liquidity_bonus(
+        &self,
+        lamports: u64,
+        sol_leg_balance: u64,
+        fee_curve: Option<&FeeCurve>,
+    ) -> u64 {
+        let sol_leg_available_balance =
+            sol_leg_balance.saturating_sub(self.rent_exempt_for_token_acc);
+        if sol_leg_available_balance >= self.liq_pool.lp_liquidity_target {
+            return 0;
+        }
+        let fee = self
+            .liq_pool
+            .unstake_fee(0, sol_leg_available_balance, fee_curve);
+        self.add_liquidity_bonus_share
+            .apply(fee.apply(lamports))
+            .min(self.add_liquidity_bonus_budget)
+    }
+
+    /// LP shares minted for `lamports`, computed with the liq-pool value BEFORE adding the deposit
+    pub fn quote_add_liquidity(
+        &self,
+        lamports: u64,
+        sol_leg_balance: u64,
+        msol_leg_balance: u64,
+        lp_supply: u64,
+    ) -> Result<u64> {
+        let sol_leg_available_balance = sol_leg_balance - self.rent_exempt_for_token_acc;
+        let msol_leg_value = self.msol_to_sol_rounded(msol_leg_balance, Rounding::Up)?;
+        let total_liq_pool_value = sol_leg_available_balance + msol_leg_value;
+        debug_msg!(
+            "liq_pool SOL:{}, liq_pool mSOL value:{} liq_pool_value:{}",
+            sol_leg_available_balance,
+            msol_leg_value,
+            total_liq_pool_value
+        );
+        shares_from_value(lamports, total_liq_pool_value, lp_supply, Rounding::Down)
+    }
+
+    pub fn quote_remove_liquidity(
+        &self,
+        tokens: u64,
+        sol_leg_balance: u64,
+        msol_leg_balance: u64,
+        lp_supply: u64,
+    ) -> Result<RemoveLiquidityQuote> {
+        Ok(RemoveLiquidityQuote {
+            sol_out: proportional(
+                tokens,
+                sol_leg_balance - self.rent_exempt_for_token_acc,
+                lp_supply,
+                Rounding::Down,
+            )?,
+            msol_out: proportional(tokens, msol_leg_balance, lp_supply, Rounding::Down)?,
+        })
+    }
+
+    pub fn quote_liquid_unstake(
+        &self,
+        msol_amount: u64,
+        sol_leg_balance: u64,
+        fee_curve: Option<&FeeCurve>,
+        integrator_partner: Option<&IntegratorPartner>,
+        holding_discount: Fee,
+    ) -> Result<LiquidUnstakeQuote> {
+        let sol_leg_available_balance =
+            sol_leg_balance.saturating_sub(self.rent_exempt_for_token_acc);
+        // fee is computed based on the liquidity *after* the user takes the sol
+        let user_remove_lamports = self.msol_to_sol(msol_amount)?;
+        let liquid_unstake_fee =
+            self.liq_pool
+                .unstake_fee(user_remove_lamports, sol_leg_available_balance, fee_curve);
+        let rebated_fee = match integrator_partner {
+            Some(integrator_partner) => integrator_partner.rebated_fee(liquid_unstake_fee),
+            None => liquid_unstake_fee,
+        };
+        let rebated_fee = holding_discount.discount(rebated_fee);
+        // compute fee in msol
+        let msol_fee = rebated_fee.apply_ceil(msol_amount);
+        let msol_fee_rebate = liquid_unstake_fee.apply_ceil(msol_amount) - msol_fee;
+        // the user receives lamport value of msol_amount - msol_fee
+        let sol_out = self.msol_to_sol(msol_amount - msol_fee)?;
+        Ok(LiquidUnstakeQuote {
+            msol_fee,
+            sol_out,
+            msol_fee_rebate,
+        })
+    }
+}
@@ -0,0 +1,38 @@
+use crate::state::Fee;
+use anchor_lang::prelude::*;
+
+/// Partner of the referral program, registered by the admin.
+/// Earns State::referral_share of the deposit fee paid by the deposits made with its referral,
+/// paid from the referral vault the DAO funds with the treasury fees
+#[account]
+#[derive(Debug)]
+pub struct ReferralPartner {
+    pub state: Pubkey,
+    pub partner: Pubkey,
+    pub bump: u8,
+    /// lamports deposited with this referral
+    pub referred_lamports: u64,
+    pub referred_deposits: u64,
+    /// rewards accrued and not claimed yet
+    pub pending_msol: u64,
+    pub claimed_msol: u64,
+}
+
+impl ReferralPartner {
+    pub const SEED: &'static [u8] = b"referral_partner";
+    pub const VAULT_SEED: &'static [u8] = b"referral_vault";
+    pub const VAULT_AUTHORITY_SEED: &'static [u8] = b"referral_vault_authority";
+    pub const LEN: usize = 8 + 32 * 2 + 1 + 8 * 4;
+    pub const MAX_REFERRAL_SHARE: Fee = Fee::from_basis_points(5000); // 50% of the deposit fee
+
+    pub fn on_deposit(&mut self, lamports: u64, msol_reward: u64) {
+        self.referred_lamports += lamports;
+        self.referred_deposits += 1;
+        self.pending_msol += msol_reward;
+    }
+
+    pub fn on_claim(&mut self, msol_amount: u64) {
+        self.pending_msol -= msol_amount;
+        self.claimed_msol += msol_amount;
+    }
+}
@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+
+/// Protocol roles and the instructions each one can sign,
+/// granted by the admin with grant_role
+#[derive(Clone, Copy, Debug, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
+pub enum Role {
+    /// config_marinade, config_lp, config_fee_curve, change_authority and the roles
+    Admin,
+    /// list reallocations and LP supply reconciliation
+    Operator,
+    /// fee sharing programs: referral, LP rewards, LP and mSOL locks
+    Treasurer,
+    /// pause and resume
+    Pauser,
+    /// validator list and stake moves
+    ValidatorManager,
+}
@@ -0,0 +1,44 @@
+use crate::calc::{shares_from_value, value_from_shares, Rounding};
+use anchor_lang::prelude::*;
+
+/// ERC-4626 style vault over mSOL for the tools expecting share based vault semantics.
+/// The asset is mSOL held in asset_vault, deposit_to_vault mints shares of share_mint
+/// and redeem_from_vault burns them for their part of the assets
+#[account]
+#[derive(Debug)]
+pub struct ShareVault {
+    pub state: Pubkey,
+    pub bump: u8,
+    pub authority_bump: u8,
+    pub asset_vault: Pubkey,
+    pub share_mint: Pubkey,
+    /// mSOL deposited and not redeemed, mSOL sent to asset_vault directly is not counted
+    pub total_assets: u64,
+    pub total_shares: u64,
+}
+
+impl ShareVault {
+    pub const SEED: &'static [u8] = b"share_vault";
+    pub const AUTHORITY_SEED: &'static [u8] = b"share_vault_authority";
+    pub const ASSET_VAULT_SEED: &'static [u8] = b"share_vault_assets";
+    pub const SHARE_MINT_SEED: &'static [u8] = b"share_vault_mint";
+    pub const LEN: usize = 8 + 32 + 1 + 1 + 32 + 32 + 8 + 8;
+
+    pub fn convert_to_shares(&self, assets: u64) -> Result<u64> {
+        shares_from_value(assets, self.total_assets, self.total_shares, Rounding::Down)
+    }
+
+    pub fn convert_to_assets(&self, shares: u64) -> Result<u64> {
+        value_from_shares(shares, self.total_assets, self.total_shares, Rounding::Down)
+    }
+
+    pub fn on_deposit(&mut self, assets: u64, shares: u64) {
+        self.total_assets += assets;
+        self.total_shares += shares;
+    }
+
+    pub fn on_redeem(&mut self, assets: u64, shares: u64) {
+        self.total_assets -= assets;
+        self.total_shares -= shares;
+    }
+}
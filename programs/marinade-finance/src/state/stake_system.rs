@@ -87,6 +87,28 @@ pub struct StakeSystem {
     pub extra_stake_delta_runs: u32,
 }
 
+/// Progress of the update_active cranks in an epoch, lets update_active_batch resume
+/// where the previous call stopped. A hint only: update_deactivated removes records
+/// and shifts the indexes after them
+#[derive(Clone, Copy, Debug, Default, AnchorSerialize, AnchorDeserialize)]
+pub struct UpdateCursor {
+    pub epoch: u64,
+    /// index after the highest stake record updated in the epoch
+    pub next_stake_index: u32,
+}
+
+impl UpdateCursor {
+    pub fn on_update(&mut self, epoch: u64, stake_index: u32) {
+        if self.epoch != epoch {
+            *self = Self {
+                epoch,
+                ..Self::default()
+            };
+        }
+        self.next_stake_index = self.next_stake_index.max(stake_index + 1);
+    }
+}
+
 impl StakeSystem {
     pub const STAKE_WITHDRAW_SEED: &'static [u8] = b"withdraw";
     pub const STAKE_DEPOSIT_SEED: &'static [u8] = b"deposit";
@@ -196,6 +218,18 @@ impl StakeSystem {
         Ok(stake_record)
     }
 
+    /// true when every stake account was updated in `epoch`.
+    /// Scans the whole list in place, it can't be paged over transactions:
+    /// List::remove moves the last record into the removed slot
+    pub fn is_updated(&self, stake_list_data: &[u8], epoch: u64) -> Result<bool> {
+        for index in 0..self.stake_count() {
+            if self.get(stake_list_data, index)?.last_update_epoch != epoch {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
     pub fn set(&self, stake_list_data: &mut [u8], index: u32, stake: StakeRecord) -> Result<()> {
         self.stake_list
             .set(stake_list_data, index, stake)
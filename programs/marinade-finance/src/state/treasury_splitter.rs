@@ -0,0 +1,89 @@
+use crate::{
+    calc::{proportional, Rounding},
+    error::MarinadeError,
+};
+use anchor_lang::prelude::*;
+
+#[derive(Clone, Copy, Debug, Default, AnchorSerialize, AnchorDeserialize, PartialEq, Eq)]
+pub struct TreasuryRecipient {
+    /// mSOL token account
+    pub token_account: Pubkey,
+    pub weight: u32,
+}
+
+/// Weighted recipients of the treasury mSOL.
+/// While it is set the State::treasury_msol_account is the splitter vault,
+/// distribute_treasury splits the vault balance between the recipients by weight
+#[account]
+#[derive(Debug)]
+pub struct TreasurySplitter {
+    pub state: Pubkey,
+    pub bump: u8,
+    pub authority_bump: u8,
+    pub vault: Pubkey,
+    pub recipient_count: u8,
+    /// only the first recipient_count are used
+    pub recipients: [TreasuryRecipient; TreasurySplitter::MAX_RECIPIENTS],
+    /// mSOL distributed since the splitter was created
+    pub total_distributed: u64,
+}
+
+impl TreasurySplitter {
+    pub const SEED: &'static [u8] = b"treasury_splitter";
+    pub const VAULT_SEED: &'static [u8] = b"treasury_vault";
+    pub const AUTHORITY_SEED: &'static [u8] = b"treasury_authority";
+    pub const MAX_RECIPIENTS: usize = 8;
+    pub const LEN: usize = 8 + 32 + 1 + 1 + 32 + 1 + (32 + 4) * Self::MAX_RECIPIENTS + 8;
+
+    pub fn recipients(&self) -> &[TreasuryRecipient] {
+        &self.recipients[..self.recipient_count as usize]
+    }
+
+    pub fn set_recipients(&mut self, recipients: &[TreasuryRecipient]) -> Result<()> {
+        require_gt!(
+            recipients.len(),
+            0,
+            MarinadeError::InvalidTreasuryRecipients
+        );
+        require_gte!(
+            Self::MAX_RECIPIENTS,
+            recipients.len(),
+            MarinadeError::InvalidTreasuryRecipients
+        );
+        for (i, recipient) in recipients.iter().enumerate() {
+            require_gt!(
+                recipient.weight,
+                0,
+                MarinadeError::InvalidTreasuryRecipients
+            );
+            require!(
+                recipient.token_account != self.vault
+                    && recipients[..i]
+                        .iter()
+                        .all(|other| other.token_account != recipient.token_account),
+                MarinadeError::InvalidTreasuryRecipients
+            );
+        }
+        self.recipients = [TreasuryRecipient::default(); Self::MAX_RECIPIENTS];
+        self.recipients[..recipients.len()].copy_from_slice(recipients);
+        self.recipient_count = recipients.len() as u8;
+        Ok(())
+    }
+
+    pub fn total_weight(&self) -> u64 {
+        self.recipients()
+            .iter()
+            .map(|recipient| recipient.weight as u64)
+            .sum()
+    }
+
+    /// share of `msol_amount` for the recipient, the rounding dust stays in the vault
+    pub fn recipient_share(&self, recipient: &TreasuryRecipient, msol_amount: u64) -> Result<u64> {
+        proportional(
+            msol_amount,
+            recipient.weight as u64,
+            self.total_weight(),
+            Rounding::Down,
+        )
+    }
+}
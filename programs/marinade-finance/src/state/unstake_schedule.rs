@@ -0,0 +1,68 @@
+use crate::error::MarinadeError;
+use anchor_lang::prelude::*;
+
+#[derive(Clone, Copy, Debug, Default, AnchorSerialize, AnchorDeserialize)]
+pub struct ScheduledUnstake {
+    pub created_epoch: u64,
+    pub lamports: u64,
+}
+
+/// Tickets ordered for a future epoch. Their lamports are part of circulating_ticket_balance
+/// but must not be unstaked before the epoch they were scheduled for
+#[derive(Clone, Copy, Debug, Default, AnchorSerialize, AnchorDeserialize)]
+pub struct UnstakeSchedule {
+    pub entries: [ScheduledUnstake; Self::MAX_EPOCHS as usize],
+}
+
+impl UnstakeSchedule {
+    /// how many epochs ahead an unstake can be scheduled,
+    /// there is always a free entry because past entries are reused
+    pub const MAX_EPOCHS: u64 = 8;
+
+    pub fn schedule(
+        &mut self,
+        created_epoch: u64,
+        lamports: u64,
+        current_epoch: u64,
+    ) -> Result<()> {
+        require_gt!(
+            created_epoch,
+            current_epoch,
+            MarinadeError::InvalidUnstakeEpoch
+        );
+        require_gte!(
+            current_epoch + Self::MAX_EPOCHS,
+            created_epoch,
+            MarinadeError::InvalidUnstakeEpoch
+        );
+        let entry = if let Some(entry) = self
+            .entries
+            .iter_mut()
+            .find(|entry| entry.created_epoch == created_epoch)
+        {
+            entry
+        } else {
+            let entry = self
+                .entries
+                .iter_mut()
+                .find(|entry| entry.created_epoch <= current_epoch)
+                .ok_or_else(|| error!(MarinadeError::InvalidUnstakeEpoch))?;
+            *entry = ScheduledUnstake {
+                created_epoch,
+                lamports: 0,
+            };
+            entry
+        };
+        entry.lamports += lamports;
+        Ok(())
+    }
+
+    /// lamports of the tickets that are not to be unstaked yet
+    pub fn pending(&self, current_epoch: u64) -> u64 {
+        self.entries
+            .iter()
+            .filter(|entry| entry.created_epoch > current_epoch)
+            .map(|entry| entry.lamports)
+            .sum()
+    }
+}
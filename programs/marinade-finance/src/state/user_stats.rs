@@ -0,0 +1,52 @@
+use crate::calc::{proportional, Rounding};
+use anchor_lang::prelude::*;
+
+/// Staking statistics of one wallet, created by the user with init_user_stats.
+/// deposit, liquid_unstake and order_unstake update it when it is passed for the wallet,
+/// the activity before it exists or without it is not counted
+#[account]
+#[derive(Debug)]
+pub struct UserStats {
+    pub state: Pubkey,
+    pub owner: Pubkey,
+    pub bump: u8,
+    /// lamports deposited for mSOL
+    pub total_deposited: u64,
+    /// lamports received or ticketed for the unstaked mSOL
+    pub total_unstaked: u64,
+    /// mSOL of the counted deposits not unstaked yet and what it cost
+    pub msol_amount: u64,
+    pub cost_basis: u64,
+    /// unstaked lamports above the cost basis of the counted mSOL, negative after fees
+    pub realized_yield: i64,
+    pub first_deposit_epoch: u64,
+}
+
+impl UserStats {
+    pub const SEED: &'static [u8] = b"user_stats";
+    pub const LEN: usize = 8 + 32 * 2 + 1 + 8 * 6;
+
+    pub fn on_deposit(&mut self, lamports: u64, msol_amount: u64, epoch: u64) {
+        if self.total_deposited == 0 {
+            self.first_deposit_epoch = epoch;
+        }
+        self.total_deposited += lamports;
+        self.msol_amount += msol_amount;
+        self.cost_basis += lamports;
+    }
+
+    /// the average cost of the counted mSOL is the basis of the yield,
+    /// mSOL received from elsewhere only adds to total_unstaked
+    pub fn on_unstake(&mut self, msol_amount: u64, lamports: u64) -> Result<()> {
+        let counted = msol_amount.min(self.msol_amount);
+        if counted > 0 {
+            let cost = proportional(self.cost_basis, counted, self.msol_amount, Rounding::Down)?;
+            let proceeds = proportional(lamports, counted, msol_amount, Rounding::Down)?;
+            self.realized_yield += proceeds as i64 - cost as i64;
+            self.cost_basis -= cost;
+            self.msol_amount -= counted;
+        }
+        self.total_unstaked += lamports;
+        Ok(())
+    }
+}
@@ -0,0 +1,30 @@
+use crate::state::validator_health::ValidatorHealth;
+use anchor_lang::prelude::*;
+
+/// mSOL bond of one validator, kept in the bond vault PDA token account.
+/// stake_reserve only stakes to validators with at least State::min_validator_bond,
+/// slash_validator_bond sends State::validator_bond_slash of it to the treasury when the validator is flagged
+#[account]
+#[derive(Debug)]
+pub struct ValidatorBond {
+    pub state: Pubkey,
+    /// validator vote account
+    pub validator: Pubkey,
+    /// the validator or its representative, the only one who can withdraw
+    pub authority: Pubkey,
+    pub bump: u8,
+    pub vault_bump: u8,
+    pub amount: u64,
+    pub last_slash_epoch: u64,
+}
+
+impl ValidatorBond {
+    pub const SEED: &'static [u8] = b"validator_bond";
+    pub const VAULT_SEED: &'static [u8] = b"validator_bond_vault";
+    pub const AUTHORITY_SEED: &'static [u8] = b"validator_bond_authority";
+    pub const LEN: usize = 8 + 32 * 3 + 1 + 1 + 8 * 2;
+
+    // misbehavior flags, a blocked validator is not slashed
+    pub const SLASHABLE_FLAGS: u8 =
+        ValidatorHealth::COMMISSION_FLAG | ValidatorHealth::DELINQUENT_FLAG;
+}
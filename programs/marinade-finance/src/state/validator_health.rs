@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+
+/// Permissionless checks of one validator, see check_validator_commission and check_validator_delinquency.
+/// A flagged validator has score 0 and its stake can be emergency unstaked by anyone.
+/// The validator manager can also block a validator with set_validator_blocked.
+/// A quarantined validator (set_validator_quarantined, quarantine_validator) has score 0 and gets
+/// no new stake, its stake leaves it gradually with redelegate instead of an emergency unstake
+#[account]
+#[derive(Debug)]
+pub struct ValidatorHealth {
+    pub state: Pubkey,
+    /// validator vote account
+    pub validator: Pubkey,
+    pub bump: u8,
+    /// ValidatorHealth::*_FLAG bits
+    pub flags: u8,
+    /// commission read on the last check
+    pub commission: u8,
+    pub last_check_epoch: u64,
+    /// vote credits observed on their last increase
+    pub credits: u64,
+    pub credits_epoch: u64,
+}
+
+impl ValidatorHealth {
+    pub const SEED: &'static [u8] = b"validator_health";
+    pub const LEN: usize = 8 + 32 * 2 + 3 + 8 * 3;
+
+    pub const COMMISSION_FLAG: u8 = 1 << 0;
+    pub const DELINQUENT_FLAG: u8 = 1 << 1;
+    pub const BLOCKED_FLAG: u8 = 1 << 2;
+    pub const QUARANTINED_FLAG: u8 = 1 << 3;
+
+    pub fn new(state: Pubkey, validator: Pubkey, bump: u8, epoch: u64) -> Self {
+        Self {
+            state,
+            validator,
+            bump,
+            flags: 0,
+            commission: 0,
+            last_check_epoch: 0,
+            credits: 0,
+            // the credits are observed from now on
+            credits_epoch: epoch,
+        }
+    }
+
+    pub fn set_flag(&mut self, flag: u8, value: bool) {
+        if value {
+            self.flags |= flag;
+        } else {
+            self.flags &= !flag;
+        }
+    }
+
+    pub fn is_flagged(&self) -> bool {
+        self.flags != 0
+    }
+
+    pub fn is_emergency_unstakable(&self) -> bool {
+        self.flags & !Self::QUARANTINED_FLAG != 0
+    }
+
+    /// `validator_health` is the ValidatorHealth PDA of the validator, not created when never checked
+    pub fn is_quarantined(validator_health: &AccountInfo) -> Result<bool> {
+        if *validator_health.owner != crate::ID {
+            return Ok(false);
+        }
+        let validator_health = Account::<ValidatorHealth>::try_from(validator_health)?;
+        Ok(validator_health.flags & Self::QUARANTINED_FLAG != 0)
+    }
+}
@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+/// On-chain metadata of one validator, set by its identity with set_validator_metadata.
+/// The text fields are stored as sha256 hashes, the texts are published off-chain
+#[account]
+#[derive(Debug)]
+pub struct ValidatorMetadata {
+    pub state: Pubkey,
+    /// validator vote account
+    pub validator: Pubkey,
+    pub bump: u8,
+    /// node_pubkey of the vote account when the metadata was set
+    pub identity: Pubkey,
+    pub name_hash: [u8; 32],
+    pub website_hash: [u8; 32],
+    pub datacenter_hash: [u8; 32],
+    /// autonomous system number of the validator network
+    pub asn: u32,
+    pub updated_epoch: u64,
+}
+
+impl ValidatorMetadata {
+    pub const SEED: &'static [u8] = b"validator_metadata";
+    pub const LEN: usize = 8 + 32 * 2 + 1 + 32 * 4 + 4 + 8;
+}
@@ -1,6 +1,10 @@
 //use std::convert::TryInto;
 
-use crate::{calc::proportional, error::MarinadeError, ID};
+use crate::{
+    calc::{proportional, Rounding},
+    error::MarinadeError,
+    ID,
+};
 use anchor_lang::{prelude::*, Discriminator};
 
 use super::list::List;
@@ -259,6 +263,22 @@ impl ValidatorSystem {
             .map_err(|e| e.with_account_name("validator_list"))
     }
 
+    /// updates the score of the validator at `index` and total_validator_score, returns the old score
+    pub fn set_score(
+        &mut self,
+        validator_list_data: &mut [u8],
+        index: u32,
+        validator_vote: &Pubkey,
+        score: u32,
+    ) -> Result<u32> {
+        let mut validator = self.get_checked(validator_list_data, index, validator_vote)?;
+        let old = validator.score;
+        self.total_validator_score = self.total_validator_score - old + score;
+        validator.score = score;
+        self.set(validator_list_data, index, validator)?;
+        Ok(old)
+    }
+
     pub fn validator_stake_target(
         &self,
         validator: &ValidatorRecord,
@@ -271,6 +291,7 @@ impl ValidatorSystem {
             total_stake_target,
             validator.score as u64,
             self.total_validator_score as u64,
+            Rounding::Down,
         )
     }
 }
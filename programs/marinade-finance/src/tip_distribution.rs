@@ -0,0 +1,54 @@
+//! CPI to the Jito tip distribution program.
+//! The MEV tips of an epoch are claimed by the stake accounts with a merkle proof
+//! and land in the stake account lamports
+
+use anchor_lang::{
+    prelude::*,
+    solana_program::instruction::{AccountMeta, Instruction},
+};
+
+declare_id!("4R3gSG8BpU4t19KYj8CfnbtRpnT8gtk4dvTHxVRwc2r7");
+
+// anchor discriminator of the claim instruction, sha256("global:claim")[..8]
+const CLAIM_DISCRIMINATOR: [u8; 8] = [62, 198, 214, 193, 213, 159, 108, 210];
+
+#[derive(AnchorSerialize)]
+struct ClaimArgs {
+    bump: u8,
+    amount: u64,
+    proof: Vec<[u8; 32]>,
+}
+
+/// `claimant` receives `amount` from `tip_distribution_account`,
+/// `claim_status` is the claim record PDA created by the program, paid by `payer`
+#[allow(clippy::too_many_arguments)]
+pub fn claim(
+    config: &Pubkey,
+    tip_distribution_account: &Pubkey,
+    claim_status: &Pubkey,
+    claimant: &Pubkey,
+    payer: &Pubkey,
+    claim_status_bump: u8,
+    amount: u64,
+    proof: Vec<[u8; 32]>,
+) -> Result<Instruction> {
+    let mut data = CLAIM_DISCRIMINATOR.to_vec();
+    ClaimArgs {
+        bump: claim_status_bump,
+        amount,
+        proof,
+    }
+    .serialize(&mut data)?;
+    Ok(Instruction {
+        program_id: ID,
+        accounts: vec![
+            AccountMeta::new_readonly(*config, false),
+            AccountMeta::new(*tip_distribution_account, false),
+            AccountMeta::new(*claim_status, false),
+            AccountMeta::new(*claimant, false),
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(anchor_lang::system_program::ID, false),
+        ],
+        data,
+    })
+}
@@ -0,0 +1,101 @@
+//! CPI to the Metaplex token metadata program.
+//! The mint authority PDAs of the mSOL and LP mints sign as mint and update authority,
+//! the metadata stays under the program control
+
+use anchor_lang::{
+    prelude::*,
+    solana_program::instruction::{AccountMeta, Instruction},
+};
+
+declare_id!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+
+const METADATA_SEED: &[u8] = b"metadata";
+
+// MetadataInstruction enum tags
+const CREATE_METADATA_ACCOUNT_V3: u8 = 33;
+const UPDATE_METADATA_ACCOUNT_V2: u8 = 15;
+
+// DataV2 up to seller_fee_basis_points, the program sets no creators, collection nor uses
+#[derive(AnchorSerialize)]
+struct TokenData {
+    name: String,
+    symbol: String,
+    uri: String,
+    seller_fee_basis_points: u16,
+}
+
+impl TokenData {
+    fn serialize_data_v2(self, data: &mut Vec<u8>) -> Result<()> {
+        self.serialize(data)?;
+        // creators, collection, uses: None
+        data.extend_from_slice(&[0, 0, 0]);
+        Ok(())
+    }
+}
+
+pub fn metadata_address(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[METADATA_SEED, ID.as_ref(), mint.as_ref()], &ID).0
+}
+
+/// mutable metadata of `mint`, `authority` is the mint authority and the update authority
+pub fn create_metadata_account(
+    metadata: &Pubkey,
+    mint: &Pubkey,
+    authority: &Pubkey,
+    payer: &Pubkey,
+    name: String,
+    symbol: String,
+    uri: String,
+) -> Result<Instruction> {
+    let mut data = vec![CREATE_METADATA_ACCOUNT_V3];
+    TokenData {
+        name,
+        symbol,
+        uri,
+        seller_fee_basis_points: 0,
+    }
+    .serialize_data_v2(&mut data)?;
+    // is_mutable: true, collection_details: None
+    data.extend_from_slice(&[1, 0]);
+    Ok(Instruction {
+        program_id: ID,
+        accounts: vec![
+            AccountMeta::new(*metadata, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new_readonly(anchor_lang::system_program::ID, false),
+        ],
+        data,
+    })
+}
+
+/// replaces name, symbol and uri keeping the update authority
+pub fn update_metadata_account(
+    metadata: &Pubkey,
+    update_authority: &Pubkey,
+    name: String,
+    symbol: String,
+    uri: String,
+) -> Result<Instruction> {
+    // data: Some
+    let mut data = vec![UPDATE_METADATA_ACCOUNT_V2, 1];
+    TokenData {
+        name,
+        symbol,
+        uri,
+        seller_fee_basis_points: 0,
+    }
+    .serialize_data_v2(&mut data)?;
+    // new_update_authority, primary_sale_happened, is_mutable: None
+    data.extend_from_slice(&[0, 0, 0]);
+    Ok(Instruction {
+        program_id: ID,
+        accounts: vec![
+            AccountMeta::new(*metadata, false),
+            AccountMeta::new_readonly(*update_authority, true),
+        ],
+        data,
+    })
+}
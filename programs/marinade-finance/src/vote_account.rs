@@ -0,0 +1,126 @@
+//! Reading the vote accounts of the validators.
+//! VoteState::deserialize is not available on-chain, the fields are read from the raw data
+
+use crate::error::MarinadeError;
+use anchor_lang::{prelude::*, solana_program::vote};
+use std::cell::Ref;
+
+// VoteStateVersions enum tag
+const VERSION_LEN: usize = 4;
+const V0_23_5: u32 = 0;
+const V1_14_11: u32 = 1;
+const CURRENT: u32 = 2;
+// node_pubkey, authorized_voter, authorized_voter_epoch, prior_voters (32 entries + index), authorized_withdrawer
+const V0_23_5_COMMISSION_OFFSET: usize = VERSION_LEN + 32 + 32 + 8 + (32 * (32 + 8 * 3) + 8) + 32;
+// node_pubkey, authorized_withdrawer
+const COMMISSION_OFFSET: usize = VERSION_LEN + 32 + 32;
+// slot, confirmation_count
+const LOCKOUT_LEN: usize = 8 + 4;
+// latency, lockout
+const LANDED_VOTE_LEN: usize = 1 + LOCKOUT_LEN;
+// 32 entries of (pubkey, start epoch, end epoch), index, is_empty
+const PRIOR_VOTERS_LEN: usize = 32 * (32 + 8 * 2) + 8 + 1;
+
+// bincode reader over the vote account data
+struct Reader<'a> {
+    data: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn read(&mut self, len: usize) -> Result<&'a [u8]> {
+        let end = self
+            .offset
+            .checked_add(len)
+            .filter(|end| *end <= self.data.len())
+            .ok_or_else(|| error!(MarinadeError::InvalidVoteAccount))?;
+        let bytes = &self.data[self.offset..end];
+        self.offset = end;
+        Ok(bytes)
+    }
+
+    fn read_u8(&mut self) -> Result<u8> {
+        Ok(self.read(1)?[0])
+    }
+
+    fn read_u64(&mut self) -> Result<u64> {
+        Ok(u64::from_le_bytes(self.read(8)?.try_into().unwrap()))
+    }
+
+    fn skip_items(&mut self, count: u64, item_len: usize) -> Result<()> {
+        let len = usize::try_from(count)
+            .ok()
+            .and_then(|count| count.checked_mul(item_len))
+            .ok_or_else(|| error!(MarinadeError::InvalidVoteAccount))?;
+        self.read(len)?;
+        Ok(())
+    }
+}
+
+fn vote_account_data<'a, 'info>(
+    vote_account: &'a AccountInfo<'info>,
+) -> Result<Ref<'a, &'info mut [u8]>> {
+    require_keys_eq!(
+        *vote_account.owner,
+        vote::program::ID,
+        MarinadeError::InvalidVoteAccount
+    );
+    let data = vote_account.try_borrow_data()?;
+    require_gte!(
+        data.len(),
+        V0_23_5_COMMISSION_OFFSET + 1,
+        MarinadeError::InvalidVoteAccount
+    );
+    Ok(data)
+}
+
+/// validator identity, the node_pubkey field comes first in all the versions
+pub fn node_pubkey(vote_account: &AccountInfo) -> Result<Pubkey> {
+    let data = vote_account_data(vote_account)?;
+    Ok(Pubkey::new_from_array(
+        data[VERSION_LEN..VERSION_LEN + 32].try_into().unwrap(),
+    ))
+}
+
+/// commission of the validator in percents
+pub fn commission(vote_account: &AccountInfo) -> Result<u8> {
+    let data = vote_account_data(vote_account)?;
+    let version = u32::from_le_bytes(data[..VERSION_LEN].try_into().unwrap());
+    match version {
+        V0_23_5 => Ok(data[V0_23_5_COMMISSION_OFFSET]),
+        V1_14_11 | CURRENT => Ok(data[COMMISSION_OFFSET]),
+        _ => err!(MarinadeError::InvalidVoteAccount),
+    }
+}
+
+/// total vote credits earned by the validator, from the last epoch_credits entry
+pub fn credits(vote_account: &AccountInfo) -> Result<u64> {
+    let data = vote_account_data(vote_account)?;
+    let version = u32::from_le_bytes(data[..VERSION_LEN].try_into().unwrap());
+    let vote_len = match version {
+        V1_14_11 => LOCKOUT_LEN,
+        CURRENT => LANDED_VOTE_LEN,
+        _ => return err!(MarinadeError::InvalidVoteAccount),
+    };
+    let mut reader = Reader {
+        data: &data,
+        offset: COMMISSION_OFFSET + 1,
+    };
+    let votes = reader.read_u64()?;
+    reader.skip_items(votes, vote_len)?;
+    // root_slot
+    if reader.read_u8()? != 0 {
+        reader.read(8)?;
+    }
+    let authorized_voters = reader.read_u64()?;
+    reader.skip_items(authorized_voters, 8 + 32)?;
+    reader.read(PRIOR_VOTERS_LEN)?;
+    // (epoch, credits, prev_credits)
+    let epoch_credits = reader.read_u64()?;
+    if epoch_credits == 0 {
+        return Ok(0);
+    }
+    reader.skip_items(epoch_credits - 1, 8 * 3)?;
+    reader.read(8)?;
+    reader.read_u64()
+}